@@ -0,0 +1,111 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use dmidecode::{EntryPoint, StringEncoding};
+
+const DMIDECODE_BIN: &[u8] = include_bytes!("../../tests/data/dmidecode.bin");
+const DMI_0_BIN: &[u8] = include_bytes!("../../tests/data/dmi.0.bin");
+const ENTRY_V3_BIN: &[u8] = include_bytes!("../../tests/data/entry_v3.bin");
+const DMI_V3_BIN: &[u8] = include_bytes!("../../tests/data/dmi_v3.bin");
+
+/// Size of the synthetic table, the largest table a 2.x entry point can describe
+const SYNTHETIC_LEN: usize = 64 * 1024;
+
+/// Length of the structures preceding the End-of-Table structure
+fn table_body_len(table: &[u8]) -> usize {
+    let mut idx = 0;
+    while table[idx] != 127 {
+        idx += table[idx + 1] as usize;
+        while table[idx] != 0 || table[idx + 1] != 0 {
+            idx += 1;
+        }
+        idx += 2;
+    }
+    idx
+}
+
+/// The structures of `dmi_v3.bin` repeated up to 64 KiB, followed by an End-of-Table structure
+fn synthetic_table() -> (EntryPoint, Vec<u8>) {
+    let body = &DMI_V3_BIN[..table_body_len(DMI_V3_BIN)];
+    let end = [127, 4, 0xFE, 0xFF, 0, 0];
+    let mut table = Vec::with_capacity(SYNTHETIC_LEN);
+    while table.len() + body.len() + end.len() <= SYNTHETIC_LEN {
+        table.extend_from_slice(body);
+    }
+    table.extend_from_slice(&end);
+
+    let entry_point = match EntryPoint::search(ENTRY_V3_BIN).unwrap() {
+        EntryPoint::V3(mut entry_point) => {
+            entry_point.smbios_len_max = table.len() as u32;
+            EntryPoint::V3(entry_point)
+        }
+        EntryPoint::V2(_) => unreachable!(),
+    };
+    (entry_point, table)
+}
+
+fn tables() -> Vec<(&'static str, EntryPoint, Vec<u8>)> {
+    let bundled = |buffer: &[u8]| {
+        let entry_point = EntryPoint::search(buffer).unwrap();
+        let table = buffer[entry_point.smbios_address() as usize..].to_vec();
+        (entry_point, table)
+    };
+    let mut tables = Vec::new();
+    for &(name, buffer) in &[("dmidecode.bin", DMIDECODE_BIN), ("dmi.0.bin", DMI_0_BIN)] {
+        let (entry_point, table) = bundled(buffer);
+        tables.push((name, entry_point, table));
+    }
+    let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+    tables.push(("dmi_v3.bin", entry_point, DMI_V3_BIN.to_vec()));
+    let (entry_point, table) = synthetic_table();
+    tables.push(("synthetic 64KiB", entry_point, table));
+    tables
+}
+
+fn entry_point(c: &mut Criterion) {
+    c.bench_function("search entry point", |b| {
+        b.iter(|| EntryPoint::search(black_box(DMIDECODE_BIN)))
+    });
+
+    // An anchor at the end of a 1 MiB memory window
+    let mut window = vec![0xFF; 1 << 20];
+    let anchor = window.len() - 0x20;
+    window[anchor..anchor + ENTRY_V3_BIN.len()].copy_from_slice(ENTRY_V3_BIN);
+    let mut group = c.benchmark_group("search entry point");
+    group.throughput(Throughput::Bytes(window.len() as u64));
+    group.bench_function("1MiB window", |b| b.iter(|| EntryPoint::search(black_box(&window))));
+    group.finish();
+}
+
+fn structures(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode structures");
+    for (name, entry_point, table) in tables() {
+        group.throughput(Throughput::Bytes(entry_point.smbios_len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for structure in entry_point.structures(black_box(&table)) {
+                    black_box(structure.ok());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterate strings");
+    for (name, entry_point, table) in tables() {
+        group.throughput(Throughput::Bytes(entry_point.smbios_len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for (_, strings) in entry_point.structures(black_box(&table)).with_strings().flatten() {
+                    for s in strings.raw() {
+                        black_box(StringEncoding::detect(s));
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, entry_point, structures, strings);
+criterion_main!(benches);
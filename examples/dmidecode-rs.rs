@@ -0,0 +1,169 @@
+//! A subset of `dmidecode` on top of the library
+//!
+//! ```text
+//! dmidecode-rs [--from-dump FILE] [-t TYPE]... [-H HANDLE] [-s KEYWORD] [--dump-bin FILE]
+//! ```
+//!
+//! The tables are read from sysfs unless `--from-dump` gives a file written by
+//! `dmidecode --dump-bin`.
+
+extern crate dmidecode;
+
+use std::{env, fs, process};
+
+use dmidecode::{
+    keyword::{self, Keyword},
+    EntryPoint, Structure, Structures,
+};
+
+const SYSFS_ENTRY_POINT: &str = "/sys/firmware/dmi/tables/smbios_entry_point";
+const SYSFS_TABLE: &str = "/sys/firmware/dmi/tables/DMI";
+
+const USAGE: &str = "Usage: dmidecode-rs [OPTIONS]
+
+Options:
+  --from-dump FILE   Read the entry point and the table from a binary dump
+  -t, --type TYPE    Only display the structures of TYPE: a keyword, a number or a type name
+  -H, --handle HANDLE
+                     Only display the structure of the given handle
+  -s, --string KEYWORD
+                     Only display the value of the given keyword
+  --dump-bin FILE    Write the entry point and the table to a binary dump
+  -h, --help         Display this help";
+
+#[derive(Default)]
+struct Options {
+    from_dump: Option<String>,
+    types: Vec<String>,
+    handle: Option<u16>,
+    string: Option<Keyword>,
+    dump_bin: Option<String>,
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|message| fail(&message));
+    let (entry_point, table) = load(&options).unwrap_or_else(|message| fail(&message));
+    let structures = entry_point.structures(&table);
+
+    if let Some(keyword) = options.string {
+        if let Some(value) = keyword::get(&structures, keyword) {
+            println!("{}", value);
+        }
+    } else if let Some(path) = options.dump_bin {
+        let consumed = structures.finish().consumed as usize;
+        let mut file = fs::File::create(&path).unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+        entry_point
+            .write_dump(&table[..consumed], &mut file)
+            .unwrap_or_else(|e| fail(&format!("{}: {}", path, e)));
+        println!("# Writing {} bytes to {}.", consumed, path);
+    } else {
+        print_header(&entry_point, &structures);
+        if let Some(handle) = options.handle {
+            match structures.find_handle(handle) {
+                Some(structure) => print_structure(structure),
+                None => fail(&format!("No structure has handle {:#06X}", handle)),
+            }
+        } else if options.types.is_empty() {
+            structures.for_each(print_structure);
+        } else {
+            for name in &options.types {
+                let selected = structures.clone().find_by_name(name);
+                selected
+                    .unwrap_or_else(|| fail(&format!("Invalid type keyword: {}", name)))
+                    .for_each(print_structure);
+            }
+        }
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Option {} requires an argument", arg))
+        };
+        match arg.as_str() {
+            "--from-dump" => options.from_dump = Some(value()?),
+            "-t" | "--type" => options
+                .types
+                .extend(value()?.split(',').map(|name| name.trim().to_owned())),
+            "-H" | "--handle" => {
+                let handle = value()?;
+                let parsed = match handle.strip_prefix("0x").or_else(|| handle.strip_prefix("0X")) {
+                    Some(hex) => u16::from_str_radix(hex, 16),
+                    None => handle.parse(),
+                };
+                options.handle = Some(parsed.map_err(|_| format!("Invalid handle: {}", handle))?);
+            }
+            "-s" | "--string" => {
+                let name = value()?;
+                let keyword = name.parse().map_err(|_| {
+                    let valid = Keyword::ALL.iter().map(|k| k.name()).collect::<Vec<_>>();
+                    format!(
+                        "Invalid string keyword: {}\nValid string keywords are:\n  {}",
+                        name,
+                        valid.join("\n  ")
+                    )
+                })?;
+                options.string = Some(keyword);
+            }
+            "--dump-bin" => options.dump_bin = Some(value()?),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => return Err(format!("Unknown option: {}\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+/// The entry point and the structure table, from a dump or from sysfs
+fn load(options: &Options) -> Result<(EntryPoint, Vec<u8>), String> {
+    let read = |path: &str| fs::read(path).map_err(|e| format!("{}: {}", path, e));
+    match options.from_dump {
+        Some(ref path) => {
+            let dump = read(path)?;
+            let entry_point = EntryPoint::search(&dump).map_err(|e| format!("{}: {}", path, e))?;
+            let table = dump
+                .get(entry_point.smbios_address() as usize..)
+                .ok_or_else(|| format!("{}: structure table address is outside of the dump", path))?
+                .to_vec();
+            Ok((entry_point, table))
+        }
+        None => {
+            let entry_point =
+                EntryPoint::search(&read(SYSFS_ENTRY_POINT)?).map_err(|e| format!("{}: {}", SYSFS_ENTRY_POINT, e))?;
+            Ok((entry_point, read(SYSFS_TABLE)?))
+        }
+    }
+}
+
+fn print_header(entry_point: &EntryPoint, structures: &Structures<'_>) {
+    let version = entry_point.to_version();
+    let status = structures.clone().finish();
+    println!("# dmidecode-rs");
+    println!("SMBIOS {}.{} present.", version.major, version.minor);
+    println!("{} structures occupying {} bytes.", status.structures, status.consumed);
+    println!("Table at {}.", entry_point.table_location());
+}
+
+fn print_structure(structure: Result<Structure<'_>, dmidecode::MalformedStructureError>) {
+    println!();
+    match structure {
+        Ok(structure) => {
+            let info = structure.info();
+            println!("Handle {:#06X}, DMI type {}", structure.handle().0, info.code());
+            println!("{}", info);
+            println!("{:#?}", structure);
+        }
+        Err(e) => println!("<{}>", e),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
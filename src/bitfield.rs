@@ -17,8 +17,9 @@ pub struct Flag<'a> {
 }
 
 /// There are 2 types of *Bit Field* flag meaningful and reserved for some purposes
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub enum FlagType<'a> {
+    #[default]
     Unknown,
     Significant(&'a str, &'a str),
     Reserved(&'a str),
@@ -100,6 +101,8 @@ pub trait BitField<'a> {
 /// # }
 /// ```
 /// It is mandatory to have trailing comma on last item!
+///
+/// Without the `strings` feature the descriptions are left empty.
 macro_rules! layout {
     // Initial call
     (length = $len:expr; $($tail:tt)*) => {
@@ -113,7 +116,7 @@ macro_rules! layout {
             let mut arr = layout!(array = $arr, index = $idx + $count; $($tail)*);
             let mut i = $idx;
             while i < $idx + $count {
-                arr[i] = FlagType::Reserved($desc);
+                arr[i] = FlagType::Reserved(description!($desc));
                 i += 1;
             }
             arr
@@ -124,7 +127,7 @@ macro_rules! layout {
     (array = $arr:expr, index = $idx:expr; $short:literal $long:literal, $($tail:tt)*) => {
         {
             let mut arr = layout!(array = $arr, index = $idx + 1; $($tail)*);
-            arr[$idx] = FlagType::Significant($short, $long);
+            arr[$idx] = FlagType::Significant(description!($short), description!($long));
             arr
         }
     };
@@ -132,12 +135,44 @@ macro_rules! layout {
     (array = $arr:expr, index = $idx:expr; $short:literal, $($tail:tt)*) => {
         {
             let mut arr = layout!(array = $arr, index = $idx + 1; $($tail)*);
-            arr[$idx] = FlagType::Significant($short, $short);
+            arr[$idx] = FlagType::Significant(description!($short), description!($short));
             arr
         }
     };
 }
 
+/// Constructor, accessor and conversions of a newtype over the raw value of a field
+///
+/// ```ignore
+/// raw_newtype!(LogStatus(u8));
+/// ```
+/// Assume internal use only
+macro_rules! raw_newtype {
+    ($name:ident($size:ty)) => {
+        impl $name {
+            /// Wrap the raw value of the field
+            pub const fn new(raw: $size) -> Self {
+                $name(raw)
+            }
+            /// The raw value of the field
+            pub const fn raw(self) -> $size {
+                self.0
+            }
+        }
+        impl From<$size> for $name {
+            fn from(raw: $size) -> Self {
+                $name(raw)
+            }
+        }
+        impl From<$name> for $size {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+#[cfg(feature = "strings")]
 impl<'a> fmt::Display for Flag<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.type_ {
@@ -166,12 +201,6 @@ impl<'a> fmt::Debug for Flag<'a> {
     }
 }
 
-impl<'a> Default for FlagType<'a> {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl<'a, T> Iter<'a, T> {
     fn new(value: T, layout: Layout<'a>) -> Self {
         Self {
@@ -358,6 +387,7 @@ mod tests {
         "E" "E Long",
         "Reserved 2": 2,
     );
+    #[cfg(feature = "strings")]
     #[test]
     fn layout_macro() {
         let sample = [
@@ -396,6 +426,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn significants() {
         let iter = Significants::new(Iter::new(0b1010_1001u8, LAYOUT));
@@ -447,23 +478,26 @@ mod tests {
         let iter = Reserved::new(Iter::new(u8::MAX, layout));
         assert_eq!(sample, iter.map(|v| v.range).collect::<Vec<_>>(), "Simple");
 
-        let layout = &layout!(
-            array = [FlagType::Unknown; 16], index = 0;
-            "S A" "A Long",
-            "S B" "B Long",
-            "R 1": 1,
-            "S C" "C Long",
-            "S C" "C Long",
-            "S D" "D Long",
-            "S E" "E Long",
-            "R 2": 2,
-            "S C" "C Long",
-            "R 2": 2,
-            "R 3": 4,
-        );
-        let sample = vec![2..=2, 7..=8, 10..=11, 12..=15];
-        let iter = Reserved::new(Iter::new(u16::MAX, layout));
-        assert_eq!(sample, iter.map(|v| v.range).collect::<Vec<_>>(), "Complex");
+        #[cfg(feature = "strings")]
+        {
+            let layout = &layout!(
+                array = [FlagType::Unknown; 16], index = 0;
+                "S A" "A Long",
+                "S B" "B Long",
+                "R 1": 1,
+                "S C" "C Long",
+                "S C" "C Long",
+                "S D" "D Long",
+                "S E" "E Long",
+                "R 2": 2,
+                "S C" "C Long",
+                "R 2": 2,
+                "R 3": 4,
+            );
+            let sample = vec![2..=2, 7..=8, 10..=11, 12..=15];
+            let iter = Reserved::new(Iter::new(u16::MAX, layout));
+            assert_eq!(sample, iter.map(|v| v.range).collect::<Vec<_>>(), "Complex");
+        }
     }
 
     #[test]
@@ -0,0 +1,223 @@
+//! Conformance corpus of dumped tables
+//!
+//! Loads every dump of a directory tree laid out as `tests/data` and runs the parser over each of
+//! them, so that a problem dump only needs to be copied to the tree to become a test fixture. The
+//! files are recognized by their content and name:
+//!
+//! - `dmidecode --dump-bin` files, the entry point followed by the table it points to;
+//! - entry point files, `entry*.bin`, next to the table file named after them, `dmi*.bin`;
+//! - single structures, header and strings included, in `entries/<type>-<n>/bin` files.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use std::path::Path;
+//!
+//! use dmidecode::{corpus, SmbiosVersion};
+//!
+//! let reports = corpus::load(Path::new("tests/data"), SmbiosVersion::V3_7).unwrap();
+//! for report in reports.iter().filter(|report| !report.is_ok()) {
+//!     eprintln!("{}: {:?}", report.path.display(), report.failures);
+//! }
+//! ```
+
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+    vec::Vec,
+};
+
+use crate::{BufferLayout, EntryPoint, MalformedStructureError, SmbiosVersion, Structures};
+
+/// How a file of the corpus was parsed
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Source {
+    /// A `dmidecode --dump-bin` file
+    Dump,
+    /// An entry point file and the table file named after it
+    Split { table: PathBuf },
+    /// A single structure of an `entries/<type>-<n>/bin` file
+    Structure,
+    /// A file that is none of the above, e.g. an entry point whose table is missing
+    Unrecognized,
+}
+
+/// The outcome of parsing one file of the corpus
+#[derive(Debug)]
+pub struct Report {
+    pub path: PathBuf,
+    pub source: Source,
+    /// The number of structures read, whether or not they decoded
+    pub structures: usize,
+    /// The errors of the structures that could not be read or decoded, in table order
+    pub failures: Vec<MalformedStructureError>,
+}
+
+impl Report {
+    /// Whether every structure of the file decoded
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Parse every file under `root`, in path order, decoding single structures as structures of an
+/// SMBIOS `structure_version` table
+///
+/// The table files read along with their entry point file are not reported on their own.
+///
+/// # Errors
+///
+/// Fails if a directory or a file cannot be read.
+pub fn load(root: &Path, structure_version: SmbiosVersion) -> io::Result<Vec<Report>> {
+    let mut files = Vec::new();
+    collect(root, &mut files)?;
+    files.sort();
+
+    let mut reports = Vec::new();
+    let mut tables = Vec::new();
+    for path in files.iter() {
+        let bytes = fs::read(path)?;
+        let report = if is_structure_file(path) {
+            let structures = Structures::new(&bytes, structure_version, bytes.len() as u32);
+            parse(path, Source::Structure, structures)
+        } else if let Ok(entry_point) = EntryPoint::search(&bytes) {
+            // The table address of a dump is its offset in the file, that of an entry point file
+            // is a physical address
+            let dump = match entry_point.smbios_address() {
+                0 => None,
+                _ => entry_point.structures_auto(&bytes, BufferLayout::PhysicalMemory { base: 0 }),
+            };
+            match (dump, table_file(path)) {
+                (Some(structures), _) => parse(path, Source::Dump, structures),
+                (None, Some(table)) if files.contains(&table) => {
+                    let table_bytes = fs::read(&table)?;
+                    tables.push(table.clone());
+                    parse(path, Source::Split { table }, entry_point.structures(&table_bytes))
+                }
+                _ => unrecognized(path),
+            }
+        } else {
+            unrecognized(path)
+        };
+        reports.push(report);
+    }
+    reports.retain(|report| !tables.contains(&report.path));
+    Ok(reports)
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `path` is an `entries/<type>-<n>/bin` file
+fn is_structure_file(path: &Path) -> bool {
+    let mut components = path.iter().rev();
+    components.next() == Some(OsStr::new("bin"))
+        && components
+            .next()
+            .and_then(OsStr::to_str)
+            .and_then(|entry| entry.split_once('-'))
+            .map_or(false, |(info, n)| {
+                info.parse::<u8>().is_ok() && n.parse::<u32>().is_ok()
+            })
+        && components.next() == Some(OsStr::new("entries"))
+}
+
+/// The table file named after the entry point file `path`, e.g. `dmi_v3.bin` for `entry_v3.bin`
+fn table_file(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let suffix = name.strip_prefix("entry")?;
+    Some(path.with_file_name(format!("dmi{}", suffix)))
+}
+
+fn parse(path: &Path, source: Source, structures: Structures<'_>) -> Report {
+    let mut report = Report {
+        path: path.into(),
+        source,
+        structures: 0,
+        failures: Vec::new(),
+    };
+    for structure in structures {
+        report.structures += 1;
+        if let Err(e) = structure {
+            report.failures.push(e);
+        }
+    }
+    report
+}
+
+fn unrecognized(path: &Path) -> Report {
+    Report {
+        path: path.into(),
+        source: Source::Unrecognized,
+        structures: 0,
+        failures: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::{string::String, vec};
+
+    #[test]
+    fn test_data() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+        let reports = load(&root, SmbiosVersion::V3_7).unwrap();
+        let summary = reports
+            .iter()
+            .map(|report| {
+                let path = report.path.strip_prefix(&root).unwrap();
+                (
+                    path.to_string_lossy().into_owned(),
+                    report.source.clone(),
+                    report.structures,
+                )
+            })
+            .collect::<Vec<(String, Source, usize)>>();
+        let split = |table: &str| Source::Split {
+            table: root.join(table),
+        };
+        assert_eq!(
+            vec![
+                ("02daadcd/entries/15-0/bin".into(), Source::Structure, 1),
+                ("02daadcd/entries/19-0/bin".into(), Source::Structure, 1),
+                ("02daadcd/entries/20-0/bin".into(), Source::Structure, 1),
+                ("________/entries/21-0/bin".into(), Source::Structure, 1),
+                ("________/entries/22-0/bin".into(), Source::Structure, 1),
+                ("caf65269/entries/18-0/bin".into(), Source::Structure, 1),
+                ("dmi.0.bin".into(), Source::Dump, 77),
+                ("dmidecode.bin".into(), Source::Dump, 69),
+                ("entry.bin".into(), split("dmi.bin"), 69),
+                ("entry_v3.bin".into(), split("dmi_v3.bin"), 99),
+                ("entry_v3_short.bin".into(), split("dmi_v3_short.bin"), 102),
+            ],
+            summary
+        );
+        for report in reports.iter() {
+            assert!(report.is_ok(), "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn file_names() {
+        assert!(is_structure_file(Path::new("data/abc/entries/17-12/bin")));
+        assert!(!is_structure_file(Path::new("data/abc/entries/17-12/bin.txt")));
+        assert!(!is_structure_file(Path::new("data/abc/17-12/bin")));
+        assert!(!is_structure_file(Path::new("data/abc/entries/dimm/bin")));
+        assert_eq!(
+            Some(PathBuf::from("data/dmi_v3.bin")),
+            table_file(Path::new("data/entry_v3.bin"))
+        );
+        assert_eq!(None, table_file(Path::new("data/dmidecode.bin")));
+    }
+}
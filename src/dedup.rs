@@ -0,0 +1,249 @@
+//! Identical structures repeated in a table
+//!
+//! Some firmware repeats structures, the same type and handle appearing twice with the same
+//! content, e.g. after a BIOS update that appends its structures to the previous table.
+//! [RawStructure::crc32] identifies the content of a structure, [Structures::duplicates] lists the
+//! repeated structures and [Structures::deduplicated] skips them, so that inventories can collapse
+//! the copies.
+//!
+//! With the `alloc` feature, both iterators keep the structures seen so far by CRC, so that a
+//! structure is only compared with the earlier ones of the same CRC. Without it, each structure is
+//! compared with all the ones before it, which takes time quadratic in the number of structures
+//! but needs no allocation; the headers are compared first, so that only structures of the same
+//! type, length and handle have their bytes compared.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::EntryPoint;
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! for duplicate in structures.duplicates() {
+//!     println!("structure {} repeats structure {}", duplicate.index, duplicate.first);
+//! }
+//! ```
+
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{HeaderPacked, RawStructure, Structures};
+
+/// A structure whose content is identical to an earlier structure of the table
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Duplicate<'buffer> {
+    pub structure: RawStructure<'buffer>,
+    /// Position of the structure in the table, the first structure being 0
+    pub index: usize,
+    /// Position of the first structure with the same content
+    pub first: usize,
+}
+
+/// An iterator over the repeated structures of a table, created by [Structures::duplicates]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Duplicates<'buffer> {
+    seen: Seen<'buffer>,
+    structures: Structures<'buffer>,
+    index: usize,
+}
+
+impl<'buffer> Iterator for Duplicates<'buffer> {
+    type Item = Duplicate<'buffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Ok(structure)) = self.structures.next_structure() {
+            let index = self.index;
+            self.index += 1;
+            if let Some(first) = self.seen.first_index(&structure, index) {
+                lib_debug!(
+                    "structure {} of type {} repeats structure {}",
+                    index,
+                    structure.info,
+                    first
+                );
+                return Some(Duplicate {
+                    structure,
+                    index,
+                    first,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over the structures of a table, skipping the repeated ones, created by
+/// [Structures::deduplicated]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Deduplicated<'buffer> {
+    seen: Seen<'buffer>,
+    structures: Structures<'buffer>,
+    index: usize,
+}
+
+impl<'buffer> Iterator for Deduplicated<'buffer> {
+    type Item = RawStructure<'buffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Ok(structure)) = self.structures.next_structure() {
+            let index = self.index;
+            self.index += 1;
+            if self.seen.first_index(&structure, index).is_none() {
+                return Some(structure);
+            }
+        }
+        None
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Iterate over the structures identical, header included, to an earlier structure of the
+    /// table. Iteration stops at the first structure that cannot be read.
+    pub fn duplicates(&self) -> Duplicates<'buffer> {
+        Duplicates {
+            seen: Seen::new(self),
+            structures: self.clone(),
+            index: 0,
+        }
+    }
+
+    /// Iterate over the structures without decoding them, skipping those identical to an earlier
+    /// structure of the table. Iteration stops at the first structure that cannot be read.
+    pub fn deduplicated(&self) -> Deduplicated<'buffer> {
+        Deduplicated {
+            seen: Seen::new(self),
+            structures: self.clone(),
+            index: 0,
+        }
+    }
+}
+
+impl<'buffer> RawStructure<'buffer> {
+    /// The CRC-32 (IEEE 802.3) of the structure bytes as found in the table: the header, the
+    /// formatted section and the strings table with its terminator
+    ///
+    /// Structures with the same content have the same CRC; the converse only holds with a high
+    /// probability, compare the structures to be sure.
+    pub fn crc32(&self) -> u32 {
+        let handle = self.handle.0.to_le_bytes();
+        let header = [self.info.code(), self.length, handle[0], handle[1]];
+        debug_assert_eq!(mem::size_of::<HeaderPacked>(), header.len());
+        crc32(&[&header, self.data, self.strings])
+    }
+}
+
+/// The CRC-32 of the concatenated `chunks`, computed bit by bit to spare a lookup table
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let crc = chunks.iter().flat_map(|bytes| bytes.iter()).fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    });
+    !crc
+}
+
+/// The structures already iterated over
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Seen<'buffer> {
+    /// Position and content of the first structure of each content, by CRC
+    #[cfg(feature = "alloc")]
+    by_crc: BTreeMap<u32, Vec<(usize, RawStructure<'buffer>)>>,
+    /// The table, whose structures before the current one are scanned again
+    #[cfg(not(feature = "alloc"))]
+    table: Structures<'buffer>,
+}
+
+impl<'buffer> Seen<'buffer> {
+    #[cfg(feature = "alloc")]
+    fn new(_table: &Structures<'buffer>) -> Self {
+        Seen {
+            by_crc: BTreeMap::new(),
+        }
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn new(table: &Structures<'buffer>) -> Self {
+        Seen { table: table.clone() }
+    }
+
+    /// The position of the first structure before `index` that is identical to `structure`, which
+    /// is at position `index` of the table
+    #[cfg(feature = "alloc")]
+    fn first_index(&mut self, structure: &RawStructure<'buffer>, index: usize) -> Option<usize> {
+        let bucket = self.by_crc.entry(structure.crc32()).or_default();
+        match bucket.iter().find(|(_, earlier)| same_content(earlier, structure)) {
+            Some(&(first, _)) => Some(first),
+            None => {
+                bucket.push((index, structure.clone()));
+                None
+            }
+        }
+    }
+
+    /// The position of the first structure before `index` that is identical to `structure`
+    #[cfg(not(feature = "alloc"))]
+    fn first_index(&mut self, structure: &RawStructure<'buffer>, index: usize) -> Option<usize> {
+        self.table
+            .clone()
+            .raw()
+            .take(index)
+            .map_while(Result::ok)
+            .position(|earlier| same_content(&earlier, structure))
+    }
+}
+
+/// Whether the structures have the same bytes, the cheap header fields being compared first
+fn same_content(a: &RawStructure<'_>, b: &RawStructure<'_>) -> bool {
+    (a.info, a.length, a.handle) == (b.info, b.length, b.handle) && a.data == b.data && a.strings == b.strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EntryPoint, Handle};
+    use pretty_assertions::assert_eq;
+    use std::{vec, vec::Vec};
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(0xCBF4_3926, crc32(&[b"123456789"]));
+        assert_eq!(0xCBF4_3926, crc32(&[b"1234", b"", b"56789"]));
+
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+        let first = entry_point.raw_structures(table).next().unwrap().unwrap();
+        assert_eq!(crc32(&[&table[..first.size()]]), first.crc32());
+        let moved = RawStructure {
+            handle: Handle(first.handle.0 + 1),
+            ..first.clone()
+        };
+        assert_ne!(first.crc32(), moved.crc32());
+    }
+
+    #[test]
+    fn repeated_structures() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+        let structures = entry_point.structures(table);
+        assert_eq!(0, structures.duplicates().count());
+        assert_eq!(69, structures.deduplicated().count());
+
+        // A copy of the first two structures in front of the table
+        let raw = structures.clone().raw().map(Result::unwrap).collect::<Vec<_>>();
+        let repeated = raw[0].size() + raw[1].size();
+        let len = entry_point.smbios_len() as usize;
+        let mut doubled = table[..repeated].to_vec();
+        doubled.extend_from_slice(&table[..len]);
+        let structures = Structures::new(&doubled, entry_point.to_version(), doubled.len() as u32);
+
+        let duplicates = structures.duplicates().map(|d| (d.index, d.first)).collect::<Vec<_>>();
+        assert_eq!(vec![(2, 0), (3, 1)], duplicates);
+        assert_eq!(69, structures.deduplicated().count());
+        assert!(structures.deduplicated().eq(raw.into_iter()));
+    }
+}
@@ -0,0 +1,172 @@
+//! Writing `dmidecode --dump-bin` files
+//!
+//! The dump layout is the entry point at offset 0, padded to 32 bytes, followed by the structure
+//! table. The entry point is patched to give 32 as the table address, so that the file can be read
+//! back with `dmidecode --from-dump` and with [EntryPoint::search].
+
+use core::convert::TryFrom;
+use std::{
+    io::{self, Write},
+    vec::Vec,
+};
+
+use crate::{EntryPoint, RawStructure};
+
+/// Offset of the structure table in a dump
+const TABLE_OFFSET: usize = 0x20;
+
+impl EntryPoint {
+    /// Write `table`, the structure table this entry point describes, as a `dmidecode --dump-bin`
+    /// file. The table is written as is, only the address and length of the entry point are
+    /// updated.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `writer` fails or with `io::ErrorKind::InvalidInput` if `table` is too large for
+    /// an SMBIOS 2.x entry point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let start = entry_point.smbios_address() as usize;
+    /// let table = &DMIDECODE_BIN[start..start + entry_point.smbios_len() as usize];
+    /// let mut dump = Vec::new();
+    /// entry_point.write_dump(table, &mut dump).unwrap();
+    /// assert_eq!(DMIDECODE_BIN, &dump[..]);
+    /// ```
+    pub fn write_dump<W: Write + ?Sized>(&self, table: &[u8], writer: &mut W) -> io::Result<()> {
+        let entry_point = self.relocated(table.len(), None)?;
+        write_dump(&entry_point, table, writer)
+    }
+
+    /// Write `structures` as the structure table of a `dmidecode --dump-bin` file, e.g. a subset
+    /// of the structures of this entry point's table. The structure count and maximum structure
+    /// size of an SMBIOS 2.x entry point are recomputed. Keep the End-of-Table structure for the
+    /// table to be complete.
+    ///
+    /// # Errors
+    ///
+    /// See [EntryPoint::write_dump].
+    pub fn write_dump_structures<'buffer, W, I>(&self, structures: I, writer: &mut W) -> io::Result<()>
+    where
+        W: Write + ?Sized,
+        I: IntoIterator<Item = RawStructure<'buffer>>,
+    {
+        let mut table = Vec::new();
+        let (mut count, mut max) = (0usize, 0usize);
+        for structure in structures {
+            let start = table.len();
+            table.push(structure.info.code());
+            table.push(structure.length);
+            table.extend_from_slice(&structure.handle.0.to_le_bytes());
+            table.extend_from_slice(structure.data);
+            table.extend_from_slice(structure.strings);
+            count += 1;
+            max = max.max(table.len() - start);
+        }
+        let entry_point = self.relocated(table.len(), Some((count, max)))?;
+        write_dump(&entry_point, &table, writer)
+    }
+
+    /// The entry point pointing to a table of `len` bytes at [TABLE_OFFSET], `stats` is the
+    /// structure count and the maximum structure size
+    fn relocated(&self, len: usize, stats: Option<(usize, usize)>) -> io::Result<EntryPoint> {
+        let too_large = |_| io::Error::new(io::ErrorKind::InvalidInput, "structure table too large for entry point");
+        let mut entry_point = *self;
+        match entry_point {
+            EntryPoint::V2(ref mut point) => {
+                point.smbios_address = TABLE_OFFSET as u32;
+                point.smbios_len = u16::try_from(len).map_err(too_large)?;
+                if let Some((count, max)) = stats {
+                    point.smbios_count = u16::try_from(count).map_err(too_large)?;
+                    point.struct_max = u16::try_from(max).map_err(too_large)?;
+                }
+                point.recompute_checksums();
+            }
+            EntryPoint::V3(ref mut point) => {
+                point.smbios_address = TABLE_OFFSET as u64;
+                point.smbios_len_max = u32::try_from(len).map_err(too_large)?;
+                point.recompute_checksum();
+            }
+        }
+        Ok(entry_point)
+    }
+}
+
+fn write_dump<W: Write + ?Sized>(entry_point: &EntryPoint, table: &[u8], writer: &mut W) -> io::Result<()> {
+    let mut header = [0u8; TABLE_OFFSET];
+    let written = match entry_point {
+        EntryPoint::V2(point) => point.write_to(&mut header),
+        EntryPoint::V3(point) => point.write_to(&mut header),
+    };
+    written.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // Bytes past the entry point length are not part of the entry point
+    header[usize::from(entry_point.len()).min(TABLE_OFFSET)..].fill(0);
+    writer.write_all(&header)?;
+    writer.write_all(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InfoType, Structure};
+    use pretty_assertions::assert_eq;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+    const ENTRY_V3_BIN: &[u8] = include_bytes!("../tests/data/entry_v3.bin");
+    const DMI_V3_BIN: &[u8] = include_bytes!("../tests/data/dmi_v3.bin");
+
+    #[test]
+    fn round_trip() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let start = entry_point.smbios_address() as usize;
+        let mut dump = Vec::new();
+        entry_point
+            .write_dump(
+                &DMIDECODE_BIN[start..start + entry_point.smbios_len() as usize],
+                &mut dump,
+            )
+            .unwrap();
+        assert_eq!(DMIDECODE_BIN, &dump[..]);
+
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let mut dump = Vec::new();
+        entry_point.write_dump(DMI_V3_BIN, &mut dump).unwrap();
+        let reread = EntryPoint::search(&dump).unwrap();
+        assert_eq!(0x20, reread.smbios_address());
+        let reread = reread.structures(&dump[0x20..]).flatten().collect::<Vec<_>>();
+        let original = entry_point.structures(DMI_V3_BIN).flatten().collect::<Vec<_>>();
+        assert!(!original.is_empty());
+        assert_eq!(original, reread);
+    }
+
+    #[test]
+    fn filtered() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let mut structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let mut kept = Vec::new();
+        while let Some(structure) = structures.next_structure() {
+            let structure = structure.unwrap();
+            if let InfoType::System | InfoType::End = structure.info {
+                kept.push(structure);
+            }
+        }
+        let mut dump = Vec::new();
+        entry_point.write_dump_structures(kept.clone(), &mut dump).unwrap();
+
+        let reread = EntryPoint::search(&dump).unwrap();
+        match reread {
+            EntryPoint::V2(point) => assert_eq!(2, { point.smbios_count }),
+            EntryPoint::V3(_) => panic!("SMBIOS 2.x entry point expected"),
+        }
+        let decoded = reread.structures(&dump[0x20..]).collect::<Result<Vec<_>, _>>().unwrap();
+        assert!(matches!(decoded[..], [Structure::System(_), Structure::Other(_)]));
+        assert_eq!(kept.len(), decoded.len());
+    }
+}
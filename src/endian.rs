@@ -0,0 +1,89 @@
+//! Little-endian field types for packed structure layouts
+//!
+//! SMBIOS stores every multi-byte value in little-endian order. Packed structures read with
+//! `let_as_struct!` use these types for their WORD, DWORD and QWORD fields instead of native
+//! integers: the bytes are kept in table order and only converted when the value is read, so the
+//! decoding does not depend on the byte order of the host.
+
+/// A WORD in little-endian byte order
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub(crate) struct Le16([u8; 2]);
+
+/// A DWORD in little-endian byte order
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub(crate) struct Le32([u8; 4]);
+
+/// A QWORD in little-endian byte order
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub(crate) struct Le64([u8; 8]);
+
+macro_rules! le_type {
+    ($name:ident, $ty:ty) => {
+        impl $name {
+            /// The value in host byte order
+            pub(crate) fn get(self) -> $ty {
+                <$ty>::from_le_bytes(self.0)
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(value: $ty) -> Self {
+                $name(value.to_le_bytes())
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+    };
+}
+
+le_type!(Le16, u16);
+le_type!(Le32, u32);
+le_type!(Le64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_independent() -> Result<(), crate::MalformedStructureError> {
+        let_as_struct!(word, Le16, &[0x34, 0x12]);
+        assert_eq!(0x1234, word.get());
+        let_as_struct!(dword, Le32, &[0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(0x1234_5678, dword.get());
+        let_as_struct!(qword, Le64, &[0xEF, 0xCD, 0xAB, 0x89, 0x67, 0x45, 0x23, 0x01]);
+        assert_eq!(0x0123_4567_89AB_CDEF, qword.get());
+        Ok(())
+    }
+
+    #[test]
+    fn short_data() {
+        let read = |data| -> Result<u32, crate::MalformedStructureError> {
+            let_as_struct!(dword, Le32, data);
+            Ok(dword.get())
+        };
+        assert!(matches!(
+            read(&[0x01]),
+            Err(crate::MalformedStructureError::InvalidSlice(_))
+        ));
+        let read_or_zero = |data| -> u32 {
+            let_as_struct!(dword, Le32, data, or return 0);
+            dword.get()
+        };
+        assert_eq!(0, read_or_zero(&[0x01]));
+        assert_eq!(1, read_or_zero(&[0x01, 0x00, 0x00, 0x00, 0xFF]));
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!([0xCD, 0xAB], Le16::from(0xABCD).0);
+        assert_eq!(0xDEAD_BEEFu32, Le32::from(0xDEAD_BEEF).into());
+        assert_eq!(u64::MAX - 1, Le64::from(u64::MAX - 1).get());
+    }
+}
@@ -0,0 +1,137 @@
+//! Stable hardware identity digests
+//!
+//! [Structures::fingerprint] feeds a normalized subset of identifying fields into a caller
+//! supplied hash function: the system UUID and serial number, baseboard serial number, processor
+//! IDs and memory device serial numbers. Volatile fields such as boot-up states, event log tokens or
+//! current speeds are never included, and vendor placeholder strings (see [crate::normalize]) are
+//! treated as absent, so the digest only changes when the hardware does.
+//!
+//! Any [core::hash::Hasher] can be used directly; cryptographic digests implement
+//! [FingerprintHasher] instead.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::EntryPoint;
+//! use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! let mut hasher = DefaultHasher::new();
+//! structures.fingerprint(&mut hasher);
+//! let id = hasher.finish();
+//! ```
+
+use crate::{normalize::normalize, Structure, Structures};
+
+/// Sink for the bytes of a fingerprint
+pub trait FingerprintHasher {
+    fn update(&mut self, bytes: &[u8]);
+}
+
+impl<H: core::hash::Hasher> FingerprintHasher for H {
+    fn update(&mut self, bytes: &[u8]) {
+        self.write(bytes);
+    }
+}
+
+/// Identifies the field a fingerprint record was taken from
+#[repr(u8)]
+#[derive(Clone, Copy)]
+enum Field {
+    SystemUuid = 1,
+    SystemSerial,
+    BaseBoardSerial,
+    ProcessorId,
+    MemoryDeviceSerial,
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Feed the identifying fields of the table into `hasher`. Structures that fail to decode are
+    /// skipped.
+    pub fn fingerprint<H: FingerprintHasher + ?Sized>(&self, hasher: &mut H) {
+        for structure in self.clone().flatten() {
+            match structure {
+                Structure::System(system) => {
+                    if let Some(uuid) = system.uuid.filter(is_valid_uuid) {
+                        record(hasher, Field::SystemUuid, &uuid);
+                    }
+                    record_str(hasher, Field::SystemSerial, system.serial);
+                }
+                Structure::BaseBoard(baseboard) => record_str(hasher, Field::BaseBoardSerial, baseboard.serial),
+                Structure::Processor(processor) if processor.is_populated() => {
+                    record(hasher, Field::ProcessorId, &processor.processor_id.to_le_bytes())
+                }
+                Structure::MemoryDevice(device) if device.size_bytes().map_or(false, |size| size > 0) => {
+                    record_str(hasher, Field::MemoryDeviceSerial, device.serial)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// All zeros means the UUID is not present, all ones that it is not set
+fn is_valid_uuid(uuid: &[u8; 16]) -> bool {
+    uuid.iter().any(|&b| b != 0x00) && uuid.iter().any(|&b| b != 0xFF)
+}
+
+fn record_str<H: FingerprintHasher + ?Sized>(hasher: &mut H, field: Field, value: &str) {
+    if let Some(value) = normalize(value) {
+        record(hasher, field, value.as_bytes());
+    }
+}
+
+/// Records are tagged and length-prefixed so that adjacent fields cannot run into each other
+fn record<H: FingerprintHasher + ?Sized>(hasher: &mut H, field: Field, bytes: &[u8]) {
+    hasher.update(&[field as u8]);
+    hasher.update(&(bytes.len() as u32).to_le_bytes());
+    hasher.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EntryPoint;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmi.0.bin");
+
+    #[derive(Default)]
+    struct Recorder(Vec<u8>);
+    impl FingerprintHasher for Recorder {
+        fn update(&mut self, bytes: &[u8]) {
+            self.0.extend_from_slice(bytes);
+        }
+    }
+
+    #[test]
+    fn fingerprint() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let mut first = Recorder::default();
+        structures.fingerprint(&mut first);
+        let mut second = Recorder::default();
+        structures.fingerprint(&mut second);
+        assert_eq!(first.0, second.0);
+
+        // UUID, system serial, baseboard serial, 2 processors and 8 populated DIMMs
+        let mut records = 0;
+        let mut rest = &first.0[..];
+        while !rest.is_empty() {
+            let len = u32::from_le_bytes([rest[1], rest[2], rest[3], rest[4]]) as usize;
+            rest = &rest[5 + len..];
+            records += 1;
+        }
+        assert_eq!(13, records);
+    }
+
+    #[test]
+    fn uuid_validity() {
+        assert!(!is_valid_uuid(&[0x00; 16]));
+        assert!(!is_valid_uuid(&[0xFF; 16]));
+        assert!(is_valid_uuid(&[0x4C; 16]));
+    }
+}
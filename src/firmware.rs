@@ -0,0 +1,257 @@
+//! Structure tables embedded in firmware images
+//!
+//! Firmware that builds its SMBIOS table at build time stores the entry point and the table in a
+//! firmware volume of the flash image, at no fixed offset. [scan] looks for the entry point
+//! anchors at every offset of the image, without parsing the volume layout, and keeps the entry
+//! points that pass stricter checks than [EntryPoint::search]: the checksums, a table length that
+//! fits in the image, and a table found in the image whose structures all read without error.
+//!
+//! The table address of an entry point is a physical address, which is mapped to the image in the
+//! ways listed by [Placement]. The candidates are ranked, most plausible first.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::firmware::scan;
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let mut image = vec![0xFF; 0x1000];
+//! image.extend_from_slice(DMIDECODE_BIN);
+//! let candidates = scan(&image);
+//! assert_eq!(0x1000, candidates[0].entry_point_offset);
+//! assert_eq!(69, candidates[0].structure_count);
+//! ```
+
+use core::{cmp::Reverse, convert::TryFrom};
+
+use alloc::vec::Vec;
+
+use crate::{EntryPoint, EntryPointRef, InfoType, SearchOptions, Structures};
+
+/// How the table address of an entry point was mapped to an offset in the image
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Placement {
+    /// The table address is relative to the entry point, as in `dmidecode --dump-bin` dumps
+    EntryPointRelative,
+    /// The image is mapped right below 4 GiB, as the boot flash of x86 systems
+    TopOfMemory,
+    /// The table address is an offset in the image
+    ImageOffset,
+}
+
+/// An entry point found in an image, with the table it points to
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Candidate<'image> {
+    pub entry_point: EntryPoint,
+    /// Offset of the entry point in the image
+    pub entry_point_offset: usize,
+    pub placement: Placement,
+    /// Offset of the structure table in the image
+    pub table_offset: usize,
+    /// The number of structures read from the table
+    pub structure_count: usize,
+    /// Whether the table contains an End-of-Table structure
+    pub terminated: bool,
+    table: &'image [u8],
+}
+
+impl<'image> Candidate<'image> {
+    /// The bytes of the image from the start of the table, as long as the entry point tells
+    pub fn table(&self) -> &'image [u8] {
+        self.table
+    }
+
+    /// Iterate over the structures of the table
+    pub fn structures(&self) -> Structures<'image> {
+        self.entry_point.structures(self.table)
+    }
+
+    /// Whether the structure count of an SMBIOS 2.x entry point matches the table, always true
+    /// for SMBIOS 3.x which has no count
+    pub fn count_matches(&self) -> bool {
+        match self.entry_point {
+            EntryPoint::V2(ref point) => point.smbios_count as usize == self.structure_count,
+            EntryPoint::V3(_) => true,
+        }
+    }
+
+    /// Candidates sort by decreasing plausibility
+    fn rank(&self) -> impl Ord {
+        (
+            Reverse(self.count_matches()),
+            Reverse(self.terminated),
+            Reverse(self.structure_count),
+            self.placement,
+            self.entry_point_offset,
+        )
+    }
+}
+
+/// Find the structure tables of a firmware image, most plausible first
+///
+/// Every anchor is tried, including those that are not on a 16-byte boundary, and every
+/// [Placement] of the table; a table is only kept if it lies within the image and contains at
+/// least one structure, all of which read without error.
+pub fn scan(image: &[u8]) -> Vec<Candidate<'_>> {
+    let mut candidates = Vec::new();
+    for offset in anchors(image) {
+        let entry_point = match EntryPointRef::search_with(&image[offset..], SearchOptions::default()) {
+            Ok(entry_point) if entry_point.offset() == 0 => EntryPoint::from(entry_point),
+            _ => continue,
+        };
+        if entry_point.smbios_len() == 0 || entry_point.smbios_len() as usize > image.len() {
+            lib_debug!(
+                "skipping entry point at {:#X} with a table length out of the image",
+                offset
+            );
+            continue;
+        }
+        let placements = [
+            (
+                Placement::EntryPointRelative,
+                offset.checked_add(entry_point.smbios_address() as usize),
+            ),
+            (
+                Placement::TopOfMemory,
+                top_of_memory_offset(image, entry_point.smbios_address()),
+            ),
+            (
+                Placement::ImageOffset,
+                usize::try_from(entry_point.smbios_address()).ok(),
+            ),
+        ];
+        for &(placement, table_offset) in placements.iter() {
+            let table_offset = match table_offset {
+                Some(table_offset) if table_offset < image.len() => table_offset,
+                _ => continue,
+            };
+            if candidates
+                .iter()
+                .any(|c: &Candidate| c.entry_point_offset == offset && c.table_offset == table_offset)
+            {
+                continue;
+            }
+            if let Some(candidate) = read_table(image, entry_point, offset, placement, table_offset) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates.sort_by_key(|candidate| candidate.rank());
+    candidates
+}
+
+/// Offsets of the `_SM_` and `_SM3_` anchors in `image`
+fn anchors(image: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    image
+        .windows(5)
+        .enumerate()
+        .filter(|(_, window)| window.starts_with(b"_SM_") || window == b"_SM3_")
+        .map(|(offset, _)| offset)
+}
+
+/// The image offset of `address` if the image ends at 4 GiB
+fn top_of_memory_offset(image: &[u8], address: u64) -> Option<usize> {
+    let base = (1u64 << 32).checked_sub(image.len() as u64)?;
+    usize::try_from(address.checked_sub(base)?).ok()
+}
+
+fn read_table<'image>(
+    image: &'image [u8],
+    entry_point: EntryPoint,
+    entry_point_offset: usize,
+    placement: Placement,
+    table_offset: usize,
+) -> Option<Candidate<'image>> {
+    let available = &image[table_offset..];
+    let table = match entry_point {
+        // The SMBIOS 2.x table length is exact, the SMBIOS 3.x one is a maximum
+        EntryPoint::V2(_) => available.get(..entry_point.smbios_len() as usize)?,
+        EntryPoint::V3(_) => &available[..available.len().min(entry_point.smbios_len() as usize)],
+    };
+    let mut structure_count = 0;
+    let mut terminated = false;
+    for structure in entry_point.raw_structures(table) {
+        let structure = structure.ok()?;
+        structure_count += 1;
+        terminated |= structure.info == InfoType::End;
+    }
+    if structure_count == 0 {
+        return None;
+    }
+    Some(Candidate {
+        entry_point,
+        entry_point_offset,
+        placement,
+        table_offset,
+        structure_count,
+        terminated,
+        table,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::vec;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+    const DMI_V3_BIN: &[u8] = include_bytes!("../tests/data/dmi_v3.bin");
+    const ENTRY_V3_BIN: &[u8] = include_bytes!("../tests/data/entry_v3.bin");
+
+    #[test]
+    fn unaligned_dump() {
+        let mut image = vec![0x5F; 0x23];
+        image.extend_from_slice(DMIDECODE_BIN);
+        let candidates = scan(&image);
+        assert_eq!(1, candidates.len());
+        let candidate = &candidates[0];
+        assert_eq!(
+            (0x23, Placement::EntryPointRelative, 0x43),
+            (
+                candidate.entry_point_offset,
+                candidate.placement,
+                candidate.table_offset
+            )
+        );
+        assert!(candidate.count_matches());
+        assert_eq!(69, candidate.structures().count());
+    }
+
+    #[test]
+    fn top_of_memory() {
+        // An image mapped at 4 GiB - 0x4000, its table at 0xFFFF_D000
+        let mut image = vec![0xFF; 0x4000];
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let mut entry = ENTRY_V3_BIN[..entry_point.len() as usize].to_vec();
+        entry[0x10..0x18].copy_from_slice(&0xFFFF_D000u64.to_le_bytes());
+        let sum = entry.iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        entry[0x05] = entry[0x05].wrapping_sub(sum);
+        image[0x800..0x800 + entry.len()].copy_from_slice(&entry);
+        let table_len = DMI_V3_BIN.len().min(0x4000 - 0x1000);
+        image[0x1000..0x1000 + table_len].copy_from_slice(&DMI_V3_BIN[..table_len]);
+
+        let candidates = scan(&image);
+        let candidate = &candidates[0];
+        assert_eq!(
+            (0x800, Placement::TopOfMemory, 0x1000),
+            (
+                candidate.entry_point_offset,
+                candidate.placement,
+                candidate.table_offset
+            )
+        );
+        assert!(candidate.terminated);
+    }
+
+    #[test]
+    fn no_table() {
+        // The entry point alone, its table address outside of the image
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        assert_eq!(
+            Vec::<Candidate>::new(),
+            scan(&DMIDECODE_BIN[..entry_point.len() as usize])
+        );
+        assert_eq!(Vec::<Candidate>::new(), scan(&[0xFF; 64]));
+    }
+}
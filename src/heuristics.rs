@@ -0,0 +1,281 @@
+//! Inferences drawn from the content of a table rather than from its structure
+//!
+//! SMBIOS has no field telling whether the system is a virtual machine, short of a BIOS
+//! characteristics bit few hypervisors set. Hypervisors and cloud providers do however fill the
+//! vendor and product strings with recognizable values; [virtualization] matches them against a
+//! list of known signatures.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{heuristics::virtualization, EntryPoint};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! assert_eq!(None, virtualization(structures));
+//! ```
+
+#[cfg(feature = "strings")]
+use core::fmt;
+
+use crate::{Structure, Structures};
+
+/// A hypervisor or a cloud provider recognized by [virtualization]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Hypervisor {
+    /// QEMU, with or without KVM acceleration
+    Qemu,
+    /// KVM with a virtual machine monitor other than QEMU
+    Kvm,
+    VMware,
+    HyperV,
+    VirtualBox,
+    Xen,
+    Bochs,
+    Parallels,
+    Bhyve,
+    AmazonEc2,
+    GoogleCompute,
+    Azure,
+    AlibabaCloud,
+    OracleCloud,
+    OpenStack,
+    /// The BIOS characteristics tell that the system is a virtual machine, but no signature
+    /// matched
+    Unknown,
+}
+
+impl Hypervisor {
+    /// Cloud providers are told apart from the hypervisor they run, which may also be matched
+    fn rank(self) -> u8 {
+        match self {
+            Hypervisor::Unknown => 0,
+            Hypervisor::AmazonEc2
+            | Hypervisor::GoogleCompute
+            | Hypervisor::Azure
+            | Hypervisor::AlibabaCloud
+            | Hypervisor::OracleCloud
+            | Hypervisor::OpenStack => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Display for Hypervisor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hypervisor::Qemu => write!(f, "QEMU"),
+            Hypervisor::Kvm => write!(f, "KVM"),
+            Hypervisor::VMware => write!(f, "VMware"),
+            Hypervisor::HyperV => write!(f, "Microsoft Hyper-V"),
+            Hypervisor::VirtualBox => write!(f, "Oracle VirtualBox"),
+            Hypervisor::Xen => write!(f, "Xen"),
+            Hypervisor::Bochs => write!(f, "Bochs"),
+            Hypervisor::Parallels => write!(f, "Parallels"),
+            Hypervisor::Bhyve => write!(f, "bhyve"),
+            Hypervisor::AmazonEc2 => write!(f, "Amazon EC2"),
+            Hypervisor::GoogleCompute => write!(f, "Google Compute Engine"),
+            Hypervisor::Azure => write!(f, "Microsoft Azure"),
+            Hypervisor::AlibabaCloud => write!(f, "Alibaba Cloud"),
+            Hypervisor::OracleCloud => write!(f, "Oracle Cloud"),
+            Hypervisor::OpenStack => write!(f, "OpenStack"),
+            Hypervisor::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Prefixes of the System, Base Board and BIOS vendor and product strings
+const VENDOR_SIGNATURES: &[(&str, Hypervisor)] = &[
+    ("Amazon EC2", Hypervisor::AmazonEc2),
+    ("Google Compute Engine", Hypervisor::GoogleCompute),
+    ("Alibaba Cloud", Hypervisor::AlibabaCloud),
+    ("OpenStack", Hypervisor::OpenStack),
+    ("KVM", Hypervisor::Kvm),
+    ("QEMU", Hypervisor::Qemu),
+    ("VMware", Hypervisor::VMware),
+    ("VMW", Hypervisor::VMware),
+    ("innotek GmbH", Hypervisor::VirtualBox),
+    ("VirtualBox", Hypervisor::VirtualBox),
+    ("Xen", Hypervisor::Xen),
+    ("Bochs", Hypervisor::Bochs),
+    ("Parallels", Hypervisor::Parallels),
+    ("BHYVE", Hypervisor::Bhyve),
+];
+
+/// Chassis asset tags set by cloud providers
+const ASSET_TAG_SIGNATURES: &[(&str, Hypervisor)] = &[
+    ("7783-7084-3265-9085-8269-3286-77", Hypervisor::Azure),
+    ("OracleCloud.com", Hypervisor::OracleCloud),
+];
+
+/// Prefixes of OEM strings
+const OEM_SIGNATURES: &[(&str, Hypervisor)] = &[("vboxVer_", Hypervisor::VirtualBox)];
+
+/// Recognize the hypervisor or cloud provider the table was generated by
+///
+/// Returns `None` if nothing in the table hints at a virtual machine, which does not prove that
+/// the system is physical. Structures that fail to decode are skipped.
+pub fn virtualization(structures: Structures<'_>) -> Option<Hypervisor> {
+    let mut found: Option<Hypervisor> = None;
+    let mut propose = |hypervisor: Hypervisor| {
+        if found.map_or(true, |found| hypervisor.rank() > found.rank()) {
+            found = Some(hypervisor);
+        }
+    };
+    let matching = |signatures: &[(&str, Hypervisor)], value: &str| {
+        signatures
+            .iter()
+            .find(|(signature, _)| value.trim().starts_with(signature))
+            .map(|&(_, hypervisor)| hypervisor)
+    };
+
+    for structure in structures.flatten() {
+        let (vendor, product) = match structure {
+            Structure::System(system) => {
+                if system.manufacturer.trim() == "Microsoft Corporation" && system.product.trim() == "Virtual Machine" {
+                    propose(Hypervisor::HyperV);
+                }
+                (system.manufacturer, system.product)
+            }
+            Structure::BaseBoard(board) => (board.manufacturer, board.product),
+            Structure::Bios(bios) => {
+                if bios.is_virtual_machine() {
+                    propose(Hypervisor::Unknown);
+                }
+                // Xen based EC2 instances only tell in the BIOS version, e.g. "4.11.amazon"
+                if bios.bios_version.trim().ends_with(".amazon") {
+                    propose(Hypervisor::AmazonEc2);
+                }
+                (bios.vendor, "")
+            }
+            Structure::Enclosure(enclosure) => {
+                if let Some(hypervisor) = matching(ASSET_TAG_SIGNATURES, enclosure.asset_tag_number) {
+                    propose(hypervisor);
+                }
+                continue;
+            }
+            Structure::OemStrings(oem) => {
+                for hypervisor in oem.strings.filter_map(|s| matching(OEM_SIGNATURES, s)) {
+                    propose(hypervisor);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        for value in [vendor, product] {
+            if let Some(hypervisor) = matching(VENDOR_SIGNATURES, value) {
+                propose(hypervisor);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{StructureBuilder, TableBuilder},
+        EntryPoint, Handle, InfoType,
+    };
+    use pretty_assertions::assert_eq;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmi.0.bin");
+
+    fn system(manufacturer: &str, product: &str) -> StructureBuilder {
+        StructureBuilder::new(InfoType::System)
+            .handle(Handle(0x0100))
+            .string(manufacturer)
+            .string(product)
+            .string("")
+            .string("")
+    }
+
+    fn detect(structures: &[StructureBuilder]) -> Option<Hypervisor> {
+        let table = structures
+            .iter()
+            .fold(TableBuilder::new((2, 8).into()), |table, structure| {
+                table.push(structure)
+            })
+            .end(Handle(0xFEFF));
+        virtualization(table.structures())
+    }
+
+    #[test]
+    fn physical() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        assert_eq!(None, virtualization(structures));
+        assert_eq!(None, detect(&[system("Dell Inc.", "PowerEdge R740")]));
+    }
+
+    #[test]
+    fn vendor_signatures() {
+        let samples = [
+            ("QEMU", "Standard PC (Q35 + ICH9, 2009)", Hypervisor::Qemu),
+            ("VMware, Inc.", "VMware7,1", Hypervisor::VMware),
+            ("innotek GmbH", "VirtualBox", Hypervisor::VirtualBox),
+            ("Xen", "HVM domU", Hypervisor::Xen),
+            ("Microsoft Corporation", "Virtual Machine", Hypervisor::HyperV),
+            ("Amazon EC2", "m5.large", Hypervisor::AmazonEc2),
+            ("Google", "Google Compute Engine", Hypervisor::GoogleCompute),
+            ("OpenStack Foundation", "OpenStack Nova", Hypervisor::OpenStack),
+        ];
+        for (manufacturer, product, expected) in samples {
+            assert_eq!(
+                Some(expected),
+                detect(&[system(manufacturer, product)]),
+                "{}",
+                manufacturer
+            );
+        }
+    }
+
+    #[test]
+    fn cloud_over_hypervisor() {
+        let azure = StructureBuilder::new(InfoType::Enclosure)
+            .handle(Handle(0x0300))
+            .string("Microsoft Corporation")
+            .byte(0x03)
+            .string("")
+            .string("")
+            .string("7783-7084-3265-9085-8269-3286-77");
+        assert_eq!(
+            Some(Hypervisor::Azure),
+            detect(&[system("Microsoft Corporation", "Virtual Machine"), azure])
+        );
+
+        let ec2 = StructureBuilder::new(InfoType::Bios)
+            .string("Xen")
+            .string("4.11.amazon")
+            .word(0xE800)
+            .string("")
+            .byte(0)
+            .qword(0);
+        assert_eq!(Some(Hypervisor::AmazonEc2), detect(&[system("Xen", "HVM domU"), ec2]));
+    }
+
+    #[test]
+    fn virtual_machine_bit() {
+        let bios = StructureBuilder::new(InfoType::Bios)
+            .string("Vendor")
+            .string("1.0")
+            .word(0xE800)
+            .string("")
+            .byte(0)
+            .qword(0)
+            .byte(0)
+            .byte(0b0001_0000);
+        assert_eq!(Some(Hypervisor::Kvm), detect(&[bios.clone(), system("", "KVM")]));
+        assert_eq!(Some(Hypervisor::Unknown), detect(&[bios]));
+
+        let oem = StructureBuilder::new(InfoType::OemStrings)
+            .byte(2)
+            .extra_string("vboxVer_7.0.10")
+            .extra_string("vboxRev_158379");
+        assert_eq!(Some(Hypervisor::VirtualBox), detect(&[oem]));
+    }
+}
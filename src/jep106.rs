@@ -0,0 +1,135 @@
+//! JEDEC JEP-106 manufacturer identification codes
+//!
+//! Memory Device structures carry the manufacturer IDs read from the SPD of the module as JEP-106
+//! codes: the number of continuation codes, which selects the bank, followed by the manufacturer
+//! code within the bank. [Jep106Id] splits them and [Jep106Id::name] looks the manufacturer up in a
+//! table of the memory module and DRAM vendors, a subset of the full JEP-106 list.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::jep106::Jep106Id;
+//!
+//! // No continuation code, then 0xCE
+//! let id = Jep106Id::from_smbios(0xCE00).unwrap();
+//! assert_eq!((1, 0xCE), (id.bank, id.code));
+//! assert_eq!(Some("Samsung"), id.name());
+//! ```
+
+#[cfg(feature = "strings")]
+use core::fmt;
+
+/// A manufacturer identified by its JEP-106 bank and code
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Jep106Id {
+    /// Bank number, one more than the number of continuation codes
+    pub bank: u8,
+    /// Manufacturer code within the bank, odd parity bit included
+    pub code: u8,
+}
+
+impl Jep106Id {
+    /// Split a two-byte ID as stored in SMBIOS, the number of continuation codes first
+    ///
+    /// Returns `None` for 0, the value of an unknown manufacturer.
+    pub fn from_smbios(id: u16) -> Option<Self> {
+        if id == 0 {
+            return None;
+        }
+        let [continuations, code] = id.to_le_bytes();
+        Some(Jep106Id {
+            bank: (continuations & 0x7F) + 1,
+            code,
+        })
+    }
+
+    /// The manufacturer name, `None` if the code is not in the table
+    pub fn name(self) -> Option<&'static str> {
+        MANUFACTURERS
+            .iter()
+            .find(|&&(bank, code, _)| bank == self.bank && code & 0x7F == self.code & 0x7F)
+            .map(|&(_, _, name)| name)
+    }
+}
+
+/// Formatted as the manufacturer name, or as in the dmidecode output when it is unknown, e.g.
+/// `Bank 1, Hex 0xCE`
+#[cfg(feature = "strings")]
+impl fmt::Display for Jep106Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "Bank {}, Hex {:#04X}", self.bank, self.code),
+        }
+    }
+}
+
+/// Bank, code and name of memory module and DRAM manufacturers
+const MANUFACTURERS: &[(u8, u8, &str)] = &[
+    (1, 0x01, "AMD"),
+    (1, 0x04, "Fujitsu"),
+    (1, 0x07, "Hitachi"),
+    (1, 0x10, "NEC"),
+    (1, 0x1C, "Mitsubishi"),
+    (1, 0x2C, "Micron Technology"),
+    (1, 0x4F, "Transcend Information"),
+    (1, 0x89, "Intel"),
+    (1, 0x94, "SMART Modular"),
+    (1, 0x97, "Texas Instruments"),
+    (1, 0x98, "Toshiba"),
+    (1, 0xA4, "IBM"),
+    (1, 0xAD, "SK Hynix"),
+    (1, 0xB3, "IDT"),
+    (1, 0xC1, "Infineon"),
+    (1, 0xC2, "Macronix"),
+    (1, 0xCE, "Samsung"),
+    (1, 0xDA, "Winbond"),
+    (1, 0xFE, "Elpida"),
+    (2, 0x7A, "Apacer Technology"),
+    (2, 0x98, "Kingston"),
+    (3, 0x02, "Patriot Memory"),
+    (3, 0x16, "Netlist"),
+    (3, 0x9E, "Corsair"),
+    (4, 0x0B, "Nanya Technology"),
+    (5, 0x43, "Ramaxel Technology"),
+    (5, 0xCB, "A-DATA Technology"),
+    (5, 0xCD, "G.Skill"),
+    (5, 0xEF, "Team Group"),
+    (6, 0x51, "Qimonda"),
+    (6, 0x9B, "Crucial Technology"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_smbios() {
+        assert_eq!(None, Jep106Id::from_smbios(0));
+        let kingston = Jep106Id::from_smbios(0x9801).unwrap();
+        assert_eq!(
+            (2, 0x98, Some("Kingston")),
+            (kingston.bank, kingston.code, kingston.name())
+        );
+        // The parity bit of the continuation count is ignored
+        assert_eq!(Some("Kingston"), Jep106Id::from_smbios(0x9881).and_then(Jep106Id::name));
+        assert_eq!(Some("SK Hynix"), Jep106Id::from_smbios(0xAD80).and_then(Jep106Id::name));
+        assert_eq!(None, Jep106Id::from_smbios(0x4F0A).unwrap().name());
+    }
+
+    #[test]
+    fn parity() {
+        for &(_, code, name) in MANUFACTURERS {
+            assert_eq!(1, code.count_ones() % 2, "{}", name);
+        }
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn display() {
+        use std::format;
+
+        assert_eq!("Samsung", format!("{}", Jep106Id { bank: 1, code: 0xCE }));
+        assert_eq!("Bank 11, Hex 0x4F", format!("{}", Jep106Id { bank: 11, code: 0x4F }));
+    }
+}
@@ -0,0 +1,378 @@
+//! Single value lookups by `dmidecode --string` keyword
+//!
+//! [get] answers the questions scripts usually ask `dmidecode -s`, e.g. `system-serial-number`,
+//! without walking the table by hand. Values that are not strings in the table, such as the UUID or
+//! the chassis type, are returned as a [Value] formatted like the dmidecode output.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{keyword::{self, Keyword}, EntryPoint};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! let keyword: Keyword = "system-serial-number".parse().unwrap();
+//! if let Some(serial) = keyword::get(&structures, keyword).and_then(|value| value.as_str()) {
+//!     println!("{}", serial);
+//! }
+//! ```
+
+use core::{fmt, str::FromStr};
+
+use crate::{
+    bios::{BiosRevision, FirmwareRevision},
+    enclosure::EnclosureType,
+    processor::ProcessorFamily,
+    SmbiosVersion, Structure, Structures,
+};
+
+/// The keywords of `dmidecode --string`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Keyword {
+    BiosVendor,
+    BiosVersion,
+    BiosReleaseDate,
+    BiosRevision,
+    FirmwareRevision,
+    SystemManufacturer,
+    SystemProductName,
+    SystemVersion,
+    SystemSerialNumber,
+    SystemUuid,
+    SystemSkuNumber,
+    SystemFamily,
+    BaseboardManufacturer,
+    BaseboardProductName,
+    BaseboardVersion,
+    BaseboardSerialNumber,
+    BaseboardAssetTag,
+    ChassisManufacturer,
+    ChassisType,
+    ChassisVersion,
+    ChassisSerialNumber,
+    ChassisAssetTag,
+    ProcessorFamily,
+    ProcessorManufacturer,
+    ProcessorVersion,
+    ProcessorFrequency,
+}
+
+impl Keyword {
+    /// Every keyword, in the order of the dmidecode manual
+    pub const ALL: &'static [Keyword] = &[
+        Keyword::BiosVendor,
+        Keyword::BiosVersion,
+        Keyword::BiosReleaseDate,
+        Keyword::BiosRevision,
+        Keyword::FirmwareRevision,
+        Keyword::SystemManufacturer,
+        Keyword::SystemProductName,
+        Keyword::SystemVersion,
+        Keyword::SystemSerialNumber,
+        Keyword::SystemUuid,
+        Keyword::SystemSkuNumber,
+        Keyword::SystemFamily,
+        Keyword::BaseboardManufacturer,
+        Keyword::BaseboardProductName,
+        Keyword::BaseboardVersion,
+        Keyword::BaseboardSerialNumber,
+        Keyword::BaseboardAssetTag,
+        Keyword::ChassisManufacturer,
+        Keyword::ChassisType,
+        Keyword::ChassisVersion,
+        Keyword::ChassisSerialNumber,
+        Keyword::ChassisAssetTag,
+        Keyword::ProcessorFamily,
+        Keyword::ProcessorManufacturer,
+        Keyword::ProcessorVersion,
+        Keyword::ProcessorFrequency,
+    ];
+
+    /// The keyword as given on the dmidecode command line, e.g. `bios-version`
+    pub fn name(self) -> &'static str {
+        match self {
+            Keyword::BiosVendor => "bios-vendor",
+            Keyword::BiosVersion => "bios-version",
+            Keyword::BiosReleaseDate => "bios-release-date",
+            Keyword::BiosRevision => "bios-revision",
+            Keyword::FirmwareRevision => "firmware-revision",
+            Keyword::SystemManufacturer => "system-manufacturer",
+            Keyword::SystemProductName => "system-product-name",
+            Keyword::SystemVersion => "system-version",
+            Keyword::SystemSerialNumber => "system-serial-number",
+            Keyword::SystemUuid => "system-uuid",
+            Keyword::SystemSkuNumber => "system-sku-number",
+            Keyword::SystemFamily => "system-family",
+            Keyword::BaseboardManufacturer => "baseboard-manufacturer",
+            Keyword::BaseboardProductName => "baseboard-product-name",
+            Keyword::BaseboardVersion => "baseboard-version",
+            Keyword::BaseboardSerialNumber => "baseboard-serial-number",
+            Keyword::BaseboardAssetTag => "baseboard-asset-tag",
+            Keyword::ChassisManufacturer => "chassis-manufacturer",
+            Keyword::ChassisType => "chassis-type",
+            Keyword::ChassisVersion => "chassis-version",
+            Keyword::ChassisSerialNumber => "chassis-serial-number",
+            Keyword::ChassisAssetTag => "chassis-asset-tag",
+            Keyword::ProcessorFamily => "processor-family",
+            Keyword::ProcessorManufacturer => "processor-manufacturer",
+            Keyword::ProcessorVersion => "processor-version",
+            Keyword::ProcessorFrequency => "processor-frequency",
+        }
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when parsing an unknown keyword
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UnknownKeyword;
+
+impl fmt::Display for UnknownKeyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown dmidecode string keyword")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownKeyword {}
+
+/// Parses the dmidecode keyword, ignoring case
+impl FromStr for Keyword {
+    type Err = UnknownKeyword;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Keyword::ALL
+            .iter()
+            .copied()
+            .find(|keyword| keyword.name().eq_ignore_ascii_case(s))
+            .ok_or(UnknownKeyword)
+    }
+}
+
+/// The value of a keyword
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Value<'buffer> {
+    String(&'buffer str),
+    BiosRevision(BiosRevision),
+    FirmwareRevision(FirmwareRevision),
+    /// The system UUID in RFC 4122 byte order, whatever the byte order in the table
+    Uuid([u8; 16]),
+    ChassisType(EnclosureType),
+    ProcessorFamily(ProcessorFamily),
+    /// Current processor speed in MHz
+    ProcessorFrequency(u16),
+}
+
+impl<'buffer> Value<'buffer> {
+    /// The value of a keyword that is a string in the table
+    pub fn as_str(&self) -> Option<&'buffer str> {
+        match *self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Formatted as in the dmidecode output
+#[cfg(feature = "strings")]
+impl<'buffer> fmt::Display for Value<'buffer> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => f.write_str(s),
+            Value::BiosRevision(revision) => write!(f, "{}", revision),
+            Value::FirmwareRevision(revision) => write!(f, "{}", revision),
+            Value::Uuid(uuid) if uuid.iter().all(|&b| b == 0xFF) => write!(f, "Not Settable"),
+            Value::Uuid(uuid) if uuid.iter().all(|&b| b == 0x00) => write!(f, "Not Present"),
+            Value::Uuid(uuid) => {
+                for (i, byte) in uuid.iter().enumerate() {
+                    if let 4 | 6 | 8 | 10 = i {
+                        write!(f, "-")?;
+                    }
+                    write!(f, "{:02X}", byte)?;
+                }
+                Ok(())
+            }
+            Value::ChassisType(enclosure_type) => write!(f, "{}", enclosure_type),
+            Value::ProcessorFamily(family) => write!(f, "{}", family),
+            Value::ProcessorFrequency(0) => write!(f, "Unknown"),
+            Value::ProcessorFrequency(mhz) => write!(f, "{} MHz", mhz),
+        }
+    }
+}
+
+/// Look up the value of `keyword`. Like dmidecode, the first structure of the keyword's type is
+/// used, for processors this is the first socket. Structures that fail to decode are skipped.
+///
+/// Returns `None` if the table has no such structure or if the field is missing from it.
+pub fn get<'buffer>(structures: &Structures<'buffer>, keyword: Keyword) -> Option<Value<'buffer>> {
+    let version = structures.smbios_version;
+    let mut structures = structures.clone().flatten();
+    match keyword {
+        Keyword::BiosVendor
+        | Keyword::BiosVersion
+        | Keyword::BiosReleaseDate
+        | Keyword::BiosRevision
+        | Keyword::FirmwareRevision => {
+            let bios = structures.find_map(|s| match s {
+                Structure::Bios(bios) => Some(bios),
+                _ => None,
+            })?;
+            match keyword {
+                Keyword::BiosVendor => Some(Value::String(bios.vendor)),
+                Keyword::BiosVersion => Some(Value::String(bios.bios_version)),
+                Keyword::BiosReleaseDate => Some(Value::String(bios.bios_release_date)),
+                Keyword::BiosRevision => bios.bios_revision.map(Value::BiosRevision),
+                _ => bios.firmware_revision.map(Value::FirmwareRevision),
+            }
+        }
+        Keyword::SystemManufacturer
+        | Keyword::SystemProductName
+        | Keyword::SystemVersion
+        | Keyword::SystemSerialNumber
+        | Keyword::SystemUuid
+        | Keyword::SystemSkuNumber
+        | Keyword::SystemFamily => {
+            let system = structures.find_map(|s| match s {
+                Structure::System(system) => Some(system),
+                _ => None,
+            })?;
+            match keyword {
+                Keyword::SystemManufacturer => Some(Value::String(system.manufacturer)),
+                Keyword::SystemProductName => Some(Value::String(system.product)),
+                Keyword::SystemVersion => Some(Value::String(system.version)),
+                Keyword::SystemSerialNumber => Some(Value::String(system.serial)),
+                Keyword::SystemUuid => system.uuid.map(|uuid| Value::Uuid(rfc4122_uuid(uuid, version))),
+                Keyword::SystemSkuNumber => system.sku.map(Value::String),
+                _ => system.family.map(Value::String),
+            }
+        }
+        Keyword::BaseboardManufacturer
+        | Keyword::BaseboardProductName
+        | Keyword::BaseboardVersion
+        | Keyword::BaseboardSerialNumber
+        | Keyword::BaseboardAssetTag => {
+            let baseboard = structures.find_map(|s| match s {
+                Structure::BaseBoard(baseboard) => Some(baseboard),
+                _ => None,
+            })?;
+            match keyword {
+                Keyword::BaseboardManufacturer => Some(Value::String(baseboard.manufacturer)),
+                Keyword::BaseboardProductName => Some(Value::String(baseboard.product)),
+                Keyword::BaseboardVersion => Some(Value::String(baseboard.version)),
+                Keyword::BaseboardSerialNumber => Some(Value::String(baseboard.serial)),
+                _ => baseboard.asset.map(Value::String),
+            }
+        }
+        Keyword::ChassisManufacturer
+        | Keyword::ChassisType
+        | Keyword::ChassisVersion
+        | Keyword::ChassisSerialNumber
+        | Keyword::ChassisAssetTag => {
+            let enclosure = structures.find_map(|s| match s {
+                Structure::Enclosure(enclosure) => Some(enclosure),
+                _ => None,
+            })?;
+            Some(match keyword {
+                Keyword::ChassisManufacturer => Value::String(enclosure.manufacturer),
+                Keyword::ChassisType => Value::ChassisType(enclosure.enclosure_type),
+                Keyword::ChassisVersion => Value::String(enclosure.version),
+                Keyword::ChassisSerialNumber => Value::String(enclosure.serial_number),
+                _ => Value::String(enclosure.asset_tag_number),
+            })
+        }
+        Keyword::ProcessorFamily
+        | Keyword::ProcessorManufacturer
+        | Keyword::ProcessorVersion
+        | Keyword::ProcessorFrequency => {
+            let processor = structures.find_map(|s| match s {
+                Structure::Processor(processor) => Some(processor),
+                _ => None,
+            })?;
+            Some(match keyword {
+                Keyword::ProcessorFamily => Value::ProcessorFamily(processor.processor_family),
+                Keyword::ProcessorManufacturer => Value::String(processor.processor_manufacturer),
+                Keyword::ProcessorVersion => Value::String(processor.processor_version),
+                _ => Value::ProcessorFrequency(processor.current_speed),
+            })
+        }
+    }
+}
+
+/// Since SMBIOS 2.6 the first three UUID fields are stored little-endian
+fn rfc4122_uuid(mut uuid: [u8; 16], version: SmbiosVersion) -> [u8; 16] {
+    if version_gate!(version, (2, 6)..) {
+        uuid[0..4].reverse();
+        uuid[4..6].reverse();
+        uuid[6..8].reverse();
+    }
+    uuid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EntryPoint;
+    use pretty_assertions::assert_eq;
+    #[cfg(feature = "strings")]
+    use std::format;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn keyword_names() {
+        for &keyword in Keyword::ALL {
+            assert_eq!(Ok(keyword), keyword.name().parse());
+        }
+        assert_eq!(Ok(Keyword::SystemUuid), "System-UUID".parse());
+        assert_eq!(Err(UnknownKeyword), "system-serial".parse::<Keyword>());
+    }
+
+    #[test]
+    fn lookup() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        for structure in structures.clone().flatten() {
+            match structure {
+                Structure::System(system) => assert_eq!(
+                    Some(Value::String(system.serial)),
+                    get(&structures, Keyword::SystemSerialNumber)
+                ),
+                Structure::Enclosure(enclosure) => assert_eq!(
+                    Some(Value::ChassisType(enclosure.enclosure_type)),
+                    get(&structures, Keyword::ChassisType)
+                ),
+                _ => {}
+            }
+        }
+        for &keyword in Keyword::ALL {
+            if let Some(value) = get(&structures, keyword) {
+                assert_eq!(value.as_str().is_some(), matches!(value, Value::String(_)));
+            }
+        }
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn uuid() {
+        let uuid = [
+            0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF,
+        ];
+        assert_eq!(
+            "00112233-4455-6677-8899-AABBCCDDEEFF",
+            format!("{}", Value::Uuid(rfc4122_uuid(uuid, (2, 6).into())))
+        );
+        assert_eq!(
+            "33221100-5544-7766-8899-AABBCCDDEEFF",
+            format!("{}", Value::Uuid(rfc4122_uuid(uuid, (2, 5).into())))
+        );
+        assert_eq!("Not Settable", format!("{}", Value::Uuid([0xFF; 16])));
+        assert_eq!("Not Present", format!("{}", Value::Uuid([0x00; 16])));
+        assert_eq!("Unknown", format!("{}", Value::ProcessorFrequency(0)));
+        assert_eq!("2400 MHz", format!("{}", Value::ProcessorFrequency(2400)));
+    }
+}
@@ -53,31 +53,91 @@
 //! - Processor Additional Information (Type 44)
 //! - Inactive (Type 126)
 //! - End-of-Table (Type 127)
+//!
+//! # Allocation
+//!
+//! Searching for the entry point, iterating over [Structures] and decoding structures never
+//! allocate, with or without the `alloc` and `std` features: every decoded structure borrows its
+//! strings from the table buffer. The table is read in a single forward pass, the strings section
+//! of each structure is scanned once to find its end. Only the APIs that build owned values, such
+//! as lossy string conversion, require the `alloc` feature.
+//!
+//! # Strings
+//!
+//! The `strings` feature, enabled by default, provides the `Display` implementations that turn
+//! decoded values into descriptive text, e.g. processor family or slot type names, and the
+//! descriptions of bit field flags. Builds that only need the numeric values can disable it to
+//! save code size: structure types are then displayed by number, and bit field flags have empty
+//! descriptions.
+//!
+//! # Logging
+//!
+//! With the `log` feature, parse decisions are reported through the [`log`](https://docs.rs/log)
+//! crate: structures that are skipped or stop the iteration are logged at the debug level, fields
+//! missing from short structures and sentinel values mapped to `None` at the trace level.
+//!
+//! # JEP-106
+//!
+//! The `jep106` feature adds a table of JEDEC manufacturer codes, so that the module and memory
+//! controller manufacturer IDs of Memory Device structures can be shown as vendor names, see the
+//! `jep106` module.
+//!
+//! # Strict mode
+//!
+//! The `strict` feature adds `Structures::conformance`, a stricter variant of
+//! [Structures::warnings] meant for firmware validation, see the [warnings] module.
 
 #![no_std]
+#![cfg_attr(test, allow(unknown_lints, ambiguous_panic_imports))]
 
 #[cfg(any(feature = "std", test))]
 #[macro_use]
 extern crate std;
+#[cfg(any(feature = "alloc", test))]
+extern crate alloc;
 #[macro_use]
 extern crate bitflags;
 #[cfg(test)]
 extern crate lazy_static;
+#[cfg(feature = "log")]
+extern crate log;
 #[cfg(test)]
 extern crate pretty_assertions;
 
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
 use core::array::TryFromSliceError;
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use core::fmt;
+#[cfg(feature = "strings")]
+use core::fmt::Write;
 use core::mem;
+use core::ops::Range;
 use core::str;
 
 #[macro_export]
 #[doc(hidden)]
 macro_rules! let_as_struct {
+    // A `$data` shorter than `$ty` returns a `MalformedStructureError::InvalidSlice`
     ($name:ident, $ty:ty, $data:expr) => {
-        use core::ptr;
-        let $name: $ty = unsafe { ptr::read($data.as_ptr() as *const _) };
+        let $name: $ty = {
+            let data: &[u8] = $data;
+            let size = core::mem::size_of::<$ty>();
+            let bytes: &[u8; core::mem::size_of::<$ty>()] =
+                core::convert::TryFrom::try_from(data.get(..size).unwrap_or(data))
+                    .map_err($crate::MalformedStructureError::InvalidSlice)?;
+            unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const $ty) }
+        };
+    };
+    // A `$data` shorter than `$ty` evaluates `$fallback`, which must diverge
+    ($name:ident, $ty:ty, $data:expr, or $fallback:expr) => {
+        let $name: $ty = {
+            let data: &[u8] = $data;
+            match data.get(..core::mem::size_of::<$ty>()) {
+                Some(bytes) => unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const $ty) },
+                None => $fallback,
+            }
+        };
     };
 }
 
@@ -90,11 +150,111 @@ macro_rules! lib_ensure {
     };
 }
 
+/// Check an SMBIOS version against the version that introduced a layout:
+/// `version_gate!(version, (2, 6)..)` is true from SMBIOS 2.6 on, `version_gate!(version, (2,
+/// 1)..(2, 6))` from SMBIOS 2.1 up to, but excluding, 2.6.
+#[doc(hidden)]
+macro_rules! version_gate {
+    ($version:expr, ($major:literal, $minor:literal)..) => {
+        $crate::SmbiosVersion::at_least($version, $major, $minor)
+    };
+    ($version:expr, ($major:literal, $minor:literal)..($until_major:literal, $until_minor:literal)) => {
+        $crate::SmbiosVersion::at_least($version, $major, $minor)
+            && !$crate::SmbiosVersion::at_least($version, $until_major, $until_minor)
+    };
+}
+
+/// Emit a `log` debug event for a parse decision, compiled out without the `log` feature
+#[doc(hidden)]
+#[cfg(feature = "log")]
+macro_rules! lib_debug {
+    ($($arg:tt)+) => {
+        ::log::debug!($($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "log"))]
+macro_rules! lib_debug {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+/// Emit a `log` trace event for a parse decision, compiled out without the `log` feature
+#[doc(hidden)]
+#[cfg(feature = "log")]
+macro_rules! lib_trace {
+    ($($arg:tt)+) => {
+        ::log::trace!($($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "log"))]
+macro_rules! lib_trace {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+/// A descriptive string literal, left empty without the `strings` feature
+#[doc(hidden)]
+#[cfg(feature = "strings")]
+macro_rules! description {
+    ($s:literal) => {
+        $s
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "strings"))]
+macro_rules! description {
+    ($s:literal) => {
+        ""
+    };
+}
+
 #[macro_use]
 pub mod bitfield;
 
+#[cfg(any(feature = "std", test))]
+#[doc(hidden)]
+pub mod corpus;
+pub mod dedup;
+#[cfg(feature = "std")]
+mod dump;
+mod endian;
+pub mod fingerprint;
+#[cfg(feature = "alloc")]
+pub mod firmware;
+pub mod heuristics;
+#[cfg(feature = "jep106")]
+pub mod jep106;
+pub mod keyword;
+pub mod normalize;
+pub mod oem;
+#[cfg(feature = "alloc")]
+pub mod owned;
+pub mod redact;
+pub mod segments;
+#[cfg(feature = "alloc")]
+pub mod stream;
+pub mod string_audit;
 pub mod structures;
+pub mod summary;
+#[cfg(any(feature = "testing", test))]
+pub mod testing;
+pub mod topology;
+pub mod type_index;
 pub use structures::*;
+pub mod visitor;
+pub use visitor::visit;
+pub mod warnings;
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 enum EntryPointFormat {
@@ -134,10 +294,56 @@ impl EntryPoint {
             EntryPoint::V3(point) => point.revision,
         }
     }
+    /// The specification document revision; SMBIOS 2.x entry points do not carry it and report 0.
+    pub fn docrev(&self) -> u8 {
+        match self {
+            EntryPoint::V2(_) => 0,
+            EntryPoint::V3(point) => point.docrev,
+        }
+    }
+    /// The physical address of the structure table, widened to 64 bits for SMBIOS 2.x, see
+    /// [EntryPoint::table_location]
     pub fn smbios_address(&self) -> u64 {
+        self.table_location().address()
+    }
+    /// The physical address of the structure table, with the width of the entry point field
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, TableLocation};
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    ///
+    /// let entry_point = EntryPoint::search(ENTRY_BIN).unwrap();
+    /// assert_eq!(TableLocation::Physical32(0xDAA9D000), entry_point.table_location());
+    /// assert!(!entry_point.table_location().requires_64bit());
+    /// ```
+    pub fn table_location(&self) -> TableLocation {
+        match self {
+            EntryPoint::V2(point) => TableLocation::Physical32(point.smbios_address),
+            EntryPoint::V3(point) => TableLocation::Physical64(point.smbios_address),
+        }
+    }
+    /// The size of the largest structure of the table, header and strings included, as declared
+    /// by SMBIOS 2.x entry points. `None` for SMBIOS 3.x, and for entry points that leave it zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let mut buffer = vec![0; entry_point.max_structure_size().unwrap() as usize];
+    /// ```
+    pub fn max_structure_size(&self) -> Option<u16> {
         match self {
-            EntryPoint::V2(point) => point.smbios_address as u64,
-            EntryPoint::V3(point) => point.smbios_address,
+            EntryPoint::V2(point) => Some(point.struct_max).filter(|&max| max != 0),
+            EntryPoint::V3(_) => None,
         }
     }
     pub fn smbios_len(&self) -> u32 {
@@ -159,8 +365,10 @@ impl EntryPoint {
     ///
     /// ```
     /// # extern crate dmidecode;
+    /// # #[allow(unused_imports)]
     /// # use std::error::Error;
     /// use dmidecode::EntryPoint;
+    /// # #[cfg(feature = "std")]
     /// # fn try_main() -> Result<(), Box<dyn Error>> {
     /// #
     /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
@@ -173,12 +381,126 @@ impl EntryPoint {
     /// # }
     /// ```
     pub fn structures<'buffer>(&self, buffer: &'buffer [u8]) -> Structures<'buffer> {
-        Structures {
-            smbios_version: self.to_version(),
-            smbios_len: self.smbios_len(),
-            idx: 0u32,
-            buffer,
+        let structures = Structures::new(buffer, self.to_version(), self.smbios_len());
+        match self.max_structure_size() {
+            Some(max) => structures.with_max_structure_size(max),
+            None => structures,
+        }
+    }
+
+    /// Decode the structure with the given handle from the table in `buffer`, see
+    /// [Structures::find_handle]
+    pub fn find_handle<'buffer, H: Into<Handle>>(
+        &self,
+        buffer: &'buffer [u8],
+        handle: H,
+    ) -> Option<Result<Structure<'buffer>, MalformedStructureError>> {
+        self.structures(buffer).find_handle(handle)
+    }
+
+    /// Create an iterator across the SMBIOS structures found in `buffer`, without decoding them.
+    ///
+    /// The structures are yielded as [RawStructure]s, with their type, handle, formatted section
+    /// and strings, e.g. to filter or re-serialize a table, or to decode types this crate does not
+    /// know.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, InfoType};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let buffer = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+    /// let oem = entry_point
+    ///     .raw_structures(buffer)
+    ///     .filter_map(Result::ok)
+    ///     .filter(|raw| matches!(raw.info, InfoType::Oem(_)))
+    ///     .count();
+    /// assert_eq!(18, oem);
+    /// ```
+    pub fn raw_structures<'buffer>(&self, buffer: &'buffer [u8]) -> RawStructures<'buffer> {
+        self.structures(buffer).raw()
+    }
+
+    /// Create an iterator across the SMBIOS structures, reading the table through `read`.
+    ///
+    /// `read` is given the physical address and length of the structure table, as stored in the
+    /// entry point, and returns the bytes at that address, e.g. by translating the address into
+    /// the memory model of a kernel crash dump. For SMBIOS 3.x the length is the maximum length of
+    /// the table, `read` may return fewer bytes as long as the table ends within them.
+    ///
+    /// Returns `None` if `read` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// // Physical memory ranges saved in a dump, with their start addresses
+    /// let segments: [(u64, &[u8]); 2] = [(0x0, DMIDECODE_BIN), (0x10_0000, &[0; 16])];
+    /// let read = |address: u64, len: u32| {
+    ///     segments.iter().find_map(|&(start, bytes)| {
+    ///         let offset = address.checked_sub(start)? as usize;
+    ///         bytes.get(offset..offset + len as usize)
+    ///     })
+    /// };
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let structures = entry_point.structures_from(read).unwrap();
+    /// assert_eq!(69, structures.count());
+    /// ```
+    pub fn structures_from<'buffer, F>(&self, read: F) -> Option<Structures<'buffer>>
+    where
+        F: Fn(u64, u32) -> Option<&'buffer [u8]>,
+    {
+        let buffer = read(self.smbios_address(), self.smbios_len());
+        if buffer.is_none() {
+            lib_debug!(
+                "structure table at {:#X} ({} bytes) is not readable",
+                self.smbios_address(),
+                self.smbios_len()
+            );
         }
+        buffer.map(|buffer| self.structures(buffer))
+    }
+
+    /// Create an iterator across the SMBIOS structures of the table found in `buffer`, according
+    /// to its `layout`
+    ///
+    /// Unlike [EntryPoint::structures], the buffer does not have to start with the table: with
+    /// [BufferLayout::PhysicalMemory] the table is located through its address in the entry
+    /// point.
+    ///
+    /// Returns `None` if the table address is outside of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{BufferLayout, EntryPoint};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// // A `dmidecode --dump-bin` file maps the table address to its offset in the file
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let layout = BufferLayout::PhysicalMemory { base: 0 };
+    /// let structures = entry_point.structures_auto(DMIDECODE_BIN, layout).unwrap();
+    /// assert_eq!(69, structures.count());
+    /// ```
+    pub fn structures_auto<'buffer>(&self, buffer: &'buffer [u8], layout: BufferLayout) -> Option<Structures<'buffer>> {
+        self.structures_from(|address, _| match layout {
+            BufferLayout::Table => Some(buffer),
+            BufferLayout::PhysicalMemory { base } => {
+                let offset = usize::try_from(address.checked_sub(base)?).ok()?;
+                buffer.get(offset..).filter(|table| !table.is_empty())
+            }
+        })
     }
 
     /// Search for an instance of an SMBIOS `EntryPoint` in a memory `buffer`.
@@ -199,169 +521,818 @@ impl EntryPoint {
     /// If this function fails to find a valid SMBIOS `EntryPoint`, it will return
     /// an `InvalidEntryPointError` variant.
     pub fn search(buffer: &[u8]) -> Result<EntryPoint, InvalidEntryPointError> {
-        find_signature(buffer)
-            .ok_or(InvalidEntryPointError::NotFound)
-            .and_then(|(kind, start)| {
-                let sub_buffer = &buffer[start..];
-
-                let entry_point = match kind {
-                    EntryPointFormat::V2 => {
-                        lib_ensure!(
-                            sub_buffer.len() >= mem::size_of::<EntryPointV2>(),
-                            InvalidEntryPointError::BadSize(sub_buffer.len() as u8)
-                        );
-                        let_as_struct!(entry_point, EntryPointV2, sub_buffer);
-                        lib_ensure!(
-                            entry_point.len as usize >= mem::size_of::<EntryPointV2>(),
-                            InvalidEntryPointError::BadSize(entry_point.len)
-                        );
-                        EntryPoint::V2(entry_point)
-                    }
-                    EntryPointFormat::V3 => {
-                        lib_ensure!(
-                            sub_buffer.len() >= mem::size_of::<EntryPointV3>(),
-                            InvalidEntryPointError::BadSize(sub_buffer.len() as u8)
-                        );
-                        let_as_struct!(entry_point, EntryPointV3, sub_buffer);
-                        lib_ensure!(
-                            entry_point.len as usize >= mem::size_of::<EntryPointV3>(),
-                            InvalidEntryPointError::BadSize(entry_point.len)
-                        );
-                        EntryPoint::V3(entry_point)
-                    }
-                };
-
-                lib_ensure!(
-                    entry_point.major() >= 2,
-                    InvalidEntryPointError::TooOldVersion(entry_point.major())
-                );
-
-                lib_ensure!(
-                    sub_buffer.len() as u8 >= entry_point.len(),
-                    InvalidEntryPointError::BadSize(sub_buffer.len() as u8)
-                );
-
-                let mut sum = 0u8;
-                for val in &sub_buffer[0..(entry_point.len() as usize)] {
-                    sum = sum.wrapping_add(*val);
-                }
-                lib_ensure!(sum == 0, InvalidEntryPointError::BadChecksum(sum));
+        EntryPoint::search_with(buffer, SearchOptions::default())
+    }
 
-                Ok(entry_point)
-            })
+    /// Search for an instance of an SMBIOS `EntryPoint` in a memory `buffer`, relaxing the
+    /// validation as selected by `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, SearchOptions};
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    ///
+    /// let options = SearchOptions {
+    ///     permissive_legacy_anchor: true,
+    ///     ..Default::default()
+    /// };
+    /// let entry_point = EntryPoint::search_with(ENTRY_BIN, options);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If this function fails to find a valid SMBIOS `EntryPoint`, it will return
+    /// an `InvalidEntryPointError` variant.
+    pub fn search_with(buffer: &[u8], options: SearchOptions) -> Result<EntryPoint, InvalidEntryPointError> {
+        EntryPointRef::search_with(buffer, options).map(EntryPoint::from)
     }
 }
 
-///
-/// An SMBIOSv2 `EntryPoint` structure.
-///
-/// The SMBIOS `EntryPoint` structure is used to verify that a set of SMBIOS tables exist
-/// in memory and what version of the SMBIOS specification should be used to
-/// access the tables.
-///
-#[repr(C)]
-#[repr(packed)]
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-pub struct EntryPointV2 {
-    pub signature: u32,
-    pub checksum: u8,
-    pub len: u8,
-    pub major: u8,
-    pub minor: u8,
-    pub struct_max: u16,
-    pub revision: u8,
-    pub formatted: [u8; 5],
-    pub dmi_signature: [u8; 5],
-    pub dmi_checksum: u8,
-    pub smbios_len: u16,
-    pub smbios_address: u32,
-    pub smbios_count: u16,
-    pub bcd_revision: u8,
+impl<'a> From<EntryPointRef<'a>> for EntryPoint {
+    fn from(entry_point: EntryPointRef<'a>) -> EntryPoint {
+        match entry_point {
+            EntryPointRef::V2(point) => EntryPoint::V2(point.into()),
+            EntryPointRef::V3(point) => EntryPoint::V3(point.into()),
+        }
+    }
 }
 
+/// An SMBIOS `EntryPoint` structure borrowed from the memory buffer it was found in
 ///
-/// An SMBIOSv3 `EntryPoint` structure.
-///
-#[repr(C)]
-#[repr(packed)]
+/// The fields are read from the buffer on demand, and the offset of the structure in the buffer
+/// is kept. Convert it into an [EntryPoint] to get a copy.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-pub struct EntryPointV3 {
-    pub signature: [u8; 5],
-    pub checksum: u8,
-    pub len: u8,
-    pub major: u8,
-    pub minor: u8,
-    pub docrev: u8,
-    pub revision: u8,
-    _reserved: u8,
-    pub smbios_len_max: u32,
-    pub smbios_address: u64,
-}
-
-/// The version number associated with the Smbios `EntryPoint`
-#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-pub struct SmbiosVersion {
-    pub major: u8,
-    pub minor: u8,
+pub enum EntryPointRef<'a> {
+    V2(EntryPointV2Ref<'a>),
+    V3(EntryPointV3Ref<'a>),
 }
 
-impl From<(usize, usize)> for SmbiosVersion {
-    fn from(other: (usize, usize)) -> SmbiosVersion {
-        SmbiosVersion {
-            major: other.0 as u8,
-            minor: other.1 as u8,
-        }
+impl<'a> EntryPointRef<'a> {
+    /// Search for an instance of an SMBIOS `EntryPoint` in a memory `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this function fails to find a valid SMBIOS `EntryPoint`, it will return
+    /// an `InvalidEntryPointError` variant.
+    pub fn search(buffer: &'a [u8]) -> Result<EntryPointRef<'a>, InvalidEntryPointError> {
+        EntryPointRef::search_with(buffer, SearchOptions::default())
     }
-}
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
-struct SmbiosBound {
-    len: u16,
-    count: u16,
-}
+    /// Search for an instance of an SMBIOS `EntryPoint` in a memory `buffer`, relaxing the
+    /// validation as selected by `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPointRef, SearchOptions};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPointRef::search_with(DMIDECODE_BIN, SearchOptions::default()).unwrap();
+    /// assert_eq!(0, entry_point.offset());
+    /// if let EntryPointRef::V2(point) = entry_point {
+    ///     assert_eq!(*b"_DMI_", point.dmi_signature());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If this function fails to find a valid SMBIOS `EntryPoint`, it will return
+    /// an `InvalidEntryPointError` variant.
+    pub fn search_with(buffer: &'a [u8], options: SearchOptions) -> Result<EntryPointRef<'a>, InvalidEntryPointError> {
+        let (kind, offset) = find_signature(buffer).ok_or(InvalidEntryPointError::NotFound)?;
+        let sub_buffer = &buffer[offset..];
 
-/// Failure type for trying to find the SMBIOS `EntryPoint` structure in memory.
-#[derive(Debug)]
-pub enum InvalidEntryPointError {
-    /// The SMBIOS `EntryPoint` structure was not found in the memory buffer.
-    NotFound,
-    /// The SMBIOS `EntryPoint` structure was versioned before 2.0.
-    TooOldVersion(u8),
-    /// The SMBIOS `EntryPoint` structure was smaller than the size of the SMBIOS 2.1 structure.
-    BadSize(u8),
-    /// The SMBIOS `EntryPoint` structure had an invalid checksum.
-    BadChecksum(u8),
-}
+        let (size, len_offset) = match kind {
+            EntryPointFormat::V2 => (mem::size_of::<EntryPointV2>(), 0x05),
+            EntryPointFormat::V3 => (mem::size_of::<EntryPointV3>(), 0x06),
+        };
+        lib_ensure!(
+            sub_buffer.len() >= size,
+            InvalidEntryPointError::BadSize(sub_buffer.len() as u8)
+        );
+        let len = sub_buffer[len_offset];
+        lib_ensure!(len as usize >= size, InvalidEntryPointError::BadSize(len));
+        lib_ensure!(
+            sub_buffer.len() >= len as usize,
+            InvalidEntryPointError::BadSize(sub_buffer.len() as u8)
+        );
+        let bytes = &sub_buffer[..len as usize];
+        let entry_point = match kind {
+            EntryPointFormat::V2 => EntryPointRef::V2(EntryPointV2Ref { bytes, offset }),
+            EntryPointFormat::V3 => EntryPointRef::V3(EntryPointV3Ref { bytes, offset }),
+        };
 
-impl fmt::Display for InvalidEntryPointError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            InvalidEntryPointError::NotFound => write!(f, "Input did not contain a valid SMBIOS entry point"),
-            InvalidEntryPointError::TooOldVersion(version) => {
-                write!(f, "Input version number was below 2.0: {}", version)
-            }
-            InvalidEntryPointError::BadSize(size) => {
-                write!(f, "Input contained an invalid-sized SMBIOS entry: {}", size)
+        lib_ensure!(
+            entry_point.major() >= 2,
+            InvalidEntryPointError::TooOldVersion(entry_point.major())
+        );
+
+        let sum = bytes.iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+        lib_ensure!(sum == 0, InvalidEntryPointError::BadChecksum(sum));
+
+        if let EntryPointRef::V2(ref entry_point) = entry_point {
+            // The intermediate checksum covers the intermediate anchor up to the BCD revision
+            let intermediate_sum = bytes[0x10..0x1F].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+            let valid = entry_point.dmi_signature() == *b"_DMI_" && intermediate_sum == 0;
+            let tolerated = options.permissive_legacy_anchor
+                && (entry_point.major(), entry_point.minor()) == (2, 0)
+                && entry_point.smbios_address() != 0
+                && entry_point.smbios_len() != 0;
+            if !valid && tolerated {
+                lib_debug!("accepting SMBIOS 2.0 entry point without a valid _DMI_ anchor");
             }
-            InvalidEntryPointError::BadChecksum(checksum) => {
-                write!(f, "SMBIOS entry point has an invalid checksum: {}", checksum)
+            lib_ensure!(valid || tolerated, InvalidEntryPointError::BadIntermediateAnchor);
+            if !entry_point.known_revision() {
+                lib_debug!(
+                    "unknown SMBIOS 2.x entry point revision {:#04X}, ignoring the formatted area",
+                    entry_point.revision()
+                );
             }
         }
+
+        Ok(entry_point)
     }
-}
 
-#[cfg(feature = "std")]
+    /// Offset of the entry point in the searched buffer
+    pub fn offset(&self) -> usize {
+        match self {
+            EntryPointRef::V2(point) => point.offset,
+            EntryPointRef::V3(point) => point.offset,
+        }
+    }
+    /// The raw entry point bytes, as long as the Entry Point Length field tells
+    pub fn as_bytes(&self) -> &'a [u8] {
+        match self {
+            EntryPointRef::V2(point) => point.bytes,
+            EntryPointRef::V3(point) => point.bytes,
+        }
+    }
+    pub fn major(&self) -> u8 {
+        match self {
+            EntryPointRef::V2(point) => point.major(),
+            EntryPointRef::V3(point) => point.major(),
+        }
+    }
+    pub fn minor(&self) -> u8 {
+        match self {
+            EntryPointRef::V2(point) => point.minor(),
+            EntryPointRef::V3(point) => point.minor(),
+        }
+    }
+}
+
+/// Decode a BCD `0xMm` byte as version M.m
+fn bcd_version(bcd: u8) -> Option<SmbiosVersion> {
+    let (major, minor) = (bcd >> 4, bcd & 0x0F);
+    if major > 9 || minor > 9 {
+        None
+    } else {
+        Some(SmbiosVersion { major, minor })
+    }
+}
+
+/// Read the little-endian field of `N` bytes at `offset`
+fn entry_point_field<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut field = [0; N];
+    field.copy_from_slice(&bytes[offset..offset + N]);
+    field
+}
+
+/// An SMBIOSv2 `EntryPoint` structure borrowed from a memory buffer, see [EntryPointV2] for the
+/// copied structure
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryPointV2Ref<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> EntryPointV2Ref<'a> {
+    /// Offset of the entry point in the searched buffer
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// The raw entry point bytes, as long as the Entry Point Length field tells
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    pub fn signature(&self) -> [u8; 4] {
+        entry_point_field(self.bytes, 0x00)
+    }
+    pub fn checksum(&self) -> u8 {
+        self.bytes[0x04]
+    }
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u8 {
+        self.bytes[0x05]
+    }
+    pub fn major(&self) -> u8 {
+        self.bytes[0x06]
+    }
+    pub fn minor(&self) -> u8 {
+        self.bytes[0x07]
+    }
+    pub fn struct_max(&self) -> u16 {
+        u16::from_le_bytes(entry_point_field(self.bytes, 0x08))
+    }
+    pub fn revision(&self) -> u8 {
+        self.bytes[0x0A]
+    }
+    pub fn formatted(&self) -> [u8; 5] {
+        entry_point_field(self.bytes, 0x0B)
+    }
+    pub fn dmi_signature(&self) -> [u8; 5] {
+        entry_point_field(self.bytes, 0x10)
+    }
+    pub fn dmi_checksum(&self) -> u8 {
+        self.bytes[0x15]
+    }
+    pub fn smbios_len(&self) -> u16 {
+        u16::from_le_bytes(entry_point_field(self.bytes, 0x16))
+    }
+    pub fn smbios_address(&self) -> u32 {
+        u32::from_le_bytes(entry_point_field(self.bytes, 0x18))
+    }
+    pub fn smbios_count(&self) -> u16 {
+        u16::from_le_bytes(entry_point_field(self.bytes, 0x1C))
+    }
+    pub fn bcd_revision(&self) -> u8 {
+        self.bytes[0x1E]
+    }
+    /// The SMBIOS version held by the BCD Revision field, see [EntryPointV2::bcd_version]
+    pub fn bcd_version(&self) -> Option<SmbiosVersion> {
+        bcd_version(self.bcd_revision())
+    }
+    /// Whether the Entry Point Revision is one this crate knows the formatted area of, see
+    /// [EntryPointV2::known_revision]
+    pub fn known_revision(&self) -> bool {
+        self.revision() == EntryPointV2::REVISION_2_1
+    }
+}
+
+impl<'a> From<EntryPointV2Ref<'a>> for EntryPointV2 {
+    fn from(point: EntryPointV2Ref<'a>) -> EntryPointV2 {
+        EntryPointV2 {
+            signature: u32::from_le_bytes(point.signature()),
+            checksum: point.checksum(),
+            len: point.len(),
+            major: point.major(),
+            minor: point.minor(),
+            struct_max: point.struct_max(),
+            revision: point.revision(),
+            formatted: point.formatted(),
+            dmi_signature: point.dmi_signature(),
+            dmi_checksum: point.dmi_checksum(),
+            smbios_len: point.smbios_len(),
+            smbios_address: point.smbios_address(),
+            smbios_count: point.smbios_count(),
+            bcd_revision: point.bcd_revision(),
+        }
+    }
+}
+
+/// An SMBIOSv3 `EntryPoint` structure borrowed from a memory buffer, see [EntryPointV3] for the
+/// copied structure
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryPointV3Ref<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> EntryPointV3Ref<'a> {
+    /// Offset of the entry point in the searched buffer
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// The raw entry point bytes, as long as the Entry Point Length field tells
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    pub fn signature(&self) -> [u8; 5] {
+        entry_point_field(self.bytes, 0x00)
+    }
+    pub fn checksum(&self) -> u8 {
+        self.bytes[0x05]
+    }
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u8 {
+        self.bytes[0x06]
+    }
+    pub fn major(&self) -> u8 {
+        self.bytes[0x07]
+    }
+    pub fn minor(&self) -> u8 {
+        self.bytes[0x08]
+    }
+    pub fn docrev(&self) -> u8 {
+        self.bytes[0x09]
+    }
+    pub fn revision(&self) -> u8 {
+        self.bytes[0x0A]
+    }
+    pub fn smbios_len_max(&self) -> u32 {
+        u32::from_le_bytes(entry_point_field(self.bytes, 0x0C))
+    }
+    pub fn smbios_address(&self) -> u64 {
+        u64::from_le_bytes(entry_point_field(self.bytes, 0x10))
+    }
+}
+
+impl<'a> From<EntryPointV3Ref<'a>> for EntryPointV3 {
+    fn from(point: EntryPointV3Ref<'a>) -> EntryPointV3 {
+        EntryPointV3 {
+            signature: point.signature(),
+            checksum: point.checksum(),
+            len: point.len(),
+            major: point.major(),
+            minor: point.minor(),
+            docrev: point.docrev(),
+            revision: point.revision(),
+            _reserved: point.bytes[0x0B],
+            smbios_len_max: point.smbios_len_max(),
+            smbios_address: point.smbios_address(),
+        }
+    }
+}
+
+///
+/// An SMBIOSv2 `EntryPoint` structure.
+///
+/// The SMBIOS `EntryPoint` structure is used to verify that a set of SMBIOS tables exist
+/// in memory and what version of the SMBIOS specification should be used to
+/// access the tables.
+///
+#[repr(C)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryPointV2 {
+    pub signature: u32,
+    pub checksum: u8,
+    pub len: u8,
+    pub major: u8,
+    pub minor: u8,
+    pub struct_max: u16,
+    pub revision: u8,
+    pub formatted: [u8; 5],
+    pub dmi_signature: [u8; 5],
+    pub dmi_checksum: u8,
+    pub smbios_len: u16,
+    pub smbios_address: u32,
+    pub smbios_count: u16,
+    pub bcd_revision: u8,
+}
+
+impl EntryPointV2 {
+    /// Entry Point Revision of the SMBIOS 2.1 layout, whose formatted area is reserved and zeroed
+    pub const REVISION_2_1: u8 = 0x00;
+
+    /// An entry point of the SMBIOS `version` table of `smbios_len` bytes at `smbios_address`,
+    /// holding `smbios_count` structures of at most `struct_max` bytes, with its checksums
+    ///
+    /// The BCD Revision is set from `version` when both numbers are single digits, to `0`
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, EntryPointV2, SmbiosVersion};
+    ///
+    /// let point = EntryPointV2::new(SmbiosVersion::V2_8, 0x000F_0020, 0x0400, 42, 0x80);
+    /// let bytes = point.to_bytes();
+    /// let found = EntryPoint::search(&bytes).unwrap();
+    /// assert_eq!((0x000F_0020, 0x0400), (found.smbios_address(), found.smbios_len()));
+    /// ```
+    pub fn new(
+        version: SmbiosVersion,
+        smbios_address: u32,
+        smbios_len: u16,
+        smbios_count: u16,
+        struct_max: u16,
+    ) -> Self {
+        let bcd_revision = if version.major < 10 && version.minor < 10 {
+            version.major << 4 | version.minor
+        } else {
+            0
+        };
+        let mut point = EntryPointV2 {
+            signature: u32::from_le_bytes(*b"_SM_"),
+            checksum: 0,
+            len: mem::size_of::<Self>() as u8,
+            major: version.major,
+            minor: version.minor,
+            struct_max,
+            revision: Self::REVISION_2_1,
+            formatted: [0; 5],
+            dmi_signature: *b"_DMI_",
+            dmi_checksum: 0,
+            smbios_len,
+            smbios_address,
+            smbios_count,
+            bcd_revision,
+        };
+        point.recompute_checksums();
+        point
+    }
+
+    /// The SMBIOS version held by the BCD Revision field, e.g. 2.1 for `0x21`
+    ///
+    /// Returns `None` if a nibble is not a decimal digit. The field cannot hold a minor version
+    /// above 9 and is often left at `0` by SMBIOS 2.x tables of such versions, use `major` and
+    /// `minor` for the version of the table.
+    pub fn bcd_version(&self) -> Option<SmbiosVersion> {
+        bcd_version(self.bcd_revision)
+    }
+
+    /// Whether the Entry Point Revision is [EntryPointV2::REVISION_2_1], the only revision the
+    /// specification defines
+    ///
+    /// Later revisions would assign the five bytes of the `formatted` area, which this crate
+    /// cannot interpret; [EntryPoint::warnings] reports them.
+    pub fn known_revision(&self) -> bool {
+        self.revision == Self::REVISION_2_1
+    }
+
+    /// Recompute both the entry point checksum and the intermediate (`_DMI_`) checksum.
+    ///
+    /// This is useful after patching fields of the entry point, e.g. when relocating the
+    /// structure table to a new address.
+    pub fn recompute_checksums(&mut self) {
+        self.checksum = 0;
+        self.dmi_checksum = 0;
+        // The intermediate checksum covers the 15 bytes starting at the `_DMI_` anchor
+        self.dmi_checksum = checksum(&self.raw_bytes()[0x10..0x1F]);
+        let len = (self.len as usize).min(mem::size_of::<Self>());
+        self.checksum = checksum(&self.raw_bytes()[..len]);
+    }
+
+    /// The entry point bytes in table order, with both checksums recomputed
+    pub fn to_bytes(&self) -> [u8; mem::size_of::<Self>()] {
+        let mut point = *self;
+        point.recompute_checksums();
+        point.raw_bytes()
+    }
+
+    /// Write the entry point bytes into `buffer` with both checksums recomputed, see
+    /// [EntryPointV2::to_bytes]
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidEntryPointError::BadSize` with the size of the structure if `buffer` is too
+    /// small to hold it.
+    pub fn write_to(&self, buffer: &mut [u8]) -> Result<(), InvalidEntryPointError> {
+        write_entry_point(&self.to_bytes(), buffer)
+    }
+
+    /// Swap the multi-byte fields between the little-endian table order and the host order
+    fn to_host_order(self) -> Self {
+        EntryPointV2 {
+            signature: u32::from_le(self.signature),
+            struct_max: u16::from_le(self.struct_max),
+            smbios_len: u16::from_le(self.smbios_len),
+            smbios_address: u32::from_le(self.smbios_address),
+            smbios_count: u16::from_le(self.smbios_count),
+            ..self
+        }
+    }
+
+    /// The raw entry point bytes, in table order
+    fn raw_bytes(self) -> [u8; mem::size_of::<Self>()] {
+        // Converting to little-endian is the same swap as converting from it
+        let le = self.to_host_order();
+        unsafe { mem::transmute(le) }
+    }
+}
+
+///
+/// An SMBIOSv3 `EntryPoint` structure.
+///
+#[repr(C)]
+#[repr(packed)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EntryPointV3 {
+    pub signature: [u8; 5],
+    pub checksum: u8,
+    pub len: u8,
+    pub major: u8,
+    pub minor: u8,
+    pub docrev: u8,
+    pub revision: u8,
+    _reserved: u8,
+    pub smbios_len_max: u32,
+    pub smbios_address: u64,
+}
+
+impl EntryPointV3 {
+    /// Entry Point Revision of the SMBIOS 3.0 layout
+    pub const REVISION_3_0: u8 = 0x01;
+
+    /// An entry point of the SMBIOS `version` table of at most `smbios_len_max` bytes at
+    /// `smbios_address`, with its checksum
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, EntryPointV3, SmbiosVersion};
+    ///
+    /// let mut bytes = [0; 32];
+    /// EntryPointV3::new(SmbiosVersion::V3_4, 0x7FFF_0000, 0x1000)
+    ///     .write_to(&mut bytes)
+    ///     .unwrap();
+    /// let found = EntryPoint::search(&bytes).unwrap();
+    /// assert_eq!(SmbiosVersion::V3_4, found.to_version());
+    /// ```
+    pub fn new(version: SmbiosVersion, smbios_address: u64, smbios_len_max: u32) -> Self {
+        let mut point = EntryPointV3 {
+            signature: *b"_SM3_",
+            checksum: 0,
+            len: mem::size_of::<Self>() as u8,
+            major: version.major,
+            minor: version.minor,
+            docrev: 0,
+            revision: Self::REVISION_3_0,
+            _reserved: 0,
+            smbios_len_max,
+            smbios_address,
+        };
+        point.recompute_checksum();
+        point
+    }
+
+    /// Recompute the entry point checksum.
+    ///
+    /// This is useful after patching fields of the entry point, e.g. when relocating the
+    /// structure table to a new address.
+    pub fn recompute_checksum(&mut self) {
+        self.checksum = 0;
+        let len = (self.len as usize).min(mem::size_of::<Self>());
+        self.checksum = checksum(&self.raw_bytes()[..len]);
+    }
+
+    /// The entry point bytes in table order, with the checksum recomputed
+    pub fn to_bytes(&self) -> [u8; mem::size_of::<Self>()] {
+        let mut point = *self;
+        point.recompute_checksum();
+        point.raw_bytes()
+    }
+
+    /// Write the entry point bytes into `buffer` with the checksum recomputed, see
+    /// [EntryPointV3::to_bytes]
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidEntryPointError::BadSize` with the size of the structure if `buffer` is too
+    /// small to hold it.
+    pub fn write_to(&self, buffer: &mut [u8]) -> Result<(), InvalidEntryPointError> {
+        write_entry_point(&self.to_bytes(), buffer)
+    }
+
+    /// Swap the multi-byte fields between the little-endian table order and the host order
+    fn to_host_order(self) -> Self {
+        EntryPointV3 {
+            smbios_len_max: u32::from_le(self.smbios_len_max),
+            smbios_address: u64::from_le(self.smbios_address),
+            ..self
+        }
+    }
+
+    /// The raw entry point bytes, in table order
+    fn raw_bytes(self) -> [u8; mem::size_of::<Self>()] {
+        // Converting to little-endian is the same swap as converting from it
+        let le = self.to_host_order();
+        unsafe { mem::transmute(le) }
+    }
+}
+
+/// Compute the value that makes the byte sum of `bytes` plus the value equal to zero
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, val| sum.wrapping_add(*val)).wrapping_neg()
+}
+
+fn write_entry_point(bytes: &[u8], buffer: &mut [u8]) -> Result<(), InvalidEntryPointError> {
+    // Entry points are at most 0x1F bytes long
+    lib_ensure!(
+        buffer.len() >= bytes.len(),
+        InvalidEntryPointError::BadSize(bytes.len() as u8)
+    );
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    Ok(())
+}
+
+/// The physical address of the structure table, as given by an SMBIOS 2.x or 3.x entry point
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TableLocation {
+    /// 32-bit address of an SMBIOS 2.x entry point
+    Physical32(u32),
+    /// 64-bit address of an SMBIOS 3.x entry point
+    Physical64(u64),
+}
+
+impl TableLocation {
+    /// The address, widened to 64 bits
+    pub fn address(self) -> u64 {
+        match self {
+            TableLocation::Physical32(address) => address as u64,
+            TableLocation::Physical64(address) => address,
+        }
+    }
+
+    /// Whether the table lies above 4 GiB, out of reach of 32-bit physical memory mappings
+    ///
+    /// An SMBIOS 3.x table below 4 GiB can still be mapped through a 32-bit path.
+    pub fn requires_64bit(self) -> bool {
+        self.address() > u32::MAX as u64
+    }
+}
+
+impl From<TableLocation> for u64 {
+    fn from(location: TableLocation) -> u64 {
+        location.address()
+    }
+}
+
+/// Formatted as in the dmidecode output, with the width of the entry point field
+impl fmt::Display for TableLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableLocation::Physical32(address) => write!(f, "{:#010X}", address),
+            TableLocation::Physical64(address) => write!(f, "{:#018X}", address),
+        }
+    }
+}
+
+/// The version number associated with the Smbios `EntryPoint`
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SmbiosVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl SmbiosVersion {
+    pub const V2_0: SmbiosVersion = SmbiosVersion::new(2, 0);
+    pub const V2_1: SmbiosVersion = SmbiosVersion::new(2, 1);
+    pub const V2_2: SmbiosVersion = SmbiosVersion::new(2, 2);
+    pub const V2_3: SmbiosVersion = SmbiosVersion::new(2, 3);
+    pub const V2_4: SmbiosVersion = SmbiosVersion::new(2, 4);
+    pub const V2_5: SmbiosVersion = SmbiosVersion::new(2, 5);
+    pub const V2_6: SmbiosVersion = SmbiosVersion::new(2, 6);
+    pub const V2_7: SmbiosVersion = SmbiosVersion::new(2, 7);
+    pub const V2_8: SmbiosVersion = SmbiosVersion::new(2, 8);
+    pub const V3_0: SmbiosVersion = SmbiosVersion::new(3, 0);
+    pub const V3_1: SmbiosVersion = SmbiosVersion::new(3, 1);
+    pub const V3_2: SmbiosVersion = SmbiosVersion::new(3, 2);
+    pub const V3_3: SmbiosVersion = SmbiosVersion::new(3, 3);
+    pub const V3_4: SmbiosVersion = SmbiosVersion::new(3, 4);
+    pub const V3_5: SmbiosVersion = SmbiosVersion::new(3, 5);
+    pub const V3_6: SmbiosVersion = SmbiosVersion::new(3, 6);
+    pub const V3_7: SmbiosVersion = SmbiosVersion::new(3, 7);
+
+    pub const fn new(major: u8, minor: u8) -> SmbiosVersion {
+        SmbiosVersion { major, minor }
+    }
+
+    /// Whether the version is `major`.`minor` or later
+    pub fn at_least(self, major: u8, minor: u8) -> bool {
+        self >= SmbiosVersion::new(major, minor)
+    }
+}
+
+impl fmt::Display for SmbiosVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Error returned when parsing a malformed SMBIOS version
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct InvalidVersionError;
+
+impl fmt::Display for InvalidVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid SMBIOS version, expected major.minor")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidVersionError {}
+
+/// Parses a dotted `major.minor` version, e.g. `3.4`; the document revision of a
+/// `major.minor.docrev` version, e.g. `2.7.1`, is accepted and dropped
+///
+/// ```
+/// # extern crate dmidecode;
+/// use dmidecode::SmbiosVersion;
+///
+/// assert_eq!(Ok(SmbiosVersion::V2_7), "2.7.1".parse());
+/// assert!(SmbiosVersion::new(2, 10) > "2.9".parse().unwrap());
+/// ```
+impl str::FromStr for SmbiosVersion {
+    type Err = InvalidVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = s.trim().split('.').map(|number| match number.as_bytes() {
+            [] | [b'+', ..] => Err(InvalidVersionError),
+            _ => number.parse::<u8>().map_err(|_| InvalidVersionError),
+        });
+        let major = numbers.next().ok_or(InvalidVersionError)??;
+        let minor = numbers.next().ok_or(InvalidVersionError)??;
+        if let Some(docrev) = numbers.next() {
+            docrev?;
+        }
+        match numbers.next() {
+            Some(_) => Err(InvalidVersionError),
+            None => Ok(SmbiosVersion { major, minor }),
+        }
+    }
+}
+
+impl From<(usize, usize)> for SmbiosVersion {
+    fn from(other: (usize, usize)) -> SmbiosVersion {
+        SmbiosVersion {
+            major: other.0 as u8,
+            minor: other.1 as u8,
+        }
+    }
+}
+
+/// What a buffer given to [EntryPoint::structures_auto] holds
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BufferLayout {
+    /// The structure table alone, e.g. `/sys/firmware/dmi/tables/DMI`
+    Table,
+    /// Physical memory from address `base` on, e.g. read from `/dev/mem`, in which the table is
+    /// found at its address in the entry point. `dmidecode --dump-bin` files have this layout
+    /// with a `base` of 0.
+    PhysicalMemory { base: u64 },
+}
+
+/// Validation relaxed by [EntryPoint::search_with]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SearchOptions {
+    /// Accept an SMBIOS 2.0 entry point whose intermediate anchor (`_DMI_`) is missing or has an
+    /// invalid checksum, as long as the structure table address and length are set. Some legacy
+    /// firmware leaves the intermediate area zeroed.
+    pub permissive_legacy_anchor: bool,
+}
+
+/// Failure type for trying to find the SMBIOS `EntryPoint` structure in memory.
+#[derive(Debug)]
+pub enum InvalidEntryPointError {
+    /// The SMBIOS `EntryPoint` structure was not found in the memory buffer.
+    NotFound,
+    /// The SMBIOS `EntryPoint` structure was versioned before 2.0.
+    TooOldVersion(u8),
+    /// The SMBIOS `EntryPoint` structure was smaller than the size of the SMBIOS 2.1 structure.
+    BadSize(u8),
+    /// The SMBIOS `EntryPoint` structure had an invalid checksum.
+    BadChecksum(u8),
+    /// The intermediate anchor (`_DMI_`) of an SMBIOS 2.x `EntryPoint` structure is missing or
+    /// has an invalid checksum.
+    BadIntermediateAnchor,
+}
+
+impl fmt::Display for InvalidEntryPointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidEntryPointError::NotFound => write!(f, "Input did not contain a valid SMBIOS entry point"),
+            InvalidEntryPointError::TooOldVersion(version) => {
+                write!(f, "Input version number was below 2.0: {}", version)
+            }
+            InvalidEntryPointError::BadSize(size) => {
+                write!(f, "Input contained an invalid-sized SMBIOS entry: {}", size)
+            }
+            InvalidEntryPointError::BadChecksum(checksum) => {
+                write!(f, "SMBIOS entry point has an invalid checksum: {}", checksum)
+            }
+            InvalidEntryPointError::BadIntermediateAnchor => {
+                write!(f, "SMBIOS entry point has an invalid intermediate anchor (_DMI_)")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
 impl std::error::Error for InvalidEntryPointError {}
 
 fn find_signature(buffer: &[u8]) -> Option<(EntryPointFormat, usize)> {
-    static STRIDE: usize = 16;
-    static V2_SIG: &[u8; 4] = &[0x5f, 0x53, 0x4d, 0x5f];
-    static V3_SIG: &[u8; 5] = &[0x5f, 0x53, 0x4d, 0x33, 0x5f];
+    const STRIDE: usize = 16;
+    // Anchors are compared as little-endian words, `_SM3_` is one byte longer than a word
+    const V2_SIG: u32 = u32::from_le_bytes(*b"_SM_");
+    const V3_SIG: u32 = u32::from_le_bytes(*b"_SM3");
 
     for (idx, chunk) in buffer.chunks(STRIDE).enumerate() {
-        if chunk.starts_with(V2_SIG) {
+        let head = match chunk {
+            [a, b, c, d, ..] => u32::from_le_bytes([*a, *b, *c, *d]),
+            _ => break,
+        };
+        if head == V2_SIG {
             return Some((EntryPointFormat::V2, idx * STRIDE));
-        } else if chunk.starts_with(V3_SIG) {
+        } else if head == V3_SIG && chunk.get(4) == Some(&b'_') {
             return Some((EntryPointFormat::V3, idx * STRIDE));
         }
     }
@@ -377,10 +1348,39 @@ pub struct Structures<'buffer> {
     smbios_len: u32,
     idx: u32,
     buffer: &'buffer [u8],
+    declared_len: u32,
+    parsed: u32,
+    end_of_table: bool,
+    failed: bool,
+    max_structure_size: Option<u16>,
+    enforce_max_structure_size: bool,
+    bios_quirks: &'static [bios::QuirkEntry],
+    trim_strings: bool,
+    tolerate_bad_string_indexes: bool,
+}
+
+/// How the iteration over a structure table ended, returned by [Structures::finish]
+///
+/// A table that was cut short, e.g. by a truncated dump, still yields its leading structures
+/// without an error; comparing `consumed` with `declared` and checking `end_of_table` tells that
+/// apart from a complete table.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TableStatus {
+    /// Whether an End-of-Table (Type 127) structure was read
+    pub end_of_table: bool,
+    /// Whether the iteration stopped at a structure that could not be read
+    pub failed: bool,
+    /// Number of bytes of the table read up to the end of the last structure
+    pub consumed: u32,
+    /// Table length given when the iterator was created, the maximum length for SMBIOS 3.x
+    pub declared: u32,
+    /// Number of structures read, whether or not they decoded
+    pub structures: u32,
 }
 
 /// Variant structure for decoding the SMBIOS table types.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
 pub enum Structure<'buffer> {
     Bios(Bios<'buffer>),
     System(System<'buffer>),
@@ -406,22 +1406,37 @@ pub enum Structure<'buffer> {
 }
 
 /// Failure type for trying to decode the SMBIOS `Structures` iterator into the `Structure` variant type.
-
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum MalformedStructureError {
     /// The SMBIOS structure exceeds the end of the memory buffer given to the `EntryPoint::structures` method.
     BadSize(u32, u8),
     /// The SMBIOS structure contains an unterminated strings section.
     UnterminatedStrings(u32),
-    /// The SMBIOS structure contains an invalid string index.
-    InvalidStringIndex(InfoType, u16, u8),
+    /// The SMBIOS structure contains an invalid string index, followed by the number of strings
+    /// the structure has.
+    InvalidStringIndex(InfoType, Handle, u8, u8),
     /// This error returned when a conversion from a slice to an array fails.
     InvalidSlice(core::array::TryFromSliceError),
     /// The SMBIOS structure formatted section length does not correspond to SMBIOS reference
     /// specification
-    InvalidFormattedSectionLength(InfoType, u16, &'static str, u8),
+    InvalidFormattedSectionLength {
+        info: InfoType,
+        handle: Handle,
+        expected: LengthExpectation,
+        /// Length found in the structure
+        actual: u8,
+    },
     /// The SMBIOS structure contains an invalid processor family
     InvalidProcessorFamily,
+    /// The SMBIOS structure, header and strings included, is larger than the maximum structure
+    /// size declared by the entry point, see [Structures::enforce_max_structure_size]
+    StructureTooLarge {
+        info: InfoType,
+        handle: Handle,
+        size: usize,
+        maximum: u16,
+    },
 }
 
 impl fmt::Display for MalformedStructureError {
@@ -437,130 +1452,714 @@ impl fmt::Display for MalformedStructureError {
             MalformedStructureError::UnterminatedStrings(offset) => {
                 write!(f, "Structure at offset {} with unterminated strings", offset)
             }
-            MalformedStructureError::InvalidStringIndex(info_type, handle, index) => {
-                write!(
-                    f,
-                    "Structure {:?} with handle {} has invalid string index {}",
-                    info_type, handle, index
-                )
+            MalformedStructureError::InvalidStringIndex(info_type, handle, index, available) => {
+                write!(
+                    f,
+                    "Structure {:?} with handle {} has invalid string index {}, {} strings present",
+                    info_type, handle.0, index, available
+                )
+            }
+            MalformedStructureError::InvalidSlice(cause) => {
+                write!(f, "{}", cause)
+            }
+            MalformedStructureError::InvalidFormattedSectionLength {
+                info, handle, expected, ..
+            } => {
+                write!(
+                    f,
+                    "Formatted section length of structure {:?} with handle {} should be {} bytes",
+                    info, handle.0, expected
+                )
+            }
+            MalformedStructureError::InvalidProcessorFamily => {
+                write!(f, "Invalid processor family")
+            }
+            MalformedStructureError::StructureTooLarge {
+                info,
+                handle,
+                size,
+                maximum,
+            } => {
+                write!(
+                    f,
+                    "Structure {:?} with handle {} is {} bytes long, larger than the maximum of {}",
+                    info, handle.0, size, maximum
+                )
+            }
+        }
+    }
+}
+
+/// Formatted section length required by the SMBIOS reference specification, see
+/// [MalformedStructureError::InvalidFormattedSectionLength]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LengthExpectation {
+    /// Length of a structure version that has no optional fields
+    Exact(u8),
+    /// Length of the fields every structure of the type has, later fields being optional
+    Minimum(u8),
+    /// Length computed from counts of the structure, e.g. `17h+(x*y)`, and its value
+    Formula(&'static str, u8),
+}
+
+impl fmt::Display for LengthExpectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthExpectation::Exact(length) => write!(f, "{}", length),
+            LengthExpectation::Minimum(length) => write!(f, "minimum of {}", length),
+            LengthExpectation::Formula(formula, length) => write!(f, "{} = {}", formula, length),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MalformedStructureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MalformedStructureError::InvalidSlice(ref cause) => Some(cause),
+            _ => None,
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Finds the final nul nul terminator of a buffer and returns the index of the final nul
+///
+/// The buffer is scanned a word at a time: the bytes of each word that are nul are found with bit
+/// arithmetic, then checked for two adjacent ones, including across word boundaries.
+fn find_nulnul(buf: &[u8]) -> Option<usize> {
+    const LOW_BITS: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+    let mut offset = 0;
+    let mut previous_ends_with_nul = false;
+    for chunk in buf.chunks_exact(8) {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(chunk);
+        let word = u64::from_le_bytes(bytes);
+        // The high bit of every byte that is zero, and only those: adding 0x7F to the low bits
+        // cannot carry into the next byte
+        let nuls = !(((word & LOW_BITS) + LOW_BITS) | word | LOW_BITS);
+        if previous_ends_with_nul && nuls & 0x80 != 0 {
+            return Some(offset);
+        }
+        let pairs = nuls & (nuls >> 8);
+        if pairs != 0 {
+            return Some(offset + (pairs.trailing_zeros() / 8) as usize + 1);
+        }
+        previous_ends_with_nul = nuls >> 63 != 0;
+        offset += 8;
+    }
+
+    // The tail, starting with the last byte of the final word
+    let start = offset.saturating_sub(1);
+    buf[start..]
+        .windows(2)
+        .position(|pair| pair == [0, 0])
+        .map(|i| start + i + 1)
+}
+
+impl<'buffer> Iterator for Structures<'buffer> {
+    type Item = Result<Structure<'buffer>, MalformedStructureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_structure()?.and_then(|structure| self.decode(structure)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.max_remaining()))
+    }
+}
+
+impl<'buffer> Structure<'buffer> {
+    /// Compare with `other` ignoring the fields that change from boot to boot or while the system
+    /// runs, so that tables read at different times compare equal when the hardware did not change
+    ///
+    /// The volatile fields are annotated in the structure modules: the current speed of
+    /// [Processor], the log status and change token of [SystemEventLog], and, for the structures
+    /// this crate does not decode, those listed by [RawStructure::eq_stable].
+    pub fn eq_stable(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Structure::Processor(s), Structure::Processor(o)) => s.eq_stable(o),
+            (Structure::SystemEventLog(s), Structure::SystemEventLog(o)) => s.eq_stable(o),
+            (Structure::Other(s), Structure::Other(o)) => s.eq_stable(o),
+            _ => self == other,
+        }
+    }
+
+    /// The handle of the structure
+    pub fn handle(&self) -> Handle {
+        match self {
+            Structure::Bios(s) => s.handle,
+            Structure::System(s) => s.handle,
+            Structure::BaseBoard(s) => s.handle,
+            Structure::Enclosure(s) => s.handle,
+            Structure::Processor(s) => s.handle,
+            Structure::Cache(s) => s.handle,
+            Structure::PortConnector(s) => s.handle,
+            Structure::SystemSlots(s) => s.handle,
+            Structure::OemStrings(s) => s.handle,
+            Structure::SystemConfigurationOptions(s) => s.handle,
+            Structure::BiosLanguage(s) => s.handle,
+            Structure::GroupAssociations(s) => s.handle,
+            Structure::SystemEventLog(s) => s.handle,
+            Structure::MemoryDevice(s) => s.handle,
+            Structure::MemoryError32(s) => s.handle,
+            Structure::MemoryArrayMappedAddress(s) => s.handle,
+            Structure::MemoryDeviceMappedAddress(s) => s.handle,
+            Structure::BuiltInPointingDevice(s) => s.handle,
+            Structure::PortableBattery(s) => s.handle,
+            Structure::PhysicalMemoryArray(s) => s.handle,
+            Structure::Other(s) => s.handle,
+        }
+    }
+
+    /// The type of the structure
+    pub fn info(&self) -> InfoType {
+        match self {
+            Structure::Bios(_) => InfoType::Bios,
+            Structure::System(_) => InfoType::System,
+            Structure::BaseBoard(_) => InfoType::BaseBoard,
+            Structure::Enclosure(_) => InfoType::Enclosure,
+            Structure::Processor(_) => InfoType::Processor,
+            Structure::Cache(_) => InfoType::Cache,
+            Structure::PortConnector(_) => InfoType::PortConnector,
+            Structure::SystemSlots(_) => InfoType::SystemSlots,
+            Structure::OemStrings(_) => InfoType::OemStrings,
+            Structure::SystemConfigurationOptions(_) => InfoType::SystemConfigurationOptions,
+            Structure::BiosLanguage(_) => InfoType::BiosLanguage,
+            Structure::GroupAssociations(_) => InfoType::GroupAssociations,
+            Structure::SystemEventLog(_) => InfoType::SystemEventLog,
+            Structure::MemoryDevice(_) => InfoType::MemoryDevice,
+            Structure::MemoryError32(_) => InfoType::MemoryError32,
+            Structure::MemoryArrayMappedAddress(_) => InfoType::MemoryArrayMappedAddress,
+            Structure::MemoryDeviceMappedAddress(_) => InfoType::MemoryDeviceMappedAddress,
+            Structure::BuiltInPointingDevice(_) => InfoType::BuiltInPointingDevice,
+            Structure::PortableBattery(_) => InfoType::PortableBattery,
+            Structure::PhysicalMemoryArray(_) => InfoType::PhysicalMemoryArray,
+            Structure::Other(s) => s.info,
+        }
+    }
+
+    fn decode(structure: RawStructure<'buffer>) -> Result<Structure<'buffer>, MalformedStructureError> {
+        let (info, handle) = (structure.info, structure.handle);
+        let decoded = structures::length::check_minimum(info, &structure).and_then(|()| match structure.info {
+            InfoType::Bios => Bios::from_raw(structure).map(Structure::Bios),
+            InfoType::System => System::from_raw(structure).map(Structure::System),
+            InfoType::BaseBoard => BaseBoard::from_raw(structure).map(Structure::BaseBoard),
+            InfoType::Enclosure => Enclosure::from_raw(structure).map(Structure::Enclosure),
+            InfoType::Processor => Processor::from_raw(structure).map(Structure::Processor),
+            InfoType::Cache => Cache::from_raw(structure).map(Structure::Cache),
+            InfoType::PortConnector => PortConnector::from_raw(structure).map(Structure::PortConnector),
+            InfoType::SystemSlots => SystemSlots::from_raw(structure).map(Structure::SystemSlots),
+            InfoType::OemStrings => OemStrings::from_raw(structure).map(Structure::OemStrings),
+            InfoType::SystemConfigurationOptions => {
+                SystemConfigurationOptions::from_raw(structure).map(Structure::SystemConfigurationOptions)
+            }
+            InfoType::BiosLanguage => BiosLanguage::from_raw(structure).map(Structure::BiosLanguage),
+            InfoType::GroupAssociations => GroupAssociations::from_raw(structure).map(Structure::GroupAssociations),
+            InfoType::SystemEventLog => SystemEventLog::from_raw(structure).map(Structure::SystemEventLog),
+            InfoType::PhysicalMemoryArray => {
+                PhysicalMemoryArray::from_raw(structure).map(Structure::PhysicalMemoryArray)
             }
-            MalformedStructureError::InvalidSlice(cause) => {
-                write!(f, "{}", cause)
+            InfoType::MemoryDevice => MemoryDevice::from_raw(structure).map(Structure::MemoryDevice),
+            InfoType::MemoryError32 => MemoryError32::from_raw(structure).map(Structure::MemoryError32),
+            InfoType::MemoryArrayMappedAddress => {
+                MemoryArrayMappedAddress::from_raw(structure).map(Structure::MemoryArrayMappedAddress)
             }
-            MalformedStructureError::InvalidFormattedSectionLength(info_type, handle, spec, length) => {
-                write!(
-                    f,
-                    "Formatted section length of structure {:?} with handle {} should be {}{} bytes",
-                    info_type, handle, spec, length
-                )
+            InfoType::MemoryDeviceMappedAddress => {
+                MemoryDeviceMappedAddress::from_raw(structure).map(Structure::MemoryDeviceMappedAddress)
             }
-            MalformedStructureError::InvalidProcessorFamily => {
-                write!(f, "Invalid processor family")
+            InfoType::BuiltInPointingDevice => {
+                BuiltInPointingDevice::from_raw(structure).map(Structure::BuiltInPointingDevice)
             }
+            InfoType::PortableBattery => PortableBattery::from_raw(structure).map(Structure::PortableBattery),
+            _ => Ok(Structure::Other(structure)),
+        });
+        if let Err(ref e) = decoded {
+            lib_debug!("skipping {} structure {:#06X}: {}", info, handle, e);
         }
+        decoded
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for MalformedStructureError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+/// An iterator over the SMBIOS structures paired with their complete strings tables
+///
+/// Created by [Structures::with_strings]. The strings table also contains the strings that are not
+/// referenced by any field of the decoded structure, e.g. additional strings appended by an OEM.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StructuresWithStrings<'buffer>(Structures<'buffer>);
+
+impl<'buffer> Iterator for StructuresWithStrings<'buffer> {
+    type Item = Result<(Structure<'buffer>, StructureStrings<'buffer>), MalformedStructureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.0.next_structure()?.and_then(|structure| {
+            let strings = structure.strings();
+            self.0.decode(structure).map(|decoded| (decoded, strings))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// An iterator over the SMBIOS structures that does not decode them
+///
+/// Created by [EntryPoint::raw_structures] or [Structures::raw]. Like [Structures], the iteration
+/// stops after a structure that cannot be read, and at the End-of-Table structure of SMBIOS 3.x
+/// tables.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RawStructures<'buffer>(Structures<'buffer>);
+
+impl<'buffer> Iterator for RawStructures<'buffer> {
+    type Item = Result<RawStructure<'buffer>, MalformedStructureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_structure()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// An iterator over the SMBIOS structures of the types selected by a name
+///
+/// Created by [Structures::find_by_name]. Structures of other types are skipped without being
+/// decoded.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StructuresByName<'buffer> {
+    structures: Structures<'buffer>,
+    types: NamedTypes,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+enum NamedTypes {
+    Type(InfoType),
+    Keyword(&'static [InfoType]),
+}
+
+impl NamedTypes {
+    fn contains(self, info: InfoType) -> bool {
         match self {
-            MalformedStructureError::InvalidSlice(ref cause) => Some(cause),
-            _ => None,
+            NamedTypes::Type(named) => named == info,
+            NamedTypes::Keyword(types) => types.contains(&info),
         }
     }
 }
 
-#[doc(hidden)]
-/// Finds the final nul nul terminator of a buffer and returns the index of the final nul
-fn find_nulnul(buf: &[u8]) -> Option<usize> {
-    for i in 0..buf.len() {
-        if i + 1 >= buf.len() {
-            return None;
+impl<'buffer> Iterator for StructuresByName<'buffer> {
+    type Item = Result<Structure<'buffer>, MalformedStructureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.structures.next_structure()? {
+                Ok(structure) if self.types.contains(structure.info) => return Some(self.structures.decode(structure)),
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
         }
+    }
+}
 
-        if buf[i] == 0 && buf[i + 1] == 0 {
-            return Some(i + 1);
+impl<'buffer> Structures<'buffer> {
+    /// Create an iterator across the SMBIOS structures found in `buffer` without an `EntryPoint`.
+    ///
+    /// This is useful when only the DMI table blob and the SMBIOS version are known, e.g. when
+    /// the table was extracted from a hypervisor or a kernel crash dump. `smbios_len` is the
+    /// length of the structure table; for SMBIOS 3.x it is the maximum length and the iteration
+    /// stops at the End-of-Table structure. Firmware often declares an SMBIOS 3.x maximum larger
+    /// than the table, so the maximum is clamped to the length of `buffer`; the warnings of a
+    /// table that has no End-of-Table structure within `buffer` then include
+    /// [warnings::Warning::TableLengthClamped].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{SmbiosVersion, Structures};
+    ///
+    /// const DMI_BIN: &'static [u8] = include_bytes!("../tests/data/dmi_v3.bin");
+    ///
+    /// let version = SmbiosVersion { major: 3, minor: 0 };
+    /// for s in Structures::new(DMI_BIN, version, DMI_BIN.len() as u32) {
+    ///   let table = s.unwrap();
+    /// }
+    /// ```
+    pub fn new(buffer: &'buffer [u8], smbios_version: SmbiosVersion, smbios_len: u32) -> Self {
+        let available = buffer.len().min(u32::MAX as usize) as u32;
+        let clamped = if smbios_version.major >= 3 && smbios_len > available {
+            lib_debug!(
+                "SMBIOS 3.x table maximum length {:#X} exceeds the {:#X} bytes available",
+                smbios_len,
+                available
+            );
+            available
+        } else {
+            smbios_len
+        };
+        Structures {
+            smbios_version,
+            smbios_len: clamped,
+            idx: 0u32,
+            buffer,
+            declared_len: smbios_len,
+            parsed: 0,
+            end_of_table: false,
+            failed: false,
+            max_structure_size: None,
+            enforce_max_structure_size: false,
+            bios_quirks: &[],
+            trim_strings: false,
+            tolerate_bad_string_indexes: false,
         }
     }
 
-    None
-}
+    /// Set the maximum structure size, header and strings included, that the structures are
+    /// expected to respect. [EntryPoint::structures] sets it from the entry point.
+    ///
+    /// Larger structures are reported by [Structures::warnings], or stop the iteration with
+    /// [Structures::enforce_max_structure_size].
+    pub fn with_max_structure_size(mut self, max: u16) -> Self {
+        self.max_structure_size = Some(max);
+        self
+    }
 
-impl<'buffer> Iterator for Structures<'buffer> {
-    type Item = Result<Structure<'buffer>, MalformedStructureError>;
+    /// Strict mode: stop the iteration with a `MalformedStructureError::StructureTooLarge` at a
+    /// structure larger than the maximum structure size, instead of only reporting a warning
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+    /// for structure in entry_point.structures(table).enforce_max_structure_size() {
+    ///     assert!(structure.is_ok());
+    /// }
+    /// ```
+    pub fn enforce_max_structure_size(mut self) -> Self {
+        self.enforce_max_structure_size = true;
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let structure = match self.next_raw()? {
+    /// The maximum structure size the structures are checked against
+    pub fn max_structure_size(&self) -> Option<u16> {
+        self.max_structure_size
+    }
+
+    /// Trim the trailing whitespace vendors pad strings with, e.g. `"PowerEdge R740    "`, from
+    /// all string fields of the structures, see [RawStructure::trim_strings]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, Structure};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+    /// for structure in entry_point.structures(table).trim_strings() {
+    ///     if let Ok(Structure::System(system)) = structure {
+    ///         assert!(!system.product.ends_with(' '));
+    ///     }
+    /// }
+    /// ```
+    pub fn trim_strings(mut self) -> Self {
+        self.trim_strings = true;
+        self
+    }
+
+    /// Read the string fields whose index is past the strings table as empty strings instead of
+    /// failing the whole structure, see [RawStructure::tolerate_bad_string_indexes]
+    pub fn tolerate_bad_string_indexes(mut self) -> Self {
+        self.tolerate_bad_string_indexes = true;
+        self
+    }
+
+    /// Work around the known firmware bugs listed in `registry` while decoding BIOS Information
+    /// structures
+    ///
+    /// The quirks apply to the structures decoded by this iterator and by [Structures::find_handle],
+    /// [Structures::find_by_name] and [Structures::with_strings]; [Structures::warnings] reports
+    /// every quirk applied as a [warnings::Warning::QuirkApplied].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{bios::{Quirk, QuirkEntry}, EntryPoint, Structure};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    /// const QUIRKS: &[QuirkEntry] = &[QuirkEntry {
+    ///     vendor: "LENOVO",
+    ///     version: None,
+    ///     quirk: Quirk::PreferExtendedRomSize,
+    /// }];
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+    /// for structure in entry_point.structures(table).with_bios_quirks(QUIRKS) {
+    ///     if let Ok(Structure::Bios(bios)) = structure {
+    ///         assert!(bios.has_quirk(QUIRKS, Quirk::PreferExtendedRomSize));
+    ///     }
+    /// }
+    /// ```
+    pub fn with_bios_quirks(mut self, registry: &'static [bios::QuirkEntry]) -> Self {
+        self.bios_quirks = registry;
+        self
+    }
+
+    /// The registry of BIOS quirks worked around while decoding
+    pub fn bios_quirks(&self) -> &'static [bios::QuirkEntry] {
+        self.bios_quirks
+    }
+
+    /// Decode a structure of the table, working around the BIOS quirks
+    fn decode(&self, structure: RawStructure<'buffer>) -> Result<Structure<'buffer>, MalformedStructureError> {
+        match Structure::decode(structure.clone())? {
+            Structure::Bios(bios) if !self.bios_quirks.is_empty() => {
+                Ok(Structure::Bios(bios.with_quirks(&structure, self.bios_quirks)))
+            }
+            decoded => Ok(decoded),
+        }
+    }
+
+    /// Length of the structure table given when the iterator was created, the maximum length for
+    /// SMBIOS 3.x
+    pub fn total_size(&self) -> u32 {
+        self.declared_len
+    }
+
+    /// The part of the table left to read: it starts after the last structure read and ends at the
+    /// table length, or where the iteration stopped
+    ///
+    /// The end moves back to the start once the iteration is over, so that `start` over `end`
+    /// reports the progress of a scan, reaching 1 at the End-of-Table structure of a table shorter
+    /// than its SMBIOS 3.x maximum length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    /// const DMI_BIN: &'static [u8] = include_bytes!("../tests/data/dmi.bin");
+    ///
+    /// let entry_point = EntryPoint::search(ENTRY_BIN).unwrap();
+    /// let mut structures = entry_point.structures(&DMI_BIN);
+    /// assert_eq!(0..structures.total_size(), structures.bounds());
+    /// while structures.next().is_some() {
+    ///     let bounds = structures.bounds();
+    ///     println!("{}%", 100 * bounds.start / bounds.end);
+    /// }
+    /// assert!(structures.bounds().is_empty());
+    /// ```
+    pub fn bounds(&self) -> Range<u32> {
+        self.idx..self.smbios_len.max(self.idx)
+    }
+
+    /// Upper bound of the number of structures left: every structure takes at least 6 bytes, its
+    /// header and an empty strings table, and the last bytes can still hold a malformed one
+    fn max_remaining(&self) -> usize {
+        let remaining = self.bounds().len();
+        (remaining + 2) / 6
+    }
+
+    /// Read the rest of the table without decoding it and report how the table ended
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::EntryPoint;
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    /// const DMI_BIN: &'static [u8] = include_bytes!("../tests/data/dmi.bin");
+    ///
+    /// let entry_point = EntryPoint::search(ENTRY_BIN).unwrap();
+    /// let status = entry_point.structures(&DMI_BIN).finish();
+    /// assert!(!status.failed);
+    /// assert_eq!(status.declared, status.consumed);
+    /// ```
+    pub fn finish(mut self) -> TableStatus {
+        while self.next_structure().is_some() {}
+        TableStatus {
+            end_of_table: self.end_of_table,
+            failed: self.failed,
+            consumed: self.idx,
+            declared: self.declared_len,
+            structures: self.parsed,
+        }
+    }
+
+    /// Yield the structures without decoding them, see [EntryPoint::raw_structures]
+    pub fn raw(self) -> RawStructures<'buffer> {
+        RawStructures(self)
+    }
+
+    /// Pair every structure with its complete strings table
+    pub fn with_strings(self) -> StructuresWithStrings<'buffer> {
+        StructuresWithStrings(self)
+    }
+
+    /// Select the structures by a `dmidecode --type` argument: a keyword (see
+    /// [InfoType::keyword_types]), a decimal type number or a structure type name (see
+    /// [InfoType::from_name]). Returns `None` if `name` matches none of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, Structure};
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    /// const DMI_BIN: &'static [u8] = include_bytes!("../tests/data/dmi.bin");
+    ///
+    /// let entry_point = EntryPoint::search(ENTRY_BIN).unwrap();
+    /// for s in entry_point.structures(&DMI_BIN).find_by_name("17").unwrap() {
+    ///     assert!(matches!(s, Ok(Structure::MemoryDevice(_))));
+    /// }
+    /// ```
+    pub fn find_by_name(self, name: &str) -> Option<StructuresByName<'buffer>> {
+        let types = if let Some(types) = InfoType::keyword_types(name) {
+            NamedTypes::Keyword(types)
+        } else if let Ok(code) = name.trim().parse::<u8>() {
+            NamedTypes::Type(InfoType::from(code))
+        } else {
+            #[cfg(feature = "strings")]
+            let named = InfoType::from_name(name);
+            #[cfg(not(feature = "strings"))]
+            let named = None;
+            NamedTypes::Type(named?)
+        };
+        Some(StructuresByName {
+            structures: self,
+            types,
+        })
+    }
+
+    /// Decode the structure with the given handle, like `dmidecode --handle`.
+    ///
+    /// The structures before the matching one are only read up to the end of their strings, they
+    /// are not decoded. Returns `None` if no structure has this handle, and an error if the table
+    /// cannot be read up to the matching structure or if it does not decode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, Structure};
+    ///
+    /// const ENTRY_BIN: &'static [u8] = include_bytes!("../tests/data/entry.bin");
+    /// const DMI_BIN: &'static [u8] = include_bytes!("../tests/data/dmi.bin");
+    ///
+    /// let entry_point = EntryPoint::search(ENTRY_BIN).unwrap();
+    /// let structures = entry_point.structures(&DMI_BIN);
+    /// assert!(matches!(structures.find_handle(0x0001), Some(Ok(Structure::Processor(_)))));
+    /// assert!(structures.find_handle(0xBEEF).is_none());
+    /// ```
+    pub fn find_handle<H: Into<Handle>>(
+        &self,
+        handle: H,
+    ) -> Option<Result<Structure<'buffer>, MalformedStructureError>> {
+        let handle = handle.into();
+        let mut structures = self.clone();
+        loop {
+            match structures.next_structure()? {
+                Ok(structure) if structure.handle == handle => return Some(structures.decode(structure)),
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// Decode the structure with the given handle, only the matching structure is decoded.
+    /// Reserved handles never match.
+    pub(crate) fn decode_handle(&self, handle: Handle) -> Option<Structure<'buffer>> {
+        if handle.is_reserved() {
+            return None;
+        }
+        self.find_handle(handle)?.ok()
+    }
+
+    /// Read the next raw structure, stopping the iteration on errors and at the end of the table
+    fn next_structure(&mut self) -> Option<Result<RawStructure<'buffer>, MalformedStructureError>> {
+        let structure = match self.next_raw()?.and_then(|structure| self.check_size(structure)) {
             Ok(s) => s,
             Err(e) => {
+                lib_debug!("stopping at offset {:#X} of the structure table: {}", self.idx, e);
                 // make any errors to get the raw structure stop
                 // future iterations. This will avoid any nfinite
                 // iterations when skipping errors
                 self.smbios_len = self.idx;
+                self.failed = true;
                 return Some(Err(e));
             }
         };
+        self.parsed += 1;
+        if structure.info == InfoType::End {
+            self.end_of_table = true;
+        }
 
         /*
          * For SMBIOS v3 we have no exact table length and no item count,
          * so stop at the end-of-table marker.
          */
         if self.smbios_version.major >= 3 && structure.info == InfoType::End {
+            lib_trace!(
+                "End-of-Table structure {:#06X} ends the structure table",
+                structure.handle
+            );
             self.smbios_len = self.idx;
         }
 
-        Some(match structure.info {
-            InfoType::Bios => Bios::try_from(structure).map(Structure::Bios),
-            InfoType::System => System::try_from(structure).map(Structure::System),
-            InfoType::BaseBoard => BaseBoard::try_from(structure).map(Structure::BaseBoard),
-            InfoType::Enclosure => Enclosure::try_from(structure).map(Structure::Enclosure),
-            InfoType::Processor => Processor::try_from(structure).map(Structure::Processor),
-            InfoType::Cache => Cache::try_from(structure).map(Structure::Cache),
-            InfoType::PortConnector => PortConnector::try_from(structure).map(Structure::PortConnector),
-            InfoType::SystemSlots => SystemSlots::try_from(structure).map(Structure::SystemSlots),
-            InfoType::OemStrings => OemStrings::try_from(structure).map(Structure::OemStrings),
-            InfoType::SystemConfigurationOptions => {
-                SystemConfigurationOptions::try_from(structure).map(Structure::SystemConfigurationOptions)
-            }
-            InfoType::BiosLanguage => BiosLanguage::try_from(structure).map(Structure::BiosLanguage),
-            InfoType::GroupAssociations => GroupAssociations::try_from(structure).map(Structure::GroupAssociations),
-            InfoType::SystemEventLog => SystemEventLog::try_from(structure).map(Structure::SystemEventLog),
-            InfoType::PhysicalMemoryArray => {
-                PhysicalMemoryArray::try_from(structure).map(Structure::PhysicalMemoryArray)
-            }
-            InfoType::MemoryDevice => MemoryDevice::try_from(structure).map(Structure::MemoryDevice),
-            InfoType::MemoryError32 => MemoryError32::try_from(structure).map(Structure::MemoryError32),
-            InfoType::MemoryArrayMappedAddress => {
-                MemoryArrayMappedAddress::try_from(structure).map(Structure::MemoryArrayMappedAddress)
-            }
-            InfoType::MemoryDeviceMappedAddress => {
-                MemoryDeviceMappedAddress::try_from(structure).map(Structure::MemoryDeviceMappedAddress)
-            }
-            InfoType::BuiltInPointingDevice => {
-                BuiltInPointingDevice::try_from(structure).map(Structure::BuiltInPointingDevice)
+        Some(Ok(structure))
+    }
+
+    /// Reject a structure larger than the maximum structure size in strict mode
+    fn check_size(&self, structure: RawStructure<'buffer>) -> Result<RawStructure<'buffer>, MalformedStructureError> {
+        match self.max_structure_size {
+            Some(maximum) if self.enforce_max_structure_size && structure.size() > maximum as usize => {
+                Err(MalformedStructureError::StructureTooLarge {
+                    info: structure.info,
+                    handle: structure.handle,
+                    size: structure.size(),
+                    maximum,
+                })
             }
-            InfoType::PortableBattery => PortableBattery::try_from(structure).map(Structure::PortableBattery),
-            _ => Ok(Structure::Other(structure)),
-        })
+            _ => Ok(structure),
+        }
     }
-}
 
-impl<'buffer> Structures<'buffer> {
     fn next_raw(&mut self) -> Option<Result<RawStructure<'buffer>, MalformedStructureError>> {
         if (self.idx + mem::size_of::<HeaderPacked>() as u32) > self.smbios_len {
             return None;
         }
 
-        let working = &self.buffer[(self.idx as usize)..];
-        let_as_struct!(header, HeaderPacked, working);
+        let header_len = mem::size_of::<HeaderPacked>();
+        let working = self.buffer.get((self.idx as usize)..).unwrap_or(&[]);
+        let_as_struct!(
+            header,
+            HeaderPacked,
+            working,
+            or return Some(Err(MalformedStructureError::BadSize(self.idx, header_len as u8)))
+        );
 
         let strings_idx: u32 = self.idx + header.len as u32;
-        if strings_idx >= self.smbios_len {
+        if strings_idx >= self.smbios_len || (header.len as usize) < header_len {
             return Some(Err(MalformedStructureError::BadSize(self.idx, header.len)));
         }
 
-        let term = find_nulnul(&self.buffer[(strings_idx as usize)..]);
+        let term = self.buffer.get((strings_idx as usize)..).and_then(find_nulnul);
         let strings_len = match term {
             Some(terminator) => (terminator + 1) as u32,
             None => {
@@ -572,9 +2171,11 @@ impl<'buffer> Structures<'buffer> {
             version: self.smbios_version,
             info: header.kind.into(),
             length: header.len,
-            handle: header.handle,
+            handle: Handle(header.handle.get()),
             data: &self.buffer[(self.idx + mem::size_of::<HeaderPacked>() as u32) as usize..strings_idx as usize],
             strings: &self.buffer[strings_idx as usize..(strings_idx + strings_len) as usize],
+            trim_strings: self.trim_strings,
+            tolerate_bad_string_indexes: self.tolerate_bad_string_indexes,
         };
 
         self.idx = strings_idx + strings_len;
@@ -589,7 +2190,82 @@ impl<'buffer> Structures<'buffer> {
 struct HeaderPacked {
     kind: u8,
     len: u8,
-    handle: u16,
+    handle: endian::Le16,
+}
+
+/// The handle of an SMBIOS structure
+///
+/// Every structure has a unique handle, other structures use it to refer to the structure. Two
+/// values are reserved for references that do not point to any structure: [Handle::NONE] and
+/// [Handle::UNKNOWN].
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Handle(pub u16);
+
+impl Handle {
+    /// The reference does not point to any structure, e.g. the information is not provided
+    pub const NONE: Handle = Handle(0xFFFF);
+    /// The structure that the reference should point to is unknown, e.g. there is no error
+    /// information structure even though the error may be tracked
+    pub const UNKNOWN: Handle = Handle(0xFFFE);
+
+    /// Whether the handle is one of the reserved values [Handle::NONE] and [Handle::UNKNOWN]
+    pub fn is_reserved(self) -> bool {
+        self == Handle::NONE || self == Handle::UNKNOWN
+    }
+
+    /// The handle as a reference to a structure, `None` for the reserved values
+    pub fn as_reference(self) -> Option<Handle> {
+        Some(self).filter(|handle| !handle.is_reserved())
+    }
+}
+
+impl From<u16> for Handle {
+    fn from(handle: u16) -> Handle {
+        Handle(handle)
+    }
+}
+
+impl From<Handle> for u16 {
+    fn from(handle: Handle) -> u16 {
+        handle.0
+    }
+}
+
+impl<'a> TryFromBytes<'a, Handle> for Handle {
+    fn try_from_bytes(bytes: &'a [u8]) -> Result<Self, TryFromSliceError> {
+        u16::try_from_bytes(bytes).map(Handle)
+    }
+}
+
+impl PartialEq<u16> for Handle {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Formatted as in the dmidecode output, e.g. `0x0C00`; the reserved values read `Not Provided`
+/// ([Handle::NONE]) and `Unknown` ([Handle::UNKNOWN]), format them with `{:#06X}` for their value
+impl fmt::Display for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Handle::NONE => write!(f, "Not Provided"),
+            Handle::UNKNOWN => write!(f, "Unknown"),
+            Handle(handle) => write!(f, "{:#06X}", handle),
+        }
+    }
+}
+
+impl fmt::LowerHex for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
 }
 
 /// The raw SMBIOS structure information for structures that are not handled by this crate, such as Oem structures.
@@ -598,9 +2274,11 @@ pub struct RawStructure<'buffer> {
     pub version: SmbiosVersion,
     pub info: InfoType,
     pub length: u8,
-    pub handle: u16,
+    pub handle: Handle,
     pub data: &'buffer [u8],
     strings: &'buffer [u8],
+    trim_strings: bool,
+    tolerate_bad_string_indexes: bool,
 }
 
 /// General trait for slice -> unsigned conversion
@@ -633,11 +2311,198 @@ impl<'a> TryFromBytes<'a, u128> for u128 {
         bytes.try_into().map(u128::from_le_bytes)
     }
 }
+impl<'a, const N: usize> TryFromBytes<'a, [u8; N]> for [u8; N] {
+    fn try_from_bytes(bytes: &'a [u8]) -> Result<Self, TryFromSliceError> {
+        bytes.try_into()
+    }
+}
+
+/// Fields of the structures this crate does not decode that change from boot to boot, as
+/// `(offset, size)`
+fn volatile_fields(info: InfoType) -> &'static [(usize, usize)] {
+    match info {
+        // Nominal Value, which some firmware fills with the reading at boot
+        InfoType::VoltageProbe | InfoType::TemperatureProbe | InfoType::ElectricalCurrentProbe => &[(0x14, 2)],
+        _ => &[],
+    }
+}
+
+/// An empty structure, so that tests building a structure field by field only spell out the
+/// fields they need
+#[cfg(test)]
+impl<'buffer> Default for RawStructure<'buffer> {
+    fn default() -> Self {
+        RawStructure {
+            version: SmbiosVersion { major: 3, minor: 0 },
+            info: InfoType::End,
+            length: 4,
+            handle: Handle(0),
+            data: &[],
+            strings: &[0, 0],
+            trim_strings: false,
+            tolerate_bad_string_indexes: false,
+        }
+    }
+}
 
 impl<'buffer> RawStructure<'buffer> {
+    /// Compare with `other` ignoring the fields that change from boot to boot: the Nominal Value of
+    /// the Voltage, Temperature and Electrical Current Probes (Types 26, 28 and 29)
+    pub fn eq_stable(&self, other: &Self) -> bool {
+        if self.info != other.info || self.data.len() != other.data.len() {
+            return false;
+        }
+        let fields = volatile_fields(self.info);
+        let volatile = |index: usize| {
+            fields
+                .iter()
+                .any(|&(offset, size)| (offset - 4..offset - 4 + size).contains(&index))
+        };
+        let data = self
+            .data
+            .iter()
+            .zip(other.data)
+            .enumerate()
+            .all(|(index, (a, b))| a == b || volatile(index));
+        data && RawStructure {
+            data: other.data,
+            ..self.clone()
+        } == *other
+    }
+
+    /// The number of bytes the structure takes in the table: the header, the formatted section and
+    /// the strings table with its terminator
+    pub fn size(&self) -> usize {
+        mem::size_of::<HeaderPacked>() + self.data.len() + self.strings.len()
+    }
+
+    /// Decode an Inactive (Type 126) structure as a structure of type `original`
+    ///
+    /// The specification lets firmware disable a structure by changing its type to 126 and
+    /// keeping its content; some vendors do so for disabled memory devices and processors. The
+    /// type the structure had is not recorded in the table, the caller chooses it. Returns `None`
+    /// if the structure is not inactive.
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{InfoType, Structure, Structures};
+    /// # const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    /// # let entry_point = dmidecode::EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// # let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+    ///
+    /// for structure in structures.raw().flatten() {
+    ///     if let Some(Ok(Structure::MemoryDevice(device))) = structure.decode_inactive_as(InfoType::MemoryDevice) {
+    ///         println!("disabled memory device {}: {:?}", device.device_locator, device.size_bytes());
+    ///     }
+    /// }
+    /// ```
+    pub fn decode_inactive_as(
+        &self,
+        original: InfoType,
+    ) -> Option<Result<Structure<'buffer>, MalformedStructureError>> {
+        if self.info != InfoType::Inactive {
+            return None;
+        }
+        lib_trace!("decoding inactive structure {:#06X} as {}", self.handle, original);
+        Some(Structure::decode(RawStructure {
+            info: original,
+            ..self.clone()
+        }))
+    }
+
+    /// Trim trailing whitespace from the strings the `&str` and `Cow<str>` based accessors return,
+    /// and so from the string fields of the decoded structure
+    ///
+    /// The raw accessors, [RawStructure::raw_strings] and [RawStructure::find_string_bytes], keep
+    /// returning the strings as found in the table.
+    pub fn trim_strings(mut self) -> Self {
+        self.trim_strings = true;
+        self
+    }
+
+    /// Resolve the string indexes past the strings table to empty strings in the `&str` based
+    /// accessors, and so in the string fields of the decoded structure, like `dmidecode` goes on
+    /// after printing `<BAD INDEX>`
+    ///
+    /// [RawStructure::resolve_string] still tells such indexes apart.
+    pub fn tolerate_bad_string_indexes(mut self) -> Self {
+        self.tolerate_bad_string_indexes = true;
+        self
+    }
+
     /// Return an iterator over the strings in the strings table.
-    fn strings(&self) -> StructureStrings<'buffer> {
-        StructureStrings::new(self.strings)
+    pub fn strings(&self) -> StructureStrings<'buffer> {
+        StructureStrings::new(self.strings).trim_end(self.trim_strings)
+    }
+
+    /// Return an iterator over the strings in the strings table paired with the index that fields
+    /// use to refer to them, starting at 1.
+    ///
+    /// Strings that are not valid UTF-8 are skipped without shifting the indices of the following
+    /// ones, which makes it suitable to explain a `MalformedStructureError::InvalidStringIndex`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, InfoType};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let system = entry_point
+    ///     .raw_structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..])
+    ///     .flatten()
+    ///     .find(|s| s.info == InfoType::System)
+    ///     .unwrap();
+    /// for (index, string) in system.strings_indexed() {
+    ///     println!("{}: {}", index, string);
+    /// }
+    /// ```
+    pub fn strings_indexed(&self) -> IndexedStrings<'buffer> {
+        IndexedStrings {
+            raw: self.raw_strings(),
+            index: 0,
+            trim: self.trim_strings,
+        }
+    }
+
+    /// Return an iterator over the strings in the strings table as raw bytes.
+    ///
+    /// Unlike the `&str` based accessors this does not stop at strings that are not valid UTF-8.
+    pub fn raw_strings(&self) -> RawStrings<'buffer> {
+        RawStrings::new(self.strings)
+    }
+
+    /// Find a string in the strings table by the string index and return its raw bytes.
+    /// If the string index is 0, the empty slice is returned.
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::InvalidStringIndex` if the index is outside of the strings table.
+    pub fn find_string_bytes(&self, idx: u8) -> Result<&'buffer [u8], MalformedStructureError> {
+        if idx == 0 {
+            Ok(&[])
+        } else {
+            self.raw_strings()
+                .nth((idx - 1) as usize)
+                .ok_or_else(|| self.invalid_string_index(idx))
+        }
+    }
+
+    /// Find a string in the strings table by the string index, replacing invalid UTF-8 sequences
+    /// with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::InvalidStringIndex` if the index is outside of the strings table.
+    #[cfg(feature = "alloc")]
+    pub fn find_string_lossy(&self, idx: u8) -> Result<Cow<'buffer, str>, MalformedStructureError> {
+        self.find_string_bytes(idx).map(String::from_utf8_lossy).map(|string| {
+            if self.trim_strings {
+                trim_end_cow(string)
+            } else {
+                string
+            }
+        })
     }
 
     /// Find a string in the strings table by the string index.
@@ -645,15 +2510,69 @@ impl<'buffer> RawStructure<'buffer> {
     /// to that string index in the strings table is returned.
     ///
     /// # Errors
-    /// Returns a `MalformedStructureError::InvalidStringIndex` if the index is outside of the strings table.
+    /// Returns a `MalformedStructureError::InvalidStringIndex` if the index is outside of the strings
+    /// table, unless [RawStructure::tolerate_bad_string_indexes] resolves it to the empty string.
     pub fn find_string(&self, idx: u8) -> Result<&'buffer str, MalformedStructureError> {
+        match self.resolve_string(idx) {
+            StringField::Found(string) => Ok(string),
+            StringField::BadIndex { .. } if self.tolerate_bad_string_indexes => {
+                lib_debug!(
+                    "{} structure {:#06X}: string index {} is past the strings table, read as empty",
+                    self.info,
+                    self.handle,
+                    idx
+                );
+                Ok("")
+            }
+            StringField::BadIndex { .. } => Err(self.invalid_string_index(idx)),
+        }
+    }
+
+    /// Find a string in the strings table by the string index without failing, so that a bad
+    /// index only affects the field that holds it.
+    ///
+    /// An index past the strings table resolves to [StringField::BadIndex], whether or not
+    /// [RawStructure::find_string] tolerates it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dmidecode;
+    /// use dmidecode::{EntryPoint, InfoType, StringField};
+    ///
+    /// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+    ///
+    /// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+    /// let system = entry_point
+    ///     .raw_structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..])
+    ///     .flatten()
+    ///     .find(|s| s.info == InfoType::System)
+    ///     .unwrap();
+    /// assert_eq!(StringField::Found("LENOVO"), system.resolve_string(1));
+    /// let available = system.string_count();
+    /// assert_eq!(StringField::BadIndex { index: 200, available }, system.resolve_string(200));
+    /// assert_eq!("", system.resolve_string(200).or_empty());
+    /// ```
+    pub fn resolve_string(&self, idx: u8) -> StringField<'buffer> {
         if idx == 0 {
-            Ok("")
-        } else {
-            self.strings()
-                .nth((idx - 1) as usize)
-                .ok_or(MalformedStructureError::InvalidStringIndex(self.info, self.handle, idx))
+            return StringField::Found("");
         }
+        match self.strings().nth((idx - 1) as usize) {
+            Some(string) => StringField::Found(string),
+            None => StringField::BadIndex {
+                index: idx,
+                available: self.string_count(),
+            },
+        }
+    }
+
+    /// Number of strings in the strings table, saturating at 255
+    pub fn string_count(&self) -> u8 {
+        self.raw_strings().count().min(u8::MAX as usize) as u8
+    }
+
+    fn invalid_string_index(&self, idx: u8) -> MalformedStructureError {
+        MalformedStructureError::InvalidStringIndex(self.info, self.handle, idx, self.string_count())
     }
     /// Get value by offset declared in SMBIOS Reference Specification.\
     /// Type meaning data length is mandatory:
@@ -661,6 +2580,7 @@ impl<'buffer> RawStructure<'buffer> {
     /// - *WORD*: u16
     /// - *DWORD*: u32
     /// - *QWORD*: u64
+    /// - fixed-size byte fields: `[u8; N]`, see [RawStructure::get_array]
     ///
     /// The only error this method returned: [MalformedStructureError::InvalidSlice] (actually is
     /// [core::array::TryFromSliceError]). If getting value index exceedes length of *Formatted
@@ -673,6 +2593,19 @@ impl<'buffer> RawStructure<'buffer> {
         let slice = self.data.get(start..(start + size)).unwrap_or(&[]);
         TryFromBytes::try_from_bytes(slice).map_err(MalformedStructureError::InvalidSlice)
     }
+    /// Get `N` raw bytes by offset declared in SMBIOS Reference Specification
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::InvalidSlice` if the formatted section ends before
+    /// `offset + N`.
+    pub fn get_array<const N: usize>(&self, offset: usize) -> Result<[u8; N], MalformedStructureError> {
+        self.get(offset)
+    }
+    /// Get a 16 byte *UUID* field by offset declared in SMBIOS Reference Specification, in the
+    /// byte order it is stored in the table
+    pub fn get_uuid(&self, offset: usize) -> Result<[u8; 16], MalformedStructureError> {
+        self.get_array(offset)
+    }
     /// Wrapper to self.data.get(..) with header offset correction
     pub fn get_slice(&self, offset: usize, size: usize) -> Option<&'buffer [u8]> {
         self.data.get(offset - 4..offset - 4 + size)
@@ -681,46 +2614,269 @@ impl<'buffer> RawStructure<'buffer> {
     pub fn get_string(&self, offset: usize) -> Result<&'buffer str, MalformedStructureError> {
         self.get::<u8>(offset).and_then(|idx| self.find_string(idx))
     }
+    /// Get a field that older structures may not have, `None` if the formatted section is too
+    /// short
+    pub(crate) fn get_optional<T: TryFromBytes<'buffer, T>>(&self, offset: usize) -> Option<T> {
+        let value = self.get(offset).ok();
+        if value.is_none() {
+            lib_trace!(
+                "{} structure {:#06X}: no field at offset {:#04X}, formatted section is {:#04X} bytes",
+                self.info,
+                self.handle,
+                offset,
+                self.length
+            );
+        }
+        value
+    }
+    /// Get an optional field that uses `sentinel` to tell that the value is unknown
+    pub(crate) fn get_unless<T>(&self, offset: usize, sentinel: T) -> Option<T>
+    where
+        T: TryFromBytes<'buffer, T> + PartialEq + fmt::Debug,
+    {
+        self.get_optional(offset).filter(|value| {
+            if *value == sentinel {
+                lib_trace!(
+                    "{} structure {:#06X}: field at offset {:#04X} is {:#X?}, mapped to None",
+                    self.info,
+                    self.handle,
+                    offset,
+                    sentinel
+                );
+            }
+            *value != sentinel
+        })
+    }
 }
 
 /// An iterator over structure strings
+///
+/// The iteration is strict: it stops at the first string that is not valid UTF-8. Use
+/// [StructureStrings::raw] or [StructureStrings::lossy] to get the remaining strings anyway.
+///
+/// The iteration is bounded, whatever the strings table holds: it stops at the first empty
+/// string, which is the terminator of the table, after [StructureStrings::MAX_COUNT] strings, and
+/// before a string that would end past [StructureStrings::MAX_BYTES] bytes.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct StructureStrings<'a> {
-    bytes: &'a [u8],
-    start: usize,
+    raw: RawStrings<'a>,
+    trim: bool,
 }
 
 impl<'a> StructureStrings<'a> {
+    /// Maximum number of strings read from a strings table, string indices being bytes
+    pub const MAX_COUNT: usize = u8::MAX as usize;
+    /// Maximum number of bytes read from a strings table, NUL terminators included; an SMBIOS 2.x
+    /// entry point cannot declare a larger structure
+    pub const MAX_BYTES: usize = u16::MAX as usize;
+
     fn new(bytes: &'a [u8]) -> Self {
-        Self { bytes, start: 0 }
+        Self {
+            raw: RawStrings::new(bytes),
+            trim: false,
+        }
+    }
+    fn trim_end(self, trim: bool) -> Self {
+        Self { trim, ..self }
+    }
+    /// The remaining strings as raw bytes, never trimmed
+    pub fn raw(&self) -> RawStrings<'a> {
+        self.raw
+    }
+    /// The remaining strings with invalid UTF-8 sequences replaced by `U+FFFD REPLACEMENT CHARACTER`
+    #[cfg(feature = "alloc")]
+    pub fn lossy(&self) -> LossyStrings<'a> {
+        LossyStrings(self.raw, self.trim)
     }
 }
 impl<'a> Iterator for StructureStrings<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let slice = self
-            .bytes
-            .get(self.start..)?
-            .split(|elm| *elm == 0)
-            .nth(0)
-            .filter(|slice| !slice.is_empty())?;
-        self.start += slice.len() + 1;
-        str::from_utf8(slice).ok()
+        let string = self.raw.next().and_then(|slice| str::from_utf8(slice).ok())?;
+        Some(if self.trim { string.trim_end() } else { string })
+    }
+}
+
+/// An iterator over structure strings as raw bytes
+///
+/// Vendors frequently put non UTF-8 (e.g. Latin-1) bytes into serial numbers and asset tags, this
+/// iterator yields them without any validation.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RawStrings<'a> {
+    bytes: &'a [u8],
+    start: usize,
+    count: usize,
+}
+
+impl<'a> RawStrings<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            start: 0,
+            count: 0,
+        }
+    }
+}
+impl<'a> Iterator for RawStrings<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == StructureStrings::MAX_COUNT {
+            return None;
+        }
+        let end = self.bytes.len().min(StructureStrings::MAX_BYTES);
+        let rest = self.bytes.get(self.start..end)?;
+        let len = match rest.iter().position(|&b| b == 0) {
+            Some(len) => len,
+            None if end < self.bytes.len() => {
+                lib_debug!("strings table truncated at {} bytes", StructureStrings::MAX_BYTES);
+                return None;
+            }
+            None => rest.len(),
+        };
+        if len == 0 {
+            return None;
+        }
+        self.start += len + 1;
+        self.count += 1;
+        Some(&rest[..len])
+    }
+}
+
+/// An iterator over structure strings and their string indices, see
+/// [RawStructure::strings_indexed]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct IndexedStrings<'a> {
+    raw: RawStrings<'a>,
+    index: u8,
+    trim: bool,
+}
+
+impl<'a> Iterator for IndexedStrings<'a> {
+    type Item = (u8, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let bytes = self.raw.next()?;
+            // Strings past index 255 cannot be referred to
+            self.index = self.index.checked_add(1)?;
+            if let Ok(string) = str::from_utf8(bytes) {
+                return Some((self.index, if self.trim { string.trim_end() } else { string }));
+            }
+        }
+    }
+}
+
+/// An iterator over structure strings with invalid UTF-8 sequences replaced by `U+FFFD
+/// REPLACEMENT CHARACTER`
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LossyStrings<'a>(RawStrings<'a>, bool);
+
+#[cfg(feature = "alloc")]
+impl<'a> Iterator for LossyStrings<'a> {
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let string = String::from_utf8_lossy(self.0.next()?);
+        Some(if self.1 { trim_end_cow(string) } else { string })
+    }
+}
+
+/// Trim trailing whitespace without copying the string
+#[cfg(feature = "alloc")]
+fn trim_end_cow(string: Cow<'_, str>) -> Cow<'_, str> {
+    match string {
+        Cow::Borrowed(string) => Cow::Borrowed(string.trim_end()),
+        Cow::Owned(mut string) => {
+            let len = string.trim_end().len();
+            string.truncate(len);
+            Cow::Owned(string)
+        }
+    }
+}
+
+/// A string field resolved by [RawStructure::resolve_string]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum StringField<'a> {
+    /// The string the index refers to, empty for index 0
+    Found(&'a str),
+    /// The index is past the strings table or refers to a string that is not valid UTF-8
+    BadIndex {
+        index: u8,
+        /// Number of strings in the strings table
+        available: u8,
+    },
+}
+
+impl<'a> StringField<'a> {
+    /// The string, `None` for a bad index
+    pub fn ok(self) -> Option<&'a str> {
+        match self {
+            StringField::Found(string) => Some(string),
+            StringField::BadIndex { .. } => None,
+        }
+    }
+
+    /// The string, an empty string for a bad index
+    pub fn or_empty(self) -> &'a str {
+        self.ok().unwrap_or("")
+    }
+}
+
+/// Formatted as in the dmidecode output, a bad index is `<BAD INDEX>`
+#[cfg(feature = "strings")]
+impl<'a> fmt::Display for StringField<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringField::Found(string) => write!(f, "{}", string),
+            StringField::BadIndex { .. } => write!(f, "<BAD INDEX>"),
+        }
+    }
+}
+
+/// Encoding hint for a raw structure string
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum StringEncoding {
+    /// 7-bit ASCII only
+    Ascii,
+    /// Valid UTF-8 with non-ASCII characters
+    Utf8,
+    /// Neither ASCII nor UTF-8, most likely a legacy 8-bit code page such as ISO 8859-1
+    Unknown,
+}
+
+impl StringEncoding {
+    /// Guess the encoding of a raw structure string
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.is_ascii() {
+            StringEncoding::Ascii
+        } else if str::from_utf8(bytes).is_ok() {
+            StringEncoding::Utf8
+        } else {
+            StringEncoding::Unknown
+        }
     }
 }
 
 /// SMBIOS Table information variant
+///
+/// Standard types are named even if the structure is not decoded by this crate yet.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
 pub enum InfoType {
     Bios,
     System,
     BaseBoard,
     Enclosure,
     Processor,
+    MemoryController,
+    MemoryModule,
     Cache,
     PortConnector,
     SystemSlots,
+    OnBoardDevices,
     OemStrings,
     SystemConfigurationOptions,
     GroupAssociations,
@@ -733,7 +2889,34 @@ pub enum InfoType {
     MemoryDeviceMappedAddress,
     BuiltInPointingDevice,
     PortableBattery,
+    SystemReset,
+    HardwareSecurity,
+    SystemPowerControls,
+    VoltageProbe,
+    CoolingDevice,
+    TemperatureProbe,
+    ElectricalCurrentProbe,
+    OutOfBandRemoteAccess,
+    BisEntryPoint,
     SystemBoot,
+    MemoryError64,
+    ManagementDevice,
+    ManagementDeviceComponent,
+    ManagementDeviceThresholdData,
+    MemoryChannel,
+    IpmiDevice,
+    SystemPowerSupply,
+    AdditionalInformation,
+    OnboardDevicesExtended,
+    ManagementControllerHostInterface,
+    TpmDevice,
+    ProcessorAdditionalInformation,
+    FirmwareInventory,
+    StringProperty,
+    Inactive,
+    /// Type 47 to 125, reserved by the specification for future standard structures
+    Reserved(u8),
+    /// Type 128 to 255, available for system and OEM specific information
     Oem(u8),
     End,
 }
@@ -746,9 +2929,12 @@ impl From<u8> for InfoType {
             2 => InfoType::BaseBoard,
             3 => InfoType::Enclosure,
             4 => InfoType::Processor,
+            5 => InfoType::MemoryController,
+            6 => InfoType::MemoryModule,
             7 => InfoType::Cache,
             8 => InfoType::PortConnector,
             9 => InfoType::SystemSlots,
+            10 => InfoType::OnBoardDevices,
             11 => InfoType::OemStrings,
             12 => InfoType::SystemConfigurationOptions,
             13 => InfoType::BiosLanguage,
@@ -761,12 +2947,216 @@ impl From<u8> for InfoType {
             20 => InfoType::MemoryDeviceMappedAddress,
             21 => InfoType::BuiltInPointingDevice,
             22 => InfoType::PortableBattery,
+            23 => InfoType::SystemReset,
+            24 => InfoType::HardwareSecurity,
+            25 => InfoType::SystemPowerControls,
+            26 => InfoType::VoltageProbe,
+            27 => InfoType::CoolingDevice,
+            28 => InfoType::TemperatureProbe,
+            29 => InfoType::ElectricalCurrentProbe,
+            30 => InfoType::OutOfBandRemoteAccess,
+            31 => InfoType::BisEntryPoint,
             32 => InfoType::SystemBoot,
+            33 => InfoType::MemoryError64,
+            34 => InfoType::ManagementDevice,
+            35 => InfoType::ManagementDeviceComponent,
+            36 => InfoType::ManagementDeviceThresholdData,
+            37 => InfoType::MemoryChannel,
+            38 => InfoType::IpmiDevice,
+            39 => InfoType::SystemPowerSupply,
+            40 => InfoType::AdditionalInformation,
+            41 => InfoType::OnboardDevicesExtended,
+            42 => InfoType::ManagementControllerHostInterface,
+            43 => InfoType::TpmDevice,
+            44 => InfoType::ProcessorAdditionalInformation,
+            45 => InfoType::FirmwareInventory,
+            46 => InfoType::StringProperty,
+            126 => InfoType::Inactive,
             127 => InfoType::End,
+            t @ 0..=127 => InfoType::Reserved(t),
             t => InfoType::Oem(t),
         }
     }
 }
+impl InfoType {
+    /// The numeric structure type
+    pub fn code(&self) -> u8 {
+        match *self {
+            InfoType::Bios => 0,
+            InfoType::System => 1,
+            InfoType::BaseBoard => 2,
+            InfoType::Enclosure => 3,
+            InfoType::Processor => 4,
+            InfoType::MemoryController => 5,
+            InfoType::MemoryModule => 6,
+            InfoType::Cache => 7,
+            InfoType::PortConnector => 8,
+            InfoType::SystemSlots => 9,
+            InfoType::OnBoardDevices => 10,
+            InfoType::OemStrings => 11,
+            InfoType::SystemConfigurationOptions => 12,
+            InfoType::BiosLanguage => 13,
+            InfoType::GroupAssociations => 14,
+            InfoType::SystemEventLog => 15,
+            InfoType::PhysicalMemoryArray => 16,
+            InfoType::MemoryDevice => 17,
+            InfoType::MemoryError32 => 18,
+            InfoType::MemoryArrayMappedAddress => 19,
+            InfoType::MemoryDeviceMappedAddress => 20,
+            InfoType::BuiltInPointingDevice => 21,
+            InfoType::PortableBattery => 22,
+            InfoType::SystemReset => 23,
+            InfoType::HardwareSecurity => 24,
+            InfoType::SystemPowerControls => 25,
+            InfoType::VoltageProbe => 26,
+            InfoType::CoolingDevice => 27,
+            InfoType::TemperatureProbe => 28,
+            InfoType::ElectricalCurrentProbe => 29,
+            InfoType::OutOfBandRemoteAccess => 30,
+            InfoType::BisEntryPoint => 31,
+            InfoType::SystemBoot => 32,
+            InfoType::MemoryError64 => 33,
+            InfoType::ManagementDevice => 34,
+            InfoType::ManagementDeviceComponent => 35,
+            InfoType::ManagementDeviceThresholdData => 36,
+            InfoType::MemoryChannel => 37,
+            InfoType::IpmiDevice => 38,
+            InfoType::SystemPowerSupply => 39,
+            InfoType::AdditionalInformation => 40,
+            InfoType::OnboardDevicesExtended => 41,
+            InfoType::ManagementControllerHostInterface => 42,
+            InfoType::TpmDevice => 43,
+            InfoType::ProcessorAdditionalInformation => 44,
+            InfoType::FirmwareInventory => 45,
+            InfoType::StringProperty => 46,
+            InfoType::Inactive => 126,
+            InfoType::End => 127,
+            InfoType::Reserved(t) | InfoType::Oem(t) => t,
+        }
+    }
+
+    /// Look up a structure type by its name, e.g. `"memory device"` or `"MemoryDevice"`
+    ///
+    /// Case, spaces and punctuation are ignored, as is a trailing "Information": `"system boot"`
+    /// names the System Boot Information type. The names are the ones of the `Display`
+    /// implementation.
+    #[cfg(feature = "strings")]
+    pub fn from_name(name: &str) -> Option<InfoType> {
+        let mut wanted = NormalizedName::new();
+        wanted.write_str(name).ok()?;
+        if wanted.as_bytes().is_empty() {
+            return None;
+        }
+        (0..=u8::MAX).map(InfoType::from).find(|info| {
+            let mut candidate = NormalizedName::new();
+            if write!(candidate, "{}", info).is_err() {
+                return false;
+            }
+            let candidate = candidate.as_bytes();
+            candidate == wanted.as_bytes() || candidate.strip_suffix(b"information") == Some(wanted.as_bytes())
+        })
+    }
+
+    /// The structure types selected by a `dmidecode --type` keyword, e.g. `"memory"` selects the
+    /// Memory Controller, Memory Module, Physical Memory Array and Memory Device types. Keywords
+    /// are matched ignoring case.
+    pub fn keyword_types(keyword: &str) -> Option<&'static [InfoType]> {
+        const KEYWORDS: &[(&str, &[InfoType])] = &[
+            ("bios", &[InfoType::Bios, InfoType::BiosLanguage]),
+            (
+                "system",
+                &[
+                    InfoType::System,
+                    InfoType::SystemConfigurationOptions,
+                    InfoType::SystemEventLog,
+                    InfoType::SystemReset,
+                    InfoType::SystemBoot,
+                ],
+            ),
+            (
+                "baseboard",
+                &[
+                    InfoType::BaseBoard,
+                    InfoType::OnBoardDevices,
+                    InfoType::OnboardDevicesExtended,
+                ],
+            ),
+            ("chassis", &[InfoType::Enclosure]),
+            ("processor", &[InfoType::Processor]),
+            (
+                "memory",
+                &[
+                    InfoType::MemoryController,
+                    InfoType::MemoryModule,
+                    InfoType::PhysicalMemoryArray,
+                    InfoType::MemoryDevice,
+                ],
+            ),
+            ("cache", &[InfoType::Cache]),
+            ("connector", &[InfoType::PortConnector]),
+            ("slot", &[InfoType::SystemSlots]),
+        ];
+        let keyword = keyword.trim();
+        KEYWORDS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(keyword))
+            .map(|(_, types)| *types)
+    }
+}
+
+/// A name reduced to its lowercase ASCII letters and digits, long enough for any type name
+#[cfg(feature = "strings")]
+struct NormalizedName {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+#[cfg(feature = "strings")]
+impl NormalizedName {
+    fn new() -> Self {
+        NormalizedName { bytes: [0; 64], len: 0 }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Write for NormalizedName {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes().filter(u8::is_ascii_alphanumeric) {
+            *self.bytes.get_mut(self.len).ok_or(fmt::Error)? = byte.to_ascii_lowercase();
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Fails for `Oem` values holding a code of a standard structure type, since such a value does not
+/// round-trip through `From<u8>`. The offending value is returned as the error.
+impl TryFrom<InfoType> for u8 {
+    type Error = InfoType;
+
+    fn try_from(info: InfoType) -> Result<u8, Self::Error> {
+        let code = info.code();
+        if InfoType::from(code) == info {
+            Ok(code)
+        } else {
+            Err(info)
+        }
+    }
+}
+
+/// Without the `strings` feature, types are displayed by number, e.g. `Type 17`
+#[cfg(not(feature = "strings"))]
+impl fmt::Display for InfoType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Type {}", self.code())
+    }
+}
+
+#[cfg(feature = "strings")]
 impl fmt::Display for InfoType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -775,12 +3165,12 @@ impl fmt::Display for InfoType {
             InfoType::BaseBoard => write!(f, "Baseboard (or Module) Information"),
             InfoType::Enclosure => write!(f, "System Enclosure or Chassis"),
             InfoType::Processor => write!(f, "Processor Information"),
-            //InfoType::                          => write!(f, "Memory Controller Information"),
-            //InfoType::                          => write!(f, "Memory Module Information"),
+            InfoType::MemoryController => write!(f, "Memory Controller Information"),
+            InfoType::MemoryModule => write!(f, "Memory Module Information"),
             InfoType::Cache => write!(f, "Cache Information"),
             InfoType::PortConnector => write!(f, "Port Connector Information"),
             InfoType::SystemSlots => write!(f, "System Slots"),
-            //InfoType::                          => write!(f, "On Board Devices Information"),
+            InfoType::OnBoardDevices => write!(f, "On Board Devices Information"),
             InfoType::OemStrings => write!(f, "OEM Strings"),
             InfoType::SystemConfigurationOptions => write!(f, "System Configuration Options"),
             InfoType::BiosLanguage => write!(f, "BIOS Language Information"),
@@ -793,62 +3183,296 @@ impl fmt::Display for InfoType {
             InfoType::MemoryDeviceMappedAddress => write!(f, "Memory Device Mapped Address"),
             InfoType::BuiltInPointingDevice => write!(f, "Built-in Pointing Device"),
             InfoType::PortableBattery => write!(f, "Portable Battery"),
-            //InfoType::                          => write!(f, "System Reset"),
-            //InfoType::                          => write!(f, "Hardware Security"),
-            //InfoType::                          => write!(f, "System Power Controls"),
-            //InfoType::                          => write!(f, "Voltage Probe"),
-            //InfoType::                          => write!(f, "Cooling Device"),
-            //InfoType::                          => write!(f, "Temperature Probe"),
-            //InfoType::                          => write!(f, "Electrical Current Probe"),
-            //InfoType::                          => write!(f, "Out-of-Band Remote Access"),
-            //InfoType::                          => write!(f, "Boot Integrity Services (BIS) Entry Point"),
+            InfoType::SystemReset => write!(f, "System Reset"),
+            InfoType::HardwareSecurity => write!(f, "Hardware Security"),
+            InfoType::SystemPowerControls => write!(f, "System Power Controls"),
+            InfoType::VoltageProbe => write!(f, "Voltage Probe"),
+            InfoType::CoolingDevice => write!(f, "Cooling Device"),
+            InfoType::TemperatureProbe => write!(f, "Temperature Probe"),
+            InfoType::ElectricalCurrentProbe => write!(f, "Electrical Current Probe"),
+            InfoType::OutOfBandRemoteAccess => write!(f, "Out-of-Band Remote Access"),
+            InfoType::BisEntryPoint => write!(f, "Boot Integrity Services (BIS) Entry Point"),
             InfoType::SystemBoot => write!(f, "System Boot Information"),
-            //InfoType::                          => write!(f, "64-Bit Memory Error Information"),
-            //InfoType::                          => write!(f, "Management Device"),
-            //InfoType::                          => write!(f, "Management Device Component"),
-            //InfoType::                          => write!(f, "Management Device Threshold Data"),
-            //InfoType::                          => write!(f, "Memory Channel"),
-            //InfoType::                          => write!(f, "IPMI Device Information"),
-            //InfoType::                          => write!(f, "System Power Supply"),
-            //InfoType::                          => write!(f, "Additional Information"),
-            //InfoType::                          => write!(f, "Onboard Devices Extended Information"),
-            //InfoType::                          => write!(f, "Management Controller Host Interface"),
-            //InfoType::                          => write!(f, "TPM Device"),
-            //InfoType::                          => write!(f, "Processor Additional Information"),
-            //InfoType::                          => write!(f, "Inactive"),
+            InfoType::MemoryError64 => write!(f, "64-Bit Memory Error Information"),
+            InfoType::ManagementDevice => write!(f, "Management Device"),
+            InfoType::ManagementDeviceComponent => write!(f, "Management Device Component"),
+            InfoType::ManagementDeviceThresholdData => write!(f, "Management Device Threshold Data"),
+            InfoType::MemoryChannel => write!(f, "Memory Channel"),
+            InfoType::IpmiDevice => write!(f, "IPMI Device Information"),
+            InfoType::SystemPowerSupply => write!(f, "System Power Supply"),
+            InfoType::AdditionalInformation => write!(f, "Additional Information"),
+            InfoType::OnboardDevicesExtended => write!(f, "Onboard Devices Extended Information"),
+            InfoType::ManagementControllerHostInterface => write!(f, "Management Controller Host Interface"),
+            InfoType::TpmDevice => write!(f, "TPM Device"),
+            InfoType::ProcessorAdditionalInformation => write!(f, "Processor Additional Information"),
+            InfoType::FirmwareInventory => write!(f, "Firmware Inventory Information"),
+            InfoType::StringProperty => write!(f, "String Property"),
+            InfoType::Inactive => write!(f, "Inactive"),
             InfoType::End => write!(f, "End-of-Table"),
+            InfoType::Reserved(t) => write!(f, "Reserved: {}", t),
             InfoType::Oem(t) => write!(f, "OEM: {}", t),
         }
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+    const ENTRY_V2_BIN: &[u8] = include_bytes!("../tests/data/entry.bin");
+    const DMI_V2_BIN: &[u8] = include_bytes!("../tests/data/dmi.bin");
+    const ENTRY_V3_BIN: &[u8] = include_bytes!("../tests/data/entry_v3.bin");
+    const DMI_V3_BIN: &[u8] = include_bytes!("../tests/data/dmi_v3.bin");
+    const DMI_V3_SHORT: &[u8] = include_bytes!("../tests/data/dmi_v3_short.bin");
+    const ENTRY_V3_SHORT: &[u8] = include_bytes!("../tests/data/entry_v3_short.bin");
+
+    #[test]
+    fn found_smbios_entry() {
+        EntryPoint::search(ENTRY_V2_BIN).unwrap();
+        EntryPoint::search(DMIDECODE_BIN).unwrap();
+    }
+
+    #[test]
+    fn found_smbios_entry_v3() {
+        EntryPoint::search(ENTRY_V3_BIN).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn doesnt_find_smbios_entry() {
+        EntryPoint::search(DMI_V2_BIN).unwrap();
+    }
+
+    #[test]
+    fn version_gate() {
+        assert!(SmbiosVersion::V2_6.at_least(2, 6));
+        assert!(SmbiosVersion::V3_0.at_least(2, 8));
+        assert!(!SmbiosVersion::V2_1.at_least(2, 6));
+        assert_eq!(SmbiosVersion::new(3, 4), SmbiosVersion::V3_4);
+        assert!(version_gate!(SmbiosVersion::V2_4, (2, 1)..(2, 6)));
+        assert!(!version_gate!(SmbiosVersion::V2_6, (2, 1)..(2, 6)));
+        assert!(version_gate!(SmbiosVersion::V3_7, (3, 5)..));
+    }
+
+    #[test]
+    fn version_ordering() {
+        let two_ten = SmbiosVersion::new(2, 10);
+        assert!(two_ten > SmbiosVersion::V2_8);
+        assert!(two_ten < SmbiosVersion::V3_0);
+        assert!(two_ten.at_least(2, 9));
+        assert!(!SmbiosVersion::V2_8.at_least(2, 10));
+        assert!(version_gate!(two_ten, (2, 6)..(3, 2)));
+        assert!(!version_gate!(SmbiosVersion::new(3, 10), (3, 2)..(3, 5)));
+        assert_eq!(
+            Some(0x28),
+            structures::length::expected_length(InfoType::MemoryDevice, two_ten)
+        );
+    }
+
+    #[test]
+    fn version_parse_and_display() {
+        assert_eq!(Ok(SmbiosVersion::V3_4), "3.4".parse());
+        assert_eq!(Ok(SmbiosVersion::V2_7), "2.7.1".parse());
+        assert_eq!(Ok(SmbiosVersion::new(2, 10)), " 2.10 ".parse());
+        for invalid in ["", "3", "3.", ".4", "3.4.", "3.4.1.0", "3.256", "3.-1", "3.+4", "v3.4"].iter() {
+            assert_eq!(
+                Err(InvalidVersionError),
+                invalid.parse::<SmbiosVersion>(),
+                "{:?}",
+                invalid
+            );
+        }
+        assert_eq!("3.4", format!("{}", SmbiosVersion::V3_4));
+        assert_eq!("2.10", format!("{}", SmbiosVersion::new(2, 10)));
+        let version = SmbiosVersion::new(2, 10);
+        assert_eq!(Ok(version), format!("{}", version).parse());
+    }
+
+    #[test]
+    fn decode_inactive() {
+        use crate::testing::StructureBuilder;
+
+        let inactive = StructureBuilder::new(InfoType::Inactive)
+            .handle(Handle(0x0030))
+            .byte(0x05)
+            .byte(0x04)
+            .byte(0x02);
+        assert!(matches!(inactive.decode(), Ok(Structure::Other(_))));
+        match inactive.raw().decode_inactive_as(InfoType::BuiltInPointingDevice) {
+            Some(Ok(Structure::BuiltInPointingDevice(device))) => {
+                assert_eq!((Handle(0x0030), Some(2)), (device.handle, device.buttons()))
+            }
+            other => panic!("{:?}", other),
+        }
+        match inactive.raw().decode_inactive_as(InfoType::Processor) {
+            Some(Err(MalformedStructureError::InvalidFormattedSectionLength {
+                info: InfoType::Processor,
+                ..
+            })) => (),
+            other => panic!("{:?}", other),
+        }
+
+        let active = StructureBuilder::new(InfoType::BuiltInPointingDevice).bytes(&[0x05, 0x04, 0x02]);
+        assert!(active
+            .raw()
+            .decode_inactive_as(InfoType::BuiltInPointingDevice)
+            .is_none());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn entry_point_docrev() {
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        assert_eq!(ENTRY_V3_BIN[0x09], entry_point.docrev());
 
-    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
-    const ENTRY_V2_BIN: &[u8] = include_bytes!("../tests/data/entry.bin");
-    const DMI_V2_BIN: &[u8] = include_bytes!("../tests/data/dmi.bin");
-    const ENTRY_V3_BIN: &[u8] = include_bytes!("../tests/data/entry_v3.bin");
-    const DMI_V3_BIN: &[u8] = include_bytes!("../tests/data/dmi_v3.bin");
-    const DMI_V3_SHORT: &[u8] = include_bytes!("../tests/data/dmi_v3_short.bin");
-    const ENTRY_V3_SHORT: &[u8] = include_bytes!("../tests/data/entry_v3_short.bin");
+        let entry_point = EntryPoint::search(ENTRY_V2_BIN).unwrap();
+        assert_eq!(0, entry_point.docrev());
+    }
 
     #[test]
-    fn found_smbios_entry() {
-        EntryPoint::search(ENTRY_V2_BIN).unwrap();
-        EntryPoint::search(DMIDECODE_BIN).unwrap();
+    fn entry_point_ref() {
+        let mut buffer = vec![0u8; 0x20];
+        buffer.extend_from_slice(ENTRY_V3_BIN);
+        let entry_point = EntryPointRef::search(&buffer).unwrap();
+        assert_eq!(0x20, entry_point.offset());
+        assert_eq!(&buffer[0x20..0x38], entry_point.as_bytes());
+        match entry_point {
+            EntryPointRef::V3(point) => {
+                assert_eq!(*b"_SM3_", point.signature());
+                assert_eq!((3, 0, 5525), (point.major(), point.minor(), point.smbios_len_max()));
+            }
+            _ => panic!("Expected a V3 entry point"),
+        }
+        assert_eq!(EntryPoint::search(ENTRY_V3_BIN).unwrap(), entry_point.into());
+
+        // The length of the buffer after the anchor is not truncated to a byte
+        buffer.resize(0x20 + 0x100, 0);
+        assert!(EntryPointRef::search(&buffer).is_ok());
+
+        let entry_point = EntryPointRef::search(DMIDECODE_BIN).unwrap();
+        assert_eq!(0, entry_point.offset());
+        match entry_point {
+            EntryPointRef::V2(point) => {
+                assert_eq!(*b"_SM_", point.signature());
+                assert_eq!(0x20, point.smbios_address());
+                assert_eq!(EntryPoint::V2(point.into()), EntryPoint::search(DMIDECODE_BIN).unwrap());
+            }
+            _ => panic!("Expected a V2 entry point"),
+        }
     }
 
     #[test]
-    fn found_smbios_entry_v3() {
-        EntryPoint::search(ENTRY_V3_BIN).unwrap();
+    fn entry_point_v2_recompute_checksums() {
+        let mut entry_point = match EntryPoint::search(ENTRY_V2_BIN).unwrap() {
+            EntryPoint::V2(point) => point,
+            _ => panic!("Expected a V2 entry point"),
+        };
+        entry_point.smbios_address = 0x1000;
+        entry_point.recompute_checksums();
+
+        let mut buffer = [0u8; 0x20];
+        assert!(matches!(
+            entry_point.write_to(&mut buffer[..0x10]),
+            Err(InvalidEntryPointError::BadSize(0x1F))
+        ));
+        entry_point.write_to(&mut buffer).unwrap();
+        let found = EntryPoint::search(&buffer).unwrap();
+        assert_eq!(0x1000, found.smbios_address());
+        assert_eq!(0, buffer[0x10..0x1F].iter().fold(0u8, |sum, v| sum.wrapping_add(*v)));
     }
 
     #[test]
-    #[should_panic]
-    fn doesnt_find_smbios_entry() {
-        EntryPoint::search(DMI_V2_BIN).unwrap();
+    fn entry_point_v3_recompute_checksum() {
+        let mut entry_point = match EntryPoint::search(ENTRY_V3_BIN).unwrap() {
+            EntryPoint::V3(point) => point,
+            _ => panic!("Expected a V3 entry point"),
+        };
+        entry_point.smbios_address = 0x1_0000_0000;
+        entry_point.recompute_checksum();
+
+        let mut buffer = [0u8; 0x20];
+        entry_point.write_to(&mut buffer).unwrap();
+        let found = EntryPoint::search(&buffer).unwrap();
+        assert_eq!(0x1_0000_0000, found.smbios_address());
+        assert_eq!(TableLocation::Physical64(0x1_0000_0000), found.table_location());
+        assert!(found.table_location().requires_64bit());
+    }
+
+    #[test]
+    fn entry_point_to_bytes() {
+        for entry in [ENTRY_V2_BIN, ENTRY_V3_BIN, DMIDECODE_BIN].iter() {
+            let mut buffer = [0u8; 0x20];
+            let len = match EntryPoint::search(entry).unwrap() {
+                EntryPoint::V2(point) => {
+                    assert_eq!(&entry[..0x1F], &point.to_bytes()[..]);
+                    point.write_to(&mut buffer).unwrap();
+                    0x1F
+                }
+                EntryPoint::V3(point) => {
+                    assert_eq!(&entry[..0x18], &point.to_bytes()[..]);
+                    assert!(point.write_to(&mut buffer[..0x10]).is_err());
+                    point.write_to(&mut buffer).unwrap();
+                    0x18
+                }
+            };
+            assert_eq!(&entry[..len], &buffer[..len]);
+        }
+
+        // Patched fields without recomputing, the written checksum still matches
+        let mut point = match EntryPoint::search(ENTRY_V3_BIN).unwrap() {
+            EntryPoint::V3(point) => point,
+            _ => panic!("Expected a V3 entry point"),
+        };
+        point.smbios_address = 0x2000;
+        let mut buffer = [0u8; 0x20];
+        point.write_to(&mut buffer).unwrap();
+        assert_eq!(0x2000, EntryPoint::search(&buffer).unwrap().smbios_address());
+
+        let mut point = EntryPointV2::new(SmbiosVersion::V2_8, 0x000F_0020, 0x0400, 42, 0x80);
+        assert_eq!(0x28, point.bcd_revision);
+        point.smbios_address = 0x1000;
+        let found = EntryPoint::search(&point.to_bytes()).unwrap();
+        assert_eq!((0x1000, 0x0400), (found.smbios_address(), found.smbios_len()));
+        assert_eq!(SmbiosVersion::V2_8, found.to_version());
+
+        let point = EntryPointV3::new(SmbiosVersion::V3_4, 0x1_0000_0000, 0x1000);
+        let found = EntryPoint::search(&point.to_bytes()).unwrap();
+        assert_eq!((0x1_0000_0000, 0x1000), (found.smbios_address(), found.smbios_len()));
+        assert_eq!(SmbiosVersion::V3_4, found.to_version());
+    }
+
+    #[test]
+    fn structures_auto() {
+        let entry_point = EntryPoint::search(ENTRY_V2_BIN).unwrap();
+        let table = entry_point.structures_auto(DMI_V2_BIN, BufferLayout::Table).unwrap();
+        assert_eq!(69, table.count());
+
+        // The table mapped in a window of physical memory
+        let address = entry_point.smbios_address();
+        let mut memory = vec![0xFF; 0x100];
+        memory.extend_from_slice(DMI_V2_BIN);
+        let base = address - 0x100;
+        let layout = BufferLayout::PhysicalMemory { base };
+        assert_eq!(69, entry_point.structures_auto(&memory, layout).unwrap().count());
+        for &base in [address + 1, address - memory.len() as u64].iter() {
+            let layout = BufferLayout::PhysicalMemory { base };
+            assert!(entry_point.structures_auto(&memory, layout).is_none(), "{:#X}", base);
+        }
+    }
+
+    #[test]
+    fn table_location() {
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let location = entry_point.table_location();
+        assert!(matches!(location, TableLocation::Physical64(_)));
+        assert!(!location.requires_64bit());
+        assert_eq!(entry_point.smbios_address(), u64::from(location));
+
+        assert_eq!("0x000F0000", format!("{}", TableLocation::Physical32(0x000F_0000)));
+        assert_eq!(
+            "0x0000000100000000",
+            format!("{}", TableLocation::Physical64(0x1_0000_0000))
+        );
     }
 
     #[test]
@@ -892,6 +3516,548 @@ mod tests {
         }
     }
 
+    #[test]
+    fn structures_without_entry_point() {
+        use std::vec::Vec;
+
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let expected = entry_point
+            .structures(DMI_V3_BIN)
+            .filter_map(|s| s.ok())
+            .collect::<Vec<_>>();
+        let result = Structures::new(DMI_V3_BIN, entry_point.to_version(), DMI_V3_BIN.len() as u32)
+            .filter_map(|s| s.ok())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn structure_handle_and_info() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        for (raw, decoded) in entry_point.raw_structures(table).zip(entry_point.structures(table)) {
+            let (raw, decoded) = (raw.unwrap(), decoded.unwrap());
+            assert_eq!((raw.handle, raw.info), (decoded.handle(), decoded.info()));
+        }
+    }
+
+    #[test]
+    fn find_handle() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        let last = entry_point.structures(table).flatten().last().unwrap();
+        assert_eq!(Some(last), entry_point.find_handle(table, 0xFEFF).map(Result::unwrap));
+        match entry_point.find_handle(table, Handle(0x0001)) {
+            Some(Ok(Structure::Processor(processor))) => assert_eq!(Handle(0x0001), processor.handle),
+            other => panic!("{:?}", other),
+        }
+        assert!(entry_point.find_handle(table, 0x7FFF).is_none());
+        // The table cannot be read up to the handle
+        assert!(matches!(entry_point.find_handle(&table[..1000], 0xFEFF), Some(Err(_))));
+    }
+
+    #[test]
+    fn raw_structures() {
+        use std::vec::Vec;
+
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        let raw = entry_point
+            .raw_structures(table)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(69, raw.len());
+        assert_eq!(InfoType::End, raw[68].info);
+        let decoded = raw.into_iter().flat_map(Structure::decode).collect::<Vec<_>>();
+        assert_eq!(entry_point.structures(table).flatten().collect::<Vec<_>>(), decoded);
+
+        let mut truncated = entry_point.raw_structures(&table[..1000]);
+        assert_eq!(17, truncated.by_ref().filter(Result::is_ok).count());
+        assert!(truncated.next().is_none());
+    }
+
+    #[test]
+    fn finish_table_status() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        assert_eq!(
+            TableStatus {
+                end_of_table: true,
+                failed: false,
+                consumed: 2736,
+                declared: 2736,
+                structures: 69,
+            },
+            entry_point.structures(table).finish()
+        );
+
+        let truncated = entry_point.structures(&table[..1000]).finish();
+        assert_eq!(
+            (false, true, 921, 2736, 17),
+            (
+                truncated.end_of_table,
+                truncated.failed,
+                truncated.consumed,
+                truncated.declared,
+                truncated.structures
+            )
+        );
+
+        // Structures already read still count
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let mut structures = entry_point.structures(DMI_V3_BIN);
+        structures.next();
+        let status = structures.finish();
+        assert!(status.end_of_table);
+        assert_eq!((99, 5525), (status.structures, status.consumed));
+    }
+
+    #[test]
+    fn bounds_and_size_hint() {
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let mut structures = entry_point.structures(DMI_V3_BIN);
+        assert_eq!(entry_point.smbios_len(), structures.total_size());
+        let (_, upper) = structures.size_hint();
+        assert!(upper.unwrap() >= 99);
+
+        let mut count = 0;
+        let mut start = 0;
+        while structures.next().is_some() {
+            count += 1;
+            let bounds = structures.bounds();
+            assert!(bounds.start > start);
+            start = bounds.start;
+            assert!(structures.size_hint().1.unwrap() >= 99 - count);
+        }
+        assert_eq!(5525..5525, structures.bounds());
+        assert_eq!((0, Some(0)), structures.size_hint());
+
+        // The last bytes still yield an error
+        let mut short = Structures::new(&[0x80, 0x08, 0x00, 0x01], (2, 8).into(), 4);
+        assert_eq!((0, Some(1)), short.size_hint());
+        assert!(matches!(
+            short.next(),
+            Some(Err(MalformedStructureError::BadSize(0, 8)))
+        ));
+        assert_eq!((0, Some(0)), short.size_hint());
+    }
+
+    #[test]
+    fn smbios3_length_heuristics() {
+        use crate::testing::{StructureBuilder, TableBuilder};
+
+        let version = SmbiosVersion::new(3, 2);
+        let oem = StructureBuilder::new(InfoType::Oem(0x80))
+            .handle(Handle(0x0100))
+            .byte(0);
+        let table = TableBuilder::new(version).push(&oem).push(&oem);
+        let bytes = table.bytes();
+
+        // The maximum exceeds the buffer, the table is read up to its end
+        let status = Structures::new(bytes, version, 0x1000).finish();
+        assert_eq!(
+            (false, false, bytes.len() as u32, 0x1000, 2),
+            (
+                status.end_of_table,
+                status.failed,
+                status.consumed,
+                status.declared,
+                status.structures
+            )
+        );
+        // An SMBIOS 2.x table length is exact
+        assert!(Structures::new(bytes, SmbiosVersion::new(2, 8), 0x1000).finish().failed);
+
+        // Padding after the End-of-Table structure is not read
+        let mut padded = table.end(Handle(0xFEFF)).bytes().to_vec();
+        let consumed = padded.len() as u32;
+        padded.extend_from_slice(&[0xFF; 0x20]);
+        let status = Structures::new(&padded, version, padded.len() as u32 + 0x100).finish();
+        assert!(status.end_of_table && !status.failed);
+        assert_eq!((consumed, 3), (status.consumed, status.structures));
+    }
+
+    #[test]
+    fn structures_from_relocated_table() {
+        use std::vec::Vec;
+
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let expected = entry_point.structures(DMI_V3_BIN).flatten().collect::<Vec<_>>();
+
+        // The table is found at its physical address in a dump that does not start at 0
+        let base = 0xE0000u64;
+        let mut dump = std::vec![0; (entry_point.smbios_address() - base) as usize];
+        dump.extend_from_slice(DMI_V3_BIN);
+        let read = |address: u64, len: u32| {
+            let start = address.checked_sub(base)? as usize;
+            // A SMBIOS 3 table may end before its maximum length
+            dump.get(start..).map(|table| &table[..table.len().min(len as usize)])
+        };
+        let result = entry_point.structures_from(read).unwrap().flatten().collect::<Vec<_>>();
+        assert_eq!(expected, result);
+
+        assert!(entry_point.structures_from(|_, _| None).is_none());
+    }
+
+    #[test]
+    fn structures_with_strings() {
+        use std::vec::Vec;
+
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let expected = entry_point
+            .structures(DMI_V3_BIN)
+            .filter_map(|s| s.ok())
+            .collect::<Vec<_>>();
+        let result = entry_point
+            .structures(DMI_V3_BIN)
+            .with_strings()
+            .filter_map(|s| s.ok())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, result.iter().map(|(s, _)| s.clone()).collect::<Vec<_>>());
+
+        let (bios, strings) = result.iter().find(|(s, _)| matches!(s, Structure::Bios(_))).unwrap();
+        match bios {
+            Structure::Bios(bios) => assert!(strings.clone().any(|s| s == bios.vendor)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn info_type_names() {
+        #[cfg(feature = "strings")]
+        use std::string::ToString;
+
+        assert_eq!(InfoType::MemoryController, 5.into());
+        assert_eq!(InfoType::OnBoardDevices, 10.into());
+        assert_eq!(InfoType::SystemReset, 23.into());
+        assert_eq!(InfoType::SystemBoot, 32.into());
+        assert_eq!(InfoType::ProcessorAdditionalInformation, 44.into());
+        assert_eq!(InfoType::Inactive, 126.into());
+        assert_eq!(InfoType::End, 127.into());
+        assert_eq!(InfoType::Oem(200), 200.into());
+        assert_eq!(InfoType::Reserved(100), 100.into());
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!("Reserved: 100", InfoType::from(100).to_string());
+            assert_eq!("TPM Device", InfoType::TpmDevice.to_string());
+            assert_eq!("64-Bit Memory Error Information", InfoType::from(33).to_string());
+        }
+        for code in 0..=127 {
+            if let InfoType::Reserved(_) = InfoType::from(code) {
+                assert!((47..126).contains(&code), "Unnamed standard type {}", code);
+            }
+        }
+    }
+
+    #[test]
+    fn info_type_code_round_trip() {
+        for code in 0..=u8::MAX {
+            let info = InfoType::from(code);
+            assert_eq!(code, info.code());
+            assert_eq!(Ok(code), u8::try_from(info));
+        }
+        assert_eq!(Err(InfoType::Oem(4)), u8::try_from(InfoType::Oem(4)));
+        assert_eq!(Err(InfoType::Reserved(200)), u8::try_from(InfoType::Reserved(200)));
+        assert_eq!(Ok(127), u8::try_from(InfoType::End));
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn info_type_from_name() {
+        assert_eq!(Some(InfoType::MemoryDevice), InfoType::from_name("memory device"));
+        assert_eq!(Some(InfoType::MemoryDevice), InfoType::from_name("MemoryDevice"));
+        assert_eq!(
+            Some(InfoType::MemoryError64),
+            InfoType::from_name("64-bit memory error")
+        );
+        assert_eq!(Some(InfoType::SystemBoot), InfoType::from_name("System Boot"));
+        assert_eq!(
+            Some(InfoType::SystemBoot),
+            InfoType::from_name("system_boot_information")
+        );
+        assert_eq!(Some(InfoType::End), InfoType::from_name("end-of-table"));
+        assert_eq!(Some(InfoType::Oem(200)), InfoType::from_name("OEM 200"));
+        assert_eq!(None, InfoType::from_name("memory"));
+        assert_eq!(None, InfoType::from_name(" - "));
+        assert_eq!(None, InfoType::from_name(&"x".repeat(100)));
+    }
+
+    #[test]
+    fn info_type_keyword_types() {
+        assert_eq!(Some(&[InfoType::Enclosure][..]), InfoType::keyword_types(" Chassis"));
+        assert!(InfoType::keyword_types("MEMORY")
+            .unwrap()
+            .contains(&InfoType::PhysicalMemoryArray));
+        assert_eq!(None, InfoType::keyword_types("memory device"));
+    }
+
+    #[test]
+    fn find_by_name() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let arrays = structures
+            .clone()
+            .filter(|s| matches!(s, Ok(Structure::PhysicalMemoryArray(_))))
+            .count();
+        let devices = structures
+            .clone()
+            .filter(|s| matches!(s, Ok(Structure::MemoryDevice(_))))
+            .count();
+        assert!(arrays > 0 && devices > 0);
+
+        #[cfg(feature = "strings")]
+        {
+            let found = structures.clone().find_by_name("Memory Device").unwrap();
+            assert!(found.clone().all(|s| matches!(s, Ok(Structure::MemoryDevice(_)))));
+            assert_eq!(devices, found.count());
+        }
+        assert_eq!(devices, structures.clone().find_by_name("17").unwrap().count());
+        assert_eq!(
+            arrays + devices,
+            structures.clone().find_by_name("memory").unwrap().count()
+        );
+        assert!(structures.find_by_name("flux capacitor").is_none());
+    }
+
+    #[cfg(all(feature = "log", feature = "strings"))]
+    #[test]
+    fn log_parse_decisions() {
+        use std::{prelude::v1::*, sync::Mutex};
+
+        struct Recorder(Mutex<Vec<String>>);
+        impl log::Log for Recorder {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+            fn log(&self, record: &log::Record) {
+                let event = format!("{} {}", record.level(), record.args());
+                self.0.lock().unwrap().push(event);
+            }
+            fn flush(&self) {}
+        }
+        lazy_static::lazy_static! {
+            static ref RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+        }
+        log::set_logger(&*RECORDER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let mut data = vec![0; 0x1C];
+        data[0x0C - 4..0x0E - 4].copy_from_slice(&[0xFF, 0xFF]);
+        let device = RawStructure {
+            version: (2, 3).into(),
+            info: InfoType::MemoryDevice,
+            length: 0x20,
+            handle: Handle(0x1100),
+            data: &data,
+            strings: &[0, 0],
+            ..Default::default()
+        };
+        assert!(Structure::decode(device.clone()).is_ok());
+        let truncated = RawStructure {
+            data: &data[..0x0C],
+            length: 0x10,
+            ..device
+        };
+        assert!(Structure::decode(truncated).is_err());
+
+        let events = RECORDER.0.lock().unwrap();
+        let logged = |event: &str| events.iter().any(|e| e == event);
+        assert!(logged(
+            "TRACE Memory Device structure 0x1100: field at offset 0x0C is 0xFFFF, mapped to None"
+        ));
+        assert!(logged(
+            "TRACE Memory Device structure 0x1100: no field at offset 0x20, formatted section is 0x20 bytes"
+        ));
+        assert!(
+            events
+                .iter()
+                .any(|e| e.starts_with("DEBUG skipping Memory Device structure 0x1100: ")),
+            "{:?}",
+            events
+        );
+    }
+
+    #[test]
+    fn get_array() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let structure = RawStructure {
+            version: (3, 4).into(),
+            info: InfoType::SystemSlots,
+            length: 0x0A,
+            handle: Handle(0x0900),
+            data: &data,
+            strings: &[0, 0],
+            ..Default::default()
+        };
+        assert_eq!([0x02, 0x03, 0x04, 0x05, 0x06], structure.get_array::<5>(0x05).unwrap());
+        assert_eq!([0u8; 0], structure.get_array::<0>(0x0A).unwrap());
+        assert!(matches!(
+            structure.get_array::<5>(0x06),
+            Err(MalformedStructureError::InvalidSlice(_))
+        ));
+        assert!(structure.get_uuid(0x04).is_err());
+    }
+
+    #[test]
+    fn handle() {
+        assert_eq!("0x0C00", format!("{}", Handle(0x0C00)));
+        assert_eq!("Unknown", format!("{}", Handle::UNKNOWN));
+        assert_eq!("Not Provided", format!("{}", Handle::NONE));
+        assert_eq!("0xFFFE", format!("{:#06X}", Handle::UNKNOWN));
+        assert_eq!(None, Handle::NONE.as_reference());
+        assert_eq!(Some(Handle(0x0C00)), Handle(0x0C00).as_reference());
+        assert_eq!("c00", format!("{:x}", Handle(0x0C00)));
+        assert!(Handle::NONE.is_reserved());
+        assert!(Handle::UNKNOWN.is_reserved());
+        assert!(!Handle(0).is_reserved());
+        assert_eq!(Handle(0x0102), Handle::try_from_bytes(&[0x02, 0x01]).unwrap());
+        assert_eq!(0x0102u16, Handle::from(0x0102).into());
+        assert!(Handle(7) == 7);
+    }
+
+    /// Multi-byte values of the fixtures, as decoded by dmidecode; these must hold on big-endian
+    /// hosts too
+    #[test]
+    fn little_endian_fixtures() {
+        let entry_point = match EntryPoint::search(DMIDECODE_BIN).unwrap() {
+            EntryPoint::V2(point) => point,
+            _ => panic!("Expected a V2 entry point"),
+        };
+        assert_eq!(u32::from_le_bytes(*b"_SM_"), { entry_point.signature });
+        assert_eq!(
+            (138, 2736, 0x20, 69),
+            (
+                entry_point.struct_max,
+                entry_point.smbios_len,
+                entry_point.smbios_address,
+                entry_point.smbios_count
+            )
+        );
+        let mut buffer = [0u8; 0x1F];
+        entry_point.write_to(&mut buffer).unwrap();
+        assert_eq!(&DMIDECODE_BIN[..0x1F], &buffer[..]);
+
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        assert_eq!(
+            (5525, 0xEAE00),
+            (entry_point.smbios_len(), entry_point.smbios_address())
+        );
+
+        let structures = EntryPoint::search(DMIDECODE_BIN)
+            .unwrap()
+            .structures(&DMIDECODE_BIN[0x20..])
+            .flatten();
+        let mut checked = 0;
+        for structure in structures {
+            match structure {
+                Structure::Bios(bios) => {
+                    assert_eq!(Handle(14), bios.handle);
+                    assert_eq!(0xE000, bios.bios_starting_address_segment);
+                    assert_eq!(
+                        0x0012_0000_7D09_9A80,
+                        bitfield::BitField::value(&bios.bios_characteristics)
+                    );
+                    checked += 1;
+                }
+                Structure::Cache(cache) if cache.handle == 5 => {
+                    assert_eq!(cache::CacheSize::Granularity1K(3072), cache.installed_size);
+                    checked += 1;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(2, checked);
+    }
+
+    #[test]
+    fn entry_point_v2_revision() {
+        let entry_point = match EntryPointRef::search(ENTRY_V2_BIN).unwrap() {
+            EntryPointRef::V2(point) => point,
+            _ => unreachable!(),
+        };
+        assert!(entry_point.known_revision());
+        assert_eq!(Some(SmbiosVersion::V2_7), entry_point.bcd_version());
+        assert_eq!(Some(SmbiosVersion::V2_7), EntryPointV2::from(entry_point).bcd_version());
+
+        assert_eq!(Some(SmbiosVersion::V2_1), bcd_version(0x21));
+        assert_eq!(Some(SmbiosVersion::new(0, 0)), bcd_version(0x00));
+        assert_eq!(None, bcd_version(0x2A));
+        assert_eq!(None, bcd_version(0xA1));
+    }
+
+    #[test]
+    fn legacy_anchor() {
+        let patched = |major, minor, smbios_len: u16| {
+            let mut buffer = ENTRY_V2_BIN.to_vec();
+            buffer[0x06] = major;
+            buffer[0x07] = minor;
+            buffer[0x10..0x1F].copy_from_slice(&[0; 0x0F]);
+            buffer[0x16..0x18].copy_from_slice(&smbios_len.to_le_bytes());
+            buffer[0x18..0x1C].copy_from_slice(&0x000F_0000u32.to_le_bytes());
+            buffer[0x04] = 0;
+            let sum = buffer[..0x1F].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+            buffer[0x04] = 0u8.wrapping_sub(sum);
+            buffer
+        };
+        let permissive = SearchOptions {
+            permissive_legacy_anchor: true,
+        };
+
+        let legacy = patched(2, 0, 0x0400);
+        assert!(matches!(
+            EntryPoint::search(&legacy),
+            Err(InvalidEntryPointError::BadIntermediateAnchor)
+        ));
+        let entry_point = EntryPoint::search_with(&legacy, permissive).unwrap();
+        assert_eq!(0x0400, entry_point.smbios_len());
+        assert_eq!(0x000F_0000, entry_point.smbios_address());
+
+        // Only 2.0 entry points with a table are tolerated
+        for buffer in &[patched(2, 1, 0x0400), patched(2, 0, 0)] {
+            assert!(matches!(
+                EntryPoint::search_with(buffer, permissive),
+                Err(InvalidEntryPointError::BadIntermediateAnchor)
+            ));
+        }
+        EntryPoint::search_with(ENTRY_V2_BIN, permissive).unwrap();
+    }
+
+    #[test]
+    fn find_nulnul_word_boundaries() {
+        let naive = |buf: &[u8]| (1..buf.len()).find(|&i| buf[i - 1] == 0 && buf[i] == 0);
+        for len in 0..40 {
+            for first in 0..len {
+                for second in first + 1..len + 1 {
+                    let mut buf = vec![0xA5; len];
+                    buf[first] = 0;
+                    if let Some(b) = buf.get_mut(second) {
+                        *b = 0;
+                    }
+                    if first > 0 {
+                        // Only the high bit set, must not be taken for a nul
+                        buf[first - 1] = 0x80;
+                    }
+                    assert_eq!(naive(&buf), find_nulnul(&buf), "{:?}", buf);
+                }
+            }
+        }
+        let mut buf = vec![0xFF; 64];
+        buf[7] = 0;
+        buf[8] = 0;
+        assert_eq!(Some(8), find_nulnul(&buf));
+    }
+
+    #[test]
+    fn find_signature_tail() {
+        assert!(find_signature(b"_SM").is_none());
+        assert!(find_signature(b"_SM3").is_none());
+        let mut buf = [0u8; 37];
+        buf[32..].copy_from_slice(b"_SM3_");
+        assert!(matches!(find_signature(&buf), Some((EntryPointFormat::V3, 32))));
+        buf[32..].copy_from_slice(b"_SM_\0");
+        assert!(matches!(find_signature(&buf), Some((EntryPointFormat::V2, 32))));
+    }
+
     #[test]
     fn find_nulnul_empty() {
         let buf = [];
@@ -925,7 +4091,7 @@ mod tests {
     #[test]
     fn structure_strings() {
         use pretty_assertions::assert_eq;
-        use std::prelude::v1::*;
+        use std::vec::Vec;
 
         let regular_bytes = &[65, 66, 67, 0, 68, 69, 0, 70, 0, 71, 72, 73, 0, 0];
         let regular_ss = StructureStrings::new(regular_bytes).collect::<Vec<_>>();
@@ -947,4 +4113,257 @@ mod tests {
         let invalid_order2_ss = StructureStrings::new(invalid_order2_bytes).collect::<Vec<&str>>();
         assert_eq!(vec![""; 0], invalid_order2_ss, "Invalid order 2 bytes");
     }
+
+    #[test]
+    fn structure_strings_bounds() {
+        use std::vec::Vec;
+
+        // 300 one character strings, only the first 255 can be referred to
+        let many = b"A\0".repeat(300);
+        assert_eq!(StructureStrings::MAX_COUNT, StructureStrings::new(&many).count());
+        assert_eq!(StructureStrings::MAX_COUNT, StructureStrings::new(&many).raw().count());
+        let structure = RawStructure {
+            version: (3, 0).into(),
+            info: InfoType::OemStrings,
+            length: 5,
+            handle: Handle(1),
+            data: &[],
+            strings: &many,
+            ..Default::default()
+        };
+        assert_eq!(255, structure.string_count());
+        assert_eq!(Ok("A"), structure.find_string(255).map_err(|_| ()));
+
+        // A string running past the limit is not yielded, not even partially
+        let mut long = b"ABC\0".to_vec();
+        long.resize(StructureStrings::MAX_BYTES + 10, b'x');
+        long.extend_from_slice(b"\0\0");
+        assert_eq!(
+            vec![&b"ABC"[..]],
+            StructureStrings::new(&long).raw().collect::<Vec<_>>()
+        );
+        let mut fits = b"ABC\0".to_vec();
+        fits.resize(StructureStrings::MAX_BYTES - 1, b'x');
+        fits.extend_from_slice(b"\0\0");
+        assert_eq!(2, StructureStrings::new(&fits).raw().count());
+
+        // Nothing past the terminator is read
+        let hidden = b"ABC\0\0DEF\0\0";
+        assert_eq!(vec!["ABC"], StructureStrings::new(hidden).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn structure_strings_non_utf8() {
+        use pretty_assertions::assert_eq;
+        use std::vec::Vec;
+
+        // "ABC", "S\xE9rie" (Latin-1), "DE"
+        let bytes = &[65, 66, 67, 0, 83, 0xE9, 114, 105, 101, 0, 68, 69, 0, 0];
+        let strict = StructureStrings::new(bytes).collect::<Vec<_>>();
+        assert_eq!(vec!["ABC"], strict, "Strict");
+
+        let raw = StructureStrings::new(bytes).raw().collect::<Vec<_>>();
+        assert_eq!(vec![&b"ABC"[..], &b"S\xE9rie"[..], &b"DE"[..]], raw, "Raw");
+
+        let encodings = raw.iter().map(|s| StringEncoding::detect(s)).collect::<Vec<_>>();
+        assert_eq!(
+            vec![StringEncoding::Ascii, StringEncoding::Unknown, StringEncoding::Ascii],
+            encodings
+        );
+        assert_eq!(StringEncoding::Utf8, StringEncoding::detect("Série".as_bytes()));
+
+        let structure = RawStructure {
+            version: (3, 0).into(),
+            info: InfoType::System,
+            length: 4,
+            handle: Handle(1),
+            data: &[],
+            strings: bytes,
+            ..Default::default()
+        };
+        assert_eq!(Ok(&b"DE"[..]), structure.find_string_bytes(3).map_err(|_| ()));
+        assert!(structure.find_string(3).is_err());
+        assert!(structure.find_string_bytes(4).is_err());
+        assert_eq!(
+            vec![(1, "ABC"), (3, "DE")],
+            structure.strings_indexed().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn invalid_formatted_section_length() {
+        use pretty_assertions::assert_eq;
+
+        let error = |expected| MalformedStructureError::InvalidFormattedSectionLength {
+            info: InfoType::SystemEventLog,
+            handle: Handle(0x0F00),
+            expected,
+            actual: 0x14,
+        };
+        let message = |expected| format!("{}", error(expected));
+        assert_eq!(
+            "Formatted section length of structure SystemEventLog with handle 3840 should be 20 bytes",
+            message(LengthExpectation::Exact(0x14))
+        );
+        assert_eq!(
+            "Formatted section length of structure SystemEventLog with handle 3840 should be minimum of 23 bytes",
+            message(LengthExpectation::Minimum(0x17))
+        );
+        assert_eq!(
+            "Formatted section length of structure SystemEventLog with handle 3840 should be 17h+(x*y) = 25 bytes",
+            message(LengthExpectation::Formula("17h+(x*y)", 0x19))
+        );
+    }
+
+    #[test]
+    fn bad_string_index() {
+        use pretty_assertions::assert_eq;
+
+        let structure = RawStructure {
+            version: (3, 0).into(),
+            info: InfoType::System,
+            length: 4,
+            handle: Handle(0x0100),
+            data: &[],
+            strings: b"ABC\0DE\0\0",
+            ..Default::default()
+        };
+        assert_eq!(2, structure.string_count());
+        let error = structure.find_string(5).unwrap_err();
+        assert!(matches!(
+            error,
+            MalformedStructureError::InvalidStringIndex(InfoType::System, Handle(0x0100), 5, 2)
+        ));
+        assert_eq!(
+            "Structure System with handle 256 has invalid string index 5, 2 strings present",
+            format!("{}", error)
+        );
+
+        assert_eq!(StringField::Found(""), structure.resolve_string(0));
+        assert_eq!(Some("DE"), structure.resolve_string(2).ok());
+        let bad = structure.resolve_string(3);
+        assert_eq!(StringField::BadIndex { index: 3, available: 2 }, bad);
+        assert_eq!("", bad.or_empty());
+        #[cfg(feature = "strings")]
+        assert_eq!("<BAD INDEX>", format!("{}", bad));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn structure_strings_lossy() {
+        use pretty_assertions::assert_eq;
+        use std::vec::Vec;
+
+        let bytes = &[65, 66, 67, 0, 83, 0xE9, 114, 105, 101, 0, 68, 69, 0, 0];
+        let lossy = StructureStrings::new(bytes).lossy().collect::<Vec<_>>();
+        assert_eq!(vec!["ABC", "S\u{FFFD}rie", "DE"], lossy);
+    }
+
+    #[test]
+    fn eq_stable() {
+        use crate::testing::StructureBuilder;
+        use std::vec::Vec;
+
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+        let processor = entry_point
+            .raw_structures(table)
+            .flatten()
+            .find(|s| s.info == InfoType::Processor)
+            .unwrap();
+        // Current Speed, then Max Speed
+        let changed = [0x16, 0x14].map(|offset: usize| {
+            let mut data = processor.data.to_vec();
+            data[offset - 4] ^= 0xFF;
+            data
+        });
+        let decode = |data| {
+            Structure::decode(RawStructure {
+                data,
+                ..processor.clone()
+            })
+            .unwrap()
+        };
+        let original = decode(processor.data);
+        let speed_changed = decode(&changed[0]);
+        assert_ne!(original, speed_changed);
+        assert!(original.eq_stable(&speed_changed));
+        assert!(!original.eq_stable(&decode(&changed[1])));
+
+        let probe = |nominal, tolerance| {
+            StructureBuilder::new(InfoType::TemperatureProbe)
+                .string("CPU")
+                .byte(0x63)
+                .word(0x8000)
+                .word(0x8000)
+                .word(0x8000)
+                .word(tolerance)
+                .word(0x8000)
+                .dword(0)
+                .word(nominal)
+        };
+        let probes = [probe(450, 5), probe(470, 5), probe(450, 10)];
+        let decoded = probes.iter().map(|p| p.decode().unwrap()).collect::<Vec<_>>();
+        assert!(decoded[0] != decoded[1] && decoded[0].eq_stable(&decoded[1]));
+        assert!(!decoded[0].eq_stable(&decoded[2]));
+        assert!(!decoded[0].eq_stable(&original));
+    }
+
+    #[test]
+    fn trim_strings() {
+        use crate::testing::{StructureBuilder, TableBuilder};
+        use pretty_assertions::assert_eq;
+        use std::vec::Vec;
+
+        let system = StructureBuilder::new(InfoType::System)
+            .version((2, 0).into())
+            .string("Dell Inc.")
+            .string("PowerEdge R740    ")
+            .string(" \t")
+            .string("S\u{E9}rie \t");
+        let raw = system.raw();
+        assert_eq!("PowerEdge R740    ", raw.find_string(2).unwrap());
+
+        let trimmed = raw.clone().trim_strings();
+        assert_eq!("PowerEdge R740", trimmed.find_string(2).unwrap());
+        assert_eq!(b"PowerEdge R740    ", trimmed.find_string_bytes(2).unwrap());
+        assert_eq!(Some(&b" \t"[..]), trimmed.raw_strings().nth(2));
+        assert_eq!(
+            vec![(1, "Dell Inc."), (2, "PowerEdge R740"), (3, ""), (4, "S\u{E9}rie")],
+            trimmed.strings_indexed().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["Dell Inc.", "PowerEdge R740", "", "S\u{E9}rie"],
+            trimmed.strings().collect::<Vec<_>>()
+        );
+        match Structure::decode(trimmed.clone()) {
+            Ok(Structure::System(system)) => {
+                assert_eq!("PowerEdge R740", system.product);
+                assert_eq!("", system.version);
+            }
+            other => panic!("{:?}", other),
+        }
+        #[cfg(feature = "alloc")]
+        {
+            let lossy = trimmed
+                .strings()
+                .lossy()
+                .map(std::string::String::from)
+                .collect::<Vec<_>>();
+            assert_eq!(vec!["Dell Inc.", "PowerEdge R740", "", "S\u{E9}rie"], lossy);
+            assert!(matches!(
+                trimmed.find_string_lossy(2),
+                Ok(Cow::Borrowed("PowerEdge R740"))
+            ));
+        }
+
+        let table = TableBuilder::new((2, 0).into()).push(&system).end(Handle(0xFEFF));
+        let structures = table.structures().trim_strings();
+        match structures.clone().next() {
+            Some(Ok(Structure::System(system))) => assert_eq!("PowerEdge R740", system.product),
+            other => panic!("{:?}", other),
+        }
+        let raw = structures.raw().next().unwrap().unwrap();
+        assert_eq!(b"PowerEdge R740    ", raw.find_string_bytes(2).unwrap());
+    }
 }
@@ -0,0 +1,86 @@
+//! Normalize vendor placeholder strings
+//!
+//! Firmware vendors often leave template values such as "To Be Filled By O.E.M." or "Default
+//! string" in string fields instead of leaving them empty. This module provides an opt-in layer to
+//! treat such values the same way as missing strings.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::normalize::normalize;
+//!
+//! assert_eq!(None, normalize("  To Be Filled By O.E.M.  "));
+//! assert_eq!(Some("PowerEdge R740"), normalize("PowerEdge R740 "));
+//! ```
+
+/// Well-known placeholder values, compared case-insensitively after trimming
+pub const PLACEHOLDERS: &[&str] = &[
+    "To Be Filled By O.E.M.",
+    "Default string",
+    "Not Specified",
+    "Not Applicable",
+    "Not Available",
+    "System Product Name",
+    "System manufacturer",
+    "System Version",
+    "System Serial Number",
+    "Base Board Product Name",
+    "Base Board Serial Number",
+    "Chassis Serial Number",
+    "Chassis Manufacture",
+    "Asset-1234567890",
+    "OEM",
+    "O.E.M.",
+    "None",
+    "N/A",
+    "Unknown",
+    "123456789",
+    "0123456789",
+    "1234567890",
+    "x.x",
+    "xxxxx",
+];
+
+/// Returns `true` if the string is empty, consists of whitespace only or is a well-known
+/// placeholder value.
+pub fn is_placeholder(s: &str) -> bool {
+    let trimmed = trim(s);
+    trimmed.is_empty() || PLACEHOLDERS.iter().any(|p| p.eq_ignore_ascii_case(trimmed))
+}
+
+/// Trim the string and map placeholder values to `None`.
+pub fn normalize(s: &str) -> Option<&str> {
+    if is_placeholder(s) {
+        None
+    } else {
+        Some(trim(s))
+    }
+}
+
+/// Trim whitespace and the padding characters found in fixed-width vendor strings
+fn trim(s: &str) -> &str {
+    s.trim_matches(|c: char| c.is_whitespace() || c == '\0' || c == '\u{FF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(None, normalize(""));
+        assert_eq!(None, normalize("   "));
+        assert_eq!(None, normalize("To Be Filled By O.E.M."));
+        assert_eq!(None, normalize("to be filled by o.e.m."));
+        assert_eq!(None, normalize(" Default string\t"));
+        assert_eq!(None, normalize("Not Specified"));
+        assert!(is_placeholder("N/A"));
+        assert!(!is_placeholder("Dell Inc."));
+    }
+
+    #[test]
+    fn trimming() {
+        assert_eq!(Some("Dell Inc."), normalize("Dell Inc."));
+        assert_eq!(Some("CN7016349E0027"), normalize("  CN7016349E0027   "));
+        assert_eq!(Some("Not Specified Here"), normalize("Not Specified Here"));
+    }
+}
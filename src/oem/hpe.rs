@@ -0,0 +1,318 @@
+//! HPE ProLiant OEM structures
+//!
+//! Decoders for the vendor structures found on HPE ProLiant systems:
+//! - Device Correlation Record (Type 203) maps PCI devices, e.g. NIC ports and NVMe drive bays, to
+//!   their slot and PCI address
+//! - Version Indicator Record (Type 216) lists the installed firmware
+//! - ProLiant Information (Type 219) announces the supported platform features
+//!
+//! Structure types in the OEM range are only meaningful together with the vendor, so [decode]
+//! must only be used on tables whose System or BIOS manufacturer passes [is_hpe].
+
+use crate::{Handle, InfoType, LengthExpectation, MalformedStructureError, RawStructure};
+
+/// Returns `true` if `manufacturer` names HP or HPE
+pub fn is_hpe(manufacturer: &str) -> bool {
+    let manufacturer = manufacturer.trim();
+    ["HP", "HPE", "Hewlett-Packard", "Hewlett Packard Enterprise"]
+        .iter()
+        .any(|name| manufacturer.eq_ignore_ascii_case(name))
+}
+
+/// HPE vendor structures, see [decode]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum HpeStructure<'buffer> {
+    DeviceCorrelation(DeviceCorrelation<'buffer>),
+    VersionIndicator(VersionIndicator<'buffer>),
+    ProLiantInformation(ProLiantInformation),
+}
+
+/// Decode the HPE structure types known to this module, an [OemDecoder](super::OemDecoder) for
+/// [Structures::with_oem_decoder](crate::Structures::with_oem_decoder)
+pub fn decode<'buffer>(
+    structure: &RawStructure<'buffer>,
+) -> Option<Result<HpeStructure<'buffer>, MalformedStructureError>> {
+    match structure.info {
+        InfoType::Oem(203) => Some(DeviceCorrelation::try_from(structure).map(HpeStructure::DeviceCorrelation)),
+        InfoType::Oem(216) => Some(VersionIndicator::try_from(structure).map(HpeStructure::VersionIndicator)),
+        InfoType::Oem(219) => Some(ProLiantInformation::try_from(structure).map(HpeStructure::ProLiantInformation)),
+        _ => None,
+    }
+}
+
+/// Device Correlation Record (Type 203)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DeviceCorrelation<'buffer> {
+    pub handle: Handle,
+    /// Handle of the associated System Slots (Type 9) or Onboard Devices Extended Information (Type
+    /// 41) structure
+    pub associated_device_handle: Option<Handle>,
+    /// Handle of the associated SMBus segment structure (Type 228)
+    pub associated_smbus_handle: Option<Handle>,
+    /// `None` if the device is not present
+    pub pci_vendor_id: Option<u16>,
+    pub pci_device_id: u16,
+    pub pci_sub_vendor_id: u16,
+    pub pci_sub_device_id: u16,
+    /// `None` if the device is not present
+    pub class_code: Option<u8>,
+    /// `None` if the device is not present
+    pub sub_class_code: Option<u8>,
+    /// Handle of the parent device structure
+    pub parent_handle: Option<Handle>,
+    pub disabled: bool,
+    pub device_type: u8,
+    pub device_location: u8,
+    pub device_instance: u8,
+    /// NIC port number or NVMe drive bay
+    pub sub_instance: u8,
+    pub bay: u8,
+    pub enclosure: u8,
+    pub uefi_device_path: &'buffer str,
+    pub uefi_device_structured_name: &'buffer str,
+    pub device_name: &'buffer str,
+    pub uefi_location: &'buffer str,
+    pub part_number: Option<&'buffer str>,
+    pub serial_number: Option<&'buffer str>,
+    pub segment_group_number: Option<u16>,
+    pub bus_number: Option<u8>,
+    pub device_and_function_number: Option<u8>,
+}
+
+impl<'buffer> DeviceCorrelation<'buffer> {
+    fn try_from(structure: &RawStructure<'buffer>) -> Result<Self, MalformedStructureError> {
+        lib_ensure!(
+            structure.length >= 0x20,
+            MalformedStructureError::InvalidFormattedSectionLength {
+                info: structure.info,
+                handle: structure.handle,
+                expected: LengthExpectation::Minimum(0x20),
+                actual: structure.length,
+            }
+        );
+        let handle = |offset| structure.get::<Handle>(offset).map(Handle::as_reference);
+        Ok(DeviceCorrelation {
+            handle: structure.handle,
+            associated_device_handle: handle(0x04)?,
+            associated_smbus_handle: handle(0x06)?,
+            pci_vendor_id: structure
+                .get::<u16>(0x08)
+                .map(|id| Some(id).filter(|&id| id != 0xFFFF))?,
+            pci_device_id: structure.get::<u16>(0x0A)?,
+            pci_sub_vendor_id: structure.get::<u16>(0x0C)?,
+            pci_sub_device_id: structure.get::<u16>(0x0E)?,
+            class_code: structure.get::<u8>(0x10).map(|c| Some(c).filter(|&c| c != 0xFF))?,
+            sub_class_code: structure.get::<u8>(0x11).map(|c| Some(c).filter(|&c| c != 0xFF))?,
+            parent_handle: handle(0x12)?,
+            disabled: structure.get::<u16>(0x14)? & 0x0001 != 0,
+            device_type: structure.get::<u8>(0x16)?,
+            device_location: structure.get::<u8>(0x17)?,
+            device_instance: structure.get::<u8>(0x18)?,
+            sub_instance: structure.get::<u8>(0x19)?,
+            bay: structure.get::<u8>(0x1A)?,
+            enclosure: structure.get::<u8>(0x1B)?,
+            uefi_device_path: structure.get_string(0x1C)?,
+            uefi_device_structured_name: structure.get_string(0x1D)?,
+            device_name: structure.get_string(0x1E)?,
+            uefi_location: structure.get_string(0x1F)?,
+            part_number: structure.get_string(0x22).ok(),
+            serial_number: structure.get_string(0x23).ok(),
+            segment_group_number: structure.get::<u16>(0x24).ok(),
+            bus_number: structure.get::<u8>(0x26).ok(),
+            device_and_function_number: structure.get::<u8>(0x27).ok(),
+        })
+    }
+}
+
+/// Version Indicator Record (Type 216), one structure per firmware component
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct VersionIndicator<'buffer> {
+    pub handle: Handle,
+    pub firmware_type: u16,
+    pub firmware_name: &'buffer str,
+    pub firmware_version: &'buffer str,
+    /// Format of the `version_data` field
+    pub data_format: u8,
+    pub version_data: [u8; 12],
+    /// Unique ID of the firmware flash
+    pub unique_id: u16,
+}
+
+impl<'buffer> VersionIndicator<'buffer> {
+    fn try_from(structure: &RawStructure<'buffer>) -> Result<Self, MalformedStructureError> {
+        lib_ensure!(
+            structure.length >= 0x17,
+            MalformedStructureError::InvalidFormattedSectionLength {
+                info: structure.info,
+                handle: structure.handle,
+                expected: LengthExpectation::Minimum(0x17),
+                actual: structure.length,
+            }
+        );
+        Ok(VersionIndicator {
+            handle: structure.handle,
+            firmware_type: structure.get::<u16>(0x04)?,
+            firmware_name: structure.get_string(0x06)?,
+            firmware_version: structure.get_string(0x07)?,
+            data_format: structure.get::<u8>(0x08)?,
+            version_data: structure.get_array::<12>(0x09)?,
+            unique_id: structure.get::<u16>(0x15)?,
+        })
+    }
+}
+
+/// ProLiant Information (Type 219)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ProLiantInformation {
+    pub handle: Handle,
+    pub power_features: u32,
+    pub omega_features: Option<u32>,
+    pub misc_features: Option<u32>,
+}
+
+impl ProLiantInformation {
+    fn try_from(structure: &RawStructure) -> Result<Self, MalformedStructureError> {
+        lib_ensure!(
+            structure.length >= 0x08,
+            MalformedStructureError::InvalidFormattedSectionLength {
+                info: structure.info,
+                handle: structure.handle,
+                expected: LengthExpectation::Minimum(0x08),
+                actual: structure.length,
+            }
+        );
+        Ok(ProLiantInformation {
+            handle: structure.handle,
+            power_features: structure.get::<u32>(0x04)?,
+            omega_features: structure.get::<u32>(0x08).ok(),
+            misc_features: structure.get::<u32>(0x10).ok(),
+        })
+    }
+    /// Integrated Common Redundant Unit support
+    pub fn icru(&self) -> Option<bool> {
+        self.misc_features.map(|features| features & 0x0001 != 0)
+    }
+    /// UEFI support
+    pub fn uefi(&self) -> Option<bool> {
+        self.misc_features.map(|features| features & 0x1400 != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn vendor() {
+        assert!(is_hpe("HPE"));
+        assert!(is_hpe("HP "));
+        assert!(is_hpe("Hewlett-Packard"));
+        assert!(!is_hpe("Dell Inc."));
+    }
+
+    #[test]
+    fn device_correlation() {
+        let structure = RawStructure {
+            version: (3, 3).into(),
+            info: InfoType::Oem(203),
+            length: 0x28,
+            handle: Handle(0x0300),
+            data: &[
+                0x09, 0x00, 0xFE, 0xFF, 0xE4, 0x14, 0x5F, 0x16, 0x3C, 0x10, 0xE3, 0x22, 0x02, 0x00, 0xFE, 0xFF, 0x00,
+                0x00, 0x06, 0x01, 0x01, 0x02, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x09, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x31, 0x00,
+            ],
+            strings:
+                b"PciRoot(0x0)/Pci(0x1C,0x0)/Pci(0x0,0x1)\0NIC.LOM.1.2\0Embedded LOM 1 Port 2\0Embedded Device\0\0",
+            ..Default::default()
+        };
+        let result = decode(&structure).unwrap().unwrap();
+        assert_eq!(
+            HpeStructure::DeviceCorrelation(DeviceCorrelation {
+                handle: Handle(0x0300),
+                associated_device_handle: Some(Handle(0x0009)),
+                associated_smbus_handle: None,
+                pci_vendor_id: Some(0x14E4),
+                pci_device_id: 0x165F,
+                pci_sub_vendor_id: 0x103C,
+                pci_sub_device_id: 0x22E3,
+                class_code: Some(0x02),
+                sub_class_code: Some(0x00),
+                parent_handle: None,
+                disabled: false,
+                device_type: 0x06,
+                device_location: 0x01,
+                device_instance: 0x01,
+                sub_instance: 0x02,
+                bay: 0x00,
+                enclosure: 0x00,
+                uefi_device_path: "PciRoot(0x0)/Pci(0x1C,0x0)/Pci(0x0,0x1)",
+                uefi_device_structured_name: "NIC.LOM.1.2",
+                device_name: "Embedded LOM 1 Port 2",
+                uefi_location: "Embedded Device",
+                part_number: Some(""),
+                serial_number: Some(""),
+                segment_group_number: Some(0),
+                bus_number: Some(0x31),
+                device_and_function_number: Some(0x00),
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn version_indicator() {
+        let structure = RawStructure {
+            version: (3, 3).into(),
+            info: InfoType::Oem(216),
+            length: 0x17,
+            handle: Handle(0x0400),
+            data: &[
+                0x01, 0x00, 0x01, 0x02, 0x0A, 0x02, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+            strings: b"System ROM\0U30 v2.54 (03/09/2021)\0\0",
+            ..Default::default()
+        };
+        match decode(&structure).unwrap().unwrap() {
+            HpeStructure::VersionIndicator(version) => {
+                assert_eq!(0x0001, version.firmware_type);
+                assert_eq!("System ROM", version.firmware_name);
+                assert_eq!("U30 v2.54 (03/09/2021)", version.firmware_version);
+                assert_eq!(0x0A, version.data_format);
+                assert_eq!([0x02, 0x36], [version.version_data[0], version.version_data[1]]);
+                assert_eq!(0, version.unique_id);
+            }
+            other => panic!("Unexpected structure {:?}", other),
+        }
+    }
+
+    #[test]
+    fn proliant_information() {
+        let structure = RawStructure {
+            version: (3, 3).into(),
+            info: InfoType::Oem(219),
+            length: 0x14,
+            handle: Handle(0x0500),
+            data: &[
+                0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x14, 0x00, 0x00,
+            ],
+            strings: &[0, 0],
+            ..Default::default()
+        };
+        match decode(&structure).unwrap().unwrap() {
+            HpeStructure::ProLiantInformation(info) => {
+                assert_eq!(0x0F, info.power_features);
+                assert_eq!(Some(true), info.icru());
+                assert_eq!(Some(true), info.uefi());
+            }
+            other => panic!("Unexpected structure {:?}", other),
+        }
+        assert!(decode(&RawStructure {
+            info: InfoType::Oem(220),
+            ..structure
+        })
+        .is_none());
+    }
+}
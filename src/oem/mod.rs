@@ -0,0 +1,193 @@
+//! Pluggable decoders for OEM and other structures not decoded by this crate
+//!
+//! Vendors define their own structure types in the OEM range (80h to FFh), e.g. HPE Type 203 or
+//! Dell Type 212. An [OemDecoder] turns such a [RawStructure] into a type defined by a downstream
+//! crate, using the same field and string accessors ([RawStructure::get],
+//! [RawStructure::find_string], ...) as the decoders in this crate. Decoders are registered with
+//! [Structures::with_oem_decoder]; several decoders can be combined as a tuple, the first one that
+//! accepts a structure wins.
+//!
+//! Decoders for the most widespread vendor structures are available behind feature flags:
+//! - `oem-hpe`: [HPE ProLiant](hpe)
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{
+//!     oem::{Decoded, OemDecoder},
+//!     EntryPoint, InfoType, MalformedStructureError, RawStructure,
+//! };
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../../tests/data/dmi.0.bin");
+//!
+//! /// Vendor structure of type 208 carrying a system ID at offset 06h
+//! struct SystemId(u16);
+//!
+//! fn type_208(structure: &RawStructure) -> Option<Result<SystemId, MalformedStructureError>> {
+//!     if structure.info != InfoType::Oem(208) {
+//!         return None;
+//!     }
+//!     Some(structure.get::<u16>(0x06).map(SystemId))
+//! }
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! for structure in structures.with_oem_decoder(type_208) {
+//!     if let Ok(Decoded::Oem(SystemId(id))) = structure {
+//!         println!("System ID: {:#06X}", id);
+//!     }
+//! }
+//! ```
+
+use crate::{MalformedStructureError, RawStructure, Structure, Structures};
+
+#[cfg(feature = "oem-hpe")]
+pub mod hpe;
+
+/// Decoder for structures that are not decoded by this crate
+pub trait OemDecoder<'buffer> {
+    /// The decoded structure
+    type Output;
+
+    /// Decode `structure`, or return `None` if this decoder does not handle its type
+    fn decode(&self, structure: &RawStructure<'buffer>) -> Option<Result<Self::Output, MalformedStructureError>>;
+}
+
+impl<'buffer, F, T> OemDecoder<'buffer> for F
+where
+    F: Fn(&RawStructure<'buffer>) -> Option<Result<T, MalformedStructureError>>,
+{
+    type Output = T;
+
+    fn decode(&self, structure: &RawStructure<'buffer>) -> Option<Result<T, MalformedStructureError>> {
+        self(structure)
+    }
+}
+
+impl<'buffer, A, B> OemDecoder<'buffer> for (A, B)
+where
+    A: OemDecoder<'buffer>,
+    B: OemDecoder<'buffer, Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn decode(&self, structure: &RawStructure<'buffer>) -> Option<Result<Self::Output, MalformedStructureError>> {
+        self.0.decode(structure).or_else(|| self.1.decode(structure))
+    }
+}
+
+impl<'buffer, A, B, C> OemDecoder<'buffer> for (A, B, C)
+where
+    A: OemDecoder<'buffer>,
+    B: OemDecoder<'buffer, Output = A::Output>,
+    C: OemDecoder<'buffer, Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn decode(&self, structure: &RawStructure<'buffer>) -> Option<Result<Self::Output, MalformedStructureError>> {
+        self.0
+            .decode(structure)
+            .or_else(|| self.1.decode(structure))
+            .or_else(|| self.2.decode(structure))
+    }
+}
+
+/// A structure decoded either by this crate or by an [OemDecoder]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Decoded<'buffer, T> {
+    Structure(Structure<'buffer>),
+    Oem(T),
+}
+
+/// An iterator over the SMBIOS structures that hands the structures not decoded by this crate to an
+/// [OemDecoder]
+///
+/// Created by [Structures::with_oem_decoder]. Structures that the decoder does not handle are
+/// yielded as [Structure::Other].
+#[derive(Clone, Debug)]
+pub struct OemStructures<'buffer, D> {
+    structures: Structures<'buffer>,
+    decoder: D,
+}
+
+impl<'buffer, D: OemDecoder<'buffer>> Iterator for OemStructures<'buffer, D> {
+    type Item = Result<Decoded<'buffer, D::Output>, MalformedStructureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.structures.next()? {
+            Ok(Structure::Other(raw)) => match self.decoder.decode(&raw) {
+                Some(result) => result.map(Decoded::Oem),
+                None => Ok(Decoded::Structure(Structure::Other(raw))),
+            },
+            result => result.map(Decoded::Structure),
+        })
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Decode the structures not handled by this crate with `decoder`
+    pub fn with_oem_decoder<D: OemDecoder<'buffer>>(self, decoder: D) -> OemStructures<'buffer, D> {
+        OemStructures {
+            structures: self,
+            decoder,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EntryPoint, Handle, InfoType};
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../../tests/data/dmi.0.bin");
+
+    #[derive(Debug, PartialEq)]
+    enum Vendor {
+        Type(u8, Handle),
+    }
+
+    fn oem_type(code: u8) -> impl Fn(&RawStructure) -> Option<Result<Vendor, MalformedStructureError>> {
+        move |structure| match structure.info {
+            InfoType::Oem(t) if t == code => Some(Ok(Vendor::Type(t, structure.handle))),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn oem_decoders() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let oem = structures
+            .clone()
+            .filter_map(|s| match s {
+                Ok(Structure::Other(raw)) => match raw.info {
+                    InfoType::Oem(t) => Some(t),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(oem.len() > 1);
+
+        let (first, second) = (oem[0], *oem.iter().find(|&&t| t != oem[0]).unwrap());
+        let decoded = structures
+            .clone()
+            .with_oem_decoder((oem_type(first), oem_type(second)))
+            .filter_map(|s| match s {
+                Ok(Decoded::Oem(Vendor::Type(t, _))) => Some(t),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let expected = oem
+            .iter()
+            .cloned()
+            .filter(|&t| t == first || t == second)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, decoded);
+
+        let total = structures.clone().count();
+        assert_eq!(total, structures.with_oem_decoder(oem_type(first)).count());
+    }
+}
@@ -0,0 +1,134 @@
+//! Owned structures for callers that cannot keep the table buffer alive
+//!
+//! [parse_table] copies every structure out of a dump, so that the dump itself can be dropped,
+//! e.g. when it was handed over by JavaScript in a WebAssembly build. Errors are reported as
+//! strings ready to be displayed. Only the `alloc` feature is required, the module builds for
+//! `wasm32-unknown-unknown`.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{owned::parse_table, Structure};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let structures = parse_table(DMIDECODE_BIN).unwrap();
+//! for structure in &structures {
+//!     if let Ok(Structure::System(system)) = structure.decode() {
+//!         println!("{} {}", system.manufacturer, system.product);
+//!     }
+//! }
+//! ```
+
+use core::convert::TryFrom;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{EntryPoint, Handle, InfoType, MalformedStructureError, RawStructure, SmbiosVersion, Structure};
+
+/// A copy of a [RawStructure] that does not borrow from the table buffer
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OwnedStructure {
+    pub version: SmbiosVersion,
+    pub info: InfoType,
+    pub length: u8,
+    pub handle: Handle,
+    pub data: Vec<u8>,
+    strings: Vec<u8>,
+    trim_strings: bool,
+    tolerate_bad_string_indexes: bool,
+}
+
+impl OwnedStructure {
+    /// Copy the structure out of the table buffer
+    pub fn from_raw(structure: &RawStructure<'_>) -> Self {
+        OwnedStructure {
+            version: structure.version,
+            info: structure.info,
+            length: structure.length,
+            handle: structure.handle,
+            data: structure.data.to_vec(),
+            strings: structure.strings.to_vec(),
+            trim_strings: structure.trim_strings,
+            tolerate_bad_string_indexes: structure.tolerate_bad_string_indexes,
+        }
+    }
+
+    /// Borrow the structure as a [RawStructure]
+    pub fn raw(&self) -> RawStructure<'_> {
+        RawStructure {
+            version: self.version,
+            info: self.info,
+            length: self.length,
+            handle: self.handle,
+            data: &self.data,
+            strings: &self.strings,
+            trim_strings: self.trim_strings,
+            tolerate_bad_string_indexes: self.tolerate_bad_string_indexes,
+        }
+    }
+
+    /// Decode the structure, the decoded structure borrows its strings from `self`
+    pub fn decode(&self) -> Result<Structure<'_>, MalformedStructureError> {
+        Structure::decode(self.raw())
+    }
+}
+
+/// Copy all structures out of a `dmidecode --dump-bin` dump: the entry point followed by the
+/// structure table at the offset given as the table address.
+///
+/// # Errors
+///
+/// Returns the description of the first problem found: a missing or invalid entry point, a table
+/// address outside of `bytes` or a malformed structure.
+pub fn parse_table(bytes: &[u8]) -> Result<Vec<OwnedStructure>, String> {
+    let entry_point = EntryPoint::search(bytes).map_err(|e| e.to_string())?;
+    let table = usize::try_from(entry_point.smbios_address())
+        .ok()
+        .and_then(|address| bytes.get(address..))
+        .ok_or_else(|| {
+            let mut message = String::from("Structure table address is outside of the dump: ");
+            message.push_str(&entry_point.smbios_address().to_string());
+            message
+        })?;
+
+    let mut structures = entry_point.structures(table);
+    let mut owned = Vec::new();
+    while let Some(structure) = structures.next_structure() {
+        owned.push(OwnedStructure::from_raw(&structure.map_err(|e| e.to_string())?));
+    }
+    Ok(owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn parse_dump() {
+        let owned = parse_table(DMIDECODE_BIN).unwrap();
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let borrowed = entry_point
+            .structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..])
+            .collect::<Vec<_>>();
+        assert_eq!(borrowed.len(), owned.len());
+        for (owned, borrowed) in owned.iter().zip(borrowed) {
+            assert_eq!(borrowed.unwrap(), owned.decode().unwrap());
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "Input did not contain a valid SMBIOS entry point",
+            parse_table(&[0; 64]).unwrap_err()
+        );
+        let truncated = &DMIDECODE_BIN[..0x40];
+        assert!(parse_table(truncated).unwrap_err().starts_with("Structure at offset "));
+    }
+}
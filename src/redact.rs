@@ -0,0 +1,237 @@
+//! Remove identifying information from decoded structures
+//!
+//! [Structure::redact] blanks serial numbers, UUIDs, asset tags and, depending on the
+//! [RedactionPolicy], OEM strings, so that the decoded table can be attached to public bug
+//! reports. Redacted strings are replaced by [REDACTED], redacted UUIDs are reported as absent.
+//! The strings of structures not decoded by this crate, e.g. the serial number of a System Power
+//! Supply, are removed whatever the policy since they cannot be told apart.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{redact::{RedactionPolicy, REDACTED}, EntryPoint, Structure};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! for structure in entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]) {
+//!     if let Structure::System(system) = structure.unwrap().redact(RedactionPolicy::Identifiers) {
+//!         assert_eq!(REDACTED, system.serial);
+//!         assert_eq!(None, system.uuid);
+//!     }
+//! }
+//! ```
+
+use crate::{portable_battery::SerialNumber, Structure, StructureStrings};
+
+/// Replacement for redacted strings
+pub const REDACTED: &str = "<redacted>";
+
+/// Selects the information removed by [Structure::redact]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum RedactionPolicy {
+    /// Serial numbers, UUIDs, asset tags and the strings of structures not decoded by this crate
+    Identifiers,
+    /// Identifiers and OEM strings sets containing a MAC address
+    IdentifiersAndMacs,
+    /// Identifiers and all OEM strings
+    Strict,
+}
+
+impl<'buffer> Structure<'buffer> {
+    /// Blank the fields selected by `policy`
+    pub fn redact(self, policy: RedactionPolicy) -> Self {
+        match self {
+            Structure::System(mut system) => {
+                system.serial = redact_str(system.serial);
+                system.uuid = None;
+                Structure::System(system)
+            }
+            Structure::BaseBoard(mut baseboard) => {
+                baseboard.serial = redact_str(baseboard.serial);
+                baseboard.asset = baseboard.asset.map(redact_str);
+                Structure::BaseBoard(baseboard)
+            }
+            Structure::Enclosure(mut enclosure) => {
+                enclosure.serial_number = redact_str(enclosure.serial_number);
+                enclosure.asset_tag_number = redact_str(enclosure.asset_tag_number);
+                Structure::Enclosure(enclosure)
+            }
+            Structure::Processor(mut processor) => {
+                processor.serial_number = processor.serial_number.map(redact_str);
+                processor.asset_tag = processor.asset_tag.map(redact_str);
+                Structure::Processor(processor)
+            }
+            Structure::MemoryDevice(mut device) => {
+                device.serial = redact_str(device.serial);
+                device.asset_tag = redact_str(device.asset_tag);
+                Structure::MemoryDevice(device)
+            }
+            Structure::PortableBattery(mut battery) => {
+                battery.serial_number = match battery.serial_number {
+                    SerialNumber::None => SerialNumber::None,
+                    _ => SerialNumber::Basic(REDACTED),
+                };
+                Structure::PortableBattery(battery)
+            }
+            Structure::OemStrings(mut oem_strings) => {
+                let redact = match policy {
+                    RedactionPolicy::Identifiers => false,
+                    RedactionPolicy::IdentifiersAndMacs => oem_strings.strings.raw().any(contains_mac_address),
+                    RedactionPolicy::Strict => true,
+                };
+                if redact {
+                    oem_strings.strings = StructureStrings::new(&[]);
+                }
+                Structure::OemStrings(oem_strings)
+            }
+            Structure::Other(mut raw) => {
+                raw.strings = &[];
+                Structure::Other(raw)
+            }
+            structure => structure,
+        }
+    }
+}
+
+/// Empty strings are kept, they carry no information
+fn redact_str(s: &str) -> &str {
+    if s.is_empty() {
+        s
+    } else {
+        REDACTED
+    }
+}
+
+/// Finds `xx:xx:xx:xx:xx:xx`, `xx-xx-xx-xx-xx-xx` and runs of exactly 12 hex digits
+fn contains_mac_address(s: &[u8]) -> bool {
+    let separated = |window: &[u8]| {
+        let separator = window[2];
+        (separator == b':' || separator == b'-')
+            && window.chunks(3).all(|group| {
+                group[0].is_ascii_hexdigit()
+                    && group[1].is_ascii_hexdigit()
+                    && group.get(2).map_or(true, |&c| c == separator)
+            })
+    };
+    if s.windows(17).any(separated) {
+        return true;
+    }
+    s.split(|c| !c.is_ascii_hexdigit()).any(|run| run.len() == 12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Handle, InfoType, OemStrings, RawStructure};
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn mac_addresses() {
+        assert!(contains_mac_address(b"MAC 00:1A:2b:3c:4D:5e"));
+        assert!(contains_mac_address(b"00-1A-2B-3C-4D-5E"));
+        assert!(contains_mac_address(b"mac=001A2B3C4D5E;"));
+        assert!(!contains_mac_address(b"00:1A:2B:3C:4D"));
+        assert!(!contains_mac_address(b"00:1A-2B:3C:4D:5E"));
+        assert!(!contains_mac_address(b"0123456789ABCDEF"));
+        assert!(!contains_mac_address(b"$HUA0464"));
+    }
+
+    #[test]
+    fn oem_strings() {
+        let structure = RawStructure {
+            version: (3, 4).into(),
+            info: InfoType::OemStrings,
+            length: 0x05,
+            handle: Handle(0x001E),
+            data: &[0x02],
+            strings: b"$HUA0464\0NIC 00:1A:2B:3C:4D:5E\0\0",
+            ..Default::default()
+        };
+        let strings =
+            |policy| match Structure::OemStrings(OemStrings::from_raw(structure.clone()).unwrap()).redact(policy) {
+                Structure::OemStrings(oem_strings) => oem_strings.strings.count(),
+                _ => unreachable!(),
+            };
+        assert_eq!(2, strings(RedactionPolicy::Identifiers));
+        assert_eq!(0, strings(RedactionPolicy::IdentifiersAndMacs));
+        assert_eq!(0, strings(RedactionPolicy::Strict));
+    }
+
+    #[test]
+    fn no_serial_survives() {
+        use crate::testing::{StructureBuilder, TableBuilder};
+
+        const SERIALS: [&str; 3] = ["SYS-0123456789", "PSU-0123456789", "PSU-ASSET-0042"];
+        let table = TableBuilder::new((3, 4).into())
+            .push(
+                &StructureBuilder::new(InfoType::System)
+                    .string("Vendor")
+                    .string("Product")
+                    .string("1.0")
+                    .string(SERIALS[0]),
+            )
+            .push(
+                &StructureBuilder::new(InfoType::SystemPowerSupply)
+                    .byte(1)
+                    .string("PSU 1")
+                    .string("Power Supply")
+                    .string("Vendor")
+                    .string(SERIALS[1])
+                    .string(SERIALS[2])
+                    .string("Model")
+                    .string("A01")
+                    .word(0x8000)
+                    .word(0x1002)
+                    .bytes(&[0xFF; 6]),
+            )
+            .end(Handle(0xFEFF));
+        for policy in [
+            RedactionPolicy::Identifiers,
+            RedactionPolicy::IdentifiersAndMacs,
+            RedactionPolicy::Strict,
+        ] {
+            for structure in table.structures() {
+                let structure = structure.unwrap().redact(policy);
+                let strings = match structure {
+                    Structure::Other(ref raw) => raw.strings,
+                    _ => &[],
+                };
+                let debug = format!("{:?}", structure);
+                for serial in SERIALS {
+                    assert!(!debug.contains(serial), "{:?}: {}", policy, debug);
+                    assert!(
+                        !strings.windows(serial.len()).any(|w| w == serial.as_bytes()),
+                        "{:?}: {:?}",
+                        policy,
+                        structure
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn identifiers() {
+        const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmi.0.bin");
+        let entry_point = crate::EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let mut redacted = 0;
+        for structure in structures.flatten() {
+            match structure.redact(RedactionPolicy::Identifiers) {
+                Structure::System(system) => {
+                    assert_eq!(REDACTED, system.serial);
+                    assert_eq!(None, system.uuid);
+                    redacted += 1;
+                }
+                Structure::MemoryDevice(device) => {
+                    assert!(device.serial.is_empty() || device.serial == REDACTED);
+                    redacted += 1;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(25, redacted);
+    }
+}
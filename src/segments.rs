@@ -0,0 +1,179 @@
+//! Structure tables concatenated in one buffer
+//!
+//! Some multi-node chassis expose the SMBIOS tables of their nodes one after the other, each
+//! closed by its own End-of-Table (Type 127) structure. The iteration over an SMBIOS 3.x table
+//! stops at the first End-of-Table structure, so the tables of the other nodes are never read.
+//! [Structures::segments] splits the table after every End-of-Table structure instead, and yields
+//! each segment as [Structures] of its own.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::EntryPoint;
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! for (node, segment) in structures.segments().enumerate() {
+//!     println!("node {}: {} structures", node, segment.count());
+//! }
+//! ```
+
+use crate::{InfoType, Structures};
+
+/// An iterator over the segments of a table, created by [Structures::segments]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Segments<'buffer> {
+    /// The table the segments take their settings from
+    table: Structures<'buffer>,
+    structures: Structures<'buffer>,
+    done: bool,
+}
+
+impl<'buffer> Iterator for Segments<'buffer> {
+    type Item = Structures<'buffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let first = self.structures.parsed == 0;
+        let start = self.structures.idx;
+        let end = loop {
+            match self.structures.next_raw() {
+                Some(Ok(structure)) => {
+                    self.structures.parsed += 1;
+                    if structure.info == InfoType::End {
+                        break self.structures.idx;
+                    }
+                }
+                None => {
+                    self.done = true;
+                    break self.structures.idx;
+                }
+                // Padding after the last table is not a segment, but a table that cannot be read
+                // is yielded whole, so that iterating it tells the error
+                Some(Err(_)) if !first && self.structures.idx == start => {
+                    lib_debug!(
+                        "ignoring {:#X} bytes after the last table",
+                        self.structures.smbios_len - start
+                    );
+                    self.done = true;
+                    return None;
+                }
+                Some(Err(_)) => {
+                    self.done = true;
+                    break self.structures.smbios_len;
+                }
+            }
+        };
+        if end == start {
+            return None;
+        }
+        lib_trace!("table segment at offset {:#X}, {:#X} bytes", start, end - start);
+        let len = end - start;
+        Some(Structures {
+            buffer: &self.structures.buffer[start as usize..end as usize],
+            smbios_len: len,
+            declared_len: len,
+            idx: 0,
+            parsed: 0,
+            end_of_table: false,
+            failed: false,
+            ..self.table.clone()
+        })
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Iterate over the whole table split into segments, each ending with an End-of-Table
+    /// structure but the last one, which ends with the table
+    ///
+    /// The segments keep the settings of the table, such as [Structures::trim_strings]. Bytes
+    /// after the last segment that do not hold a structure are ignored. A table without multiple
+    /// End-of-Table structures is a single segment.
+    pub fn segments(&self) -> Segments<'buffer> {
+        let len = (self.buffer.len() as u32).min(self.declared_len);
+        Segments {
+            table: self.clone(),
+            structures: Structures::new(&self.buffer[..len as usize], self.smbios_version, len),
+            done: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::StructureBuilder, testing::TableBuilder, EntryPoint, Handle, MalformedStructureError};
+    use pretty_assertions::assert_eq;
+    use std::{vec, vec::Vec};
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    fn handles(structures: Structures<'_>) -> Vec<u16> {
+        structures.raw().map(|s| s.unwrap().handle.0).collect()
+    }
+
+    #[test]
+    fn test_data() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+        let segments = structures.segments().collect::<Vec<_>>();
+        assert_eq!(1, segments.len());
+        assert_eq!(69, segments[0].clone().count());
+    }
+
+    #[test]
+    fn concatenated_tables() {
+        let node = |handle| StructureBuilder::new(InfoType::Oem(0x80)).handle(Handle(handle));
+        let mut table = TableBuilder::new((3, 0).into())
+            .push(&node(0x0000))
+            .push(&node(0x0001))
+            .end(Handle(0xFEFF))
+            .push(&node(0x1000))
+            .end(Handle(0xFEFE))
+            .bytes()
+            .to_vec();
+        // Padding up to the maximum table length of the entry point
+        table.extend_from_slice(&[0; 0x20]);
+        let structures = Structures::new(&table, (3, 0).into(), table.len() as u32).trim_strings();
+        assert_eq!(vec![0x0000, 0x0001, 0xFEFF], handles(structures.clone()));
+
+        let segments = structures.segments().collect::<Vec<_>>();
+        assert_eq!(
+            vec![vec![0x0000, 0x0001, 0xFEFF], vec![0x1000, 0xFEFE]],
+            segments.iter().cloned().map(handles).collect::<Vec<_>>()
+        );
+        assert!(segments.iter().all(|segment| segment.trim_strings));
+        let status = segments[1].clone().finish();
+        assert!(status.end_of_table && !status.failed);
+        assert_eq!(status.declared, status.consumed);
+
+        // Reading the table first does not change its segments
+        let mut read = structures.clone();
+        read.by_ref().for_each(drop);
+        assert_eq!(segments, read.segments().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unreadable_table() {
+        let table = TableBuilder::new((3, 0).into())
+            .push(&StructureBuilder::new(InfoType::Oem(0x80)).handle(Handle(0x0000)))
+            .bytes()
+            .to_vec();
+        let mut truncated = table.clone();
+        truncated.extend_from_slice(&[0x80, 0x10, 0x01, 0x00]);
+        let structures = Structures::new(&truncated, (3, 0).into(), truncated.len() as u32);
+        let segments = structures.segments().collect::<Vec<_>>();
+        assert_eq!(1, segments.len());
+        let mut segment = segments[0].clone().raw();
+        assert!(segment.next().unwrap().is_ok());
+        assert!(matches!(
+            segment.next(),
+            Some(Err(MalformedStructureError::BadSize(..)))
+        ));
+
+        assert_eq!(0, Structures::new(&[], (3, 0).into(), 0).segments().count());
+    }
+}
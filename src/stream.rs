@@ -0,0 +1,248 @@
+//! Incremental parsing of a structure table received in chunks
+//!
+//! Out-of-band channels such as Redfish or IPMI blob transfers deliver the table a few bytes at a
+//! time. [TableAccumulator] keeps the bytes of the structure being received and emits each
+//! structure as an [OwnedStructure] as soon as its strings table is terminated, so that the
+//! caller never needs the whole table in one contiguous buffer.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{stream::{Event, TableAccumulator}, EntryPoint, Structure};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+//! let mut accumulator = TableAccumulator::new(&entry_point);
+//! for chunk in table.chunks(64) {
+//!     for event in accumulator.push(chunk) {
+//!         if let Event::Structure(structure) = event {
+//!             if let Ok(Structure::System(system)) = structure.decode() {
+//!                 assert_eq!("LENOVO", system.manufacturer);
+//!             }
+//!         }
+//!     }
+//! }
+//! assert!(accumulator.finish().is_ok());
+//! ```
+
+use core::mem;
+
+use alloc::vec::Vec;
+
+use crate::{
+    find_nulnul, owned::OwnedStructure, EntryPoint, Handle, HeaderPacked, InfoType, MalformedStructureError,
+    RawStructure, SmbiosVersion,
+};
+
+/// What a [TableAccumulator] found in the bytes pushed to it
+#[derive(Debug)]
+pub enum Event {
+    /// A structure whose bytes are complete
+    Structure(OwnedStructure),
+    /// The bytes do not form a structure; no further event is emitted
+    Malformed(MalformedStructureError),
+    /// The table ended, at the End-of-Table structure of SMBIOS 3.x tables or at the table length
+    /// of SMBIOS 2.x tables; the bytes pushed afterwards are ignored
+    End,
+}
+
+/// A push based parser of a structure table, see the [module documentation](self)
+///
+/// The structures are cut as by [Structures](crate::Structures): an SMBIOS 2.x table ends after
+/// its declared length, an SMBIOS 3.x table at its End-of-Table structure or its maximum length.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TableAccumulator {
+    smbios_version: SmbiosVersion,
+    smbios_len: u32,
+    /// Table offset of the first byte of `pending`
+    offset: u32,
+    /// The bytes of the structures not emitted yet
+    pending: Vec<u8>,
+    done: bool,
+}
+
+impl TableAccumulator {
+    /// Parser of the table described by `entry_point`
+    pub fn new(entry_point: &EntryPoint) -> Self {
+        Self::with_version(entry_point.to_version(), entry_point.smbios_len())
+    }
+
+    /// Parser of a table of `smbios_len` bytes, the maximum length for SMBIOS 3.x
+    pub fn with_version(smbios_version: SmbiosVersion, smbios_len: u32) -> Self {
+        TableAccumulator {
+            smbios_version,
+            smbios_len,
+            offset: 0,
+            pending: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Append the next bytes of the table, returning the events they completed, in table order
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<Event> {
+        let mut events = Vec::new();
+        if self.done {
+            return events;
+        }
+        self.pending.extend_from_slice(bytes);
+
+        let mut start = 0;
+        while let Some((event, len)) = self.next_event(start) {
+            start += len;
+            match event {
+                Event::Structure(ref structure)
+                    if self.smbios_version.major >= 3 && structure.info == InfoType::End =>
+                {
+                    lib_trace!(
+                        "End-of-Table structure {:#06X} ends the structure table",
+                        structure.handle
+                    );
+                    events.push(event);
+                    events.push(Event::End);
+                    self.done = true;
+                    break;
+                }
+                Event::Structure(_) => events.push(event),
+                _ => {
+                    events.push(event);
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..start);
+        self.offset += start as u32;
+        if self.done {
+            self.pending = Vec::new();
+        }
+        events
+    }
+
+    /// Whether the table ended or turned out malformed, so that no more bytes are needed
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Signal that the table has been received completely
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::UnterminatedStrings` if a structure is missing the end
+    /// of its strings table, or a `MalformedStructureError::BadSize` if the bytes stop in the
+    /// middle of its header or formatted section.
+    pub fn finish(self) -> Result<(), MalformedStructureError> {
+        let header_len = mem::size_of::<HeaderPacked>();
+        match self.pending.len() {
+            0 => Ok(()),
+            len if len < header_len => Err(MalformedStructureError::BadSize(self.offset, len as u8)),
+            len if len <= self.pending[1] as usize => {
+                Err(MalformedStructureError::BadSize(self.offset, self.pending[1]))
+            }
+            _ => Err(MalformedStructureError::UnterminatedStrings(self.offset)),
+        }
+    }
+
+    /// The event of the structure at `start` in `pending` and its length, `None` if more bytes
+    /// are needed
+    fn next_event(&self, start: usize) -> Option<(Event, usize)> {
+        let header_len = mem::size_of::<HeaderPacked>();
+        let idx = self.offset + start as u32;
+        if idx + header_len as u32 > self.smbios_len {
+            return Some((Event::End, 0));
+        }
+        let working = &self.pending[start..];
+        let_as_struct!(header, HeaderPacked, working, or return None);
+
+        let strings_idx = idx + header.len as u32;
+        if strings_idx >= self.smbios_len || (header.len as usize) < header_len {
+            return Some((Event::Malformed(MalformedStructureError::BadSize(idx, header.len)), 0));
+        }
+        let strings = working.get(header.len as usize..)?;
+        let strings_len = find_nulnul(strings)? + 1;
+
+        let structure = RawStructure {
+            version: self.smbios_version,
+            info: header.kind.into(),
+            length: header.len,
+            handle: Handle(header.handle.get()),
+            data: &working[header_len..header.len as usize],
+            strings: &strings[..strings_len],
+            trim_strings: false,
+            tolerate_bad_string_indexes: false,
+        };
+        let len = header.len as usize + strings_len;
+        Some((Event::Structure(OwnedStructure::from_raw(&structure)), len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+    const DMI_V3_BIN: &[u8] = include_bytes!("../tests/data/dmi_v3.bin");
+    const ENTRY_V3_BIN: &[u8] = include_bytes!("../tests/data/entry_v3.bin");
+
+    fn stream(entry_point: &EntryPoint, table: &[u8], chunk: usize) -> (Vec<Event>, TableAccumulator) {
+        let mut accumulator = TableAccumulator::new(entry_point);
+        let events = table.chunks(chunk).flat_map(|bytes| accumulator.push(bytes)).collect();
+        (events, accumulator)
+    }
+
+    #[test]
+    fn chunked() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        let expected = entry_point
+            .raw_structures(table)
+            .map(|structure| OwnedStructure::from_raw(&structure.unwrap()))
+            .collect::<Vec<_>>();
+        for &chunk in [1, 3, 64, 4096].iter() {
+            let (mut events, accumulator) = stream(&entry_point, table, chunk);
+            assert!(matches!(events.pop(), Some(Event::End)), "chunks of {} bytes", chunk);
+            let structures = events
+                .into_iter()
+                .map(|event| match event {
+                    Event::Structure(structure) => structure,
+                    other => panic!("{:?}", other),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(expected, structures, "chunks of {} bytes", chunk);
+            assert!(accumulator.is_done());
+        }
+    }
+
+    #[test]
+    fn end_of_table() {
+        let entry_point = EntryPoint::search(ENTRY_V3_BIN).unwrap();
+        let (events, accumulator) = stream(&entry_point, DMI_V3_BIN, 100);
+        assert_eq!(99 + 1, events.len());
+        match &events[98] {
+            Event::Structure(structure) => assert_eq!(InfoType::End, structure.info),
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(events.last(), Some(Event::End)));
+        assert!(accumulator.finish().is_ok());
+    }
+
+    #[test]
+    fn truncated() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[(entry_point.smbios_address() as usize)..];
+        let mut accumulator = TableAccumulator::new(&entry_point);
+        let events = accumulator.push(&table[..0x30]);
+        assert!(!events.is_empty());
+        assert!(!accumulator.is_done());
+        assert!(accumulator.finish().is_err());
+
+        let mut accumulator = TableAccumulator::with_version((2, 7).into(), 0x100);
+        let events = accumulator.push(&[0x01, 0x02, 0x00, 0x01, 0x00, 0x00]);
+        assert!(matches!(
+            events[..],
+            [Event::Malformed(MalformedStructureError::BadSize(0, 2))]
+        ));
+        assert!(accumulator.is_done());
+        assert!(accumulator.push(&[0x00]).is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! Strings tables compared with the fields that refer to them
+//!
+//! A string of the strings table is only reachable through a field that holds its index. Firmware
+//! that fills its tables from templates leaves strings no field refers to, e.g. placeholder
+//! strings of removed fields, and fields that refer to strings past the end of the table.
+//! [RawStructure::string_audit] sorts the string indexes of a structure into those referred to by
+//! its fields and those present in its strings table, so that both kinds of garbage show up.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::EntryPoint;
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! for structure in entry_point.raw_structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]) {
+//!     let structure = structure.unwrap();
+//!     if let Some(audit) = structure.string_audit() {
+//!         for index in audit.orphaned().iter() {
+//!             println!("{} structure {}: string {} is unused", structure.info, structure.handle, index);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{InfoType, RawStructure};
+
+/// A set of string indexes, from 1 to 255
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StringSet([u64; 4]);
+
+impl StringSet {
+    /// Whether the set holds string `index`
+    pub fn contains(&self, index: u8) -> bool {
+        self.0[index as usize / 64] & 1 << (index % 64) != 0
+    }
+
+    /// Number of indexes in the set
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&word| word == 0)
+    }
+
+    /// The indexes of the set, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (1..=u8::MAX).filter(move |&index| self.contains(index))
+    }
+
+    /// The indexes of the set that `other` does not hold
+    pub fn difference(&self, other: &StringSet) -> StringSet {
+        let mut set = *self;
+        for (word, other) in set.0.iter_mut().zip(other.0.iter()) {
+            *word &= !other;
+        }
+        set
+    }
+
+    /// Add string `index` to the set, 0 standing for no string is never added
+    fn with(mut self, index: u8) -> StringSet {
+        if index != 0 {
+            self.0[index as usize / 64] |= 1 << (index % 64);
+        }
+        self
+    }
+}
+
+/// The string indexes of a structure, see [RawStructure::string_audit]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct StringAudit {
+    /// Indexes held by the string fields of the structure
+    pub referenced: StringSet,
+    /// Indexes of the strings of the strings table
+    pub present: StringSet,
+}
+
+impl StringAudit {
+    /// Strings of the strings table that no field refers to
+    pub fn orphaned(&self) -> StringSet {
+        self.present.difference(&self.referenced)
+    }
+
+    /// Indexes held by fields that are past the end of the strings table
+    pub fn missing(&self) -> StringSet {
+        self.referenced.difference(&self.present)
+    }
+
+    /// Whether every string is referred to and every reference resolves
+    pub fn is_consistent(&self) -> bool {
+        self.referenced == self.present
+    }
+}
+
+impl<'buffer> RawStructure<'buffer> {
+    /// Compare the string indexes held by the fields of the structure with the strings of its
+    /// strings table, `None` if the string fields of the structure type are not known
+    ///
+    /// The string fields are known for the structure types this crate decodes and a few more
+    /// with simple layouts. Fields past the formatted section length are not taken into account.
+    pub fn string_audit(&self) -> Option<StringAudit> {
+        let present = (1..=self.string_count()).fold(StringSet::default(), StringSet::with);
+        Some(StringAudit {
+            referenced: referenced_strings(self)?,
+            present,
+        })
+    }
+}
+
+/// Indexes of the strings the fields of `structure` refer to, `None` if the string fields of the
+/// structure type are not known
+fn referenced_strings(structure: &RawStructure<'_>) -> Option<StringSet> {
+    let fields = |offsets: &[usize]| {
+        offsets
+            .iter()
+            .filter_map(|&offset| structure.get_optional::<u8>(offset))
+            .fold(StringSet::default(), StringSet::with)
+    };
+    let referenced = match structure.info {
+        InfoType::Bios => fields(&[0x04, 0x05, 0x08]),
+        InfoType::System => fields(&[0x04, 0x05, 0x06, 0x07, 0x19, 0x1A]),
+        InfoType::BaseBoard => fields(&[0x04, 0x05, 0x06, 0x07, 0x08, 0x0A]),
+        InfoType::Enclosure => {
+            // The SKU number follows the contained elements
+            let count = structure.get_optional::<u8>(0x13).unwrap_or(0) as usize;
+            let size = structure.get_optional::<u8>(0x14).unwrap_or(0) as usize;
+            fields(&[0x04, 0x06, 0x07, 0x08, 0x15 + count * size])
+        }
+        InfoType::Processor => fields(&[0x04, 0x07, 0x10, 0x20, 0x21, 0x22]),
+        InfoType::MemoryModule | InfoType::Cache | InfoType::SystemSlots | InfoType::GroupAssociations => {
+            fields(&[0x04])
+        }
+        InfoType::PortConnector => fields(&[0x04, 0x06]),
+        InfoType::OnBoardDevices => (0..(structure.length as usize).saturating_sub(4) / 2)
+            .filter_map(|device| structure.get_optional::<u8>(0x05 + 2 * device))
+            .fold(StringSet::default(), StringSet::with),
+        InfoType::OemStrings | InfoType::SystemConfigurationOptions | InfoType::BiosLanguage => {
+            (1..=structure.get_optional::<u8>(0x04)?).fold(StringSet::default(), StringSet::with)
+        }
+        InfoType::MemoryDevice => fields(&[0x10, 0x11, 0x17, 0x18, 0x19, 0x1A, 0x2B]),
+        InfoType::PortableBattery => fields(&[0x04, 0x05, 0x06, 0x07, 0x08, 0x0E, 0x14]),
+        InfoType::VoltageProbe
+        | InfoType::TemperatureProbe
+        | InfoType::ElectricalCurrentProbe
+        | InfoType::OutOfBandRemoteAccess
+        | InfoType::ManagementDevice
+        | InfoType::OnboardDevicesExtended => fields(&[0x04]),
+        InfoType::CoolingDevice => fields(&[0x0E]),
+        InfoType::SystemPowerSupply => fields(&[0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B]),
+        InfoType::PhysicalMemoryArray
+        | InfoType::MemoryError32
+        | InfoType::MemoryArrayMappedAddress
+        | InfoType::MemoryDeviceMappedAddress
+        | InfoType::BuiltInPointingDevice
+        | InfoType::End => StringSet::default(),
+        _ => return None,
+    };
+    Some(referenced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::StructureBuilder, EntryPoint};
+    use pretty_assertions::assert_eq;
+    use std::{vec, vec::Vec};
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn string_set() {
+        let set = [1, 63, 64, 200, 255]
+            .iter()
+            .fold(StringSet::default(), |set, &i| set.with(i));
+        assert_eq!(vec![1, 63, 64, 200, 255], set.iter().collect::<Vec<_>>());
+        assert_eq!(5, set.len());
+        assert!(!set.contains(0) && !set.contains(2));
+        assert!(StringSet::default().with(0).is_empty());
+        let other = StringSet::default().with(64).with(2);
+        assert_eq!(vec![1, 63, 200, 255], set.difference(&other).iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn orphaned_and_missing() {
+        // Serial number refers to a missing string 5, string 3 is a leftover
+        let board = StructureBuilder::new(InfoType::BaseBoard)
+            .string("Vendor")
+            .string("Board")
+            .byte(0)
+            .byte(5)
+            .extra_string("\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}");
+        let audit = board.raw().string_audit().unwrap();
+        assert_eq!(
+            vec![1, 2],
+            audit.referenced.difference(&audit.missing()).iter().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![3], audit.orphaned().iter().collect::<Vec<_>>());
+        assert_eq!(vec![5], audit.missing().iter().collect::<Vec<_>>());
+        assert!(!audit.is_consistent());
+
+        assert_eq!(None, StructureBuilder::new(InfoType::Oem(0x80)).raw().string_audit());
+    }
+
+    #[test]
+    fn test_data() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let table = &DMIDECODE_BIN[entry_point.smbios_address() as usize..];
+        for structure in entry_point.raw_structures(table) {
+            let structure = structure.unwrap();
+            if let Some(audit) = structure.string_audit() {
+                assert!(audit.missing().is_empty(), "{:?}", structure);
+            }
+        }
+    }
+}
@@ -5,13 +5,13 @@
 use core::fmt;
 
 use crate::bitfield::{BitField, FlagType, Layout};
-use crate::{MalformedStructureError, RawStructure};
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure, SmbiosVersion};
 
 /// BIOS Information
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Default)]
 pub struct Bios<'buffer> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// BIOS Vendor’s Name
     pub vendor: &'buffer str,
     /// BIOS Version. This value is a free-form string that may contain Core and OEM version
@@ -28,13 +28,16 @@ pub struct Bios<'buffer> {
     pub rom_size: RomSize,
     /// Defines which functions the BIOS supports: PCI, PCMCIA, Flash, etc.
     pub bios_characteristics: Characteristics,
-    /// For version 2.1 and later implementations one Extensions Byte defined
+    /// For version 2.1 and later implementations one Extensions Byte defined, `None` if the
+    /// formatted section stops before offset 12h
     pub bios_characteristics_exttension_1: Option<CharacteristicsExtension1>,
-    /// For version 2.4 and later implementations two Extensions Bytes defined
+    /// For version 2.3 and later implementations two Extensions Bytes defined, `None` if the
+    /// formatted section stops before offset 13h
     pub bios_characteristics_exttension_2: Option<CharacteristicsExtension2>,
-    /// System BIOS Revision
+    /// System BIOS Revision, `None` if missing or not supported by the system (FFh)
     pub bios_revision: Option<BiosRevision>,
-    /// Embedded Controller Firmware Revision
+    /// Embedded Controller Firmware Revision, `None` if missing or if the system has no field
+    /// upgradeable embedded controller firmware (FFh)
     pub firmware_revision: Option<FirmwareRevision>,
 }
 
@@ -52,6 +55,10 @@ pub struct CharacteristicsExtension1(u8);
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Default)]
 pub struct CharacteristicsExtension2(u8);
 
+raw_newtype!(Characteristics(u64));
+raw_newtype!(CharacteristicsExtension1(u8));
+raw_newtype!(CharacteristicsExtension2(u8));
+
 /// BIOS Revision assembled from *System BIOS Major Release* and *System BIOS Minor Release* fields
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Default)]
 pub struct BiosRevision {
@@ -77,133 +84,213 @@ pub struct RomSize {
     pub extended: Option<u16>,
 }
 
+/// Formatted section of the BIOS Information structure, per SMBIOS version
+///
+/// Every field past the characteristics is optional: the extension bytes were *zero or more
+/// bytes* up to SMBIOS 2.3, and tables of any version may end the formatted section early.
+mod layout {
+    use super::Field;
+
+    pub const VENDOR: Field<u8> = Field::new(0x04, 2, 0);
+    pub const BIOS_VERSION: Field<u8> = Field::new(0x05, 2, 0);
+    pub const STARTING_ADDRESS_SEGMENT: Field<u16> = Field::new(0x06, 2, 0);
+    pub const RELEASE_DATE: Field<u8> = Field::new(0x08, 2, 0);
+    pub const ROM_SIZE: Field<u8> = Field::new(0x09, 2, 0);
+    pub const CHARACTERISTICS: Field<u64> = Field::new(0x0A, 2, 0);
+    pub const CHARACTERISTICS_EXTENSION_1: Field<u8> = Field::new(0x12, 2, 1);
+    pub const CHARACTERISTICS_EXTENSION_2: Field<u8> = Field::new(0x13, 2, 3);
+    pub const BIOS_REVISION: Field<[u8; 2]> = Field::new(0x14, 2, 4);
+    pub const FIRMWARE_REVISION: Field<[u8; 2]> = Field::new(0x16, 2, 4);
+    pub const EXTENDED_ROM_SIZE: Field<u16> = Field::new(0x18, 3, 1);
+}
+
 impl<'buffer> Bios<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<Bios<'buffer>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct BiosPacked_3_1 {
-            vendor: u8,
-            bios_version: u8,
-            bios_starting_address_segment: u16,
-            bios_release_date: u8,
-            bios_rom_size: u8,
-            bios_characteristics: u64,
-            bios_characteristics_exttension_1: u8,
-            bios_characteristics_exttension_2: u8,
-            system_bios_major_release: u8,
-            system_bios_minor_release: u8,
-            embedded_controller_firmware_major_release: u8,
-            embedded_controller_firmware_minor_release: u8,
-            extended_bios_rom_size: u16,
-        }
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<Bios<'buffer>, MalformedStructureError> {
+        use self::layout::*;
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct BiosPacked_2_4 {
-            vendor: u8,
-            bios_version: u8,
-            bios_starting_address_segment: u16,
-            bios_release_date: u8,
-            bios_rom_size: u8,
-            bios_characteristics: u64,
-            bios_characteristics_exttension_1: u8,
-            bios_characteristics_exttension_2: u8,
-            system_bios_major_release: u8,
-            system_bios_minor_release: u8,
-            embedded_controller_firmware_major_release: u8,
-            embedded_controller_firmware_minor_release: u8,
-        }
+        Ok(Bios {
+            handle: structure.handle,
+            vendor: VENDOR.string(&structure)?,
+            bios_version: BIOS_VERSION.string(&structure)?,
+            bios_starting_address_segment: STARTING_ADDRESS_SEGMENT.get(&structure)?,
+            bios_release_date: RELEASE_DATE.string(&structure)?,
+            rom_size: RomSize {
+                basic: ROM_SIZE.get(&structure)?,
+                extended: EXTENDED_ROM_SIZE.get_since(&structure),
+            },
+            bios_characteristics: Characteristics(CHARACTERISTICS.get(&structure)?),
+            bios_characteristics_exttension_1: CHARACTERISTICS_EXTENSION_1
+                .get_since(&structure)
+                .map(CharacteristicsExtension1),
+            bios_characteristics_exttension_2: CHARACTERISTICS_EXTENSION_2
+                .get_since(&structure)
+                .map(CharacteristicsExtension2),
+            // FFh in either byte tells that the field is not supported
+            bios_revision: BIOS_REVISION
+                .get_since(&structure)
+                .filter(|release| !release.contains(&0xFF))
+                .map(|[major, minor]| BiosRevision { major, minor }),
+            firmware_revision: FIRMWARE_REVISION
+                .get_since(&structure)
+                .filter(|release| !release.contains(&0xFF))
+                .map(|[major, minor]| FirmwareRevision { major, minor }),
+        })
+    }
+}
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct BiosPacked_2_0 {
-            vendor: u8,
-            bios_version: u8,
-            bios_starting_address_segment: u16,
-            bios_release_date: u8,
-            bios_rom_size: u8,
-            bios_characteristics: u64,
+/// A firmware bug of the BIOS Information structure that can be worked around while decoding
+///
+/// Quirks are only applied to the firmware listed in a registry of [QuirkEntry], see
+/// [Structures::with_bios_quirks](crate::Structures::with_bios_quirks).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Quirk {
+    /// The table declares an earlier SMBIOS version than the structure layout, e.g. characteristics
+    /// extension bytes in an SMBIOS 2.0 table: the fields of later versions are read whenever the
+    /// formatted section is long enough to hold them
+    IgnoreVersion,
+    /// The Extended BIOS ROM Size is in megabytes, whatever its unit bits tell
+    ExtendedRomSizeInMegabytes,
+    /// The BIOS ROM Size is not FFh although the Extended BIOS ROM Size is given, which is then
+    /// used instead
+    PreferExtendedRomSize,
+}
+
+impl Quirk {
+    pub(crate) const ALL: [Quirk; 3] = [
+        Quirk::IgnoreVersion,
+        Quirk::ExtendedRomSizeInMegabytes,
+        Quirk::PreferExtendedRomSize,
+    ];
+}
+
+impl fmt::Display for Quirk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quirk::IgnoreVersion => write!(f, "fields read regardless of the SMBIOS version"),
+            Quirk::ExtendedRomSizeInMegabytes => write!(f, "extended ROM size read in megabytes"),
+            Quirk::PreferExtendedRomSize => write!(f, "extended ROM size preferred to the basic size"),
         }
+    }
+}
+
+/// The firmware a [Quirk] applies to
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct QuirkEntry {
+    /// BIOS Vendor, compared with the vendor string without its surrounding whitespace
+    pub vendor: &'static str,
+    /// Prefix of the BIOS Version string, `None` for every version of the vendor
+    pub version: Option<&'static str>,
+    pub quirk: Quirk,
+}
+
+impl QuirkEntry {
+    /// Whether the entry applies to the firmware that produced `bios`
+    pub fn matches(&self, bios: &Bios<'_>) -> bool {
+        bios.vendor.trim() == self.vendor
+            && self
+                .version
+                .map_or(true, |version| bios.bios_version.trim().starts_with(version))
+    }
+}
+
+impl<'buffer> Bios<'buffer> {
+    /// Whether PCI is supported
+    pub fn supports_pci(&self) -> bool {
+        self.characteristic(7)
+    }
+
+    /// Whether Plug and Play is supported
+    pub fn supports_pnp(&self) -> bool {
+        self.characteristic(9)
+    }
+
+    /// Whether the BIOS is upgradeable (Flash)
+    pub fn is_upgradeable(&self) -> bool {
+        self.characteristic(11)
+    }
+
+    /// Whether booting from CD is supported
+    pub fn supports_boot_from_cd(&self) -> bool {
+        self.characteristic(15)
+    }
+
+    /// Whether ACPI is supported, from the first extension byte
+    pub fn supports_acpi(&self) -> bool {
+        self.bios_characteristics_exttension_1
+            .map_or(false, |extension| extension.0 & 1 << 0 != 0)
+    }
+
+    /// Whether USB legacy is supported, from the first extension byte
+    pub fn supports_usb_legacy(&self) -> bool {
+        self.bios_characteristics_exttension_1
+            .map_or(false, |extension| extension.0 & 1 << 1 != 0)
+    }
+
+    /// Whether UEFI is supported, from the second extension byte
+    pub fn supports_uefi(&self) -> bool {
+        self.bios_characteristics_exttension_2
+            .map_or(false, |extension| extension.0 & 1 << 3 != 0)
+    }
+
+    /// Whether the table describes a virtual machine, from the second extension byte. When it
+    /// does not, nothing can be inferred about the virtuality of the system.
+    pub fn is_virtual_machine(&self) -> bool {
+        self.bios_characteristics_exttension_2
+            .map_or(false, |extension| extension.0 & 1 << 4 != 0)
+    }
+
+    /// The firmware version for fleet-wide comparisons, reconciled from the first dotted numeric
+    /// version of the free-form BIOS Version string, e.g. `2.54` in `"U30 v2.54 (02/09/2022)"`,
+    /// and the System BIOS Major and Minor Release fields
+    ///
+    /// The string is preferred when it holds a version, being the one vendors show, and the
+    /// release fields are used otherwise. `None` if neither source holds a version.
+    pub fn version(&self) -> Option<BiosVersion> {
+        let revision = self.bios_revision.map(DottedVersion::from);
+        let (version, source) = match (DottedVersion::parse(self.bios_version), revision) {
+            (Some(version), Some(revision)) if version.parts()[..2] == revision.parts()[..] => {
+                (version, VersionSource::Both)
+            }
+            (Some(version), _) => (version, VersionSource::BiosVersion),
+            (None, Some(revision)) => (revision, VersionSource::BiosRevision),
+            (None, None) => return None,
+        };
+        Some(BiosVersion { version, source })
+    }
+
+    /// Whether `bit` of the BIOS Characteristics is set, and the characteristics are supported
+    fn characteristic(&self, bit: u8) -> bool {
+        let characteristics = self.bios_characteristics.0;
+        characteristics & 1 << 3 == 0 && characteristics & 1 << bit != 0
+    }
+
+    /// Whether an entry of `registry` lists `quirk` for the firmware that produced the structure
+    pub fn has_quirk(&self, registry: &[QuirkEntry], quirk: Quirk) -> bool {
+        registry.iter().any(|entry| entry.quirk == quirk && entry.matches(self))
+    }
 
-        match structure.version {
-            v if v >= (3, 1).into() => {
-                let_as_struct!(packed, BiosPacked_3_1, structure.data);
-                Ok(Bios {
-                    handle: structure.handle,
-                    vendor: structure.find_string(packed.vendor)?,
-                    bios_version: structure.find_string(packed.bios_version)?,
-                    bios_starting_address_segment: packed.bios_starting_address_segment,
-                    bios_release_date: structure.find_string(packed.bios_release_date)?,
-                    rom_size: RomSize {
-                        basic: packed.bios_rom_size,
-                        extended: Some(packed.extended_bios_rom_size),
-                    },
-                    bios_characteristics: Characteristics(packed.bios_characteristics),
-                    bios_characteristics_exttension_1: Some(CharacteristicsExtension1(
-                        packed.bios_characteristics_exttension_1,
-                    )),
-                    bios_characteristics_exttension_2: Some(CharacteristicsExtension2(
-                        packed.bios_characteristics_exttension_2,
-                    )),
-                    bios_revision: Some(BiosRevision {
-                        major: packed.system_bios_major_release,
-                        minor: packed.system_bios_minor_release,
-                    }),
-                    firmware_revision: Some(FirmwareRevision {
-                        major: packed.embedded_controller_firmware_major_release,
-                        minor: packed.embedded_controller_firmware_minor_release,
-                    }),
-                })
+    /// Work around the quirks of `registry` that apply to the structure decoded from `structure`
+    pub(crate) fn with_quirks(self, structure: &RawStructure<'buffer>, registry: &[QuirkEntry]) -> Self {
+        let mut bios = self;
+        if bios.has_quirk(registry, Quirk::IgnoreVersion) {
+            let latest = RawStructure {
+                version: SmbiosVersion::new(u8::MAX, u8::MAX),
+                ..structure.clone()
+            };
+            if let Ok(decoded) = Bios::from_raw(latest) {
+                bios = decoded;
             }
-            v if v >= (2, 4).into() => {
-                let_as_struct!(packed, BiosPacked_2_4, structure.data);
-                Ok(Bios {
-                    handle: structure.handle,
-                    vendor: structure.find_string(packed.vendor)?,
-                    bios_version: structure.find_string(packed.bios_version)?,
-                    bios_starting_address_segment: packed.bios_starting_address_segment,
-                    bios_release_date: structure.find_string(packed.bios_release_date)?,
-                    rom_size: RomSize {
-                        basic: packed.bios_rom_size,
-                        extended: None,
-                    },
-                    bios_characteristics: Characteristics(packed.bios_characteristics),
-                    bios_characteristics_exttension_1: Some(CharacteristicsExtension1(
-                        packed.bios_characteristics_exttension_1,
-                    )),
-                    bios_characteristics_exttension_2: Some(CharacteristicsExtension2(
-                        packed.bios_characteristics_exttension_2,
-                    )),
-                    bios_revision: Some(BiosRevision {
-                        major: packed.system_bios_major_release,
-                        minor: packed.system_bios_minor_release,
-                    }),
-                    firmware_revision: Some(FirmwareRevision {
-                        major: packed.embedded_controller_firmware_major_release,
-                        minor: packed.embedded_controller_firmware_minor_release,
-                    }),
-                })
+        }
+        if let Some(extended) = bios.rom_size.extended {
+            if bios.has_quirk(registry, Quirk::ExtendedRomSizeInMegabytes) {
+                bios.rom_size.extended = Some(extended & 0x3FFF);
             }
-            _ => {
-                let_as_struct!(packed, BiosPacked_2_0, structure.data);
-                Ok(Bios {
-                    handle: structure.handle,
-                    vendor: structure.find_string(packed.vendor)?,
-                    bios_version: structure.find_string(packed.bios_version)?,
-                    bios_starting_address_segment: packed.bios_starting_address_segment,
-                    bios_release_date: structure.find_string(packed.bios_release_date)?,
-                    rom_size: RomSize {
-                        basic: packed.bios_rom_size,
-                        extended: None,
-                    },
-                    bios_characteristics: Characteristics(packed.bios_characteristics),
-                    bios_characteristics_exttension_1: None,
-                    bios_characteristics_exttension_2: None,
-                    bios_revision: None,
-                    firmware_revision: None,
-                })
+            if bios.has_quirk(registry, Quirk::PreferExtendedRomSize) {
+                bios.rom_size.basic = 0xFF;
             }
         }
+        bios
     }
 }
 
@@ -315,24 +402,135 @@ impl<'a> BitField<'a> for CharacteristicsExtension2 {
     );
 }
 
+impl RomSize {
+    /// Size of the BIOS ROM in bytes, `None` if the basic size is FFh and the extended size is
+    /// missing or uses a reserved unit
+    pub fn bytes(&self) -> Option<u64> {
+        if self.basic != 0xFF {
+            Some((self.basic as u64 + 1) * (64 << 10))
+        } else {
+            let extended = self.extended?;
+            let size = (extended & 0x3fff) as u64;
+            match (extended >> 14) & 0b11 {
+                0b00 => Some(size << 20),
+                0b01 => Some(size << 30),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Sizes that cannot be decoded are reported as 16 MB, the lower bound implied by a basic size of
+/// FFh
 impl From<RomSize> for u64 {
     fn from(rom_size: RomSize) -> Self {
-        if rom_size.basic != 0xFF {
-            (rom_size.basic + 1) as u64 * (64 << 10)
-        } else if let Some(extended) = rom_size.extended {
-            let unit = (extended >> 14) & 0b11;
-            let size = (extended & 0x3fff) as u64;
-            match unit {
-                0b00 => size << 20,
-                0b01 => size << 30,
-                _ => unimplemented!(),
+        rom_size.bytes().unwrap_or(16 << 20)
+    }
+}
+
+/// A firmware version reconciled from both sources of the BIOS Information structure, see
+/// [Bios::version]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BiosVersion {
+    pub version: DottedVersion,
+    /// Which fields the version was found in
+    pub source: VersionSource,
+}
+
+/// The fields a [BiosVersion] was found in
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum VersionSource {
+    /// Only the BIOS Version string holds a version, or its major and minor numbers differ from
+    /// the System BIOS Release fields
+    BiosVersion,
+    /// The BIOS Version string holds no version, the System BIOS Release fields are used
+    BiosRevision,
+    /// The BIOS Version string holds a version that starts with the System BIOS Release fields
+    Both,
+}
+
+/// A numeric version of two to four dot-separated parts, ordered part by part
+///
+/// Versions with fewer parts sort before the longer versions they start, `2.8` < `2.8.0`.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DottedVersion {
+    parts: [u32; 4],
+    len: u8,
+}
+
+impl DottedVersion {
+    /// The first dotted numeric version found in `s`, e.g. `1.49` in `"N2HET71W (1.49 )"`
+    ///
+    /// Digits that are not followed by a dot are skipped, so that `"A17"` holds no version. Parts
+    /// past the fourth are ignored.
+    pub fn parse(s: &str) -> Option<DottedVersion> {
+        let bytes = s.as_bytes();
+        let mut start = 0;
+        while start < bytes.len() {
+            if !bytes[start].is_ascii_digit() || (start > 0 && bytes[start - 1].is_ascii_digit()) {
+                start += 1;
+                continue;
             }
-        } else {
-            unreachable!();
+            let mut version = DottedVersion::default();
+            // End of the digits read so far
+            let mut end = start;
+            for part in s[start..].split('.') {
+                let digits = part.bytes().take_while(u8::is_ascii_digit).count();
+                let value = match part[..digits].parse() {
+                    Ok(value) if version.len < 4 => value,
+                    _ => break,
+                };
+                version.parts[version.len as usize] = value;
+                version.len += 1;
+                end += digits;
+                if digits < part.len() {
+                    break;
+                }
+                end += 1;
+            }
+            if version.len >= 2 {
+                return Some(version);
+            }
+            start = end.max(start + 1);
+        }
+        None
+    }
+
+    /// The parts of the version, major first
+    pub fn parts(&self) -> &[u32] {
+        &self.parts[..self.len as usize]
+    }
+
+    pub fn major(&self) -> u32 {
+        self.parts[0]
+    }
+
+    pub fn minor(&self) -> u32 {
+        self.parts[1]
+    }
+}
+
+impl From<BiosRevision> for DottedVersion {
+    fn from(revision: BiosRevision) -> Self {
+        DottedVersion {
+            parts: [revision.major.into(), revision.minor.into(), 0, 0],
+            len: 2,
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Display for DottedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.parts[0])?;
+        for part in &self.parts()[1..] {
+            write!(f, ".{}", part)?;
         }
+        Ok(())
     }
 }
 
+#[cfg(feature = "strings")]
 impl fmt::Display for BiosRevision {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.major == 0xFF && self.minor == 0xFF {
@@ -343,6 +541,7 @@ impl fmt::Display for BiosRevision {
     }
 }
 
+#[cfg(feature = "strings")]
 impl fmt::Display for FirmwareRevision {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.major == 0xFF && self.minor == 0xFF {
@@ -379,31 +578,37 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result, "Positions");
 
-        let sample = vec!["ISA is supported", "EISA is supported"];
-        let qword = 0b0101_0000;
-        let iter = Characteristics(qword).significants();
-        let result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            sample, result,
-            "Significant values, default formatting ({:064b})",
-            qword
-        );
-        let result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            sample, result,
-            "Significant values, alternative formatting ({:064b})",
-            qword
-        );
+        #[cfg(feature = "strings")]
+        {
+            let sample = vec!["ISA is supported", "EISA is supported"];
+            let qword = 0b0101_0000;
+            let iter = Characteristics(qword).significants();
+            let result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                sample, result,
+                "Significant values, default formatting ({:064b})",
+                qword
+            );
+            let result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                sample, result,
+                "Significant values, alternative formatting ({:064b})",
+                qword
+            );
+        }
 
-        let sample = vec![
-            ("Reserved for BIOS vendor", 32..=47),
-            ("Reserved for system vendor", 48..=63),
-        ];
-        let result = Characteristics(0)
-            .reserved()
-            .map(|v| (v.description, v.range))
-            .collect::<Vec<_>>();
-        assert_eq!(sample, result, "Reserved fields");
+        #[cfg(feature = "strings")]
+        {
+            let sample = vec![
+                ("Reserved for BIOS vendor", 32..=47),
+                ("Reserved for system vendor", 48..=63),
+            ];
+            let result = Characteristics(0)
+                .reserved()
+                .map(|v| (v.description, v.range))
+                .collect::<Vec<_>>();
+            assert_eq!(sample, result, "Reserved fields");
+        }
     }
     #[test]
     fn characteristics_extension1() {
@@ -415,22 +620,25 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result, "Positions");
 
-        let dflt_sample = vec!["ACPI is supported", "IEEE 1394 boot is supported"];
-        let alt_sample = vec!["ACPI is supported", "1394 boot is supported"];
-        let byte = 0b0100_0001;
-        let iter = CharacteristicsExtension1(byte).significants();
-        let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            dflt_sample, dflt_result,
-            "Significant values, default formatting ({:08b})",
-            byte
-        );
-        let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            alt_sample, alt_result,
-            "Significant values, alternative formatting ({:08b})",
-            byte
-        );
+        #[cfg(feature = "strings")]
+        {
+            let dflt_sample = vec!["ACPI is supported", "IEEE 1394 boot is supported"];
+            let alt_sample = vec!["ACPI is supported", "1394 boot is supported"];
+            let byte = 0b0100_0001;
+            let iter = CharacteristicsExtension1(byte).significants();
+            let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                dflt_sample, dflt_result,
+                "Significant values, default formatting ({:08b})",
+                byte
+            );
+            let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                alt_sample, alt_result,
+                "Significant values, alternative formatting ({:08b})",
+                byte
+            );
+        }
 
         let result = CharacteristicsExtension1(0).reserved().count();
         assert_eq!(0, result, "Reserved fields");
@@ -445,29 +653,35 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result, "Positions");
 
-        let short_sample = vec!["UEFI is supported", "System is a virtual machine"];
-        let long_sample = vec!["UEFI is supported","SMBIOS table describes a virtual machine. (If this bit is not set, no inference can be made about the virtuality of the system.)"];
-        let byte = 0b0001_1000;
-        let iter = CharacteristicsExtension2(byte).significants();
-        let result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            short_sample, result,
-            "Significant values, default formatting ({:08b})",
-            byte
-        );
-        let result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            long_sample, result,
-            "Significant values, alternative formatting ({:08b})",
-            byte
-        );
+        #[cfg(feature = "strings")]
+        {
+            let short_sample = vec!["UEFI is supported", "System is a virtual machine"];
+            let long_sample = vec!["UEFI is supported","SMBIOS table describes a virtual machine. (If this bit is not set, no inference can be made about the virtuality of the system.)"];
+            let byte = 0b0001_1000;
+            let iter = CharacteristicsExtension2(byte).significants();
+            let result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                short_sample, result,
+                "Significant values, default formatting ({:08b})",
+                byte
+            );
+            let result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                long_sample, result,
+                "Significant values, alternative formatting ({:08b})",
+                byte
+            );
+        }
 
-        let sample = vec![("Reserved for future assignment", 5..=7)];
-        let result = CharacteristicsExtension2(0)
-            .reserved()
-            .map(|v| (v.description, v.range))
-            .collect::<Vec<_>>();
-        assert_eq!(sample, result, "Reserved fields");
+        #[cfg(feature = "strings")]
+        {
+            let sample = vec![("Reserved for future assignment", 5..=7)];
+            let result = CharacteristicsExtension2(0)
+                .reserved()
+                .map(|v| (v.description, v.range))
+                .collect::<Vec<_>>();
+            assert_eq!(sample, result, "Reserved fields");
+        }
     }
     #[test]
     fn rom_size() {
@@ -490,11 +704,18 @@ mod tests {
             })
             .collect();
         assert_eq!(sample, result, "ROM Size");
+
+        let undecodable = [(0xFF, None), (0xFF, Some(0x8010)), (0xFF, Some(0xC010))];
+        for &(basic, extended) in undecodable.iter() {
+            let rom_size = RomSize { basic, extended };
+            assert_eq!(None, rom_size.bytes(), "{:?}", rom_size);
+            assert_eq!(16 << 20, u64::from(rom_size), "{:?}", rom_size);
+        }
     }
     #[test]
     fn dmi_bin_full_bios_structure() {
         let bios_sample = Bios {
-            handle: 0,
+            handle: Handle(0),
             vendor: "Dell Inc.",
             bios_version: "2.8.2",
             bios_starting_address_segment: 0xF000,
@@ -537,10 +758,7 @@ mod tests {
                 [Position(0), Position(1), Position(2), Position(3)].iter().collect(),
             )),
             bios_revision: Some(BiosRevision { major: 2, minor: 8 }),
-            firmware_revision: Some(FirmwareRevision {
-                major: 0xFF,
-                minor: 0xFF,
-            }),
+            firmware_revision: None,
         };
         let bios_result = ENTRY_POINT
             .structures(&DMIDECODE_BIN[(ENTRY_POINT.smbios_address() as usize)..])
@@ -555,6 +773,7 @@ mod tests {
         assert_eq!(bios_sample, bios_result, "Full BIOS Struct");
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn dmi_bin_all_characteristics() {
         let all_characteristics_sample = vec![
@@ -603,10 +822,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn characteristic_helpers() {
+        let bios = ENTRY_POINT
+            .structures(&DMIDECODE_BIN[(ENTRY_POINT.smbios_address() as usize)..])
+            .find_map(|s| {
+                if let Ok(crate::Structure::Bios(bios)) = s {
+                    Some(bios)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        let flags = [
+            bios.supports_pci(),
+            bios.supports_pnp(),
+            bios.is_upgradeable(),
+            bios.supports_boot_from_cd(),
+            bios.supports_acpi(),
+            bios.supports_usb_legacy(),
+            bios.supports_uefi(),
+            bios.is_virtual_machine(),
+        ];
+        assert_eq!([true, true, true, true, true, true, true, false], flags);
+
+        // "BIOS Characteristics are not supported" voids the other bits
+        let unsupported = Bios {
+            bios_characteristics: Characteristics(1 << 3 | 1 << 7),
+            ..bios
+        };
+        assert!(!unsupported.supports_pci());
+        let legacy = Bios {
+            bios_characteristics_exttension_1: None,
+            bios_characteristics_exttension_2: None,
+            ..bios
+        };
+        assert!(legacy.supports_pci());
+        assert!(!legacy.supports_usb_legacy() && !legacy.supports_uefi());
+        let virtual_machine = Bios {
+            bios_characteristics_exttension_2: Some(CharacteristicsExtension2(0x18)),
+            ..bios
+        };
+        assert!(virtual_machine.is_virtual_machine() && virtual_machine.supports_uefi());
+    }
+
     #[test]
     fn dmi_bin_revisions() {
-        let bios_revision = "2.8";
-        let firmware_revision = "N/A";
         let bios_result = ENTRY_POINT
             .structures(&DMIDECODE_BIN[(ENTRY_POINT.smbios_address() as usize)..])
             .find_map(|s| {
@@ -617,16 +878,62 @@ mod tests {
                 }
             })
             .unwrap();
-        assert_eq!(
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "2.8",
+                format!("{}", bios_result.bios_revision.unwrap()),
+                "BIOS Revision"
+            );
+        }
+        assert_eq!(None, bios_result.firmware_revision, "Firmware Revision");
+        let version = bios_result.version().unwrap();
+        assert_eq!(VersionSource::Both, version.source);
+        #[cfg(feature = "strings")]
+        assert_eq!("2.8.2", format!("{}", version.version));
+    }
+
+    #[test]
+    fn version() {
+        let parse = |s| DottedVersion::parse(s).map(|version| version.parts().to_vec());
+        assert_eq!(Some(vec![2, 8, 2]), parse("2.8.2"));
+        assert_eq!(Some(vec![2, 54]), parse("U30 v2.54 (02/09/2022)"));
+        assert_eq!(Some(vec![1, 49]), parse("N2HET71W (1.49 )"));
+        assert_eq!(Some(vec![6, 0]), parse("6.00 PG"));
+        assert_eq!(Some(vec![1, 2, 3, 4]), parse("1.2.3.4.5"));
+        assert_eq!(Some(vec![3, 1]), parse("99999999999.1 3.1"));
+        assert_eq!(None, parse("A17"));
+        assert_eq!(None, parse("1..2"));
+        assert_eq!(None, parse(""));
+
+        let older = DottedVersion::parse("2.9").unwrap();
+        let newer = DottedVersion::parse("2.10").unwrap();
+        assert!(older < newer);
+        assert!(DottedVersion::parse("2.8").unwrap() < DottedVersion::parse("2.8.0").unwrap());
+        assert_eq!((2, 10), (newer.major(), newer.minor()));
+
+        let bios = |bios_version, bios_revision| Bios {
+            bios_version,
             bios_revision,
-            format!("{}", bios_result.bios_revision.unwrap()),
-            "BIOS Revision"
+            ..Default::default()
+        };
+        let revision = Some(BiosRevision { major: 5, minor: 14 });
+        let version = bios("3.4", revision).version().unwrap();
+        assert_eq!(
+            (VersionSource::BiosVersion, vec![3, 4]),
+            (version.source, version.version.parts().to_vec())
+        );
+        let version = bios("F20", revision).version().unwrap();
+        assert_eq!(
+            (VersionSource::BiosRevision, vec![5, 14]),
+            (version.source, version.version.parts().to_vec())
         );
+        let version = bios("5.14.1", revision).version().unwrap();
         assert_eq!(
-            firmware_revision,
-            format!("{}", bios_result.firmware_revision.unwrap()),
-            "Firmware Revision"
+            (VersionSource::Both, vec![5, 14, 1]),
+            (version.source, version.version.parts().to_vec())
         );
+        assert_eq!(None, bios("F20", None).version());
     }
 
     #[test]
@@ -644,4 +951,127 @@ mod tests {
             .unwrap();
         assert_eq!(size, bios_result.rom_size.into(), "ROM BIOS size");
     }
+
+    #[test]
+    fn optional_fields_by_length() {
+        const DATA: &[u8] = &[
+            0x01, 0x02, 0x00, 0xE8, 0x03, 0x0F, // Vendor, Version, Segment, Release Date, ROM Size
+            0x80, 0x98, 0x8B, 0x3F, 0x01, 0x00, 0x11, 0x00, // Characteristics
+            0x03, 0x0D, // Characteristics Extension Bytes
+            0x02, 0x08, 0xFF, 0xFF, // System BIOS and Embedded Controller Firmware Release
+            0x00, 0x00, // Extended ROM Size
+        ];
+        let decode = |version: (usize, usize), length: usize| {
+            Bios::from_raw(RawStructure {
+                version: version.into(),
+                info: crate::InfoType::Bios,
+                length: length as u8,
+                handle: Handle(0x0000),
+                data: &DATA[..length - 4],
+                strings: b"Vendor\0v1.0\0Jan 2020\0\0",
+                ..Default::default()
+            })
+        };
+
+        let short = decode((2, 0), 0x12).unwrap();
+        assert_eq!(None, short.bios_characteristics_exttension_1);
+        assert_eq!(None, short.bios_characteristics_exttension_2);
+        assert_eq!(None, short.bios_revision);
+
+        // A 2.4 table may still end after the first extension byte
+        let truncated = decode((2, 4), 0x13).unwrap();
+        assert_eq!(
+            Some(CharacteristicsExtension1(0x03)),
+            truncated.bios_characteristics_exttension_1
+        );
+        assert_eq!(None, truncated.bios_characteristics_exttension_2);
+        assert_eq!(None, truncated.bios_revision);
+
+        let older = decode((2, 0), 0x18).unwrap();
+        assert_eq!(None, older.bios_characteristics_exttension_1);
+        assert_eq!(None, older.bios_revision);
+
+        let full = decode((3, 1), 0x1A).unwrap();
+        assert_eq!(
+            Some(CharacteristicsExtension2(0x0D)),
+            full.bios_characteristics_exttension_2
+        );
+        assert_eq!(Some(BiosRevision { major: 2, minor: 8 }), full.bios_revision);
+        assert_eq!(None, full.firmware_revision, "FFh means not supported");
+        assert_eq!(Some(0), full.rom_size.extended);
+
+        // Characteristics are mandatory
+        assert!(decode((2, 0), 0x10).is_err());
+    }
+
+    #[test]
+    fn quirks() {
+        use crate::{testing::StructureBuilder, InfoType, Structures};
+
+        const QUIRKS: &[QuirkEntry] = &[
+            QuirkEntry {
+                vendor: "Vendor",
+                version: Some("1."),
+                quirk: Quirk::IgnoreVersion,
+            },
+            QuirkEntry {
+                vendor: "Vendor",
+                version: None,
+                quirk: Quirk::ExtendedRomSizeInMegabytes,
+            },
+            QuirkEntry {
+                vendor: "Vendor",
+                version: None,
+                quirk: Quirk::PreferExtendedRomSize,
+            },
+        ];
+        // A 3.1 layout in a 2.0 table, its extended ROM size of 64 with the GB unit and its basic
+        // ROM size not set to FFh
+        let builder = |version: &str| {
+            StructureBuilder::new(InfoType::Bios)
+                .version((2, 0).into())
+                .string("Vendor ")
+                .string(version)
+                .word(0xE800)
+                .string("01/01/2020")
+                .byte(0x3F)
+                .qword(0x0000_0000_0001_0000)
+                .byte(0x03)
+                .byte(0x0D)
+                .word(0x0802)
+                .word(0xFFFF)
+                .word(0x4040)
+        };
+        let decode = |table: &[u8]| {
+            let structures = Structures::new(table, (2, 0).into(), table.len() as u32).with_bios_quirks(QUIRKS);
+            match structures.find_handle(0x0000) {
+                Some(Ok(crate::Structure::Bios(bios))) => (
+                    bios.bios_revision,
+                    bios.rom_size,
+                    bios.has_quirk(QUIRKS, Quirk::IgnoreVersion),
+                    bios.bios_characteristics_exttension_2,
+                ),
+                other => panic!("{:?}", other),
+            }
+        };
+
+        let early = builder("1.02");
+        let plain = match early.decode() {
+            Ok(crate::Structure::Bios(bios)) => bios,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(None, plain.bios_revision);
+        assert_eq!(Some(4 << 20), plain.rom_size.bytes());
+
+        let (revision, rom_size, ignore_version, extension_2) = decode(&early.to_bytes());
+        assert!(ignore_version);
+        assert_eq!(Some(CharacteristicsExtension2(0x0D)), extension_2);
+        assert_eq!(Some(BiosRevision { major: 2, minor: 8 }), revision);
+        assert_eq!(Some(64 << 20), rom_size.bytes());
+
+        let (revision, rom_size, ignore_version, _) = decode(&builder("2.00").to_bytes());
+        assert!(!ignore_version);
+        assert_eq!(None, revision);
+        assert_eq!(plain.rom_size, rom_size, "No extended ROM size to prefer");
+    }
 }
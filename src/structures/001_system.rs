@@ -5,7 +5,10 @@
 //! associated with a single system instance and contains one and only one System Information
 //! (Type 1) structure.
 
-use crate::{MalformedStructureError, RawStructure};
+#[cfg(feature = "strings")]
+use core::fmt;
+
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
 /// The wakeup type defined in the SMBIOS specification.
 #[allow(non_camel_case_types)]
@@ -40,13 +43,31 @@ impl From<u8> for WakeupType {
     }
 }
 
+#[cfg(feature = "strings")]
+impl fmt::Display for WakeupType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WakeupType::Reserved => write!(f, "Reserved"),
+            WakeupType::Other => write!(f, "Other"),
+            WakeupType::Unknown => write!(f, "Unknown"),
+            WakeupType::APM_Timer => write!(f, "APM Timer"),
+            WakeupType::Modem_Ring => write!(f, "Modem Ring"),
+            WakeupType::LAN_Remote => write!(f, "LAN Remote"),
+            WakeupType::Power_Switch => write!(f, "Power Switch"),
+            WakeupType::PCI_PME => write!(f, "PCI PME#"),
+            WakeupType::AC_Power_Restored => write!(f, "AC Power Restored"),
+            WakeupType::Undefined(t) => write!(f, "Undefined: {}", t),
+        }
+    }
+}
+
 /// The `System` table defined in the SMBIOS specification.
 ///
 /// Optional fields will only be set if the version of the parsed SMBIOS table
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct System<'buffer> {
-    pub handle: u16,
+    pub handle: Handle,
     pub manufacturer: &'buffer str,
     pub product: &'buffer str,
     pub version: &'buffer str,
@@ -57,75 +78,117 @@ pub struct System<'buffer> {
     pub family: Option<&'buffer str>,
 }
 
+/// Formatted section of the System Information structure, per SMBIOS version
+mod layout {
+    use super::Field;
+
+    pub const MANUFACTURER: Field<u8> = Field::new(0x04, 2, 0);
+    pub const PRODUCT: Field<u8> = Field::new(0x05, 2, 0);
+    pub const VERSION: Field<u8> = Field::new(0x06, 2, 0);
+    pub const SERIAL: Field<u8> = Field::new(0x07, 2, 0);
+    pub const UUID: Field<[u8; 16]> = Field::new(0x08, 2, 1);
+    pub const WAKEUP: Field<u8> = Field::new(0x18, 2, 1);
+    pub const SKU: Field<u8> = Field::new(0x19, 2, 4);
+    pub const FAMILY: Field<u8> = Field::new(0x1A, 2, 4);
+}
+
 impl<'buffer> System<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<System<'buffer>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct SystemPacked_2_0 {
-            manufacturer: u8,
-            product: u8,
-            version: u8,
-            serial: u8,
-        }
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<System<'buffer>, MalformedStructureError> {
+        use self::layout::*;
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct SystemPacked_2_1 {
-            v2_0: SystemPacked_2_0,
-            uuid: [u8; 16],
-            wakeup: u8,
-        }
+        Ok(System {
+            handle: structure.handle,
+            manufacturer: MANUFACTURER.string(&structure)?,
+            product: PRODUCT.string(&structure)?,
+            version: VERSION.string(&structure)?,
+            serial: SERIAL.string(&structure)?,
+            uuid: UUID.get_since(&structure),
+            wakeup: WAKEUP.get_since(&structure).map(WakeupType::from),
+            sku: SKU.string_since(&structure)?,
+            family: FAMILY.string_since(&structure)?,
+        })
+    }
+}
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct SystemPacked_2_4 {
-            v2_1: SystemPacked_2_1,
-            sku: u8,
-            family: u8,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InfoType;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
 
-        if structure.version < (2, 1).into() {
-            let_as_struct!(packed, SystemPacked_2_0, structure.data);
-
-            Ok(System {
-                handle: structure.handle,
-                manufacturer: structure.find_string(packed.manufacturer)?,
-                product: structure.find_string(packed.product)?,
-                version: structure.find_string(packed.version)?,
-                serial: structure.find_string(packed.serial)?,
-                uuid: None,
-                wakeup: None,
-                sku: None,
-                family: None,
-            })
-        } else if structure.version < (2, 4).into() {
-            let_as_struct!(packed, SystemPacked_2_1, structure.data);
-
-            Ok(System {
-                handle: structure.handle,
-                manufacturer: structure.find_string(packed.v2_0.manufacturer)?,
-                product: structure.find_string(packed.v2_0.product)?,
-                version: structure.find_string(packed.v2_0.version)?,
-                serial: structure.find_string(packed.v2_0.serial)?,
-                uuid: Some(packed.uuid),
-                wakeup: Some(packed.wakeup.into()),
-                sku: None,
-                family: None,
-            })
-        } else {
-            let_as_struct!(packed, SystemPacked_2_4, structure.data);
-
-            Ok(System {
-                handle: structure.handle,
-                manufacturer: structure.find_string(packed.v2_1.v2_0.manufacturer)?,
-                product: structure.find_string(packed.v2_1.v2_0.product)?,
-                version: structure.find_string(packed.v2_1.v2_0.version)?,
-                serial: structure.find_string(packed.v2_1.v2_0.serial)?,
-                uuid: Some(packed.v2_1.uuid),
-                wakeup: Some(packed.v2_1.wakeup.into()),
-                sku: Some(structure.find_string(packed.sku)?),
-                family: Some(structure.find_string(packed.family)?),
-            })
-        }
+    const DATA: &[u8] = &[
+        0x01, 0x02, 0x03, 0x04, // Manufacturer, Product, Version, Serial
+        0x44, 0x45, 0x4C, 0x4C, 0x34, 0x00, 0x10, 0x38, 0x80, 0x35, 0xC4, 0xC0, 0x4F, 0x54, 0x36, 0x32, // UUID
+        0x06, // Wake-up Type: Power Switch
+        0x05, 0x06, // SKU Number, Family
+    ];
+    const STRINGS: &[u8] = b"Dell Inc.\0PowerEdge R640\0Not Specified\0ABC1234\0SKU=0716\0PowerEdge\0\0";
+
+    fn decode(version: (usize, usize), length: u8) -> Result<System<'static>, MalformedStructureError> {
+        System::from_raw(RawStructure {
+            version: version.into(),
+            info: InfoType::System,
+            length,
+            handle: Handle(0x0100),
+            data: &DATA[..length as usize - 4],
+            strings: STRINGS,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn system_2_4() {
+        let system = decode((3, 2), 0x1B).unwrap();
+        assert_eq!("PowerEdge R640", system.product);
+        assert_eq!(Some(0x44), system.uuid.map(|uuid| uuid[0]));
+        assert_eq!(Some(0x32), system.uuid.map(|uuid| uuid[15]));
+        assert_eq!(Some(WakeupType::Power_Switch), system.wakeup);
+        assert_eq!(Some("SKU=0716"), system.sku);
+        assert_eq!(Some("PowerEdge"), system.family);
+    }
+
+    #[test]
+    fn short_structures() {
+        // SMBIOS 2.0 layout
+        let system = decode((2, 0), 0x08).unwrap();
+        assert_eq!("ABC1234", system.serial);
+        assert_eq!((None, None, None), (system.uuid, system.wakeup, system.sku));
+
+        // 2.1 layout in a table claiming a later version
+        let system = decode((2, 7), 0x19).unwrap();
+        assert_eq!(Some(WakeupType::Power_Switch), system.wakeup);
+        assert_eq!((None, None), (system.sku, system.family));
+
+        assert!(decode((2, 0), 0x07).is_err());
+    }
+
+    #[test]
+    fn bad_string_index() {
+        let structure = RawStructure {
+            version: (3, 2).into(),
+            info: InfoType::System,
+            length: 0x1B,
+            handle: Handle(0x0100),
+            data: &DATA[..0x17],
+            strings: b"Dell Inc.\0PowerEdge R640\0Not Specified\0ABC1234\0SKU=0716\0\0",
+            ..Default::default()
+        };
+        assert!(matches!(
+            System::from_raw(structure.clone()),
+            Err(MalformedStructureError::InvalidStringIndex(_, _, 6, 5))
+        ));
+
+        let system = System::from_raw(structure.tolerate_bad_string_indexes()).unwrap();
+        assert_eq!(Some("SKU=0716"), system.sku);
+        assert_eq!(Some(""), system.family);
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn wakeup_type() {
+        assert_eq!("PCI PME#", format!("{}", WakeupType::from(7)));
+        assert_eq!("AC Power Restored", format!("{}", WakeupType::from(8)));
+        assert_eq!("Undefined: 9", format!("{}", WakeupType::from(9)));
     }
 }
@@ -2,9 +2,11 @@
 //!
 //! The information in this structure defines attributes of a system baseboard (for example, a
 //! motherboard, planar, server blade, or other standard system module).
+#[cfg(feature = "strings")]
 use core::fmt;
 
-use crate::{MalformedStructureError, RawStructure};
+use crate::bitfield::{BitField, FlagType, Layout};
+use crate::{Handle, MalformedStructureError, RawStructure};
 
 /// The baseboard type defined in the SMBIOS specification.
 #[allow(non_camel_case_types)]
@@ -46,8 +48,12 @@ impl From<u8> for BoardType {
         }
     }
 }
+/// Formatted as in the dmidecode output, the alternate form gives the wording of the
+/// specification
+#[cfg(feature = "strings")]
 impl fmt::Display for BoardType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_alt = f.alternate();
         match self {
             BoardType::Unknown => write!(f, "Unknown"),
             BoardType::Other => write!(f, "Other"),
@@ -57,13 +63,18 @@ impl fmt::Display for BoardType {
             BoardType::ProcessorModule => write!(f, "Processor Module"),
             BoardType::IoModule => write!(f, "I/O Module"),
             BoardType::MemoryModule => write!(f, "Memory Module"),
-            BoardType::DaughterBoard => write!(f, "Daughter board"),
-            BoardType::MotherBoard => {
+            BoardType::DaughterBoard if is_alt => write!(f, "Daughter board"),
+            BoardType::DaughterBoard => write!(f, "Daughter Board"),
+            BoardType::MotherBoard if is_alt => {
                 write!(f, "Motherboard (includes processor, memory, and I/O)")
             }
-            BoardType::ProcessorMemoryModule => write!(f, "Processor/Memory Module"),
-            BoardType::ProcessorIoModule => write!(f, "Processor/IO Module"),
-            BoardType::InterconnectBoard => write!(f, "Interconnect board"),
+            BoardType::MotherBoard => write!(f, "Motherboard"),
+            BoardType::ProcessorMemoryModule if is_alt => write!(f, "Processor/Memory Module"),
+            BoardType::ProcessorMemoryModule => write!(f, "Processor+Memory Module"),
+            BoardType::ProcessorIoModule if is_alt => write!(f, "Processor/IO Module"),
+            BoardType::ProcessorIoModule => write!(f, "Processor+I/O Module"),
+            BoardType::InterconnectBoard if is_alt => write!(f, "Interconnect board"),
+            BoardType::InterconnectBoard => write!(f, "Interconnect Board"),
             BoardType::Undefined(t) => write!(f, "Undefined: {}", t),
         }
     }
@@ -71,6 +82,7 @@ impl fmt::Display for BoardType {
 
 bitflags! {
     /// The baseboard characteristic flags defined in the SMBIOS specification.
+    #[derive(Default)]
     pub struct BaseBoardFlags: u8 {
         const HOSTING = 0b0000_0001;
         const REQUIRES_DAUGHTER = 0b0000_0010;
@@ -80,13 +92,38 @@ bitflags! {
     }
 }
 
+impl<'a> BitField<'a> for BaseBoardFlags {
+    type Size = u8;
+    fn value(&self) -> Self::Size {
+        self.bits()
+    }
+    layout!(
+        length = 8;
+        "Board is a hosting board"
+            "Set if the board is a hosting board (for example, a motherboard)",
+        "Board requires at least one daughter board"
+            "Set if the board requires at least one daughter board or auxiliary card to function \
+            properly",
+        "Board is removable"
+            "Set if the board is removable; it is designed to be taken in and out of the chassis \
+            without impairing the function of the chassis",
+        "Board is replaceable"
+            "Set if the board is replaceable; it is possible to replace (either as a field repair \
+            or as an upgrade) the board with a physically different board",
+        "Board is hot swappable"
+            "Set if the board is hot swappable; it is possible to replace the board with a \
+            physically different but equivalent board while power is applied to the board",
+        "Reserved": 3,
+    );
+}
+
 /// The `BaseBoard` table defined in the SMBIOS specification.
 ///
 /// Optional fields will only be set if the version of the parsed SMBIOS table
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct BaseBoard<'buffer> {
-    pub handle: u16,
+    pub handle: Handle,
     pub manufacturer: &'buffer str,
     pub product: &'buffer str,
     pub version: &'buffer str,
@@ -94,65 +131,101 @@ pub struct BaseBoard<'buffer> {
     pub asset: Option<&'buffer str>,
     pub feature_flags: Option<BaseBoardFlags>,
     pub location_in_chassis: Option<&'buffer str>,
-    pub chassis_handle: Option<u16>,
+    pub chassis_handle: Option<Handle>,
     pub board_type: Option<BoardType>,
 }
 
 impl<'buffer> BaseBoard<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<BaseBoard<'buffer>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct BaseBoardPacked {
-            manufacturer: u8,
-            product: u8,
-            version: u8,
-            serial: u8,
-            asset: u8,
-            feature_flags: u8,
-            location_in_chassis: u8,
-            chassis_handle: u16,
-            board_type: u8,
-        }
-
-        let_as_struct!(packed, BaseBoardPacked, structure.data);
-
-        let asset = if structure.data.len() > 4 {
-            Some(structure.find_string(packed.asset)?)
-        } else {
-            None
-        };
-        let feature_flags = if structure.data.len() > 5 {
-            Some(BaseBoardFlags::from_bits_truncate(packed.feature_flags))
-        } else {
-            None
-        };
-        let location_in_chassis = if structure.data.len() > 6 {
-            Some(structure.find_string(packed.location_in_chassis)?)
-        } else {
-            None
-        };
-        let chassis_handle = if structure.data.len() > 7 {
-            Some(packed.chassis_handle)
-        } else {
-            None
-        };
-        let board_type = if structure.data.len() > 9 {
-            Some(packed.board_type.into())
-        } else {
-            None
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<BaseBoard<'buffer>, MalformedStructureError> {
+        let string = |offset| {
+            structure
+                .get_optional::<u8>(offset)
+                .map(|idx| structure.find_string(idx))
+                .transpose()
         };
 
         Ok(BaseBoard {
             handle: structure.handle,
-            manufacturer: structure.find_string(packed.manufacturer)?,
-            product: structure.find_string(packed.product)?,
-            version: structure.find_string(packed.version)?,
-            serial: structure.find_string(packed.serial)?,
-            asset,
-            feature_flags,
-            location_in_chassis,
-            chassis_handle,
-            board_type,
+            manufacturer: structure.get_string(0x04)?,
+            product: structure.get_string(0x05)?,
+            version: structure.get_string(0x06)?,
+            serial: structure.get_string(0x07)?,
+            asset: string(0x08)?,
+            feature_flags: structure
+                .get_optional::<u8>(0x09)
+                .map(BaseBoardFlags::from_bits_truncate),
+            location_in_chassis: string(0x0A)?,
+            chassis_handle: structure
+                .get_optional::<u16>(0x0B)
+                .and_then(|handle| Handle(handle).as_reference()),
+            board_type: structure.get_optional::<u8>(0x0D).map(Into::into),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn board_type() {
+        let samples = &[
+            (0x03, BoardType::ServerBlade, "Server Blade", "Server Blade"),
+            (0x09, BoardType::DaughterBoard, "Daughter Board", "Daughter board"),
+            (
+                0x0A,
+                BoardType::MotherBoard,
+                "Motherboard",
+                "Motherboard (includes processor, memory, and I/O)",
+            ),
+            (
+                0x0C,
+                BoardType::ProcessorIoModule,
+                "Processor+I/O Module",
+                "Processor/IO Module",
+            ),
+            (0x0E, BoardType::Undefined(14), "Undefined: 14", "Undefined: 14"),
+        ];
+        for &(byte, board_type, short, long) in samples {
+            assert_eq!(board_type, byte.into(), "{:#x}", byte);
+            assert_eq!(short, format!("{}", board_type));
+            assert_eq!(long, format!("{:#}", board_type));
+        }
+    }
+
+    #[test]
+    fn short_structure() {
+        use crate::InfoType;
+
+        // The four strings and the asset tag of SMBIOS 2.0
+        let structure = RawStructure {
+            version: (3, 2).into(),
+            info: InfoType::BaseBoard,
+            length: 0x09,
+            handle: Handle(0x0200),
+            data: &[0x01, 0x02, 0x03, 0x04, 0x00],
+            strings: b"Dell Inc.\0PowerEdge R640\0A01\0..CN7016\0\0",
+            ..Default::default()
+        };
+        let baseboard = BaseBoard::from_raw(structure).unwrap();
+        assert_eq!(("PowerEdge R640", Some("")), (baseboard.product, baseboard.asset));
+        assert_eq!(
+            (None, None, None),
+            (baseboard.feature_flags, baseboard.chassis_handle, baseboard.board_type)
+        );
+    }
+
+    #[test]
+    fn feature_flags() {
+        let flags = BaseBoardFlags::HOSTING | BaseBoardFlags::IS_REPLACEABLE;
+        #[cfg(feature = "strings")]
+        {
+            let result = flags.significants().map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(vec!["Board is a hosting board", "Board is replaceable"], result);
+        }
+        assert_eq!(3, flags.reserved().next().unwrap().range.count());
+    }
+}
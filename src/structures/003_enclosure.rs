@@ -5,17 +5,18 @@
 //! devices, two structures would be returned: one for the main system enclosure and the second for
 //! the peripheral device enclosure.
 
+#[cfg(feature = "strings")]
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::slice::Chunks;
 
-use crate::{HeaderPacked, MalformedStructureError, RawStructure};
+use crate::{Handle, HeaderPacked, MalformedStructureError, RawStructure, TryFromBytes};
 
 /// System Enclosure or Chassis structure
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Enclosure<'buffer> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Manufacturer string is non-null
     pub manufacturer: &'buffer str,
     /// Chassis lock is present
@@ -39,8 +40,8 @@ pub struct Enclosure<'buffer> {
     /// OEM- or BIOS vendor-specific information
     pub oem_defined: Option<u32>,
     /// Height of the enclosure , in 'U's A U is a standard unit of measure for the height of a
-    /// rack or rack-mountable component and is equal to 1.75 inches or 4.445 cm. A value of 00h
-    /// indicates that the enclosure height is unspecified.
+    /// rack or rack-mountable component and is equal to 1.75 inches or 4.445 cm. The value 00h,
+    /// which indicates that the enclosure height is unspecified, is reported as `None`.
     pub height: Option<u8>,
     /// Number of power cords associated with the enclosure or chassis A value of 00h indicates
     /// that the number is unspecified.
@@ -162,7 +163,7 @@ pub enum ContainedElementType {
 }
 
 impl<'buffer> Enclosure<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<Enclosure<'buffer>, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<Enclosure<'buffer>, MalformedStructureError> {
         #[repr(C)]
         #[repr(packed)]
         struct EnclosurePacked_2_0 {
@@ -193,12 +194,12 @@ impl<'buffer> Enclosure<'buffer> {
         }
 
         if structure.data.len() < core::mem::size_of::<EnclosurePacked_2_0>() {
-            return Err(crate::MalformedStructureError::InvalidFormattedSectionLength(
-                structure.info,
-                structure.handle,
-                "minimum of ",
-                core::mem::size_of::<EnclosurePacked_2_0>() as u8,
-            ));
+            return Err(crate::MalformedStructureError::InvalidFormattedSectionLength {
+                info: structure.info,
+                handle: structure.handle,
+                expected: crate::LengthExpectation::Minimum(core::mem::size_of::<EnclosurePacked_2_0>() as u8 + 4),
+                actual: structure.length,
+            });
         }
 
         let (minimum, mut extra) = structure.data.split_at(core::mem::size_of::<EnclosurePacked_2_0>());
@@ -248,7 +249,7 @@ impl<'buffer> Enclosure<'buffer> {
                 read_bytes(data)
             })
             .and_then(|height: u8| {
-                enclosure.height = Some(height);
+                enclosure.height = Some(height).filter(|&h| h != 0);
                 read_bytes(data)
             })
             .and_then(|power_cords_number: u8| {
@@ -269,6 +270,72 @@ impl<'buffer> Enclosure<'buffer> {
     }
 }
 
+/// Coarse classification of the chassis types, see [Enclosure::chassis_class]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ChassisClass {
+    /// Stationary client systems, from towers to stick PCs
+    Desktop,
+    /// Portable systems, including tablets and convertibles
+    Laptop,
+    /// Rack, blade and other server chassis
+    Server,
+    /// Embedded PCs and IoT gateways
+    Embedded,
+    /// Chassis that extend another system, e.g. expansion, RAID or docking stations
+    Expansion,
+    /// Other, Unknown and undefined types; virtual machines usually report one of these
+    Unknown,
+}
+
+impl EnclosureType {
+    /// The coarse classification of the chassis type
+    pub fn class(&self) -> ChassisClass {
+        match self {
+            Self::Desktop
+            | Self::LowProfileDesktop
+            | Self::PizzaBox
+            | Self::MiniTower
+            | Self::Tower
+            | Self::AllInOne
+            | Self::SpaceSaving
+            | Self::LunchBox
+            | Self::SealedCasePc
+            | Self::MiniPc
+            | Self::StickPc => ChassisClass::Desktop,
+            Self::Portable
+            | Self::Laptop
+            | Self::Notebook
+            | Self::HandHeld
+            | Self::SubNotebook
+            | Self::Tablet
+            | Self::Convertible
+            | Self::Detachable => ChassisClass::Laptop,
+            Self::MainServerChassis
+            | Self::RackMountChassis
+            | Self::MultiSystemChassis
+            | Self::CompactPci
+            | Self::AdvancedTca
+            | Self::Blade
+            | Self::BladeEnclosure => ChassisClass::Server,
+            Self::IotGateway | Self::EmbeddedPc => ChassisClass::Embedded,
+            Self::DockingStation
+            | Self::ExpansionChassis
+            | Self::SubChassis
+            | Self::BusExpansionChassis
+            | Self::PeripheralChassis
+            | Self::RaidChassis => ChassisClass::Expansion,
+            Self::Other | Self::Unknown | Self::Undefined(_) => ChassisClass::Unknown,
+        }
+    }
+}
+
+impl<'buffer> Enclosure<'buffer> {
+    /// Whether the enclosure is a desktop, a laptop, a server... as told by its type
+    pub fn chassis_class(&self) -> ChassisClass {
+        self.enclosure_type.class()
+    }
+}
+
 impl From<u8> for EnclosureType {
     fn from(byte: u8) -> EnclosureType {
         match byte {
@@ -312,6 +379,7 @@ impl From<u8> for EnclosureType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for EnclosureType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -369,6 +437,7 @@ impl From<u8> for State {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for State {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -395,6 +464,7 @@ impl From<u8> for SecurityStatus {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SecurityStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -421,7 +491,7 @@ impl<'buffer> ContainedElements<'buffer> {
             });
         }
 
-        let length = (count * record_length) as usize;
+        let length = count as usize * record_length as usize;
         let chunks = data.get(0..length)?.chunks(record_length as usize);
         *data = &data[length..];
         Some(Self {
@@ -461,21 +531,16 @@ impl<'buffer> Iterator for ContainedElements<'buffer> {
 
 impl From<&[u8]> for ContainedElement {
     fn from(data: &[u8]) -> ContainedElement {
-        #[repr(C)]
-        #[repr(packed)]
-        struct ContainedElement_2_3 {
-            type_: u8,
-            minimum: u8,
-            maximum: u8,
-        }
-        let_as_struct!(packed, ContainedElement_2_3, data);
+        // Records shorter than 3 bytes are invalid, but should not read beyond the record
+        let byte = |offset: usize| data.get(offset).copied().unwrap_or_default();
         ContainedElement {
-            type_: packed.type_.into(),
-            minimum: packed.minimum,
-            maximum: packed.maximum,
+            type_: byte(0).into(),
+            minimum: byte(1),
+            maximum: byte(2),
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for ContainedElement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} ({}-{})", self.type_, self.minimum, self.maximum)
@@ -492,6 +557,7 @@ impl From<u8> for ContainedElementType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for ContainedElementType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -501,19 +567,17 @@ impl fmt::Display for ContainedElementType {
     }
 }
 
-fn read_bytes<T: Copy>(data: &mut &[u8]) -> Option<T> {
-    if data.len() < core::mem::size_of::<T>() {
-        return None;
-    }
-
-    let value = unsafe { core::ptr::read_unaligned((*data).as_ptr() as *const T) };
-    *data = &data[core::mem::size_of::<T>()..];
+fn read_bytes<'a, T: TryFromBytes<'a, T>>(data: &mut &'a [u8]) -> Option<T> {
+    let size = core::mem::size_of::<T>();
+    let value = T::try_from_bytes(data.get(..size)?).ok()?;
+    *data = &data[size..];
     Some(value)
 }
 
 #[cfg(test)]
 mod tests {
     use std::prelude::v1::*;
+    #[cfg(feature = "strings")]
     #[test]
     fn enclosure_type() {
         use super::EnclosureType::*;
@@ -531,6 +595,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn chassis_class() {
+        use super::{ChassisClass, EnclosureType};
+        let samples = [
+            (0x01, ChassisClass::Unknown),
+            (0x03, ChassisClass::Desktop),
+            (0x0A, ChassisClass::Laptop),
+            (0x0C, ChassisClass::Expansion),
+            (0x17, ChassisClass::Server),
+            (0x1D, ChassisClass::Server),
+            (0x1F, ChassisClass::Laptop),
+            (0x22, ChassisClass::Embedded),
+            (0x24, ChassisClass::Desktop),
+            (0x7F, ChassisClass::Unknown),
+        ];
+        for (byte, class) in samples {
+            assert_eq!(class, EnclosureType::from(byte).class(), "{:#x}", byte);
+        }
+    }
+
+    #[cfg(feature = "strings")]
     #[test]
     fn state() {
         use super::State::*;
@@ -547,6 +632,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn security_status() {
         use super::SecurityStatus::*;
@@ -563,6 +649,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn contained_element() {
         use super::{ContainedElement, ContainedElementType};
@@ -631,6 +718,77 @@ mod tests {
         assert_eq!(data, &structure_data[8..]);
     }
 
+    #[test]
+    fn contained_elements_large_and_short_records() {
+        use super::{ContainedElement, ContainedElementType, ContainedElements};
+        // count * record length exceeds u8
+        let structure_data = [200, 2, 0x91, 0x01];
+        let mut data: &[u8] = &structure_data;
+        assert_eq!(None, ContainedElements::new(&mut data), "Truncated elements");
+
+        // Records of 2 bytes only have type and minimum
+        let structure_data = [2, 2, 0x91, 0x01, 0x07, 0x03, 0x05];
+        let mut data: &[u8] = &structure_data;
+        let contained_elements = ContainedElements::new(&mut data).expect("should not be empty");
+        assert_eq!(
+            vec![
+                ContainedElement {
+                    type_: ContainedElementType::InfoType(crate::InfoType::MemoryDevice),
+                    minimum: 1,
+                    maximum: 0
+                },
+                ContainedElement {
+                    type_: ContainedElementType::BoardType(crate::baseboard::BoardType::IoModule),
+                    minimum: 3,
+                    maximum: 0
+                },
+            ],
+            contained_elements.collect::<Vec<_>>()
+        );
+        assert_eq!(data, &[0x05]);
+    }
+
+    #[test]
+    fn optional_fields_by_length() {
+        use super::*;
+        let structure = |data: &'static [u8]| RawStructure {
+            version: (2, 7).into(),
+            info: crate::InfoType::Enclosure,
+            length: data.len() as u8 + 4,
+            handle: Handle(1),
+            data,
+            strings: b"Vendor\0SKU-1\0\0",
+            ..Default::default()
+        };
+
+        // SMBIOS 2.0
+        let enclosure = Enclosure::from_raw(structure(&[1, 0x17, 0, 0, 0])).unwrap();
+        assert_eq!(None, enclosure.boot_up_state);
+        assert_eq!(None, enclosure.oem_defined);
+
+        // SMBIOS 2.1
+        let enclosure = Enclosure::from_raw(structure(&[1, 0x17, 0, 0, 0, 3, 3, 3, 3])).unwrap();
+        assert_eq!(Some(State::Safe), enclosure.thermal_state);
+        assert_eq!(None, enclosure.oem_defined);
+        assert_eq!(None, enclosure.power_cords_number);
+
+        // SMBIOS 2.3 without contained elements
+        let data = &[1, 0x17, 0, 0, 0, 3, 3, 3, 3, 0xEF, 0xBE, 0xAD, 0xDE, 4, 2, 0, 0];
+        let enclosure = Enclosure::from_raw(structure(data)).unwrap();
+        assert_eq!(Some(0xDEADBEEF), enclosure.oem_defined);
+        assert_eq!(Some(4), enclosure.height);
+        assert_eq!(Some(2), enclosure.power_cords_number);
+        assert_eq!(Some(0), enclosure.contained_elements.map(|ce| ce.count()));
+        assert_eq!(None, enclosure.sku_number);
+
+        // SMBIOS 2.7 with one contained element
+        let data = &[1, 0x17, 0, 0, 0, 3, 3, 3, 3, 0, 0, 0, 0, 0, 1, 1, 3, 0x91, 1, 2, 2];
+        let enclosure = Enclosure::from_raw(structure(data)).unwrap();
+        assert_eq!(None, enclosure.height, "Unspecified height");
+        assert_eq!(Some(1), enclosure.contained_elements.map(|ce| ce.count()));
+        assert_eq!(Some("SKU-1"), enclosure.sku_number);
+    }
+
     #[test]
     fn dmi_bin() {
         use super::*;
@@ -647,7 +805,7 @@ mod tests {
             })
             .unwrap();
         let sample = Enclosure {
-            handle: 768,
+            handle: Handle(768),
             manufacturer: "Dell Inc.",
             chassis_lock: true,
             enclosure_type: EnclosureType::RackMountChassis,
@@ -671,31 +829,36 @@ mod tests {
 
         assert_eq!(sample, enc);
         assert_eq!(format!("{}", enc.manufacturer), "Dell Inc.", "Manufacturer");
+        #[cfg(feature = "strings")]
         assert_eq!(format!("{}", enc.enclosure_type), "Rack Mount Chassis", "Type");
+        assert_eq!(super::ChassisClass::Server, enc.chassis_class(), "Class");
         assert_eq!(format!("{}", enc.chassis_lock), "true", "Lock");
         assert_eq!(format!("{}", enc.version), "", "Version");
         assert_eq!(format!("{}", enc.serial_number), "XXXXXXX", "Serial Number");
         assert_eq!(format!("{}", enc.asset_tag_number), "", "Asset Tag");
-        assert_eq!(
-            enc.boot_up_state.map(|v| format!("{}", v)),
-            Some("Safe".into()),
-            "Boot-up State"
-        );
-        assert_eq!(
-            enc.power_supply_state.map(|v| format!("{}", v)),
-            Some("Safe".into()),
-            "Power Supply State"
-        );
-        assert_eq!(
-            enc.thermal_state.map(|v| format!("{}", v)),
-            Some("Safe".into()),
-            "Thermal State"
-        );
-        assert_eq!(
-            enc.security_status.map(|v| format!("{}", v)),
-            Some("Unknown".into()),
-            "Security Status"
-        );
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                enc.boot_up_state.map(|v| format!("{}", v)),
+                Some("Safe".into()),
+                "Boot-up State"
+            );
+            assert_eq!(
+                enc.power_supply_state.map(|v| format!("{}", v)),
+                Some("Safe".into()),
+                "Power Supply State"
+            );
+            assert_eq!(
+                enc.thermal_state.map(|v| format!("{}", v)),
+                Some("Safe".into()),
+                "Thermal State"
+            );
+            assert_eq!(
+                enc.security_status.map(|v| format!("{}", v)),
+                Some("Unknown".into()),
+                "Security Status"
+            );
+        }
         assert_eq!(
             enc.oem_defined.map(|v| format!("{:#010X}", v)),
             Some("0x01010101".into()),
@@ -703,20 +866,23 @@ mod tests {
         );
         assert_eq!(enc.height, Some(2), "Height");
         assert_eq!(enc.power_cords_number, Some(2), "Number Of Power Cords");
-        assert_eq!(
-            enc.contained_elements
-                .clone()
-                .and_then(|mut ce| ce.next().map(|s| format!("{}", s))),
-            Some("Structure type: Memory Device (1-2)".into()),
-            "Number Of Power Cords"
-        );
-        assert_eq!(
-            enc.contained_elements
-                .clone()
-                .and_then(|mut ce| ce.nth(1).map(|s| format!("{}", s))),
-            Some("Baseboard type: Server Blade (255-0)".into()),
-            "Number Of Power Cords"
-        );
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                enc.contained_elements
+                    .clone()
+                    .and_then(|mut ce| ce.next().map(|s| format!("{}", s))),
+                Some("Structure type: Memory Device (1-2)".into()),
+                "Number Of Power Cords"
+            );
+            assert_eq!(
+                enc.contained_elements
+                    .clone()
+                    .and_then(|mut ce| ce.nth(1).map(|s| format!("{}", s))),
+                Some("Baseboard type: Server Blade (255-0)".into()),
+                "Number Of Power Cords"
+            );
+        }
         assert_eq!(
             enc.sku_number.map(|v| v.to_string()),
             Some("SKU Number".into()),
@@ -728,20 +894,21 @@ mod tests {
     fn no_sku_on_3_14() {
         use super::*;
 
-        let enclosure = Enclosure::try_from(RawStructure {
+        let enclosure = Enclosure::from_raw(RawStructure {
             version: crate::SmbiosVersion { major: 3, minor: 14 },
             info: crate::InfoType::Enclosure,
             length: 20,
-            handle: 153,
+            handle: Handle(153),
             data: &[1, 1, 0, 0, 0, 3, 3, 3, 2, 0, 0, 0, 0, 0, 0, 0],
             strings: &[71, 111, 111, 103, 108, 101, 0, 0],
+            ..Default::default()
         })
         .expect("failed to create enclosure");
 
         assert_eq!(
             enclosure,
             Enclosure {
-                handle: 153,
+                handle: Handle(153),
                 manufacturer: "Google",
                 chassis_lock: false,
                 enclosure_type: EnclosureType::Other,
@@ -753,7 +920,7 @@ mod tests {
                 thermal_state: Some(State::Safe),
                 security_status: Some(SecurityStatus::Unknown),
                 oem_defined: Some(0),
-                height: Some(0),
+                height: None,
                 power_cords_number: Some(0),
                 contained_elements: None,
                 sku_number: None
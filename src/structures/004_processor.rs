@@ -14,7 +14,7 @@ use core::{
     fmt,
 };
 
-use crate::{MalformedStructureError, RawStructure};
+use crate::{normalize::normalize, structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
 /// The processor types defined in the SMBIOS specification.
 #[allow(non_camel_case_types)]
@@ -45,6 +45,11 @@ impl From<u8> for ProcessorType {
 
 bitflags! {
     /// The processor status flags defined in the SMBIOS specification.
+    ///
+    /// Bits 2:0 hold the CPU status as a value, not as flags: `CPU_DISABLED_BY_BIOS` and
+    /// `CPU_OTHER` share their bits with `CPU_ENABLED` and `CPU_DISABLED_BY_USER`, so `contains`
+    /// gives misleading answers for them. Use [ProcessorStatus::cpu_status] instead.
+    #[derive(Default)]
     pub struct ProcessorStatus: u8 {
         const CPU_SOCKET_POPULATED = 0b0100_0000;
         const CPU_ENABLED = 0b0000_0001;
@@ -55,8 +60,94 @@ bitflags! {
     }
 }
 
+impl ProcessorStatus {
+    /// A processor is installed in the socket
+    pub fn is_populated(&self) -> bool {
+        self.contains(ProcessorStatus::CPU_SOCKET_POPULATED)
+    }
+
+    /// The CPU status value of bits 2:0
+    pub fn cpu_status(&self) -> CpuStatus {
+        CpuStatus::from(self.bits())
+    }
+}
+
+/// The CPU status of a processor socket, bits 2:0 of the Status field
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum CpuStatus {
+    Unknown,
+    Enabled,
+    /// CPU Disabled by User through BIOS Setup
+    DisabledByUser,
+    /// CPU Disabled by BIOS (POST Error)
+    DisabledByBios,
+    /// CPU is Idle, waiting to be enabled
+    Idle,
+    Other,
+    Undefined(u8),
+}
+
+/// Only bits 2:0 are used, the other bits of the Status field are ignored
+impl From<u8> for CpuStatus {
+    fn from(status: u8) -> CpuStatus {
+        match status & 0b111 {
+            0 => CpuStatus::Unknown,
+            1 => CpuStatus::Enabled,
+            2 => CpuStatus::DisabledByUser,
+            3 => CpuStatus::DisabledByBios,
+            4 => CpuStatus::Idle,
+            7 => CpuStatus::Other,
+            n => CpuStatus::Undefined(n),
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Display for CpuStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CpuStatus::Unknown => write!(f, "Unknown"),
+            CpuStatus::Enabled => write!(f, "Enabled"),
+            CpuStatus::DisabledByUser => write!(f, "Disabled By User"),
+            CpuStatus::DisabledByBios => write!(f, "Disabled By BIOS"),
+            CpuStatus::Idle => write!(f, "Idle"),
+            CpuStatus::Other => write!(f, "Other"),
+            CpuStatus::Undefined(n) => write!(f, "Undefined {}", n),
+        }
+    }
+}
+
+/// Socket population and CPU status, decoded from the Status field
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SocketStatus {
+    pub populated: bool,
+    pub cpu: CpuStatus,
+}
+
+impl From<ProcessorStatus> for SocketStatus {
+    fn from(status: ProcessorStatus) -> SocketStatus {
+        SocketStatus {
+            populated: status.is_populated(),
+            cpu: status.cpu_status(),
+        }
+    }
+}
+
+/// Formatted as in the dmidecode output, e.g. `Populated, Enabled`
+#[cfg(feature = "strings")]
+impl fmt::Display for SocketStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.populated {
+            write!(f, "Populated, {}", self.cpu)
+        } else {
+            write!(f, "Unpopulated")
+        }
+    }
+}
+
 bitflags! {
     /// The processor characteristic flags defined in the SMBIOS specification.
+    #[derive(Default)]
     pub struct ProcessorCharacteristics: u16 {
         const RESERVED = 0b0000_0001;
         const UNKNOWN = 0b0000_0010;
@@ -76,7 +167,7 @@ bitflags! {
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Processor<'buffer> {
-    pub handle: u16,
+    pub handle: Handle,
     /// String number for Reference Designation EXAMPLE: ‘J202’,0
     pub socket_designation: &'buffer str,
     /// Processor Type field
@@ -96,21 +187,23 @@ pub struct Processor<'buffer> {
     /// Maximum processor speed (in MHz) supported by the system for this processor socket
     pub max_speed: u16,
     /// This field identifies the processor's speed at system boot; the processor may support more
-    /// than one speed.
+    /// than one speed.\
+    /// Volatile: ignored by [Processor::eq_stable]
     pub current_speed: u16,
     /// Status
     pub status: ProcessorStatus,
     /// Processor Upgrade field
     pub processor_upgrade: ProcessorUpgrade,
     /// Handle of a Cache Information structure that defines the attributes of the primary
-    /// (Level 1) cache for this processor
-    pub l1_cache_handle: Option<u16>,
+    /// (Level 1) cache for this processor, `None` if the field is missing or if the processor has
+    /// no such cache ([Handle::NONE])
+    pub l1_cache_handle: Option<Handle>,
     /// Handle of a Cache Information structure that defines the attributes of the secondary
-    /// (Level 2) cache for this processor
-    pub l2_cache_handle: Option<u16>,
+    /// (Level 2) cache for this processor, `None` if the field is missing or reserved
+    pub l2_cache_handle: Option<Handle>,
     /// Handle of a Cache Information structure that defines the attributes of the tertiary
-    /// (Level 3) cache for this processor
-    pub l3_cache_handle: Option<u16>,
+    /// (Level 3) cache for this processor, `None` if the field is missing or reserved
+    pub l3_cache_handle: Option<Handle>,
     /// String number for the serial number of this processor
     pub serial_number: Option<&'buffer str>,
     /// String number for the asset tag of this processor
@@ -373,6 +466,30 @@ pub enum ProcessorFamily {
     RISCVRV32,
     RISCVRV64,
     RISCVRV128,
+    Loongson1,
+    Loongson2,
+    Loongson3,
+    Loongson2K,
+    Loongson3A,
+    Loongson3B,
+    Loongson3C,
+    Loongson3D,
+    Loongson3E,
+    DualCoreLoongson2K2xxx,
+    QuadCoreLoongson3A5xxx,
+    MultiCoreLoongson3A5xxx,
+    QuadCoreLoongson3B5xxx,
+    MultiCoreLoongson3B5xxx,
+    MultiCoreLoongson3C5xxx,
+    MultiCoreLoongson3D5xxx,
+    IntelCore3,
+    IntelCore5,
+    IntelCore7,
+    IntelCore9,
+    IntelCoreUltra3,
+    IntelCoreUltra5,
+    IntelCoreUltra7,
+    IntelCoreUltra9,
     Available(u16),
     NotUsed(u16),
     ForFutureUse,
@@ -393,6 +510,7 @@ pub enum Voltage {
 
 bitflags! {
     /// Voltage Capability. A set bit indicates that the voltage is supported
+    #[derive(Default)]
     pub struct VoltageLegacy: u8 {
         const VOLTAGE_CAPABILITY_5V0  = 0b0000_0001;
         const VOLTAGE_CAPABILITY_3V3  = 0b0000_0010;
@@ -464,361 +582,128 @@ pub enum ProcessorUpgrade {
     SocketBGA1528,
     SocketLGA4189,
     SocketLGA1200,
+    SocketLGA4677,
+    SocketLGA1700,
+    SocketBGA1744,
+    SocketBGA1781,
+    SocketBGA1211,
+    SocketBGA2422,
+    SocketLGA1211,
+    SocketLGA2422,
+    SocketLGA5773,
+    SocketBGA5773,
+    SocketAM5,
+    SocketSP5,
+    SocketSP6,
+    SocketBGA883,
+    SocketBGA1190,
+    SocketBGA4129,
+    SocketLGA4710,
+    SocketLGA7529,
     Undefined(u8),
 }
 
-impl<'buffer> Processor<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<Processor<'buffer>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_2_0 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-        }
+/// Formatted section of the Processor Information structure, per SMBIOS version
+mod layout {
+    use super::{Field, Handle};
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_2_1 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-            l1_cache_handle: u16,
-            l2_cache_handle: u16,
-            l3_cache_handle: u16,
-        }
+    pub const SOCKET_DESIGNATION: Field<u8> = Field::new(0x04, 2, 0);
+    pub const PROCESSOR_TYPE: Field<u8> = Field::new(0x05, 2, 0);
+    pub const PROCESSOR_FAMILY: Field<u8> = Field::new(0x06, 2, 0);
+    pub const PROCESSOR_MANUFACTURER: Field<u8> = Field::new(0x07, 2, 0);
+    pub const PROCESSOR_ID: Field<u64> = Field::new(0x08, 2, 0);
+    pub const PROCESSOR_VERSION: Field<u8> = Field::new(0x10, 2, 0);
+    pub const VOLTAGE: Field<u8> = Field::new(0x11, 2, 0);
+    pub const EXTERNAL_CLOCK: Field<u16> = Field::new(0x12, 2, 0);
+    pub const MAX_SPEED: Field<u16> = Field::new(0x14, 2, 0);
+    pub const CURRENT_SPEED: Field<u16> = Field::new(0x16, 2, 0);
+    pub const STATUS: Field<u8> = Field::new(0x18, 2, 0);
+    pub const PROCESSOR_UPGRADE: Field<u8> = Field::new(0x19, 2, 0);
+    pub const L1_CACHE_HANDLE: Field<Handle> = Field::new(0x1A, 2, 1);
+    pub const L2_CACHE_HANDLE: Field<Handle> = Field::new(0x1C, 2, 1);
+    pub const L3_CACHE_HANDLE: Field<Handle> = Field::new(0x1E, 2, 1);
+    pub const SERIAL_NUMBER: Field<u8> = Field::new(0x20, 2, 3);
+    pub const ASSET_TAG: Field<u8> = Field::new(0x21, 2, 3);
+    pub const PART_NUMBER: Field<u8> = Field::new(0x22, 2, 3);
+    pub const CORE_COUNT: Field<u8> = Field::new(0x23, 2, 5);
+    pub const CORE_ENABLED: Field<u8> = Field::new(0x24, 2, 5);
+    pub const THREAD_COUNT: Field<u8> = Field::new(0x25, 2, 5);
+    pub const PROCESSOR_CHARACTERISTICS: Field<u16> = Field::new(0x26, 2, 6);
+    pub const PROCESSOR_FAMILY_2: Field<u16> = Field::new(0x28, 2, 6);
+    pub const CORE_COUNT_2: Field<u16> = Field::new(0x2A, 3, 0);
+    pub const CORE_ENABLED_2: Field<u16> = Field::new(0x2C, 3, 0);
+    pub const THREAD_COUNT_2: Field<u16> = Field::new(0x2E, 3, 0);
+}
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_2_3 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-            l1_cache_handle: u16,
-            l2_cache_handle: u16,
-            l3_cache_handle: u16,
-            serial_number: u8,
-            asset_tag: u8,
-            part_number: u8,
-        }
+impl<'buffer> Processor<'buffer> {
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<Processor<'buffer>, MalformedStructureError> {
+        use self::layout::*;
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_2_5 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-            l1_cache_handle: u16,
-            l2_cache_handle: u16,
-            l3_cache_handle: u16,
-            serial_number: u8,
-            asset_tag: u8,
-            part_number: u8,
-            core_count: u8,
-            core_enabled: u8,
-            thread_count: u8,
-            processor_characteristics: u16,
-        }
+        // smbios spec specifies 0xFE as an indicator to obtain processor
+        // family from the Processor Family 2 field.
+        let processor_family = match PROCESSOR_FAMILY.get(&structure)?.try_into()? {
+            ProcessorFamily::ProcessorFamily2 => match PROCESSOR_FAMILY_2.get_since(&structure) {
+                Some(family) => family.try_into()?,
+                None => ProcessorFamily::ProcessorFamily2,
+            },
+            family => family,
+        };
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_2_6 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-            l1_cache_handle: u16,
-            l2_cache_handle: u16,
-            l3_cache_handle: u16,
-            serial_number: u8,
-            asset_tag: u8,
-            part_number: u8,
-            core_count: u8,
-            core_enabled: u8,
-            thread_count: u8,
-            processor_characteristics: u16,
-            processor_family_2: u16,
-        }
+        // The Core Count 2 field supports core counts > 255. For core counts of 256 or greater, the Core Count
+        // field is set to FFh and the Core Count 2 field is set to the number of cores. For core counts of 255 or
+        // fewer, if Core Count 2 is present it shall be set the same value as Core Count
+        //
+        // The rule is same for Core Enabled and Thread Count as well.
+        let count = |count: &Field<u8>, count_2: &Field<u16>| match count.get_since(&structure)? {
+            0xFF => count_2.get_since(&structure).or(Some(0xFF)),
+            count => Some(count as u16),
+        };
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct ProcessorPacked_3_0 {
-            socket_designation: u8,
-            processor_type: u8,
-            processor_family: u8,
-            processor_manufacturer: u8,
-            processor_id: u64,
-            processor_version: u8,
-            voltage: u8,
-            external_clock: u16,
-            max_speed: u16,
-            current_speed: u16,
-            status: u8,
-            processor_upgrade: u8,
-            l1_cache_handle: u16,
-            l2_cache_handle: u16,
-            l3_cache_handle: u16,
-            serial_number: u8,
-            asset_tag: u8,
-            part_number: u8,
-            core_count: u8,
-            core_enabled: u8,
-            thread_count: u8,
-            processor_characteristics: u16,
-            processor_family_2: u16,
-            core_count_2: u16,
-            core_enabled_2: u16,
-            thread_count_2: u16,
-        }
+        Ok(Processor {
+            handle: structure.handle,
+            socket_designation: SOCKET_DESIGNATION.string(&structure)?,
+            processor_type: PROCESSOR_TYPE.get(&structure)?.into(),
+            processor_family,
+            processor_manufacturer: PROCESSOR_MANUFACTURER.string(&structure)?,
+            processor_id: PROCESSOR_ID.get(&structure)?,
+            processor_version: PROCESSOR_VERSION.string(&structure)?,
+            voltage: VOLTAGE.get(&structure)?.into(),
+            external_clock: EXTERNAL_CLOCK.get(&structure)?,
+            max_speed: MAX_SPEED.get(&structure)?,
+            current_speed: CURRENT_SPEED.get(&structure)?,
+            status: ProcessorStatus::from_bits_truncate(STATUS.get(&structure)?),
+            processor_upgrade: PROCESSOR_UPGRADE.get(&structure)?.into(),
+            l1_cache_handle: L1_CACHE_HANDLE.get_since(&structure).and_then(Handle::as_reference),
+            l2_cache_handle: L2_CACHE_HANDLE.get_since(&structure).and_then(Handle::as_reference),
+            l3_cache_handle: L3_CACHE_HANDLE.get_since(&structure).and_then(Handle::as_reference),
+            serial_number: SERIAL_NUMBER.string_since(&structure)?,
+            asset_tag: ASSET_TAG.string_since(&structure)?,
+            part_number: PART_NUMBER.string_since(&structure)?,
+            core_count: count(&CORE_COUNT, &CORE_COUNT_2),
+            core_enabled: count(&CORE_ENABLED, &CORE_ENABLED_2),
+            thread_count: count(&THREAD_COUNT, &THREAD_COUNT_2),
+            processor_characteristics: PROCESSOR_CHARACTERISTICS
+                .get_since(&structure)
+                .map(ProcessorCharacteristics::from_bits_truncate),
+        })
+    }
 
-        if structure.version < (2, 1).into() {
-            let_as_struct!(packed, ProcessorPacked_2_0, structure.data);
-
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family: packed.processor_family.try_into()?,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: None,
-                l2_cache_handle: None,
-                l3_cache_handle: None,
-                serial_number: None,
-                asset_tag: None,
-                part_number: None,
-                core_count: None,
-                core_enabled: None,
-                thread_count: None,
-                processor_characteristics: None,
-            })
-        } else if structure.version < (2, 3).into() {
-            let_as_struct!(packed, ProcessorPacked_2_1, structure.data);
-
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family: packed.processor_family.try_into()?,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: Some(packed.l1_cache_handle),
-                l2_cache_handle: Some(packed.l2_cache_handle),
-                l3_cache_handle: Some(packed.l3_cache_handle),
-                serial_number: None,
-                asset_tag: None,
-                part_number: None,
-                core_count: None,
-                core_enabled: None,
-                thread_count: None,
-                processor_characteristics: None,
-            })
-        } else if structure.version < (2, 5).into() {
-            let_as_struct!(packed, ProcessorPacked_2_3, structure.data);
-
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family: packed.processor_family.try_into()?,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: Some(packed.l1_cache_handle),
-                l2_cache_handle: Some(packed.l2_cache_handle),
-                l3_cache_handle: Some(packed.l3_cache_handle),
-                serial_number: Some(structure.find_string(packed.serial_number)?),
-                asset_tag: Some(structure.find_string(packed.asset_tag)?),
-                part_number: Some(structure.find_string(packed.part_number)?),
-                core_count: None,
-                core_enabled: None,
-                thread_count: None,
-                processor_characteristics: None,
-            })
-        } else if structure.version < (2, 6).into() {
-            let_as_struct!(packed, ProcessorPacked_2_5, structure.data);
-
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family: packed.processor_family.try_into()?,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: Some(packed.l1_cache_handle),
-                l2_cache_handle: Some(packed.l2_cache_handle),
-                l3_cache_handle: Some(packed.l3_cache_handle),
-                serial_number: Some(structure.find_string(packed.serial_number)?),
-                asset_tag: Some(structure.find_string(packed.asset_tag)?),
-                part_number: Some(structure.find_string(packed.part_number)?),
-                core_count: Some(packed.core_count as u16),
-                core_enabled: Some(packed.core_enabled as u16),
-                thread_count: Some(packed.thread_count as u16),
-                processor_characteristics: None,
-            })
-        } else if structure.version < (3, 0).into() {
-            let_as_struct!(packed, ProcessorPacked_2_6, structure.data);
-            // smbios spec specifies 0xFE as an indicator to obtain processor
-            // family from the Processor Family 2 field.
-            let processor_family = match packed.processor_family.try_into()? {
-                ProcessorFamily::ProcessorFamily2 => packed.processor_family_2.try_into()?,
-                family => family,
-            };
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: Some(packed.l1_cache_handle),
-                l2_cache_handle: Some(packed.l2_cache_handle),
-                l3_cache_handle: Some(packed.l3_cache_handle),
-                serial_number: Some(structure.find_string(packed.serial_number)?),
-                asset_tag: Some(structure.find_string(packed.asset_tag)?),
-                part_number: Some(structure.find_string(packed.part_number)?),
-                core_count: Some(packed.core_count as u16),
-                core_enabled: Some(packed.core_enabled as u16),
-                thread_count: Some(packed.thread_count as u16),
-                processor_characteristics: Some(ProcessorCharacteristics::from_bits_truncate(
-                    packed.processor_characteristics,
-                )),
-            })
-        } else {
-            let_as_struct!(packed, ProcessorPacked_3_0, structure.data);
-            // smbios spec specifies 0xFE as an indicator to obtain processor
-            // family from the Processor Family 2 field.
-            let processor_family = match packed.processor_family.try_into()? {
-                ProcessorFamily::ProcessorFamily2 => packed.processor_family_2.try_into()?,
-                family => family,
-            };
+    /// A processor is installed in the socket
+    pub fn is_populated(&self) -> bool {
+        self.status.is_populated()
+    }
 
-            // The Core Count 2 field supports core counts > 255. For core counts of 256 or greater, the Core Count
-            // field is set to FFh and the Core Count 2 field is set to the number of cores. For core counts of 255 or
-            // fewer, if Core Count 2 is present it shall be set the same value as Core Count
-            //
-            // The rule is same for Core Enabled and Thread Count as well.
-            let core_count = if packed.core_count == 0xFF {
-                Some(packed.core_count_2)
-            } else {
-                Some(packed.core_count as u16)
-            };
-            let core_enabled = if packed.core_enabled == 0xFF {
-                Some(packed.core_enabled_2)
-            } else {
-                Some(packed.core_enabled as u16)
-            };
-            let thread_count = if packed.thread_count == 0xFF {
-                Some(packed.thread_count_2)
-            } else {
-                Some(packed.thread_count as u16)
-            };
-            Ok(Processor {
-                handle: structure.handle,
-                socket_designation: structure.find_string(packed.socket_designation)?,
-                processor_type: packed.processor_type.into(),
-                processor_family,
-                processor_manufacturer: structure.find_string(packed.processor_manufacturer)?,
-                processor_id: packed.processor_id,
-                processor_version: structure.find_string(packed.processor_version)?,
-                voltage: packed.voltage.into(),
-                external_clock: packed.external_clock,
-                max_speed: packed.max_speed,
-                current_speed: packed.current_speed,
-                status: ProcessorStatus::from_bits_truncate(packed.status),
-                processor_upgrade: packed.processor_upgrade.into(),
-                l1_cache_handle: Some(packed.l1_cache_handle),
-                l2_cache_handle: Some(packed.l2_cache_handle),
-                l3_cache_handle: Some(packed.l3_cache_handle),
-                serial_number: Some(structure.find_string(packed.serial_number)?),
-                asset_tag: Some(structure.find_string(packed.asset_tag)?),
-                part_number: Some(structure.find_string(packed.part_number)?),
-                core_count,
-                core_enabled,
-                thread_count,
-                processor_characteristics: Some(ProcessorCharacteristics::from_bits_truncate(
-                    packed.processor_characteristics,
-                )),
-            })
-        }
+    /// Socket population and CPU status, decoded from the [Processor::status] field
+    pub fn status(&self) -> SocketStatus {
+        self.status.into()
+    }
+
+    /// Compare with `other` ignoring the current speed, which firmware sets at every boot
+    pub fn eq_stable(&self, other: &Self) -> bool {
+        Processor {
+            current_speed: other.current_speed,
+            ..self.clone()
+        } == *other
     }
 }
 
@@ -1097,7 +982,34 @@ impl TryFrom<u16> for ProcessorFamily {
             0x200 => ProcessorFamily::RISCVRV32,
             0x201 => ProcessorFamily::RISCVRV64,
             0x202 => ProcessorFamily::RISCVRV128,
-            n @ 0x203..=0xFFFD => ProcessorFamily::Available(n),
+            n @ 0x203..=0x257 => ProcessorFamily::Available(n),
+            0x258 => ProcessorFamily::Loongson1,
+            0x259 => ProcessorFamily::Loongson2,
+            0x25A => ProcessorFamily::Loongson3,
+            0x25B => ProcessorFamily::Loongson2K,
+            0x25C => ProcessorFamily::Loongson3A,
+            0x25D => ProcessorFamily::Loongson3B,
+            0x25E => ProcessorFamily::Loongson3C,
+            0x25F => ProcessorFamily::Loongson3D,
+            0x260 => ProcessorFamily::Loongson3E,
+            0x261 => ProcessorFamily::DualCoreLoongson2K2xxx,
+            n @ 0x262..=0x26B => ProcessorFamily::Available(n),
+            0x26C => ProcessorFamily::QuadCoreLoongson3A5xxx,
+            0x26D => ProcessorFamily::MultiCoreLoongson3A5xxx,
+            0x26E => ProcessorFamily::QuadCoreLoongson3B5xxx,
+            0x26F => ProcessorFamily::MultiCoreLoongson3B5xxx,
+            0x270 => ProcessorFamily::MultiCoreLoongson3C5xxx,
+            0x271 => ProcessorFamily::MultiCoreLoongson3D5xxx,
+            n @ 0x272..=0x2FF => ProcessorFamily::Available(n),
+            0x300 => ProcessorFamily::IntelCore3,
+            0x301 => ProcessorFamily::IntelCore5,
+            0x302 => ProcessorFamily::IntelCore7,
+            0x303 => ProcessorFamily::IntelCore9,
+            0x304 => ProcessorFamily::IntelCoreUltra3,
+            0x305 => ProcessorFamily::IntelCoreUltra5,
+            0x306 => ProcessorFamily::IntelCoreUltra7,
+            0x307 => ProcessorFamily::IntelCoreUltra9,
+            n @ 0x308..=0xFFFD => ProcessorFamily::Available(n),
             0xFFFE => ProcessorFamily::ForFutureUse,
             n @ 0xFFFF => ProcessorFamily::NotUsed(n),
         };
@@ -1105,6 +1017,7 @@ impl TryFrom<u16> for ProcessorFamily {
         Ok(family)
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for ProcessorFamily {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1483,6 +1396,30 @@ impl fmt::Display for ProcessorFamily {
             ProcessorFamily::RISCVRV32 => write!(f, "RISC-V RV32"),
             ProcessorFamily::RISCVRV64 => write!(f, "RISC-V RV64"),
             ProcessorFamily::RISCVRV128 => write!(f, "RISC-V RV128"),
+            ProcessorFamily::Loongson1 => write!(f, "Loongson 1"),
+            ProcessorFamily::Loongson2 => write!(f, "Loongson 2"),
+            ProcessorFamily::Loongson3 => write!(f, "Loongson 3"),
+            ProcessorFamily::Loongson2K => write!(f, "Loongson 2K"),
+            ProcessorFamily::Loongson3A => write!(f, "Loongson 3A"),
+            ProcessorFamily::Loongson3B => write!(f, "Loongson 3B"),
+            ProcessorFamily::Loongson3C => write!(f, "Loongson 3C"),
+            ProcessorFamily::Loongson3D => write!(f, "Loongson 3D"),
+            ProcessorFamily::Loongson3E => write!(f, "Loongson 3E"),
+            ProcessorFamily::DualCoreLoongson2K2xxx => write!(f, "Dual-Core Loongson 2K 2xxx"),
+            ProcessorFamily::QuadCoreLoongson3A5xxx => write!(f, "Quad-Core Loongson 3A 5xxx"),
+            ProcessorFamily::MultiCoreLoongson3A5xxx => write!(f, "Multi-Core Loongson 3A 5xxx"),
+            ProcessorFamily::QuadCoreLoongson3B5xxx => write!(f, "Quad-Core Loongson 3B 5xxx"),
+            ProcessorFamily::MultiCoreLoongson3B5xxx => write!(f, "Multi-Core Loongson 3B 5xxx"),
+            ProcessorFamily::MultiCoreLoongson3C5xxx => write!(f, "Multi-Core Loongson 3C 5xxx"),
+            ProcessorFamily::MultiCoreLoongson3D5xxx => write!(f, "Multi-Core Loongson 3D 5xxx"),
+            ProcessorFamily::IntelCore3 => write!(f, "Intel® Core™ 3"),
+            ProcessorFamily::IntelCore5 => write!(f, "Intel® Core™ 5"),
+            ProcessorFamily::IntelCore7 => write!(f, "Intel® Core™ 7"),
+            ProcessorFamily::IntelCore9 => write!(f, "Intel® Core™ 9"),
+            ProcessorFamily::IntelCoreUltra3 => write!(f, "Intel® Core™ Ultra 3"),
+            ProcessorFamily::IntelCoreUltra5 => write!(f, "Intel® Core™ Ultra 5"),
+            ProcessorFamily::IntelCoreUltra7 => write!(f, "Intel® Core™ Ultra 7"),
+            ProcessorFamily::IntelCoreUltra9 => write!(f, "Intel® Core™ Ultra 9"),
             ProcessorFamily::ForFutureUse => write!(f, "For special use in the future"),
             ProcessorFamily::ProcessorFamily2 => {
                 write!(f, "Processor Family 2 has the enumerated value")
@@ -1493,6 +1430,17 @@ impl fmt::Display for ProcessorFamily {
     }
 }
 
+impl Voltage {
+    /// The current voltage of the processor in millivolts, `None` for the voltage capabilities of
+    /// legacy sockets
+    pub fn millivolts(&self) -> Option<u16> {
+        match *self {
+            Self::Current(v) => Some(v as u16 * 100),
+            _ => None,
+        }
+    }
+}
+
 impl From<u8> for Voltage {
     fn from(byte: u8) -> Self {
         if (byte & 0b1000_0000) == 0 {
@@ -1506,10 +1454,11 @@ impl From<u8> for Voltage {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for Voltage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Current(v) => write!(f, "Current voltage: {:.1} V", *v as f32 / 10.0),
+            Self::Current(v) => write!(f, "Current voltage: {}.{} V", v / 10, v % 10),
             Self::Undefined(n) => write!(f, "Undefined {:#b}", n),
             Self::Legacy(legacy) => {
                 let s55 = if legacy.contains(VoltageLegacy::VOLTAGE_CAPABILITY_5V0) {
@@ -1602,10 +1551,29 @@ impl From<u8> for ProcessorUpgrade {
             0x3c => ProcessorUpgrade::SocketBGA1528,
             0x3d => ProcessorUpgrade::SocketLGA4189,
             0x3e => ProcessorUpgrade::SocketLGA1200,
+            0x3f => ProcessorUpgrade::SocketLGA4677,
+            0x40 => ProcessorUpgrade::SocketLGA1700,
+            0x41 => ProcessorUpgrade::SocketBGA1744,
+            0x42 => ProcessorUpgrade::SocketBGA1781,
+            0x43 => ProcessorUpgrade::SocketBGA1211,
+            0x44 => ProcessorUpgrade::SocketBGA2422,
+            0x45 => ProcessorUpgrade::SocketLGA1211,
+            0x46 => ProcessorUpgrade::SocketLGA2422,
+            0x47 => ProcessorUpgrade::SocketLGA5773,
+            0x48 => ProcessorUpgrade::SocketBGA5773,
+            0x49 => ProcessorUpgrade::SocketAM5,
+            0x4a => ProcessorUpgrade::SocketSP5,
+            0x4b => ProcessorUpgrade::SocketSP6,
+            0x4c => ProcessorUpgrade::SocketBGA883,
+            0x4d => ProcessorUpgrade::SocketBGA1190,
+            0x4e => ProcessorUpgrade::SocketBGA4129,
+            0x4f => ProcessorUpgrade::SocketLGA4710,
+            0x50 => ProcessorUpgrade::SocketLGA7529,
             n => ProcessorUpgrade::Undefined(n),
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for ProcessorUpgrade {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -1671,18 +1639,393 @@ impl fmt::Display for ProcessorUpgrade {
             ProcessorUpgrade::SocketBGA1528 => write!(f, "Socket BGA1528"),
             ProcessorUpgrade::SocketLGA4189 => write!(f, "Socket LGA4189"),
             ProcessorUpgrade::SocketLGA1200 => write!(f, "Socket LGA1200"),
+            ProcessorUpgrade::SocketLGA4677 => write!(f, "Socket LGA4677"),
+            ProcessorUpgrade::SocketLGA1700 => write!(f, "Socket LGA1700"),
+            ProcessorUpgrade::SocketBGA1744 => write!(f, "Socket BGA1744"),
+            ProcessorUpgrade::SocketBGA1781 => write!(f, "Socket BGA1781"),
+            ProcessorUpgrade::SocketBGA1211 => write!(f, "Socket BGA1211"),
+            ProcessorUpgrade::SocketBGA2422 => write!(f, "Socket BGA2422"),
+            ProcessorUpgrade::SocketLGA1211 => write!(f, "Socket LGA1211"),
+            ProcessorUpgrade::SocketLGA2422 => write!(f, "Socket LGA2422"),
+            ProcessorUpgrade::SocketLGA5773 => write!(f, "Socket LGA5773"),
+            ProcessorUpgrade::SocketBGA5773 => write!(f, "Socket BGA5773"),
+            ProcessorUpgrade::SocketAM5 => write!(f, "Socket AM5"),
+            ProcessorUpgrade::SocketSP5 => write!(f, "Socket SP5"),
+            ProcessorUpgrade::SocketSP6 => write!(f, "Socket SP6"),
+            ProcessorUpgrade::SocketBGA883 => write!(f, "Socket BGA883"),
+            ProcessorUpgrade::SocketBGA1190 => write!(f, "Socket BGA1190"),
+            ProcessorUpgrade::SocketBGA4129 => write!(f, "Socket BGA4129"),
+            ProcessorUpgrade::SocketLGA4710 => write!(f, "Socket LGA4710"),
+            ProcessorUpgrade::SocketLGA7529 => write!(f, "Socket LGA7529"),
             ProcessorUpgrade::Undefined(n) => write!(f, "Undefined {}", n),
         }
     }
 }
 
+/// Processor vendor, normalized from the manufacturer string and the processor family
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Vendor {
+    Intel,
+    Amd,
+    Hygon,
+    Zhaoxin,
+    Via,
+    Arm,
+    Ampere,
+    Ibm,
+    Loongson,
+}
+
+impl Vendor {
+    /// Vendor whose processors report the x86 CPUID signature in the Processor ID field
+    fn is_x86(self) -> bool {
+        matches!(
+            self,
+            Vendor::Intel | Vendor::Amd | Vendor::Hygon | Vendor::Zhaoxin | Vendor::Via
+        )
+    }
+
+    /// Vendor that owns a processor family value
+    fn from_family(family: ProcessorFamily) -> Option<Vendor> {
+        use self::ProcessorFamily as F;
+        match family {
+            F::Intel8086
+            | F::Intel80286
+            | F::Intel386Processor
+            | F::Intel486Processor
+            | F::Intel8087
+            | F::Intel80287
+            | F::Intel80387
+            | F::Intel80487
+            | F::IntelPentiumProcessor
+            | F::PentiumProProcessor
+            | F::PentiumIIProcessor
+            | F::PentiumProcessorWithMMXTechnology
+            | F::IntelCeleronProcessor
+            | F::PentiumIIXeonProcessor
+            | F::PentiumIIIProcessor
+            | F::IntelCeleronMProcessor
+            | F::IntelPentium4HTProcessor
+            | F::IntelCoreDuoProcessor
+            | F::IntelCoreDuoMobileProcessor
+            | F::IntelCoreSoloMobileProcessor
+            | F::IntelAtomProcessor
+            | F::IntelCoreMProcessor
+            | F::IntelCoreM3Processor
+            | F::IntelCoreM5Processor
+            | F::IntelCoreM7Processor
+            | F::ItaniumProcessor
+            | F::QuadCoreIntelXeonProcessor3200Series
+            | F::DualCoreIntelXeonProcessor3000Series
+            | F::QuadCoreIntelXeonProcessor5300Series
+            | F::DualCoreIntelXeonProcessor5100Series
+            | F::DualCoreIntelXeonProcessor5000Series
+            | F::DualCoreIntelXeonProcessorLV
+            | F::DualCoreIntelXeonProcessorULV
+            | F::DualCoreIntelXeonProcessor7100Series
+            | F::QuadCoreIntelXeonProcessor5400Series
+            | F::QuadCoreIntelXeonProcessor
+            | F::DualCoreIntelXeonProcessor5200Series
+            | F::DualCoreIntelXeonProcessor7200Series
+            | F::QuadCoreIntelXeonProcessor7300Series
+            | F::QuadCoreIntelXeonProcessor7400Series
+            | F::MultiCoreIntelXeonProcessor7400Series
+            | F::PentiumIIIXeonProcessor
+            | F::PentiumIIIProcessorWithIntelSpeedStepTechnology
+            | F::Pentium4Processor
+            | F::IntelXeonProcessor
+            | F::IntelXeonProcessorMP
+            | F::IntelItanium2Processor
+            | F::IntelPentiumMProcessor
+            | F::IntelCeleronDProcessor
+            | F::IntelPentiumDProcessor
+            | F::IntelPentiumProcessorExtremeEdition
+            | F::IntelCoreSoloProcessor
+            | F::IntelCore2DuoProcessor
+            | F::IntelCore2SoloProcessor
+            | F::IntelCore2ExtremeProcessor
+            | F::IntelCore2QuadProcessor
+            | F::IntelCore2ExtremeMobileProcessor
+            | F::IntelCore2DuoMobileProcessor
+            | F::IntelCore2SoloMobileProcessor
+            | F::IntelCoreI7Processor
+            | F::DualCoreIntelCeleronProcessor
+            | F::IntelCoreI5Processor
+            | F::IntelCoreI3Processor
+            | F::IntelCoreI9Processor
+            | F::MultiCoreIntelXeonProcessor
+            | F::DualCoreIntelXeonProcessor3xxxSeries
+            | F::QuadCoreIntelXeonProcessor3xxxSeries
+            | F::DualCoreIntelXeonProcessor5xxxSeries
+            | F::QuadCoreIntelXeonProcessor5xxxSeries
+            | F::DualCoreIntelXeonProcessor7xxxSeries
+            | F::QuadCoreIntelXeonProcessor7xxxSeries
+            | F::MultiCoreIntelXeonProcessor7xxxSeries
+            | F::MultiCoreIntelXeonProcessor3400Series
+            | F::I860
+            | F::I960
+            | F::IntelCore3
+            | F::IntelCore5
+            | F::IntelCore7
+            | F::IntelCore9
+            | F::IntelCoreUltra3
+            | F::IntelCoreUltra5
+            | F::IntelCoreUltra7
+            | F::IntelCoreUltra9 => Some(Vendor::Intel),
+            F::AMDDuronProcessorFamily
+            | F::K5Family
+            | F::K6Family
+            | F::K62
+            | F::K63
+            | F::AMDAthlonProcessorFamily
+            | F::AMD29000Family
+            | F::K62Plus
+            | F::AMDTurionIIUltraDualCoreMobileMProcessorFamily
+            | F::AMDTurionIIDualCoreMobileMProcessorFamily
+            | F::AMDAthlonIIDualCoreMProcessorFamily
+            | F::AMDOpteron6100SeriesProcessor
+            | F::AMDOpteron4100SeriesProcessor
+            | F::AMDOpteron6200SeriesProcessor
+            | F::AMDOpteron4200SeriesProcessor
+            | F::AMDFXSeriesProcessor
+            | F::AMDCSeriesProcessor
+            | F::AMDESeriesProcessor
+            | F::AMDASeriesProcessor
+            | F::AMDGSeriesProcessor
+            | F::AMDZSeriesProcessor
+            | F::AMDRSeriesProcessor
+            | F::AMDOpteron4300SeriesProcessor
+            | F::AMDOpteron6300SeriesProcessor
+            | F::AMDOpteron3300SeriesProcessor
+            | F::AMDFireProSeriesProcessor
+            | F::AMDAthlonX4QuadCoreProcessorFamily
+            | F::AMDOpteronX1000SeriesProcessor
+            | F::AMDOpteronX2000SeriesAPU
+            | F::AMDOpteronASeriesProcessor
+            | F::AMDOpteronX3000SeriesAPU
+            | F::AMDZenProcessorFamily
+            | F::AMDAthlon64ProcessorFamily
+            | F::AMDOpteronProcessorFamily
+            | F::AMDSempronProcessorFamily
+            | F::AMDTurion64MobileTechnology
+            | F::DualCoreAMDOpteronProcessorFamily
+            | F::AMDAthlon64X2DualCoreProcessorFamily
+            | F::AMDTurion64X2MobileTechnology
+            | F::QuadCoreAMDOpteronProcessorFamily
+            | F::ThirdGenerationAMDOpteronProcessorFamily
+            | F::AMDPhenomFXQuadCoreProcessorFamily
+            | F::AMDPhenomX4QuadCoreProcessorFamily
+            | F::AMDPhenomX2DualCoreProcessorFamily
+            | F::AMDAthlonX2DualCoreProcessorFamily
+            | F::AMDAthlonXPProcessorFamily
+            | F::AMDAthlonMPProcessorFamily
+            | F::AMDOpteron3000SeriesProcessor
+            | F::AMDSempronIIProcessor
+            | F::EmbeddedAMDOpteronQuadCoreProcessorFamily
+            | F::AMDPhenomTripleCoreProcessorFamily
+            | F::AMDTurionUltraDualCoreMobileProcessorFamily
+            | F::AMDTurionDualCoreMobileProcessorFamily
+            | F::AMDAthlonDualCoreProcessorFamily
+            | F::AMDSempronSIProcessorFamily
+            | F::AMDPhenomIIProcessorFamily
+            | F::AMDAthlonIIProcessorFamily
+            | F::SixCoreAMDOpteronProcessorFamily
+            | F::AMDSempronMProcessorFamily => Some(Vendor::Amd),
+            F::VIAC7MProcessorFamily
+            | F::VIAC7DProcessorFamily
+            | F::VIAC7ProcessorFamily
+            | F::VIAEdenProcessorFamily
+            | F::VIANanoProcessorFamily => Some(Vendor::Via),
+            F::ARMv7 | F::ARMv8 | F::ARMv9 | F::ARM | F::StrongARM => Some(Vendor::Arm),
+            F::PowerPCFamily
+            | F::PowerPC601
+            | F::PowerPC603
+            | F::PowerPC603Plus
+            | F::PowerPC604
+            | F::PowerPC620
+            | F::PowerPCX704
+            | F::PowerPC750
+            | F::AS400Family
+            | F::IBM390Family
+            | F::G4
+            | F::G5
+            | F::ESA390G6
+            | F::ZArchitectureBase => Some(Vendor::Ibm),
+            F::Loongson1
+            | F::Loongson2
+            | F::Loongson3
+            | F::Loongson2K
+            | F::Loongson3A
+            | F::Loongson3B
+            | F::Loongson3C
+            | F::Loongson3D
+            | F::Loongson3E
+            | F::DualCoreLoongson2K2xxx
+            | F::QuadCoreLoongson3A5xxx
+            | F::MultiCoreLoongson3A5xxx
+            | F::QuadCoreLoongson3B5xxx
+            | F::MultiCoreLoongson3B5xxx
+            | F::MultiCoreLoongson3C5xxx
+            | F::MultiCoreLoongson3D5xxx => Some(Vendor::Loongson),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Display for Vendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vendor::Intel => write!(f, "Intel"),
+            Vendor::Amd => write!(f, "AMD"),
+            Vendor::Hygon => write!(f, "Hygon"),
+            Vendor::Zhaoxin => write!(f, "Zhaoxin"),
+            Vendor::Via => write!(f, "VIA"),
+            Vendor::Arm => write!(f, "ARM"),
+            Vendor::Ampere => write!(f, "Ampere"),
+            Vendor::Ibm => write!(f, "IBM"),
+            Vendor::Loongson => write!(f, "Loongson"),
+        }
+    }
+}
+
+/// Prefixes of the Processor Manufacturer string, compared case-insensitively, including the
+/// CPUID vendor identification strings some firmware copies there
+const MANUFACTURER_SIGNATURES: &[(&str, Vendor)] = &[
+    ("Intel", Vendor::Intel),
+    ("GenuineIntel", Vendor::Intel),
+    ("AMD", Vendor::Amd),
+    ("Advanced Micro Devices", Vendor::Amd),
+    ("AuthenticAMD", Vendor::Amd),
+    ("Hygon", Vendor::Hygon),
+    ("Zhaoxin", Vendor::Zhaoxin),
+    ("Shanghai Zhaoxin", Vendor::Zhaoxin),
+    ("VIA", Vendor::Via),
+    ("CentaurHauls", Vendor::Via),
+    ("Ampere", Vendor::Ampere),
+    ("ARM", Vendor::Arm),
+    ("IBM", Vendor::Ibm),
+    ("Loongson", Vendor::Loongson),
+];
+
+/// The x86 processor signature, as returned by CPUID leaf 1 in EAX
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Signature {
+    /// Processor type, bits 13:12
+    pub processor_type: u8,
+    /// Family, the extended family added when the base family is Fh
+    pub family: u16,
+    /// Model, extended by the extended model when the base family is 6 or Fh
+    pub model: u8,
+    pub stepping: u8,
+}
+
+impl Signature {
+    /// Decode the low doubleword of the Processor ID field, `None` if it is zero
+    pub fn from_processor_id(processor_id: u64) -> Option<Signature> {
+        let eax = processor_id as u32;
+        if eax == 0 {
+            return None;
+        }
+        let base_family = ((eax >> 8) & 0x0F) as u16;
+        let base_model = ((eax >> 4) & 0x0F) as u8;
+        let extended_model = ((eax >> 16) & 0x0F) as u8;
+        let family = match base_family {
+            0x0F => base_family + ((eax >> 20) & 0xFF) as u16,
+            _ => base_family,
+        };
+        let model = match base_family {
+            0x06 | 0x0F => (extended_model << 4) | base_model,
+            _ => base_model,
+        };
+        Some(Signature {
+            processor_type: ((eax >> 12) & 0x03) as u8,
+            family,
+            model,
+            stepping: (eax & 0x0F) as u8,
+        })
+    }
+}
+
+/// Formatted as in the dmidecode output, e.g. `Type 0, Family 6, Model 77, Stepping 8`
+#[cfg(feature = "strings")]
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Type {}, Family {}, Model {}, Stepping {}",
+            self.processor_type, self.family, self.model, self.stepping
+        )
+    }
+}
+
+/// Normalized identity of a processor, see [identity]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Identity<'buffer> {
+    /// `None` if neither the manufacturer string nor the family tell the vendor
+    pub vendor: Option<Vendor>,
+    /// Processor family, displayed as the family name
+    pub family: ProcessorFamily,
+    /// Processor Version string, trimmed, `None` if empty or a vendor placeholder
+    pub model_hint: Option<&'buffer str>,
+    /// CPUID signature of x86 processors
+    pub signature: Option<Signature>,
+}
+
+/// Combine the manufacturer, family, version and ID fields of a processor into a normalized
+/// identity
+///
+/// The manufacturer string takes precedence over the family, which firmware often leaves as
+/// Other or Unknown; the family is a fallback for manufacturer strings that are empty or
+/// unrecognized.
+///
+/// # Example
+///
+/// ```
+/// # extern crate dmidecode;
+/// use dmidecode::{processor::{identity, Vendor}, EntryPoint, Structure};
+///
+/// const DMIDECODE_BIN: &'static [u8] = include_bytes!("../../tests/data/dmidecode.bin");
+///
+/// let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+/// let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+/// for structure in structures.flatten() {
+///     if let Structure::Processor(processor) = structure {
+///         assert_eq!(Some(Vendor::Intel), identity(&processor).vendor);
+///     }
+/// }
+/// ```
+pub fn identity<'buffer>(processor: &Processor<'buffer>) -> Identity<'buffer> {
+    let manufacturer = processor.processor_manufacturer.trim();
+    let vendor = MANUFACTURER_SIGNATURES
+        .iter()
+        .find(|(signature, _)| {
+            manufacturer
+                .get(..signature.len())
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case(signature))
+        })
+        .map(|&(_, vendor)| vendor)
+        .or_else(|| Vendor::from_family(processor.processor_family));
+    Identity {
+        vendor,
+        family: processor.processor_family,
+        model_hint: normalize(processor.processor_version),
+        signature: vendor
+            .filter(|vendor| vendor.is_x86())
+            .and_then(|_| Signature::from_processor_id(processor.processor_id)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "strings")]
     use core::convert::TryInto;
 
     use super::*;
-    use crate::InfoType;
+    use crate::{testing::StructureBuilder, InfoType};
 
+    #[cfg(feature = "strings")]
     #[test]
     fn processor_family() {
         use super::ProcessorFamily::*;
@@ -1722,7 +2065,17 @@ mod tests {
                 n @ 0xF0..=0xF9 => (Available(n), format!("Available {:#X}", n)),
                 n @ 0xFC..=0xFD => (Available(n), format!("Available {:#X}", n)),
                 n @ 0x1F5..=0x1FF => (Available(n), format!("Available {:#X}", n)),
-                n @ 0x203..=0xFFFD => (Available(n), format!("Available {:#X}", n)),
+                0x202 => (RISCVRV128, "RISC-V RV128".into()),
+                0x258 => (Loongson1, "Loongson 1".into()),
+                0x261 => (DualCoreLoongson2K2xxx, "Dual-Core Loongson 2K 2xxx".into()),
+                0x26C => (QuadCoreLoongson3A5xxx, "Quad-Core Loongson 3A 5xxx".into()),
+                0x271 => (MultiCoreLoongson3D5xxx, "Multi-Core Loongson 3D 5xxx".into()),
+                0x300 => (IntelCore3, "Intel® Core™ 3".into()),
+                0x307 => (IntelCoreUltra9, "Intel® Core™ Ultra 9".into()),
+                n @ 0x203..=0x257 => (Available(n), format!("Available {:#X}", n)),
+                n @ 0x262..=0x26B => (Available(n), format!("Available {:#X}", n)),
+                n @ 0x272..=0x2FF => (Available(n), format!("Available {:#X}", n)),
+                n @ 0x308..=0xFFFD => (Available(n), format!("Available {:#X}", n)),
                 n @ 0xFF => (
                     NotUsed(n),
                     format!("Not used. {:X}h is the un-initialized value of Flash memory.", n),
@@ -1741,6 +2094,7 @@ mod tests {
 
     #[test]
     fn processor_voltage() {
+        #[cfg(feature = "strings")]
         let test_data = [
             (
                 0b0000_0000,
@@ -1768,15 +2122,23 @@ mod tests {
             ),
             (0b0000_1000, Voltage::Undefined(8), "Undefined 0b1000"),
             (0b1001_0010, Voltage::Current(18), "Current voltage: 1.8 V"),
+            (0b1000_0101, Voltage::Current(5), "Current voltage: 0.5 V"),
             (0b1111_1111, Voltage::Current(127), "Current voltage: 12.7 V"),
         ];
-        for (byte, sample, display) in test_data.iter() {
-            let result = Voltage::from(*byte);
-            assert_eq!(*sample, result, "Byte: {:#b}", byte);
-            assert_eq!(format!("{}", result), format!("{}", display), "Byte: {:#b}", byte);
+        #[cfg(feature = "strings")]
+        {
+            for (byte, sample, display) in test_data.iter() {
+                let result = Voltage::from(*byte);
+                assert_eq!(*sample, result, "Byte: {:#b}", byte);
+                assert_eq!(format!("{}", result), format!("{}", display), "Byte: {:#b}", byte);
+            }
         }
+        assert_eq!(Some(1800), Voltage::from(0b1001_0010).millivolts());
+        assert_eq!(Some(12700), Voltage::from(0xFF).millivolts());
+        assert_eq!(None, Voltage::from(0b0000_0011).millivolts());
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn processor_upgrade() {
         use super::ProcessorUpgrade::*;
@@ -1787,7 +2149,12 @@ mod tests {
                 0x18 => (SocketF, "Socket F (1207)".into()),
                 0x2B => (SocketLGA2011Three, "Socket LGA2011-3".into()),
                 0x3E => (SocketLGA1200, "Socket LGA1200".into()),
-                n @ 0x3F..=0xFF => (Undefined(n), format!("Undefined {}", n)),
+                0x3F => (SocketLGA4677, "Socket LGA4677".into()),
+                0x40 => (SocketLGA1700, "Socket LGA1700".into()),
+                0x49 => (SocketAM5, "Socket AM5".into()),
+                0x4A => (SocketSP5, "Socket SP5".into()),
+                0x50 => (SocketLGA7529, "Socket LGA7529".into()),
+                n @ 0x51..=0xFF => (Undefined(n), format!("Undefined {}", n)),
                 _ => continue,
             };
             assert_eq!(e, i.into(), "{:#x}", i);
@@ -1795,59 +2162,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn socket_status() {
+        let status = |bits| SocketStatus::from(ProcessorStatus::from_bits_truncate(bits));
+        assert_eq!(
+            SocketStatus {
+                populated: true,
+                cpu: CpuStatus::DisabledByBios
+            },
+            status(0b0100_0011)
+        );
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!("Populated, Enabled", format!("{}", status(0b0100_0001)));
+            assert_eq!("Populated, Disabled By User", format!("{}", status(0b0100_0010)));
+            assert_eq!("Unpopulated", format!("{}", status(0b0000_0000)));
+        }
+        assert_eq!(CpuStatus::Other, status(0b0000_0111).cpu);
+        assert_eq!(CpuStatus::Undefined(5), status(0b0100_0101).cpu);
+        // Disabled by BIOS is not Enabled, even though the bits overlap
+        assert!(ProcessorStatus::from_bits_truncate(0b0100_0011).contains(ProcessorStatus::CPU_ENABLED));
+        assert_ne!(CpuStatus::Enabled, status(0b0100_0011).cpu);
+    }
+
     #[test]
     fn smbios_2_8_processor_intel_atom_parses() {
-        let structure = RawStructure {
-            version: (2, 8).into(),
-            info: InfoType::Processor,
-            length: 0x2a,
-            handle: 0x48,
-            // data and strings from processor handler, eg: dmidecode -H 0x48 -u
-            data: &[
-                // omit first 4 header bytes
-                // 04 // type
-                // 2a // length
-                // 48 00 // handle
-                0x01, // socket_designation
-                0x03, // processor_type
-                0x2b, // processor_family
-                0x02, // processor_manufacturer
-                0xd8, 0x06, 0x04, 0x00, 0xff, 0xfb, 0xeb, 0xbf, // processor_id
-                0x03, // processor_version
-                0x90, // voltage
-                0x64, 0x00, // external_clock
-                0x28, 0x0a, // max_speed
-                0x60, 0x09, // current_speed
-                0x41, // status
-                0x01, // processor_upgrade
-                0x46, 0x00, // l1_cache
-                0x47, 0x00, // l2_cache
-                0xff, 0xff, // l3_cache
-                0x00, // serial_number
-                0x04, // asset_tag
-                0x00, // part_number
-                0x08, // core_count
-                0x08, // core_enabled
-                0x08, // thread_count
-                0x04, 0x00, // processor_characteristics
-                0x00, 0x00, // processor_family2
-            ],
-            strings: &[
-                // CPU0
-                0x43, 0x50, 0x55, 0x30, 0x00, // Intel(R) Corporation
-                0x49, 0x6E, 0x74, 0x65, 0x6C, 0x28, 0x52, 0x29, 0x20, 0x43, 0x6F, 0x72, 0x70, 0x6F, 0x72, 0x61, 0x74,
-                0x69, 0x6F, 0x6E, 0x00, // Intel(R) Atom(TM) CPU  C2750  @ 2.40GHz
-                0x49, 0x6E, 0x74, 0x65, 0x6C, 0x28, 0x52, 0x29, 0x20, 0x41, 0x74, 0x6F, 0x6D, 0x28, 0x54, 0x4D, 0x29,
-                0x20, 0x43, 0x50, 0x55, 0x20, 0x20, 0x43, 0x32, 0x37, 0x35, 0x30, 0x20, 0x20, 0x40, 0x20, 0x32, 0x2E,
-                0x34, 0x30, 0x47, 0x48, 0x7A, 0x00, // ProcessorInfo_ASSET_TAG
-                0x50, 0x72, 0x6F, 0x63, 0x65, 0x73, 0x73, 0x6F, 0x72, 0x49, 0x6E, 0x66, 0x6F, 0x5F, 0x41, 0x53, 0x53,
-                0x45, 0x54, 0x5F, 0x54, 0x41, 0x47, 0x00,
-            ],
-        };
+        // fields from processor handler, eg: dmidecode -H 0x48 -u
+        let builder = StructureBuilder::new(InfoType::Processor)
+            .version((2, 8).into())
+            .handle(Handle(0x48))
+            .string("CPU0") // socket_designation
+            .byte(0x03) // processor_type
+            .byte(0x2b) // processor_family
+            .string("Intel(R) Corporation") // processor_manufacturer
+            .qword(0xbfeb_fbff_0004_06d8) // processor_id
+            .string("Intel(R) Atom(TM) CPU  C2750  @ 2.40GHz") // processor_version
+            .byte(0x90) // voltage
+            .word(100) // external_clock
+            .word(2600) // max_speed
+            .word(2400) // current_speed
+            .byte(0x41) // status
+            .byte(0x01) // processor_upgrade
+            .handle_ref(Handle(0x46)) // l1_cache
+            .handle_ref(Handle(0x47)) // l2_cache
+            .handle_ref(Handle::NONE) // l3_cache
+            .string("") // serial_number
+            .string("ProcessorInfo_ASSET_TAG") // asset_tag
+            .string("") // part_number
+            .byte(0x08) // core_count
+            .byte(0x08) // core_enabled
+            .byte(0x08) // thread_count
+            .word(0x0004) // processor_characteristics
+            .word(0x0000); // processor_family2
+        let structure = builder.raw();
+        assert_eq!(0x2a, structure.length);
 
         assert_eq!(
             Processor {
-                handle: 0x48,
+                handle: Handle(0x48),
                 socket_designation: "CPU0",
                 processor_type: ProcessorType::CentralProcessor,
                 processor_family: ProcessorFamily::IntelAtomProcessor,
@@ -1860,9 +2232,9 @@ mod tests {
                 current_speed: 2400,
                 status: ProcessorStatus::from_bits_truncate(0b0100_0001),
                 processor_upgrade: ProcessorUpgrade::Other,
-                l1_cache_handle: Some(70),
-                l2_cache_handle: Some(71),
-                l3_cache_handle: Some(65535),
+                l1_cache_handle: Some(Handle(70)),
+                l2_cache_handle: Some(Handle(71)),
+                l3_cache_handle: None,
                 serial_number: Some(""),
                 asset_tag: Some("ProcessorInfo_ASSET_TAG"),
                 part_number: Some(""),
@@ -1871,8 +2243,11 @@ mod tests {
                 thread_count: Some(8),
                 processor_characteristics: Some(ProcessorCharacteristics::from_bits_truncate(0b0000_0100)),
             },
-            Processor::try_from(structure).unwrap()
+            Processor::from_raw(structure.clone()).unwrap()
         );
+        let processor = Processor::from_raw(structure).unwrap();
+        assert!(processor.is_populated());
+        assert_eq!(CpuStatus::Enabled, processor.status().cpu);
     }
 
     #[test]
@@ -1882,7 +2257,7 @@ mod tests {
             version: (2, 8).into(),
             info: InfoType::Processor,
             length: 0x2a,
-            handle: 0x48,
+            handle: Handle(0x48),
             // data and strings from processor handler, eg: dmidecode -H 0x48 -u
             // $ hexdump -s 0x4c6 -n 42 -C processor_bin
             data: &[
@@ -1921,11 +2296,12 @@ mod tests {
                 0x46, 0x41, 0x4b, 0x45, 0x20, 0x56, 0x45, 0x52, 0x53, 0x49, 0x4f, 0x4e, 0x00, // FAKE ASSET
                 0x46, 0x41, 0x4b, 0x45, 0x20, 0x41, 0x53, 0x53, 0x45, 0x54, 0x20, 0x54, 0x41, 0x47, 0x00,
             ],
+            ..Default::default()
         };
 
         assert_eq!(
             Processor {
-                handle: 0x48,
+                handle: Handle(0x48),
                 socket_designation: "CPU0",
                 processor_type: ProcessorType::CentralProcessor,
                 processor_family: ProcessorFamily::ARM,
@@ -1938,9 +2314,9 @@ mod tests {
                 current_speed: 2400,
                 status: ProcessorStatus::from_bits_truncate(0b0100_0001),
                 processor_upgrade: ProcessorUpgrade::Other,
-                l1_cache_handle: Some(70),
-                l2_cache_handle: Some(71),
-                l3_cache_handle: Some(65535),
+                l1_cache_handle: Some(Handle(70)),
+                l2_cache_handle: Some(Handle(71)),
+                l3_cache_handle: None,
                 serial_number: Some(""),
                 asset_tag: Some("FAKE ASSET TAG"),
                 part_number: Some(""),
@@ -1949,7 +2325,89 @@ mod tests {
                 thread_count: Some(8),
                 processor_characteristics: Some(ProcessorCharacteristics::from_bits_truncate(0b0000_0100)),
             },
-            Processor::try_from(structure).unwrap()
+            Processor::from_raw(structure).unwrap()
+        );
+    }
+
+    #[test]
+    fn processor_identity() {
+        let processor = |manufacturer, family, version, processor_id| Processor {
+            handle: Handle(0x0400),
+            socket_designation: "CPU0",
+            processor_type: ProcessorType::CentralProcessor,
+            processor_family: family,
+            processor_manufacturer: manufacturer,
+            processor_id,
+            processor_version: version,
+            voltage: Voltage::Current(16),
+            external_clock: 100,
+            max_speed: 2600,
+            current_speed: 2400,
+            status: ProcessorStatus::from_bits_truncate(0b0100_0001),
+            processor_upgrade: ProcessorUpgrade::Other,
+            l1_cache_handle: None,
+            l2_cache_handle: None,
+            l3_cache_handle: None,
+            serial_number: None,
+            asset_tag: None,
+            part_number: None,
+            core_count: None,
+            core_enabled: None,
+            thread_count: None,
+            processor_characteristics: None,
+        };
+
+        let atom = processor(
+            "Intel(R) Corporation",
+            ProcessorFamily::IntelAtomProcessor,
+            "Intel(R) Atom(TM) CPU  C2750  @ 2.40GHz ",
+            0xbfeb_fbff_0004_06d8,
+        );
+        let signature = Signature {
+            processor_type: 0,
+            family: 6,
+            model: 0x4D,
+            stepping: 8,
+        };
+        assert_eq!(
+            Identity {
+                vendor: Some(Vendor::Intel),
+                family: ProcessorFamily::IntelAtomProcessor,
+                model_hint: Some("Intel(R) Atom(TM) CPU  C2750  @ 2.40GHz"),
+                signature: Some(signature),
+            },
+            identity(&atom)
+        );
+        #[cfg(feature = "strings")]
+        assert_eq!("Type 0, Family 6, Model 77, Stepping 8", format!("{}", signature));
+
+        // Family Fh adds the extended family, e.g. AMD Zen 3
+        let epyc = processor(
+            "AuthenticAMD",
+            ProcessorFamily::AMDZenProcessorFamily,
+            "",
+            0x178b_fbff_00a0_0f11,
+        );
+        let identity_epyc = identity(&epyc);
+        assert_eq!(Some(Vendor::Amd), identity_epyc.vendor);
+        assert_eq!(None, identity_epyc.model_hint);
+        assert_eq!(
+            Some((0x19, 0x01, 1)),
+            identity_epyc.signature.map(|s| (s.family, s.model, s.stepping))
+        );
+
+        // The family tells the vendor when the manufacturer string does not
+        let graviton = processor(
+            "To Be Filled By O.E.M.",
+            ProcessorFamily::ARMv8,
+            "",
+            0x0000_0000_413f_d0c1,
+        );
+        assert_eq!(
+            (Some(Vendor::Arm), None),
+            (identity(&graviton).vendor, identity(&graviton).signature)
         );
+        let unknown = processor("", ProcessorFamily::Unknown, "", 0);
+        assert_eq!(None, identity(&unknown).vendor);
     }
 }
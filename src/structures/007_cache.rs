@@ -5,9 +5,11 @@
 //! the CPU module. Cache modules can be associated with a processor structure in one or two ways
 //! depending on the SMBIOS version.
 
+use core::convert::TryFrom;
+#[cfg(feature = "strings")]
 use core::fmt;
 
-use crate::{MalformedStructureError, RawStructure};
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
 /// The `Cache Information` table defined in the SMBIOS specification.
 ///
@@ -15,7 +17,7 @@ use crate::{MalformedStructureError, RawStructure};
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Cache<'buffer> {
-    pub handle: u16,
+    pub handle: Handle,
     /// String number for reference designation EXAMPLE: “CACHE1”, 0
     pub socket_designation: &'buffer str,
     /// Cache Configuration
@@ -47,18 +49,10 @@ pub struct Cache<'buffer> {
     pub installed_size_2: Option<CacheSize2>,
 }
 
+/// Cache Configuration word, decoded with `CacheConfiguration::from`
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct CacheConfiguration {
-    /// Cache Level – 1 through 8
-    level: CacheLevel,
-    /// Cache Socketed (e.g. Cache on a Stick)
-    socketed: bool,
-    /// Location, relative to the CPU module
-    location: CacheLocation,
-    /// Enabled/Disabled (at boot time)
-    enabled_at_boot_time: bool,
-    /// Operational Mode
-    operational_mode: CacheOperationalMode,
+    raw: u16,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -71,6 +65,7 @@ pub enum CacheLevel {
     L6,
     L7,
     L8,
+    Undefined(u16),
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -79,6 +74,7 @@ pub enum CacheLocation {
     External,
     Reserved,
     Unknown,
+    Undefined(u16),
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -87,6 +83,7 @@ pub enum CacheOperationalMode {
     WriteBack,
     ValuesWithMemoryAddress,
     Unknown,
+    Undefined(u16),
 }
 
 /// Cache size is same for Maximum Cache Size and Installed Size
@@ -98,6 +95,7 @@ pub enum CacheSize {
 
 bitflags! {
     /// Cache SRAM Type is same for Supported SRAM Type and Current SRAM Type
+    #[derive(Default)]
     pub struct CacheSramType: u16 {
         const OTHER             = 0b0000_0001;
         const UNKNOWN           = 0b0000_0010;
@@ -163,120 +161,96 @@ pub enum CacheSize2 {
     Granularity64K(u32),
 }
 
-impl<'buffer> Cache<'buffer> {
-    pub(crate) fn try_from(structure: RawStructure<'buffer>) -> Result<Cache<'buffer>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct CachePacked_3_1 {
-            socket_designation: u8,
-            cache_configuration: u16,
-            maximum_cache_size: u16,
-            installed_size: u16,
-            supported_sram_type: u16,
-            current_sram_type: u16,
-            cache_speed: u8,
-            error_correction_type: u8,
-            system_cache_type: u8,
-            associativity: u8,
-            maximum_cache_size_2: u32,
-            installed_size_2: u32,
-        }
+/// Formatted section of the Cache Information structure, per SMBIOS version
+mod layout {
+    use super::Field;
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct CachePacked_2_1 {
-            socket_designation: u8,
-            cache_configuration: u16,
-            maximum_cache_size: u16,
-            installed_size: u16,
-            supported_sram_type: u16,
-            current_sram_type: u16,
-            cache_speed: u8,
-            error_correction_type: u8,
-            system_cache_type: u8,
-            associativity: u8,
-        }
+    pub const SOCKET_DESIGNATION: Field<u8> = Field::new(0x04, 2, 0);
+    pub const CACHE_CONFIGURATION: Field<u16> = Field::new(0x05, 2, 0);
+    pub const MAXIMUM_CACHE_SIZE: Field<u16> = Field::new(0x07, 2, 0);
+    pub const INSTALLED_SIZE: Field<u16> = Field::new(0x09, 2, 0);
+    pub const SUPPORTED_SRAM_TYPE: Field<u16> = Field::new(0x0B, 2, 0);
+    pub const CURRENT_SRAM_TYPE: Field<u16> = Field::new(0x0D, 2, 0);
+    pub const CACHE_SPEED: Field<u8> = Field::new(0x0F, 2, 1);
+    pub const ERROR_CORRECTION_TYPE: Field<u8> = Field::new(0x10, 2, 1);
+    pub const SYSTEM_CACHE_TYPE: Field<u8> = Field::new(0x11, 2, 1);
+    pub const ASSOCIATIVITY: Field<u8> = Field::new(0x12, 2, 1);
+    pub const MAXIMUM_CACHE_SIZE_2: Field<u32> = Field::new(0x13, 3, 1);
+    pub const INSTALLED_SIZE_2: Field<u32> = Field::new(0x17, 3, 1);
+}
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct CachePacked_2_0 {
-            socket_designation: u8,
-            cache_configuration: u16,
-            maximum_cache_size: u16,
-            installed_size: u16,
-            supported_sram_type: u16,
-            current_sram_type: u16,
-        }
+impl<'buffer> Cache<'buffer> {
+    pub(crate) fn from_raw(structure: RawStructure<'buffer>) -> Result<Cache<'buffer>, MalformedStructureError> {
+        use self::layout::*;
 
-        match structure.version {
-            v if v > (3, 1).into() => {
-                let_as_struct!(packed, CachePacked_3_1, structure.data);
-                Ok(Cache {
-                    handle: structure.handle,
-                    socket_designation: structure.find_string(packed.socket_designation)?,
-                    cache_configuration: packed.cache_configuration.into(),
-                    maximum_cache_size: packed.maximum_cache_size.into(),
-                    installed_size: packed.installed_size.into(),
-                    supported_sram_type: CacheSramType::from_bits_truncate(packed.supported_sram_type),
-                    current_sram_type: CacheSramType::from_bits_truncate(packed.current_sram_type),
-                    cache_speed: Some(packed.cache_speed),
-                    error_correction_type: Some(packed.error_correction_type.into()),
-                    system_cache_type: Some(packed.system_cache_type.into()),
-                    associativity: Some(packed.associativity.into()),
-                    maximum_cache_size_2: Some(packed.maximum_cache_size_2.into()),
-                    installed_size_2: Some(packed.installed_size_2.into()),
-                })
-            }
-            v if v > (2, 1).into() => {
-                let_as_struct!(packed, CachePacked_2_1, structure.data);
-                Ok(Cache {
-                    handle: structure.handle,
-                    socket_designation: structure.find_string(packed.socket_designation)?,
-                    cache_configuration: packed.cache_configuration.into(),
-                    maximum_cache_size: packed.maximum_cache_size.into(),
-                    installed_size: packed.installed_size.into(),
-                    supported_sram_type: CacheSramType::from_bits_truncate(packed.supported_sram_type),
-                    current_sram_type: CacheSramType::from_bits_truncate(packed.current_sram_type),
-                    cache_speed: Some(packed.cache_speed),
-                    error_correction_type: Some(packed.error_correction_type.into()),
-                    system_cache_type: Some(packed.system_cache_type.into()),
-                    associativity: Some(packed.associativity.into()),
-                    maximum_cache_size_2: None,
-                    installed_size_2: None,
-                })
-            }
-            v if v > (2, 0).into() => {
-                let_as_struct!(packed, CachePacked_2_0, structure.data);
-                Ok(Cache {
-                    handle: structure.handle,
-                    socket_designation: structure.find_string(packed.socket_designation)?,
-                    cache_configuration: packed.cache_configuration.into(),
-                    maximum_cache_size: packed.maximum_cache_size.into(),
-                    installed_size: packed.installed_size.into(),
-                    supported_sram_type: CacheSramType::from_bits_truncate(packed.supported_sram_type),
-                    current_sram_type: CacheSramType::from_bits_truncate(packed.current_sram_type),
-                    cache_speed: None,
-                    error_correction_type: None,
-                    system_cache_type: None,
-                    associativity: None,
-                    maximum_cache_size_2: None,
-                    installed_size_2: None,
-                })
-            }
-            _ => unreachable!(),
-        }
+        Ok(Cache {
+            handle: structure.handle,
+            socket_designation: SOCKET_DESIGNATION.string(&structure)?,
+            cache_configuration: CACHE_CONFIGURATION.get(&structure)?.into(),
+            maximum_cache_size: MAXIMUM_CACHE_SIZE.get(&structure)?.into(),
+            installed_size: INSTALLED_SIZE.get(&structure)?.into(),
+            supported_sram_type: CacheSramType::from_bits_truncate(SUPPORTED_SRAM_TYPE.get(&structure)?),
+            current_sram_type: CacheSramType::from_bits_truncate(CURRENT_SRAM_TYPE.get(&structure)?),
+            cache_speed: CACHE_SPEED.get_since(&structure),
+            error_correction_type: ERROR_CORRECTION_TYPE.get_since(&structure).map(Into::into),
+            system_cache_type: SYSTEM_CACHE_TYPE.get_since(&structure).map(Into::into),
+            associativity: ASSOCIATIVITY.get_since(&structure).map(Into::into),
+            maximum_cache_size_2: MAXIMUM_CACHE_SIZE_2.get_since(&structure).map(Into::into),
+            installed_size_2: INSTALLED_SIZE_2.get_since(&structure).map(Into::into),
+        })
     }
 }
 
 impl From<u16> for CacheConfiguration {
     fn from(word: u16) -> CacheConfiguration {
-        CacheConfiguration {
-            level: CacheLevel::from(word & 0b0000_0111),
-            socketed: (word & 0b0000_1000) >> 3 == 1,
-            location: CacheLocation::from((word & 0b0110_0000) >> 5),
-            enabled_at_boot_time: (word & 0b1000_0000) >> 7 == 1,
-            operational_mode: CacheOperationalMode::from((word & 0b0000_0011_0000_0000) >> 8),
-        }
+        CacheConfiguration { raw: word }
+    }
+}
+
+impl CacheConfiguration {
+    /// Cache Level – 1 through 8
+    pub fn level(&self) -> CacheLevel {
+        CacheLevel::from(self.raw & 0b0000_0111)
+    }
+    /// Cache Socketed (e.g. Cache on a Stick)
+    pub fn is_socketed(&self) -> bool {
+        self.raw & 0b0000_1000 != 0
+    }
+    /// Location, relative to the CPU module
+    pub fn location(&self) -> CacheLocation {
+        CacheLocation::from((self.raw & 0b0110_0000) >> 5)
+    }
+    /// Enabled/Disabled (at boot time)
+    pub fn is_enabled_at_boot_time(&self) -> bool {
+        self.raw & 0b1000_0000 != 0
+    }
+    /// Operational Mode
+    pub fn operational_mode(&self) -> CacheOperationalMode {
+        CacheOperationalMode::from((self.raw & 0b0000_0011_0000_0000) >> 8)
+    }
+    /// The Cache Configuration word as stored in the structure, reserved bits included
+    pub fn raw(&self) -> u16 {
+        self.raw
+    }
+}
+
+/// Formatted as the dmidecode fields, e.g. `Write Back, Enabled, Internal, Socketed, Level 3`
+#[cfg(feature = "strings")]
+impl fmt::Display for CacheConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}, {}, Level {}",
+            self.operational_mode(),
+            if self.is_enabled_at_boot_time() {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+            self.location(),
+            if self.is_socketed() { "Socketed" } else { "Not Socketed" },
+            self.level().number()
+        )
     }
 }
 
@@ -299,6 +273,14 @@ impl CacheSize {
     }
 }
 
+/// Formatted in the largest unit that represents the size exactly, e.g. `32 KB` or `20 MB`
+#[cfg(feature = "strings")]
+impl fmt::Display for CacheSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_size(self.bytes(), f)
+    }
+}
+
 impl From<u16> for CacheLevel {
     fn from(word: u16) -> CacheLevel {
         match word {
@@ -310,10 +292,27 @@ impl From<u16> for CacheLevel {
             5 => CacheLevel::L6,
             6 => CacheLevel::L7,
             7 => CacheLevel::L8,
-            _ => unreachable!(),
+            t => CacheLevel::Undefined(t),
+        }
+    }
+}
+impl CacheLevel {
+    /// The cache level as a number, 1 for [CacheLevel::L1]
+    pub fn number(&self) -> u16 {
+        match self {
+            Self::L1 => 1,
+            Self::L2 => 2,
+            Self::L3 => 3,
+            Self::L4 => 4,
+            Self::L5 => 5,
+            Self::L6 => 6,
+            Self::L7 => 7,
+            Self::L8 => 8,
+            Self::Undefined(t) => t.saturating_add(1),
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CacheLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -325,6 +324,7 @@ impl fmt::Display for CacheLevel {
             Self::L6 => write!(f, "L6"),
             Self::L7 => write!(f, "L7"),
             Self::L8 => write!(f, "L8"),
+            Self::Undefined(t) => write!(f, "Undefined: {}", t),
         }
     }
 }
@@ -336,10 +336,11 @@ impl From<u16> for CacheLocation {
             1 => CacheLocation::External,
             2 => CacheLocation::Reserved,
             3 => CacheLocation::Unknown,
-            _ => unreachable!(),
+            t => CacheLocation::Undefined(t),
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CacheLocation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -347,6 +348,7 @@ impl fmt::Display for CacheLocation {
             Self::External => write!(f, "External"),
             Self::Reserved => write!(f, "Reserved"),
             Self::Unknown => write!(f, "Unknown"),
+            Self::Undefined(t) => write!(f, "Undefined: {}", t),
         }
     }
 }
@@ -358,10 +360,11 @@ impl From<u16> for CacheOperationalMode {
             1 => CacheOperationalMode::WriteBack,
             2 => CacheOperationalMode::ValuesWithMemoryAddress,
             3 => CacheOperationalMode::Unknown,
-            _ => unreachable!(),
+            t => CacheOperationalMode::Undefined(t),
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CacheOperationalMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -369,6 +372,7 @@ impl fmt::Display for CacheOperationalMode {
             Self::WriteBack => write!(f, "Write Back"),
             Self::ValuesWithMemoryAddress => write!(f, "Values with Memory Address"),
             Self::Unknown => write!(f, "Unknown"),
+            Self::Undefined(t) => write!(f, "Undefined: {}", t),
         }
     }
 }
@@ -386,6 +390,7 @@ impl From<u8> for CacheErrorCorrectionType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CacheErrorCorrectionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -412,6 +417,7 @@ impl From<u8> for SystemCacheType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SystemCacheType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -446,6 +452,7 @@ impl From<u8> for CacheAssociativity {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CacheAssociativity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -487,21 +494,133 @@ impl CacheSize2 {
     }
 }
 
+/// Formatted in the largest unit that represents the size exactly, e.g. `32 KB` or `20 MB`
+#[cfg(feature = "strings")]
+impl fmt::Display for CacheSize2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_size(self.bytes(), f)
+    }
+}
+
+#[cfg(feature = "strings")]
+fn fmt_size(bytes: u64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    // Cache sizes are multiples of 1 KB
+    let mut size = bytes >> 10;
+    let mut unit = 0;
+    while size != 0 && size % 1024 == 0 && unit + 1 < UNITS.len() {
+        size /= 1024;
+        unit += 1;
+    }
+    write!(f, "{} {}", size, UNITS[unit])
+}
+
+/// A cache size in bytes as a `usize`, saturating at `usize::MAX` on targets where the largest
+/// 64K-granularity sizes do not fit
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CacheSizeBytes(pub usize);
+
+impl CacheSizeBytes {
+    fn saturating(bytes: u64) -> Self {
+        CacheSizeBytes(usize::try_from(bytes).unwrap_or(usize::MAX))
+    }
+}
+
+impl From<CacheSize> for CacheSizeBytes {
+    fn from(size: CacheSize) -> Self {
+        CacheSizeBytes::saturating(size.bytes())
+    }
+}
+
+impl From<CacheSize2> for CacheSizeBytes {
+    fn from(size: CacheSize2) -> Self {
+        CacheSizeBytes::saturating(size.bytes())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn cache(version: (usize, usize), length: u8) -> Result<Cache<'static>, MalformedStructureError> {
+        const DATA: &[u8] = &[
+            0x01, // Socket Designation
+            0x81, 0x01, // Cache Configuration: Write Back, Enabled, Internal, Level 2
+            0x00, 0x05, 0x00, 0x05, // Maximum Cache Size, Installed Size: 1280 KB
+            0x10, 0x00, 0x10, 0x00, // Supported, Current SRAM Type: Pipeline Burst
+            0x00, 0x05, 0x05, 0x07, // Speed, Error Correction, System Cache Type, Associativity
+            0x00, 0x05, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, // Maximum, Installed Size 2
+        ];
+        Cache::from_raw(RawStructure {
+            version: version.into(),
+            info: crate::InfoType::Cache,
+            length,
+            handle: Handle(0x0700),
+            data: &DATA[..length as usize - 4],
+            strings: b"L2 Cache\0\0",
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn short_layouts() {
+        let cache_3_1 = cache((3, 2), 0x1B).unwrap();
+        assert_eq!(Some(CacheSize2::Granularity1K(1280)), cache_3_1.installed_size_2);
+        assert_eq!(
+            Some(CacheAssociativity::EightWaySetAssociative),
+            cache_3_1.associativity
+        );
+
+        // The 2.1 layout in a 3.x table
+        let cache_2_1 = cache((3, 2), 0x13).unwrap();
+        assert_eq!(
+            Cache {
+                maximum_cache_size_2: None,
+                installed_size_2: None,
+                ..cache_3_1.clone()
+            },
+            cache_2_1
+        );
+
+        // The 2.0 layout in a table of a later version
+        let cache_2_0 = cache((2, 8), 0x0F).unwrap();
+        assert_eq!("L2 Cache", cache_2_0.socket_designation);
+        assert_eq!(CacheSize::Granularity1K(1280), cache_2_0.installed_size);
+        assert_eq!((None, None), (cache_2_0.cache_speed, cache_2_0.associativity));
+
+        // Fields are present from exactly the version that introduced them
+        assert_eq!(cache_3_1, cache((3, 1), 0x1B).unwrap());
+        assert_eq!(cache_2_1, cache((2, 1), 0x1B).unwrap());
+        assert_eq!(cache_2_0, cache((2, 0), 0x1B).unwrap());
+
+        // Shorter than the 2.0 layout
+        assert!(matches!(
+            cache((3, 2), 0x0E),
+            Err(MalformedStructureError::InvalidSlice(_))
+        ));
+    }
+
     #[test]
     fn cache_configuration() {
         let data = 0b0000_0010_1010_1010;
-        let sample = CacheConfiguration {
-            level: CacheLevel::L3,
-            socketed: true,
-            location: CacheLocation::External,
-            enabled_at_boot_time: true,
-            operational_mode: CacheOperationalMode::ValuesWithMemoryAddress,
-        };
         let result: CacheConfiguration = data.into();
-        assert_eq!(sample, result);
+        assert_eq!(CacheLevel::L3, result.level());
+        assert!(result.is_socketed());
+        assert_eq!(CacheLocation::External, result.location());
+        assert!(result.is_enabled_at_boot_time());
+        assert_eq!(CacheOperationalMode::ValuesWithMemoryAddress, result.operational_mode());
+        assert_eq!(data, result.raw());
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "Values with Memory Address, Enabled, External, Socketed, Level 3",
+                format!("{}", result)
+            );
+            assert_eq!(
+                "Write Back, Disabled, Internal, Not Socketed, Level 1",
+                format!("{}", CacheConfiguration::from(0x0100))
+            );
+        }
     }
     #[test]
     fn cache_size() {
@@ -519,6 +638,26 @@ mod tests {
         assert_eq!(CacheSize2::Granularity64K(44695552), cs2_64k);
         assert_eq!(44695552 * 65536, cs2_64k.bytes());
     }
+    #[cfg(feature = "strings")]
+    #[test]
+    fn cache_size_display() {
+        assert_eq!("32 KB", format!("{}", CacheSize::Granularity1K(32)));
+        assert_eq!("1000 KB", format!("{}", CacheSize::Granularity1K(1000)));
+        assert_eq!("20 MB", format!("{}", CacheSize::Granularity64K(320)));
+        assert_eq!("0 KB", format!("{}", CacheSize::Granularity1K(0)));
+        assert_eq!("2 GB", format!("{}", CacheSize2::Granularity64K(32768)));
+        assert_eq!("4 TB", format!("{}", CacheSize2::Granularity64K(1 << 26)));
+    }
+    #[test]
+    fn cache_size_bytes() {
+        assert_eq!(CacheSizeBytes(32 << 10), CacheSize::Granularity1K(32).into());
+        let maximum = CacheSizeBytes::from(CacheSize2::Granularity64K(0x7FFF_FFFF));
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(CacheSizeBytes(0x7FFF_FFFF << 16), maximum);
+        } else {
+            assert_eq!(CacheSizeBytes(usize::MAX), maximum);
+        }
+    }
     #[test]
     fn cache_enums() {
         let data = 0b0101_0101;
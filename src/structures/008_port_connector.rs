@@ -4,9 +4,11 @@
 //! parallel, serial, keyboard, or mouse ports). The port’s type and connector information are
 //! provided. One structure is present for each port provided by the system.
 
+#[cfg(feature = "strings")]
 use core::fmt;
 
 use crate::{
+    Handle,
     MalformedStructureError::{self},
     RawStructure,
 };
@@ -18,7 +20,7 @@ use crate::{
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct PortConnector<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Internal Reference Designator, that is, internal to the system enclosure\
     /// EXAMPLE: ‘J101’, 0
     pub internal_reference_designator: &'a str,
@@ -33,6 +35,19 @@ pub struct PortConnector<'a> {
     pub port_type: PortType,
 }
 
+/// Which sides of the system enclosure a port has a connector on, see [PortConnector::placement]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ConnectorPlacement {
+    /// Only an internal connector, e.g. a header on the motherboard
+    Internal,
+    /// Only an external connector, reachable from outside the enclosure
+    External,
+    /// An internal connector cabled to an external one, e.g. a front panel port
+    PassThrough,
+    /// Neither connector type is given
+    Unspecified,
+}
+
 #[doc(hidden)]
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ConnectorType {
@@ -126,7 +141,7 @@ pub enum PortType {
 }
 
 impl<'a> PortConnector<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<PortConnector<'a>, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<PortConnector<'a>, MalformedStructureError> {
         #[repr(C)]
         #[repr(packed)]
         struct PortConnectorPacked {
@@ -147,6 +162,41 @@ impl<'a> PortConnector<'a> {
             port_type: packed.port_type.into(),
         })
     }
+
+    /// Classify the port by which of its connector types are set
+    pub fn placement(&self) -> ConnectorPlacement {
+        match (
+            self.internal_connector_type != ConnectorType::None,
+            self.external_connector_type != ConnectorType::None,
+        ) {
+            (true, true) => ConnectorPlacement::PassThrough,
+            (true, false) => ConnectorPlacement::Internal,
+            (false, true) => ConnectorPlacement::External,
+            (false, false) => ConnectorPlacement::Unspecified,
+        }
+    }
+}
+
+/// Formatted as the dmidecode block of the structure, an empty designator is "Not Specified"
+#[cfg(feature = "strings")]
+impl<'a> fmt::Display for PortConnector<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let designator = |s: &'a str| if s.is_empty() { "Not Specified" } else { s };
+        writeln!(f, "Port Connector Information")?;
+        writeln!(
+            f,
+            "\tInternal Reference Designator: {}",
+            designator(self.internal_reference_designator)
+        )?;
+        writeln!(f, "\tInternal Connector Type: {}", self.internal_connector_type)?;
+        writeln!(
+            f,
+            "\tExternal Reference Designator: {}",
+            designator(self.external_reference_designator)
+        )?;
+        writeln!(f, "\tExternal Connector Type: {}", self.external_connector_type)?;
+        write!(f, "\tPort Type: {}", self.port_type)
+    }
 }
 
 impl From<u8> for ConnectorType {
@@ -198,6 +248,7 @@ impl From<u8> for ConnectorType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for ConnectorType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -294,6 +345,7 @@ impl From<u8> for PortType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for PortType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -345,6 +397,7 @@ impl fmt::Display for PortType {
 mod test {
     use pretty_assertions::assert_eq;
     use std::prelude::v1::*;
+    #[cfg(feature = "strings")]
     #[test]
     fn connector_type() {
         use super::ConnectorType;
@@ -365,6 +418,7 @@ mod test {
             result.iter().map(|r| (r, format!("{}", r))).collect::<Vec<_>>(),
         );
     }
+    #[cfg(feature = "strings")]
     #[test]
     fn port_type() {
         use super::PortType;
@@ -384,9 +438,9 @@ mod test {
     #[test]
     fn port_connector() {
         use super::{ConnectorType, PortConnector, PortType};
-        use crate::{InfoType, RawStructure};
+        use crate::{Handle, InfoType, RawStructure};
         let sample = PortConnector {
-            handle: 8,
+            handle: Handle(8),
             internal_reference_designator: "J1A1",
             internal_connector_type: ConnectorType::None,
             external_reference_designator: "Keyboard",
@@ -397,7 +451,7 @@ mod test {
             version: (0, 0).into(),
             info: InfoType::PortConnector,
             length: 0,
-            handle: 0x0008,
+            handle: Handle(0x0008),
             // Remove 4 bytes from `dmidecode -H 8 -u` 'Header and Data'
             data: &[0x01, 0x00, 0x02, 0x0F, 0x0D],
             strings: &[
@@ -405,9 +459,72 @@ mod test {
                 0x4A, 0x31, 0x41, 0x31, 0x00, // Keyboard
                 0x4B, 0x65, 0x79, 0x62, 0x6F, 0x61, 0x72, 0x64, 0x00,
             ],
+            ..Default::default()
         };
-        let result = PortConnector::try_from(structure).unwrap();
+        let result = PortConnector::from_raw(structure).unwrap();
         assert_eq!(sample, result);
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "Port Connector Information\n\
+                 \tInternal Reference Designator: J1A1\n\
+                 \tInternal Connector Type: None\n\
+                 \tExternal Reference Designator: Keyboard\n\
+                 \tExternal Connector Type: PS/2\n\
+                 \tPort Type: Keyboard Port",
+                format!("{}", result)
+            );
+        }
+    }
+    #[test]
+    fn placement() {
+        use super::{ConnectorPlacement, ConnectorType, PortConnector, PortType};
+        use crate::Handle;
+        let port = |internal, external| PortConnector {
+            handle: Handle(8),
+            internal_reference_designator: "",
+            internal_connector_type: internal,
+            external_reference_designator: "",
+            external_connector_type: external,
+            port_type: PortType::Usb,
+        };
+        let samples = [
+            (
+                ConnectorType::AccessBus,
+                ConnectorType::None,
+                ConnectorPlacement::Internal,
+            ),
+            (ConnectorType::None, ConnectorType::Rj45, ConnectorPlacement::External),
+            (
+                ConnectorType::AccessBus,
+                ConnectorType::AccessBus,
+                ConnectorPlacement::PassThrough,
+            ),
+            (
+                ConnectorType::None,
+                ConnectorType::None,
+                ConnectorPlacement::Unspecified,
+            ),
+        ];
+        for (internal, external, placement) in samples {
+            assert_eq!(
+                placement,
+                port(internal, external).placement(),
+                "{:?}/{:?}",
+                internal,
+                external
+            );
+        }
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "\tExternal Reference Designator: Not Specified",
+                format!("{}", port(ConnectorType::None, ConnectorType::None))
+                    .lines()
+                    .nth(3)
+                    .unwrap()
+            );
+        }
     }
     #[test]
     fn dmi_bin() {
@@ -421,7 +538,7 @@ mod test {
             .collect::<Vec<_>>();
 
         let usb_sample = PortConnector {
-            handle: 0x0800,
+            handle: Handle(0x0800),
             internal_reference_designator: "Internal USB port 1",
             internal_connector_type: ConnectorType::AccessBus,
             external_reference_designator: "",
@@ -436,20 +553,24 @@ mod test {
             })
             .unwrap();
         assert_eq!(&usb_sample, usb_result, "USB");
-        assert_eq!(
-            "Access Bus (USB)",
-            format!("{}", usb_result.internal_connector_type),
-            "USB: Internal Connector Type"
-        );
-        assert_eq!(
-            "None",
-            format!("{}", usb_result.external_connector_type),
-            "USB: External Connector Type"
-        );
-        assert_eq!("USB", format!("{}", usb_result.port_type), "USB: Port Type");
+        assert_eq!(ConnectorPlacement::Internal, usb_result.placement(), "USB: placement");
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "Access Bus (USB)",
+                format!("{}", usb_result.internal_connector_type),
+                "USB: Internal Connector Type"
+            );
+            assert_eq!(
+                "None",
+                format!("{}", usb_result.external_connector_type),
+                "USB: External Connector Type"
+            );
+            assert_eq!("USB", format!("{}", usb_result.port_type), "USB: Port Type");
+        }
 
         let rj45_sample = PortConnector {
-            handle: 0x080A,
+            handle: Handle(0x080A),
             internal_reference_designator: "",
             internal_connector_type: ConnectorType::None,
             external_reference_designator: "4",
@@ -465,15 +586,23 @@ mod test {
             .unwrap();
         assert_eq!(&rj45_sample, rj45_result, "RJ-45");
         assert_eq!(
-            "None",
-            format!("{}", rj45_result.internal_connector_type),
-            "RJ-45: Internal Connector Type"
-        );
-        assert_eq!(
-            "RJ-45",
-            format!("{}", rj45_result.external_connector_type),
-            "RJ-45: External Connector Type"
+            ConnectorPlacement::External,
+            rj45_result.placement(),
+            "RJ-45: placement"
         );
-        assert_eq!("Network Port", format!("{}", rj45_result.port_type), "RJ-45: Port Type");
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "None",
+                format!("{}", rj45_result.internal_connector_type),
+                "RJ-45: Internal Connector Type"
+            );
+            assert_eq!(
+                "RJ-45",
+                format!("{}", rj45_result.external_connector_type),
+                "RJ-45: External Connector Type"
+            );
+            assert_eq!("Network Port", format!("{}", rj45_result.port_type), "RJ-45: Port Type");
+        }
     }
 }
@@ -3,16 +3,22 @@
 //! Information in this structure defines the attributes of a system slot. One structure is
 //! provided for each slot in the system.
 
+#[cfg(feature = "strings")]
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::slice::Chunks;
+use core::slice::ChunksExact;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 use crate::{
     bitfield::{BitField, FlagType, Layout},
-    InfoType,
+    Handle, InfoType, LengthExpectation,
     MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
+    RawStructure, SmbiosVersion,
 };
+#[cfg(feature = "alloc")]
+use crate::{Structure, Structures};
 
 /// The `System Slots` table defined in the SMBIOS specification.
 ///
@@ -21,7 +27,7 @@ use crate::{
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SystemSlots<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// String number for reference designation\
     /// EXAMPLE: ‘PCI-1’,0
     pub slot_designation: &'a str,
@@ -67,6 +73,8 @@ pub struct SystemSlots<'a> {
     /// The Slot Pitch field contains a numeric value that indicates the pitch of the slot in units
     /// of 1/100 millimeter.
     pub slot_pitch: Option<SlotPitch>,
+    /// This field indicates the maximum supported card height for the slot
+    pub slot_height: Option<SlotHeight>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -290,12 +298,26 @@ pub enum SlotLength {
     Undefined(u8),
 }
 
+/// Maximum supported card height for the slot
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SlotHeight {
+    NotApplicable,
+    Other,
+    Unknown,
+    FullHeight,
+    LowProfile,
+    Undefined(u8),
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct SlotCharacteristics1(u8);
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct SlotCharacteristics2(u8);
 
+raw_newtype!(SlotCharacteristics1(u8));
+raw_newtype!(SlotCharacteristics2(u8));
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Device {
     /// Segment Group Number is defined in the PCI Firmware Specification. The value is 0 for a
@@ -310,22 +332,26 @@ pub struct Device {
     pub data_bus_width: u8,
 }
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+/// PCI device number in bits 7:3 and function number in bits 2:0
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct DeviceAndFunctionNumber(u8, u8);
 
-// Used in 2 Base Device and in Peer Devices
-#[repr(C)]
-#[repr(packed)]
-struct DevicePacked {
-    segment_group_number: u16,
-    bus_number: u8,
-    dev_and_fun_number: u8,
-    data_bus_width: u8,
+/// A physical slot described by several System Slots structures
+///
+/// A slot that is bifurcated, e.g. an x16 riser split into four x4 links, may be reported as one
+/// structure per link. The structure that lists the others among its peer devices is the
+/// `primary` one.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PhysicalSlot<'a> {
+    pub primary: SystemSlots<'a>,
+    /// Structures whose base device is one of the peer devices of the primary structure
+    pub peers: Vec<SystemSlots<'a>>,
 }
 
 /// An iterator over Peer Segment/Bus/Device/Function/Width groups
 #[derive(Clone, Debug)]
-pub struct PeerDevices<'a>(Chunks<'a, u8>);
+pub struct PeerDevices<'a>(ChunksExact<'a, u8>);
 
 /// The Slot Pitch field contains a numeric value that indicates the pitch of the slot in units of
 /// 1/100 millimeter.
@@ -336,32 +362,51 @@ pub struct PeerDevices<'a>(Chunks<'a, u8>);
 /// SFF-TA-1008 table 6-1 (SSD to SSD pitch).  For example, if the pitch for the slot is 12.5 mm,
 /// the value 1250 would be used.  A value of 0 implies that the slot pitch is not given or is
 /// unknown.
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct SlotPitch(u16);
 
+raw_newtype!(SlotPitch(u16));
+
+impl SlotPitch {
+    /// The pitch in units of 1/100 millimeter, `None` if it is not given or is unknown
+    pub fn pitch_hundredths_mm(&self) -> Option<u16> {
+        Some(self.0).filter(|&pitch| pitch != 0)
+    }
+}
+
 impl<'a> SystemSlots<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<SystemSlots<'a>, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<SystemSlots<'a>, MalformedStructureError> {
         let data_len = structure.data.len() + 4;
         let handle = structure.handle;
-        match ((structure.version.major, structure.version.minor), data_len) {
-            (v, l) if ((2, 0)..(2, 1)).contains(&v) && l != 0x0C => {
-                Err(InvalidFormattedSectionLength(InfoType::SystemSlots, handle, "", 0x0C))
-            }
-            (v, l) if ((2, 1)..(2, 6)).contains(&v) && l != 0x0D => {
-                Err(InvalidFormattedSectionLength(InfoType::SystemSlots, handle, "", 0x0D))
-            }
-            (v, l) if ((2, 6)..(3, 2)).contains(&v) && l != 0x11 => {
-                Err(InvalidFormattedSectionLength(InfoType::SystemSlots, handle, "", 0x11))
-            }
-            (v, l) if v >= (3, 2) && l < 0x11 => Err(InvalidFormattedSectionLength(
-                InfoType::SystemSlots,
+        match (structure.version, data_len) {
+            (v, l) if version_gate!(v, (2, 0)..(2, 1)) && l != 0x0C => Err(InvalidFormattedSectionLength {
+                info: InfoType::SystemSlots,
+                handle,
+                expected: LengthExpectation::Exact(0x0C),
+                actual: l as u8,
+            }),
+            (v, l) if version_gate!(v, (2, 1)..(2, 6)) && l != 0x0D => Err(InvalidFormattedSectionLength {
+                info: InfoType::SystemSlots,
+                handle,
+                expected: LengthExpectation::Exact(0x0D),
+                actual: l as u8,
+            }),
+            (v, l) if version_gate!(v, (2, 6)..(3, 2)) && l != 0x11 => Err(InvalidFormattedSectionLength {
+                info: InfoType::SystemSlots,
                 handle,
-                "minimum of ",
-                0x11,
-            )),
+                expected: LengthExpectation::Exact(0x11),
+                actual: l as u8,
+            }),
+            (v, l) if version_gate!(v, (3, 2)..) && l < 0x11 => Err(InvalidFormattedSectionLength {
+                info: InfoType::SystemSlots,
+                handle,
+                expected: LengthExpectation::Minimum(0x11),
+                actual: l as u8,
+            }),
             _ => {
                 let peer_grouping_count: u8 = structure.get::<u8>(0x12).unwrap_or(0);
                 let n = peer_grouping_count as usize;
+                let tail = tail_offset(structure.version, data_len, peer_grouping_count);
                 Ok(SystemSlots {
                     handle,
                     slot_designation: structure.get_string(0x04)?,
@@ -390,20 +435,104 @@ impl<'a> SystemSlots<'a> {
                         .map(Into::into),
                     data_bus_width: structure.get::<u8>(0x11).ok(),
                     peer_devices: structure.get_slice(0x13, 5 * n).map(Into::into),
-                    /// According to (SMBIOS Reference Specification
-                    /// 3.4)[https://www.dmtf.org/sites/default/files/standards/documents/DSP0134_3.4.0.pdf]
-                    /// fields below starts from offset 14h + 5*n, that looks like mistake.
-                    /// It shoud start from 13h + 5*n, because *Peer (S/B/D/F/Width)
-                    /// groups* field may has zero length
-                    slot_information: structure.get::<u8>(0x14 + 5 * n).ok(),
-                    slot_physical_width: structure.get::<u8>(0x15 + 5 * n).ok().map(Into::into),
-                    slot_pitch: structure.get::<u16>(0x16 + 5 * n).ok().map(Into::into),
+                    slot_information: structure.get::<u8>(tail).ok(),
+                    slot_physical_width: structure.get::<u8>(tail + 1).ok().map(Into::into),
+                    slot_pitch: structure.get::<u16>(tail + 2).ok().map(Into::into),
+                    slot_height: structure.get::<u8>(tail + 4).ok().map(Into::into),
                 })
             }
         }
     }
 }
 
+impl<'a> SystemSlots<'a> {
+    /// The base Segment/Bus/Device/Function/Width, `None` if the slot has no bus address
+    pub fn base_device(&self) -> Option<Device> {
+        Some(Device {
+            segment_group_number: self.segment_group_number?,
+            bus_number: self.bus_number?,
+            device_and_function_number: self.device_and_function_number?,
+            data_bus_width: self.data_bus_width.unwrap_or(0),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'buffer> Structures<'buffer> {
+    /// The System Slots structures grouped by physical slot, in table order of the primary
+    /// structures
+    ///
+    /// A structure is grouped under the first structure that lists its base device among its
+    /// peer devices, unless it lists peers of its own. Structures that fail to decode are
+    /// skipped.
+    pub fn physical_slots(&self) -> impl Iterator<Item = PhysicalSlot<'buffer>> {
+        let slots = self
+            .clone()
+            .flatten()
+            .filter_map(|structure| match structure {
+                Structure::SystemSlots(slot) => Some(slot),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut primaries = alloc::vec![None; slots.len()];
+        for (i, slot) in slots.iter().enumerate() {
+            if primaries[i].is_some() {
+                continue;
+            }
+            for peer in slot.peer_devices.clone().into_iter().flatten() {
+                for (j, other) in slots.iter().enumerate() {
+                    let has_peers = other.peer_devices.clone().into_iter().flatten().next().is_some();
+                    if j != i
+                        && primaries[j].is_none()
+                        && !has_peers
+                        && other.base_device().map_or(false, |base| base.same_function(&peer))
+                    {
+                        primaries[j] = Some(i);
+                    }
+                }
+            }
+        }
+
+        let mut slots = slots.into_iter().map(Some).collect::<Vec<_>>();
+        let mut grouped = Vec::new();
+        for (i, primary) in primaries.iter().enumerate() {
+            if primary.is_some() {
+                continue;
+            }
+            let peers = (0..slots.len())
+                .filter(|&j| primaries[j] == Some(i))
+                .filter_map(|j| slots[j].take())
+                .collect();
+            if let Some(primary) = slots[i].take() {
+                grouped.push(PhysicalSlot { primary, peers });
+            }
+        }
+        grouped.into_iter()
+    }
+}
+
+/// Offset of the first field following the *Peer (S/B/D/F/Width) groups* field
+///
+/// According to (SMBIOS Reference Specification
+/// 3.4)[https://www.dmtf.org/sites/default/files/standards/documents/DSP0134_3.4.0.pdf]
+/// fields after the peer groups start from offset 14h + 5*n, that looks like mistake. They should
+/// start from 13h + 5*n, because *Peer (S/B/D/F/Width) groups* field may has zero length.
+///
+/// Both interpretations are found in the wild, so the formatted section `length` is used to tell
+/// them apart: a structure that is exactly one byte longer than the fields known for its
+/// `version` is considered to follow the 14h + 5*n layout.
+fn tail_offset(version: SmbiosVersion, length: usize, peer_grouping_count: u8) -> usize {
+    let offset = 0x13 + 5 * peer_grouping_count as usize;
+    // Slot Information, Slot Physical Width, Slot Pitch and since 3.5 Slot Height
+    let tail_len = if version_gate!(version, (3, 5)..) { 5 } else { 4 };
+    if length == offset + tail_len + 1 {
+        offset + 1
+    } else {
+        offset
+    }
+}
+
 impl From<u8> for SlotType {
     fn from(byte: u8) -> SlotType {
         match byte {
@@ -490,6 +619,7 @@ impl From<u8> for SlotType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SlotType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let is_alt = f.alternate();
@@ -652,6 +782,7 @@ impl From<u8> for SlotWidth {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SlotWidth {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -686,6 +817,7 @@ impl From<u8> for CurrentUsage {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for CurrentUsage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let is_alt = f.alternate();
@@ -719,6 +851,7 @@ impl From<u8> for SlotLength {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SlotLength {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -733,6 +866,32 @@ impl fmt::Display for SlotLength {
     }
 }
 
+impl From<u8> for SlotHeight {
+    fn from(byte: u8) -> SlotHeight {
+        match byte {
+            0x00 => Self::NotApplicable,
+            0x01 => Self::Other,
+            0x02 => Self::Unknown,
+            0x03 => Self::FullHeight,
+            0x04 => Self::LowProfile,
+            v => Self::Undefined(v),
+        }
+    }
+}
+#[cfg(feature = "strings")]
+impl fmt::Display for SlotHeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotApplicable => write!(f, "Not applicable"),
+            Self::Other => write!(f, "Other"),
+            Self::Unknown => write!(f, "Unknown"),
+            Self::FullHeight => write!(f, "Full height"),
+            Self::LowProfile => write!(f, "Low-profile"),
+            Self::Undefined(v) => write!(f, "Undefined: {}", v),
+        }
+    }
+}
+
 impl<'a> BitField<'a> for SlotCharacteristics1 {
     type Size = u8;
     fn value(&self) -> Self::Size {
@@ -757,11 +916,6 @@ impl<'a> BitField<'a> for SlotCharacteristics1 {
             "PC Card slot supports Modem Ring Resume",
     );
 }
-impl From<u8> for SlotCharacteristics1 {
-    fn from(byte: u8) -> Self {
-        Self(byte)
-    }
-}
 
 impl<'a> BitField<'a> for SlotCharacteristics2 {
     type Size = u8;
@@ -786,29 +940,34 @@ impl<'a> BitField<'a> for SlotCharacteristics2 {
             operating system, device driver, or applications)",
         "Flexbus slot, CXL 1.0 capable",
         "Flexbus slot, CXL 2.0 capable",
-        "Reserved": 1,
+        "Flexbus slot, CXL 3.0 capable",
     );
 }
-impl From<u8> for SlotCharacteristics2 {
-    fn from(byte: u8) -> Self {
-        Self(byte)
+
+impl From<[u8; 5]> for Device {
+    fn from(bytes: [u8; 5]) -> Device {
+        Device {
+            segment_group_number: u16::from_le_bytes([bytes[0], bytes[1]]),
+            bus_number: bytes[2],
+            device_and_function_number: bytes[3].into(),
+            data_bus_width: bytes[4],
+        }
     }
 }
-
+/// Deprecated, convert a `[u8; 5]` instead
+///
+/// Bytes past the first five are ignored and missing bytes read as zero.
 impl<'a> From<&'a [u8]> for Device {
     fn from(data: &'a [u8]) -> Device {
-        let_as_struct!(packed, DevicePacked, data);
-        Device {
-            segment_group_number: packed.segment_group_number,
-            bus_number: packed.bus_number,
-            device_and_function_number: packed.dev_and_fun_number.into(),
-            data_bus_width: packed.data_bus_width,
-        }
+        let mut bytes = [0u8; 5];
+        let len = core::cmp::min(bytes.len(), data.len());
+        bytes[..len].copy_from_slice(&data[..len]);
+        bytes.into()
     }
 }
 impl<'a> From<&'a Device> for [u8; 5] {
     fn from(d: &'a Device) -> [u8; 5] {
-        let segment = d.segment_group_number.to_ne_bytes();
+        let segment = d.segment_group_number.to_le_bytes();
         [
             segment[0],
             segment[1],
@@ -818,6 +977,22 @@ impl<'a> From<&'a Device> for [u8; 5] {
         ]
     }
 }
+#[cfg(feature = "alloc")]
+impl Device {
+    /// Whether both refer to the same PCI function, whatever their data bus widths
+    fn same_function(&self, other: &Device) -> bool {
+        (
+            self.segment_group_number,
+            self.bus_number,
+            self.device_and_function_number,
+        ) == (
+            other.segment_group_number,
+            other.bus_number,
+            other.device_and_function_number,
+        )
+    }
+}
+#[cfg(feature = "strings")]
 impl fmt::Display for Device {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -832,20 +1007,37 @@ impl fmt::Display for Device {
     }
 }
 
+impl DeviceAndFunctionNumber {
+    /// Split the raw value of the field
+    pub const fn new(raw: u8) -> Self {
+        Self(raw >> 3, raw & 0b0111)
+    }
+    /// The raw value of the field
+    pub const fn raw(self) -> u8 {
+        (self.0 << 3) | self.1
+    }
+    pub const fn device(self) -> u8 {
+        self.0
+    }
+    pub const fn function(self) -> u8 {
+        self.1
+    }
+}
 impl From<u8> for DeviceAndFunctionNumber {
     fn from(byte: u8) -> Self {
-        Self(byte >> 3, byte & 0b0111)
+        Self::new(byte)
     }
 }
 impl From<DeviceAndFunctionNumber> for u8 {
     fn from(df: DeviceAndFunctionNumber) -> Self {
-        (df.0 << 3) | df.1
+        df.raw()
     }
 }
 
 impl<'a> From<&'a [u8]> for PeerDevices<'a> {
-    fn from(data: &'a [u8]) -> PeerDevices {
-        Self(data.chunks(5))
+    fn from(data: &'a [u8]) -> PeerDevices<'a> {
+        // Trailing bytes short of a whole peer device are not a device
+        Self(data.chunks_exact(5))
     }
 }
 impl<'a> PartialEq for PeerDevices<'a> {
@@ -863,21 +1055,19 @@ impl<'buffer> Iterator for PeerDevices<'buffer> {
     type Item = Device;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().map(Into::into)
+        let mut bytes = [0; 5];
+        bytes.copy_from_slice(self.0.next()?);
+        Some(bytes.into())
     }
 }
 
-impl From<u16> for SlotPitch {
-    fn from(word: u16) -> Self {
-        Self(word)
-    }
-}
+#[cfg(feature = "strings")]
 impl fmt::Display for SlotPitch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.0 == 0 {
             write!(f, "Not given or is unknown")
         } else {
-            write!(f, "{:.1} mm", (self.0 as f32) / 10.0)
+            write!(f, "{}.{:02} mm", self.0 / 100, self.0 % 100)
         }
     }
 }
@@ -888,6 +1078,7 @@ mod tests {
     use std::prelude::v1::*;
     const PRIMES: &[usize] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61];
 
+    #[cfg(feature = "strings")]
     #[test]
     fn slot_type() {
         use super::SlotType;
@@ -923,6 +1114,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn slot_width() {
         use super::SlotWidth;
@@ -941,6 +1133,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn current_usage() {
         use super::CurrentUsage;
@@ -962,6 +1155,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn slot_length() {
         use super::SlotLength;
@@ -991,22 +1185,25 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result, "Positions");
 
-        let dflt_sample = vec!["5.0 V is provided", "Modem ring resume is supported"];
-        let alt_sample = vec!["Provides 5.0 volts", "PC Card slot supports Modem Ring Resume"];
-        let byte = 0b1000_0010;
-        let iter = SlotCharacteristics1(byte).significants();
-        let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            dflt_sample, dflt_result,
-            "Significant values, default formatting ({:08b})",
-            byte
-        );
-        let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            alt_sample, alt_result,
-            "Significant values, alternative formatting ({:08b})",
-            byte
-        );
+        #[cfg(feature = "strings")]
+        {
+            let dflt_sample = vec!["5.0 V is provided", "Modem ring resume is supported"];
+            let alt_sample = vec!["Provides 5.0 volts", "PC Card slot supports Modem Ring Resume"];
+            let byte = 0b1000_0010;
+            let iter = SlotCharacteristics1(byte).significants();
+            let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                dflt_sample, dflt_result,
+                "Significant values, default formatting ({:08b})",
+                byte
+            );
+            let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                alt_sample, alt_result,
+                "Significant values, alternative formatting ({:08b})",
+                byte
+            );
+        }
 
         let result = SlotCharacteristics1(0).reserved().count();
         assert_eq!(0, result, "Reserved fields");
@@ -1024,46 +1221,163 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(sample, result, "Positions");
 
-        let dflt_sample = vec!["PME signal is supported", "Async/surprise removal is supported"];
-        let alt_sample = vec!["PCI slot supports Power Management Event (PME#) signal","Slot supports async/surprise removal (i.e., removal without prior notification to the operating system, device driver, or applications)"];
-        let byte = 0b0001_0001;
-        let iter = SlotCharacteristics2(byte).significants();
-        let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
-        assert_eq!(
-            dflt_sample, dflt_result,
-            "Significant values, default formatting ({:08b})",
-            byte
-        );
-        let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+        #[cfg(feature = "strings")]
+        {
+            let dflt_sample = vec!["PME signal is supported", "Async/surprise removal is supported"];
+            let alt_sample = vec!["PCI slot supports Power Management Event (PME#) signal","Slot supports async/surprise removal (i.e., removal without prior notification to the operating system, device driver, or applications)"];
+            let byte = 0b0001_0001;
+            let iter = SlotCharacteristics2(byte).significants();
+            let dflt_result = iter.map(|f| format!("{}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                dflt_sample, dflt_result,
+                "Significant values, default formatting ({:08b})",
+                byte
+            );
+            let alt_result = iter.map(|f| format!("{:#}", f)).collect::<Vec<_>>();
+            assert_eq!(
+                alt_sample, alt_result,
+                "Significant values, alternative formatting ({:08b})",
+                byte
+            );
+        }
+
+        let result = SlotCharacteristics2(0).reserved().count();
+        assert_eq!(0, result, "Reserved fields");
+
+        #[cfg(feature = "strings")]
+        {
+            let result = SlotCharacteristics2(0b1110_0000)
+                .significants()
+                .map(|f| format!("{}", f))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    "Flexbus slot, CXL 1.0 capable",
+                    "Flexbus slot, CXL 2.0 capable",
+                    "Flexbus slot, CXL 3.0 capable"
+                ],
+                result
+            );
+        }
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn slot_height() {
+        use super::SlotHeight;
+        let samples = &[
+            (0x00, SlotHeight::NotApplicable, "Not applicable"),
+            (0x03, SlotHeight::FullHeight, "Full height"),
+            (0x04, SlotHeight::LowProfile, "Low-profile"),
+            (0xFE, SlotHeight::Undefined(254), "Undefined: 254"),
+        ];
+        let result = samples.iter().map(|v| Into::into(v.0)).collect::<Vec<_>>();
         assert_eq!(
-            alt_sample, alt_result,
-            "Significant values, alternative formatting ({:08b})",
-            byte
+            samples.iter().map(|(_, v, s)| (v, (*s).into())).collect::<Vec<_>>(),
+            result.iter().map(|r| (r, format!("{}", r))).collect::<Vec<_>>(),
         );
+    }
 
-        let result = SlotCharacteristics2(0).reserved().count();
-        assert_eq!(1, result, "Reserved fields");
+    #[test]
+    fn tail_offset() {
+        use super::tail_offset;
+        // Spec-conformant 3.4: 13h + 5*n followed by 4 bytes
+        assert_eq!(0x13, tail_offset((3, 4).into(), 0x17, 0));
+        assert_eq!(0x1D, tail_offset((3, 4).into(), 0x21, 2));
+        // 3.4 with the extra byte: 14h + 5*n
+        assert_eq!(0x14, tail_offset((3, 4).into(), 0x18, 0));
+        assert_eq!(0x1E, tail_offset((3, 4).into(), 0x22, 2));
+        // Spec-conformant 3.5 with Slot Height
+        assert_eq!(0x13, tail_offset((3, 5).into(), 0x18, 0));
+        assert_eq!(0x14, tail_offset((3, 5).into(), 0x19, 0));
+        // 3.5 firmware still reporting a 3.4 layout
+        assert_eq!(0x18, tail_offset((3, 5).into(), 0x1C, 1));
+        // Structure ends before the peer groups
+        assert_eq!(0x13, tail_offset((3, 2).into(), 0x11, 0));
+    }
+
+    #[test]
+    fn system_slots_3_5() {
+        use super::*;
+        use crate::{InfoType, RawStructure};
+        let structure = RawStructure {
+            version: (3, 5).into(),
+            info: InfoType::SystemSlots,
+            length: 0x1D,
+            handle: Handle(0x0024),
+            data: &[
+                0x01, 0xC4, 0x0D, 0x03, 0x04, 0x03, 0x00, 0x04, 0x01, 0x00, 0x00, 0x17, 0x00, 0x10,
+                0x01, // Peer grouping count: 1
+                0x00, 0x00, 0x18, 0x00, 0x08, // Peer group
+                0x06, // Slot information: Gen6
+                0x0D, // Slot physical width: x16
+                0xE2, 0x04, // Slot pitch: 12.5 mm
+                0x04, // Slot height: Low-profile
+            ],
+            strings: &[0x53, 0x31, 0x00, 0x00],
+            ..Default::default()
+        };
+        let result = SystemSlots::from_raw(structure).unwrap();
+        assert_eq!(Some(6), result.slot_information);
+        assert_eq!(Some(SlotWidth::X16), result.slot_physical_width);
+        assert_eq!(Some(SlotPitch(1250)), result.slot_pitch);
+        assert_eq!(Some(SlotHeight::LowProfile), result.slot_height);
+        #[cfg(feature = "strings")]
+        assert_eq!("Low-profile", format!("{}", result.slot_height.unwrap()));
     }
 
     #[test]
     fn device() {
-        use super::Device;
+        use super::{Device, PeerDevices};
         let sample_data = &[
             0x09, 0x11, 0x03, 0x09, 0x01, 0xB1, 0x0D, 0x04, 0x04, 0x04, 0x00, 0x04, 0x01, 0xE9, 0x05, 0xB5, 0xDF, 0x10,
         ];
-        let result: Device = sample_data[0x0D..=0x11].into();
+        let result = Device::from([0xE9, 0x05, 0xB5, 0xDF, 0x10]);
+        assert_eq!(Some(result), PeerDevices::from(&sample_data[0x0D..]).next());
+        #[cfg(feature = "strings")]
         assert_eq!("05e9:b5:1b.7 (Width 16)", format!("{}", result), "Display trait");
         let as_array: [u8; 5] = (&result).into();
         assert_eq!([0xE9, 0x05, 0xB5, 0xDF, 0x10], as_array, "Display into [u8; 5]");
+        assert_eq!(result, Device::from(&sample_data[0x0D..]), "From slice");
+        assert_eq!(
+            Device::from([0xE9, 0x05, 0, 0, 0]),
+            Device::from(&sample_data[0x0D..0x0F]),
+            "From short slice"
+        );
+    }
+
+    #[test]
+    fn raw_newtypes() {
+        use super::{DeviceAndFunctionNumber, SlotCharacteristics2, SlotPitch};
+        use bitfield::BitField;
+
+        const HOT_PLUG: SlotCharacteristics2 = SlotCharacteristics2::new(0b10);
+        assert_eq!(0b10, HOT_PLUG.raw());
+        assert_eq!(1, HOT_PLUG.significants().count());
+        assert_eq!(0, SlotCharacteristics2::default().significants().count());
+        assert_eq!(1250, u16::from(SlotPitch::new(1250)));
+        assert_eq!(SlotPitch::new(0), SlotPitch::default());
+        assert_eq!(Some(1250), SlotPitch::new(1250).pitch_hundredths_mm());
+        assert_eq!(None, SlotPitch::default().pitch_hundredths_mm());
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!("12.50 mm", format!("{}", SlotPitch::new(1250)));
+            assert_eq!("0.05 mm", format!("{}", SlotPitch::new(5)));
+            assert_eq!("Not given or is unknown", format!("{}", SlotPitch::new(0)));
+        }
+
+        const DEVICE: DeviceAndFunctionNumber = DeviceAndFunctionNumber::new(0xDF);
+        assert_eq!((0x1B, 0x07, 0xDF), (DEVICE.device(), DEVICE.function(), DEVICE.raw()));
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn peer_devices() {
         use super::PeerDevices;
         let sample_data = &[
             0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x01, 0x00, 0x00, 0x00, 0xE4, 0x04,
         ];
-        let result = PeerDevices(sample_data.chunks(5));
+        let result = PeerDevices::from(&sample_data[..]);
         let display_sample: Vec<String> = [
             "0000:00:01.0 (Width 0)",
             "0000:00:1c.3 (Width 1)",
@@ -1085,11 +1399,12 @@ mod tests {
                     version: SmbiosVersion { major, minor },
                     info: InfoType::SystemSlots,
                     length: 0,
-                    handle: 666,
+                    handle: Handle(666),
                     data: &[],
                     strings: &[],
+                    ..Default::default()
                 };
-                let result = SystemSlots::try_from(structure);
+                let result = SystemSlots::from_raw(structure);
                 match ((major, minor), result) {
                     (v, Err(e)) if ((2, 0)..(2, 1)).contains(&v) => {
                         assert_eq!(
@@ -1123,7 +1438,7 @@ mod tests {
                         assert_eq!("could not convert slice to array", format!("{}", e));
                     }
                     (_, Ok(ss)) => {
-                        assert_eq!(666, ss.handle);
+                        assert_eq!(Handle(666), ss.handle);
                     }
                 }
             }
@@ -1155,7 +1470,7 @@ mod tests {
             acc
         });
         let sample = SystemSlots {
-            handle: 0x0023,
+            handle: Handle(0x0023),
             slot_designation: "SSD1",
             slot_type: SlotType::PciExpress,
             slot_data_bus_width: SlotWidth::X4,
@@ -1172,12 +1487,13 @@ mod tests {
             slot_information: Some(0x06),
             slot_physical_width: Some(SlotWidth::X16),
             slot_pitch: Some(SlotPitch(0x04E2)),
+            slot_height: None,
         };
         let structure = RawStructure {
             version: (3, 4).into(),
             info: InfoType::SystemSlots,
             length: 0,
-            handle: 0x0023,
+            handle: Handle(0x0023),
             // Remove 4 bytes from `dmidecode -H 8 -u` 'Header and Data'
             data: &[
                 0x01, // Slot designation: first string
@@ -1221,8 +1537,9 @@ mod tests {
                 // SSD1
                 0x53, 0x53, 0x44, 0x31, 0x00,
             ],
+            ..Default::default()
         };
-        let result = SystemSlots::try_from(structure).unwrap();
+        let result = SystemSlots::from_raw(structure).unwrap();
         assert_eq!(sample, result, "Sample:\n{:X?}\nResult:\n{:X?}", sample, result);
     }
     #[test]
@@ -1243,7 +1560,7 @@ mod tests {
         assert_eq!(4, slots.len(), "Slots count: {}. Should be 4", slots.len());
 
         let slot1_sample = SystemSlots {
-            handle: 0x0900,
+            handle: Handle(0x0900),
             slot_designation: "PCIe Slot 1",
             slot_type: SlotType::PciExpressGen3x16,
             slot_data_bus_width: SlotWidth::X8,
@@ -1260,6 +1577,7 @@ mod tests {
             slot_information: None,
             slot_physical_width: None,
             slot_pitch: None,
+            slot_height: None,
         };
         let slot1_result = slots
             .iter()
@@ -1270,7 +1588,7 @@ mod tests {
             .unwrap();
         assert_eq!(&slot1_sample, slot1_result, "Entire SystemSlots struct: Slot 1");
         let slot4_sample = SystemSlots {
-            handle: 0x0903,
+            handle: Handle(0x0903),
             slot_designation: "PCIe Slot 4",
             slot_type: SlotType::PciExpressGen3,
             slot_data_bus_width: SlotWidth::X16,
@@ -1287,6 +1605,7 @@ mod tests {
             slot_information: None,
             slot_physical_width: None,
             slot_pitch: None,
+            slot_height: None,
         };
         let slot4_result = slots
             .iter()
@@ -1297,4 +1616,55 @@ mod tests {
             .unwrap();
         assert_eq!(&slot4_sample, slot4_result, "Entire SystemSlots struct: Slot 4");
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn physical_slots() {
+        use super::*;
+        use crate::testing::{StructureBuilder, TableBuilder};
+
+        let slot = |handle, designation, bus, peers: &[u8]| {
+            StructureBuilder::new(InfoType::SystemSlots)
+                .handle(Handle(handle))
+                .version((3, 2).into())
+                .string(designation)
+                .bytes(&[0xB6, 0x0B, 0x04, 0x04])
+                .word(handle)
+                .bytes(&[0x04, 0x01])
+                .word(0)
+                .bytes(&[bus, 0x00, 0x0B, (peers.len() / 5) as u8])
+                .bytes(peers)
+        };
+        let table = TableBuilder::new((3, 2).into())
+            // The second half of a bifurcated x16 slot, listed before the first half
+            .push(&slot(0x0901, "RISER1-B", 0x18, &[]))
+            .push(&slot(0x0900, "RISER1-A", 0x17, &[0x00, 0x00, 0x18, 0x00, 0x0B]))
+            .push(&slot(0x0902, "SLOT2", 0x3A, &[0x00, 0x00, 0x3B, 0x00, 0x0B]))
+            // Lists peers of its own, so it stays a physical slot despite being a peer of SLOT2
+            .push(&slot(0x0903, "SLOT3", 0x3B, &[0x00, 0x00, 0x3C, 0x00, 0x0B]))
+            .push(&slot(0x0904, "SLOT4", 0x3C, &[]))
+            .end(Handle(0xFEFF));
+
+        let grouped = table.structures().physical_slots().collect::<Vec<_>>();
+        let handles = grouped
+            .iter()
+            .map(|slot| {
+                (
+                    slot.primary.handle.0,
+                    slot.peers.iter().map(|s| s.handle.0).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(0x0900, vec![0x0901]), (0x0902, vec![]), (0x0903, vec![0x0904])],
+            handles
+        );
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "0000:17:00.0 (Width 11)",
+                format!("{}", grouped[0].primary.base_device().unwrap())
+            );
+        }
+    }
 }
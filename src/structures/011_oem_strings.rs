@@ -3,19 +3,19 @@
 //! This SMBIOS structure contains free-form strings defined by the OEM. Examples of this are part
 //! numbers for system reference documents, contact information for the manufacturer, etc.
 
-use crate::{MalformedStructureError, RawStructure, StructureStrings};
+use crate::{Handle, MalformedStructureError, RawStructure, StructureStrings};
 
 /// Contains free-form strings defined by the OEM
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct OemStrings<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// OEM defined strings
     pub strings: StructureStrings<'a>,
 }
 
 impl<'a> OemStrings<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let strings = structure.strings();
         Ok(OemStrings {
             handle: structure.handle,
@@ -39,7 +39,7 @@ mod tests {
             version: (3, 4).into(),
             info: InfoType::OemStrings,
             length: 0x05,
-            handle: 0x001E,
+            handle: Handle(0x001E),
             // Remove 4 bytes from `dmidecode -H 8 -u` 'Header and Data'
             data: &[
                 0x03, // Strings count
@@ -51,8 +51,9 @@ mod tests {
                 0x24, 0x48, 0x55, 0x41, 0x30, 0x34, 0x36, 0x34, 0x00, // $XXX0000
                 0x24, 0x58, 0x58, 0x58, 0x30, 0x30, 0x30, 0x30, 0x00, 0x00,
             ],
+            ..Default::default()
         };
-        let result = OemStrings::try_from(structure).unwrap();
+        let result = OemStrings::from_raw(structure).unwrap();
 
         assert_eq!(sample, result.strings.collect::<Vec<_>>());
     }
@@ -74,7 +75,7 @@ mod tests {
             .collect::<Vec<_>>();
 
         let sample = OemStrings {
-            handle: 0x0B00,
+            handle: Handle(0x0B00),
             strings: StructureStrings::new(&[
                 // Dell System
                 0x44, 0x65, 0x6C, 0x6C, 0x20, 0x53, 0x79, 0x73, 0x74, 0x65, 0x6D, 0x00, // 5[0000]
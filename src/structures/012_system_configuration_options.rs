@@ -4,7 +4,7 @@
 //! Switches.
 
 use crate::{
-    InfoType,
+    Handle, InfoType,
     MalformedStructureError::{self, InvalidStringIndex},
     RawStructure, StructureStrings,
 };
@@ -13,13 +13,13 @@ use crate::{
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct SystemConfigurationOptions<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Configuration strings
     pub strings: StructureStrings<'a>,
 }
 
 impl<'a> SystemConfigurationOptions<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let count: u8 = structure.get::<u8>(0x04)?;
         let strings = structure.strings();
         if count as usize != strings.count() {
@@ -27,6 +27,7 @@ impl<'a> SystemConfigurationOptions<'a> {
                 InfoType::SystemConfigurationOptions,
                 structure.handle,
                 count,
+                structure.string_count(),
             ))
         } else {
             Ok(SystemConfigurationOptions {
@@ -52,7 +53,7 @@ mod tests {
             version: (3, 4).into(),
             info: InfoType::SystemConfigurationOptions,
             length: 0x05,
-            handle: 0x001F,
+            handle: Handle(0x001F),
             // Remove 4 bytes from `dmidecode -H 8 -u` 'Header and Data'
             data: &[
                 0x03, // Strings count
@@ -65,8 +66,9 @@ mod tests {
                 0x00, // ConfigOptions3
                 0x43, 0x6F, 0x6E, 0x66, 0x69, 0x67, 0x4F, 0x70, 0x74, 0x69, 0x6F, 0x6E, 0x73, 0x33, 0x00,
             ],
+            ..Default::default()
         };
-        let result = SystemConfigurationOptions::try_from(structure).unwrap();
+        let result = SystemConfigurationOptions::from_raw(structure).unwrap();
 
         assert_eq!(sample, result.strings.collect::<Vec<_>>());
     }
@@ -88,7 +90,7 @@ mod tests {
             .collect::<Vec<_>>();
 
         let sample = SystemConfigurationOptions {
-            handle: 0x0C00,
+            handle: Handle(0x0C00),
             strings: StructureStrings::new(&[
                 // NVRAM_CLR: Clear user settable NVRAM areas and set defaults
                 0x4E, 0x56, 0x52, 0x41, 0x4D, 0x5F, 0x43, 0x4C, 0x52, 0x3A, 0x20, 0x43, 0x6C, 0x65, 0x61, 0x72, 0x20,
@@ -2,16 +2,22 @@
 //!
 //! The information in this structure defines the installable language attributes of the BIOS.
 
+#[cfg(feature = "strings")]
+use core::fmt;
+
 use crate::bitfield::{BitField, FlagType, Layout};
-use crate::{MalformedStructureError, RawStructure};
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
 /// The `BIOS Language Information` table defined in the SMBIOS specification.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct BiosLanguage<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Available languages
     pub installable_languages: InstallableLanguages<'a>,
+    /// Number of languages available as declared by the structure, firmware does not always
+    /// match it with the number of strings
+    pub installable_count: u8,
     /// Flags
     pub flags: Option<LanguageFlags>,
     /// String number (one-based) of the currently installed language
@@ -25,50 +31,86 @@ pub struct InstallableLanguages<'a> {
     index: u8,
 }
 
+/// An iterator through available languages decoded into [Language] items
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Languages<'a> {
+    strings: InstallableLanguages<'a>,
+    abbreviated: bool,
+}
+
+/// Language string decoded according to the language format flag
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Language<'a> {
+    /// ISO 639-1 Language Name
+    pub language: &'a str,
+    /// ISO 3166-1-alpha-2 Territory Name
+    pub territory: Option<&'a str>,
+    /// Encoding Method, not available in the abbreviated format
+    pub encoding: Option<&'a str>,
+}
+
 /// BIOS Language flags
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Default)]
 pub struct LanguageFlags(u8);
 
+raw_newtype!(LanguageFlags(u8));
+
+mod layout {
+    use super::Field;
+
+    pub const INSTALLABLE_LANGUAGES: Field<u8> = Field::new(0x04, 2, 0);
+    pub const FLAGS: Field<u8> = Field::new(0x05, 2, 1);
+    // The Current Language field is at offset 15h in every version
+    pub const CURRENT_LANGUAGE: Field<u8> = Field::new(0x15, 2, 0);
+}
+
 impl<'a> BiosLanguage<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<BiosLanguage<'a>, MalformedStructureError> {
-        #[repr(C)]
-        #[repr(packed)]
-        struct BiosLanguagePacked_2_1 {
-            installable_languages: u8,
-            flags: u8,
-            reserved: [u8; 15],
-            current_language: u8,
-        }
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<BiosLanguage<'a>, MalformedStructureError> {
+        use self::layout::*;
+
+        Ok(BiosLanguage {
+            handle: structure.handle,
+            installable_count: INSTALLABLE_LANGUAGES.get(&structure)?,
+            flags: FLAGS.get_since(&structure).map(LanguageFlags),
+            current_language: CURRENT_LANGUAGE.get(&structure)?,
+            installable_languages: InstallableLanguages::new(structure),
+        })
+    }
+}
 
-        #[repr(C)]
-        #[repr(packed)]
-        struct BiosLanguagePacked_2_0 {
-            installable_languages: u8,
-            reserved: [u8; 15],
-            current_language: u8,
+impl<'a> BiosLanguage<'a> {
+    /// Iterator through available languages decoded according to the language format flag
+    pub fn languages(&self) -> Languages<'a> {
+        Languages {
+            strings: self.installable_languages.clone(),
+            abbreviated: self.is_abbreviated(),
         }
+    }
 
-        match structure.version {
-            v if v >= (2, 1).into() => {
-                let_as_struct!(packed, BiosLanguagePacked_2_1, structure.data);
-                Ok(BiosLanguage {
-                    handle: structure.handle,
-                    installable_languages: InstallableLanguages::new(structure),
-                    flags: Some(LanguageFlags(packed.flags)),
-                    current_language: packed.current_language,
-                })
-            }
-            _ => {
-                let_as_struct!(packed, BiosLanguagePacked_2_0, structure.data);
-                Ok(BiosLanguage {
-                    handle: structure.handle,
-                    installable_languages: InstallableLanguages::new(structure),
-                    flags: None,
-                    current_language: packed.current_language,
-                })
-            }
+    /// The currently installed language string as found in the structure, e.g. `en|US|iso8859-1`
+    ///
+    /// The string is resolved even if it is not within the declared installable count.
+    pub fn current_language_str(&self) -> Option<&'a str> {
+        match self.current_language {
+            0 => None,
+            index => self.installable_languages.structure.find_string(index).ok(),
         }
     }
+
+    /// The currently installed language decoded according to the language format flag
+    pub fn current(&self) -> Option<Language<'a>> {
+        self.current_language_str()
+            .map(|s| Language::parse(s, self.is_abbreviated()))
+    }
+
+    /// Whether the number of language strings differs from the declared installable count
+    pub fn count_mismatch(&self) -> bool {
+        self.installable_languages.clone().count() != usize::from(self.installable_count)
+    }
+
+    fn is_abbreviated(&self) -> bool {
+        self.flags.map_or(false, |f| f.is_abbreviated())
+    }
 }
 
 impl<'a> InstallableLanguages<'a> {
@@ -86,6 +128,68 @@ impl<'a> Iterator for InstallableLanguages<'a> {
     }
 }
 
+impl<'a> Iterator for Languages<'a> {
+    type Item = Language<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.strings.next().map(|s| Language::parse(s, self.abbreviated))
+    }
+}
+
+impl<'a> Language<'a> {
+    /// Decode language string in the long (“en|US|iso8859-1”) or abbreviated (“enUS”) format.
+    ///
+    /// Strings containing the `|` separator are always decoded in the long format since some
+    /// firmware sets the abbreviated format flag regardless of the actual string format.
+    pub fn parse(s: &'a str, abbreviated: bool) -> Self {
+        let non_empty = |s: &'a str| Some(s).filter(|s| !s.is_empty());
+        if s.contains('|') {
+            let mut parts = s.splitn(3, '|');
+            Language {
+                language: parts.next().unwrap_or_default(),
+                territory: parts.next().and_then(non_empty),
+                encoding: parts.next().and_then(non_empty),
+            }
+        } else if abbreviated && s.len() == 4 && s.is_char_boundary(2) {
+            let (language, territory) = s.split_at(2);
+            Language {
+                language,
+                territory: Some(territory),
+                encoding: None,
+            }
+        } else {
+            Language {
+                language: s,
+                territory: None,
+                encoding: None,
+            }
+        }
+    }
+}
+
+/// Normalized as an IETF language tag, e.g. `en-US`, whatever the language string format
+#[cfg(feature = "strings")]
+impl<'a> fmt::Display for Language<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.language.chars() {
+            write!(f, "{}", c.to_ascii_lowercase())?;
+        }
+        if let Some(territory) = self.territory {
+            write!(f, "-")?;
+            for c in territory.chars() {
+                write!(f, "{}", c.to_ascii_uppercase())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LanguageFlags {
+    /// Language strings use the abbreviated format
+    pub fn is_abbreviated(&self) -> bool {
+        self.0 & 0b1 != 0
+    }
+}
+
 impl<'a> BitField<'a> for LanguageFlags {
     type Size = u8;
     fn value(&self) -> Self::Size {
@@ -110,6 +214,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::{testing::StructureBuilder, InfoType};
 
     const DMIDECODE_BIN: &[u8] = include_bytes!("../../tests/data/dmi.0.bin");
     lazy_static! {
@@ -118,7 +223,6 @@ mod tests {
 
     #[test]
     fn installable_languages() {
-        use crate::InfoType;
         let sample = vec![
             "en|US|iso8859-1",
             "fr|FR|iso8859-1",
@@ -138,7 +242,7 @@ mod tests {
             version: (0, 0).into(),
             info: InfoType::BiosLanguage,
             length: 0x1A,
-            handle: 0,
+            handle: Handle(0),
             data: &[],
             strings: &[
                 // "en|US|iso8859-1"
@@ -168,14 +272,146 @@ mod tests {
                 // "zh|CN|unicode"
                 0x7A, 0x68, 0x7C, 0x43, 0x4E, 0x7C, 0x75, 0x6E, 0x69, 0x63, 0x6F, 0x64, 0x65, 0x00,
             ],
+            ..Default::default()
         };
         let result = InstallableLanguages::new(structure);
         assert_eq!(sample, result.collect::<Vec<_>>(), "Installable language list");
     }
 
+    #[test]
+    fn language_formats() {
+        let long = Language::parse("en|US|iso8859-1", false);
+        assert_eq!(
+            Language {
+                language: "en",
+                territory: Some("US"),
+                encoding: Some("iso8859-1")
+            },
+            long
+        );
+        assert_eq!(
+            long,
+            Language::parse("en|US|iso8859-1", true),
+            "Long with abbreviated flag"
+        );
+        assert_eq!(
+            Language {
+                language: "fr",
+                territory: Some("FR"),
+                encoding: None
+            },
+            Language::parse("frFR", true)
+        );
+        assert_eq!(
+            Language {
+                language: "frFR",
+                territory: None,
+                encoding: None
+            },
+            Language::parse("frFR", false)
+        );
+    }
+
+    #[test]
+    fn abbreviated_languages() {
+        let structure = RawStructure {
+            version: (2, 1).into(),
+            info: InfoType::BiosLanguage,
+            length: 0x16,
+            handle: Handle(0),
+            data: &[2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            strings: b"enUS\0jaJP\0\0",
+            ..Default::default()
+        };
+        let bios_language = BiosLanguage::from_raw(structure).unwrap();
+        let result = bios_language
+            .languages()
+            .map(|l| (l.language, l.territory))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("en", Some("US")), ("ja", Some("JP"))], result);
+    }
+
+    #[test]
+    fn current_language() {
+        // Declares a single language but has two, the current one is the second
+        let builder = StructureBuilder::new(InfoType::BiosLanguage)
+            .version((2, 1).into())
+            .byte(1)
+            .byte(0)
+            .bytes(&[0; 15])
+            .byte(2)
+            .extra_string("en|US|iso8859-1")
+            .extra_string("fr|CA|iso8859-1");
+        let bios_language = BiosLanguage::from_raw(builder.raw()).unwrap();
+        assert!(bios_language.count_mismatch());
+        assert_eq!(Some("fr|CA|iso8859-1"), bios_language.current_language_str());
+        #[cfg(feature = "strings")]
+        assert_eq!("fr-CA", format!("{}", bios_language.current().unwrap()));
+
+        // The 2.0 layout has no flags, but the current language is at the same offset
+        let builder = StructureBuilder::new(InfoType::BiosLanguage)
+            .version((2, 0).into())
+            .byte(1)
+            .bytes(&[0; 16])
+            .byte(1)
+            .extra_string("EN|us|iso8859-1");
+        let bios_language = BiosLanguage::from_raw(builder.raw()).unwrap();
+        assert!(!bios_language.count_mismatch());
+        #[cfg(feature = "strings")]
+        assert_eq!("en-US", format!("{}", bios_language.current().unwrap()));
+
+        let none = BiosLanguage {
+            current_language: 0,
+            ..bios_language.clone()
+        };
+        assert_eq!(None, none.current());
+        let out_of_range = BiosLanguage {
+            current_language: 3,
+            ..bios_language
+        };
+        assert_eq!(None, out_of_range.current());
+    }
+
+    #[test]
+    fn short_structures() {
+        let builder = |version: (usize, usize), length: usize| {
+            let data = [1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+            StructureBuilder::new(InfoType::BiosLanguage)
+                .version(version.into())
+                .bytes(&data[..length - 4])
+                .extra_string("enUS")
+        };
+        let structure = builder((3, 2), 0x16);
+        let bios_language = BiosLanguage::from_raw(structure.raw()).unwrap();
+        assert_eq!(
+            (Some(LanguageFlags(1)), 1),
+            (bios_language.flags, bios_language.current_language)
+        );
+
+        // The flags are read from exactly the version that introduced them
+        let structure = builder((2, 0), 0x16);
+        let bios_language = BiosLanguage::from_raw(structure.raw()).unwrap();
+        assert_eq!((None, 1), (bios_language.flags, bios_language.current_language));
+
+        // The Current Language field is required by every version
+        for version in [(2, 0), (2, 1), (3, 2)] {
+            assert!(
+                matches!(
+                    BiosLanguage::from_raw(builder(version, 0x15).raw()),
+                    Err(MalformedStructureError::InvalidSlice(_))
+                ),
+                "{:?}",
+                version
+            );
+        }
+        assert!(matches!(
+            BiosLanguage::from_raw(builder((3, 2), 0x04).raw()),
+            Err(MalformedStructureError::InvalidSlice(_))
+        ));
+    }
+
     #[test]
     fn dmi_bin() {
-        use crate::InfoType;
         let bios_language_result = ENTRY_POINT
             .structures(&DMIDECODE_BIN[(ENTRY_POINT.smbios_address() as usize)..])
             .find_map(|s| {
@@ -187,18 +423,20 @@ mod tests {
             })
             .unwrap();
         let bios_language_sample = BiosLanguage {
-            handle: 0x0D00,
+            handle: Handle(0x0D00),
             installable_languages: InstallableLanguages::new(RawStructure {
                 version: (3, 2).into(),
                 info: InfoType::BiosLanguage,
                 length: 0x16,
-                handle: 0x0D00,
+                handle: Handle(0x0D00),
                 data: &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
                 strings: &[
                     0x65, 0x6E, 0x7C, 0x55, 0x53, 0x7C, 0x69, 0x73, 0x6F, 0x38, 0x38, 0x35, 0x39, 0x2D, 0x31, 0x00,
                     0x00,
                 ],
+                ..Default::default()
             }),
+            installable_count: 1,
             flags: Some(LanguageFlags([].iter().collect())),
             current_language: 1,
         };
@@ -5,17 +5,13 @@
 //! example, you can use the Group Associations structure to indicate that two CPUs share a common
 //! external cache system.
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure, TryFromBytes,
-};
+use crate::{Handle, MalformedStructureError, RawStructure, TryFromBytes};
 
 /// Named group with member items
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct GroupAssociations<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// String describing the group
     pub group_name: &'a str,
     /// Items iterator
@@ -23,10 +19,14 @@ pub struct GroupAssociations<'a> {
 }
 
 /// An iterator through certain components
+///
+/// The number of items is given by the structure length; the iteration stops early if the data
+/// ends before, rather than reading past the formatted section.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct GroupItems<'a> {
     data: &'a [u8],
     index: usize,
+    declared: usize,
 }
 
 /// Group member
@@ -35,32 +35,46 @@ pub struct GroupItem {
     /// Item (Structure) Type of this member
     pub type_: u8,
     /// Handle corresponding to this structure
-    pub handle: u16,
+    pub handle: Handle,
 }
 
 impl<'a> GroupAssociations<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let handle = structure.handle;
-        let slice =
-            structure
-                .get_slice(0x05, structure.length as usize - 0x05)
-                .ok_or(InvalidFormattedSectionLength(
-                    InfoType::GroupAssociations,
-                    handle,
-                    "",
-                    structure.length,
-                ))?;
+        let declared = (structure.length as usize).saturating_sub(0x05) / 3;
+        let data = structure.data.get(0x05 - 4..).unwrap_or(&[]);
         Ok(GroupAssociations {
             handle,
             group_name: structure.get_string(0x04)?,
-            items: GroupItems::new(slice),
+            items: GroupItems::new(data, declared),
         })
     }
+
+    /// Whether the structure length holds a whole number of items that are all present in the
+    /// formatted section
+    pub fn is_consistent(&self) -> bool {
+        self.items.data.len() == self.items.declared * 3
+    }
 }
 
 impl<'a> GroupItems<'a> {
-    fn new(data: &'a [u8]) -> Self {
-        Self { data, index: 0 }
+    fn new(data: &'a [u8], declared: usize) -> Self {
+        Self {
+            data,
+            index: 0,
+            declared,
+        }
+    }
+
+    /// Number of items implied by the structure length, `(length - 5) / 3`
+    pub fn declared(&self) -> usize {
+        self.declared
+    }
+
+    /// Number of items the iteration yields in total, the declared items that are present in the
+    /// formatted section
+    pub fn available(&self) -> usize {
+        self.declared.min(self.data.len() / 3)
     }
 }
 impl<'a> Iterator for GroupItems<'a> {
@@ -69,16 +83,20 @@ impl<'a> Iterator for GroupItems<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let start = self.index;
         let end = start + 3;
+        if end > self.available() * 3 {
+            return None;
+        }
         let slice = self.data.get(start..end)?;
         self.index = end;
         let type_ = *slice.first()?;
-        let handle = slice.get(1..).and_then(|s| u16::try_from_bytes(s).ok())?;
+        let handle = slice.get(1..).and_then(|s| Handle::try_from_bytes(s).ok())?;
         Some(GroupItem { type_, handle })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::Handle;
     use pretty_assertions::assert_eq;
     use std::prelude::v1::*;
 
@@ -87,27 +105,31 @@ mod tests {
         use super::{GroupItem, GroupItems};
 
         let data = &[4, 0x00, 0x04, 7, 0x00, 0x07, 7, 0x01, 0x07, 7, 0x02, 0x07];
-        let result = GroupItems::new(data);
+        let result = GroupItems::new(data, 4);
         let sample = vec![
             GroupItem {
                 type_: 4,
-                handle: 0x0400,
+                handle: Handle(0x0400),
             },
             GroupItem {
                 type_: 7,
-                handle: 0x0700,
+                handle: Handle(0x0700),
             },
             GroupItem {
                 type_: 7,
-                handle: 0x0701,
+                handle: Handle(0x0701),
             },
             GroupItem {
                 type_: 7,
-                handle: 0x0702,
+                handle: Handle(0x0702),
             },
         ];
 
         assert_eq!(sample, result.collect::<Vec<_>>());
+
+        // The shorter of the declared items and the data wins
+        assert_eq!(2, GroupItems::new(data, 2).count());
+        assert_eq!(3, GroupItems::new(&data[..11], 4).count());
     }
 
     #[test]
@@ -116,15 +138,24 @@ mod tests {
         use crate::{InfoType, RawStructure};
 
         let sample = vec![
-            GroupItem { type_: 4, handle: 0x08 },
-            GroupItem { type_: 4, handle: 0x0A },
-            GroupItem { type_: 7, handle: 0x09 },
+            GroupItem {
+                type_: 4,
+                handle: Handle(0x08),
+            },
+            GroupItem {
+                type_: 4,
+                handle: Handle(0x0A),
+            },
+            GroupItem {
+                type_: 7,
+                handle: Handle(0x09),
+            },
         ];
         let structure = RawStructure {
             version: (3, 4).into(),
             info: InfoType::GroupAssociations,
             length: 14,
-            handle: 0x0028,
+            handle: Handle(0x0028),
             // Remove 4 bytes from `dmidecode -H 8 -u` 'Header and Data'
             data: &[
                 0x01, // String number
@@ -140,10 +171,42 @@ mod tests {
                 0x44, 0x75, 0x61, 0x6c, 0x2d, 0x50, 0x72, 0x6f, 0x63, 0x65, 0x73, 0x73, 0x6f, 0x72, 0x20, 0x43, 0x50,
                 0x55, 0x20, 0x43, 0x6f, 0x6d, 0x70, 0x6c, 0x65, 0x78, 0x00, 0x00,
             ],
+            ..Default::default()
         };
-        let result = GroupAssociations::try_from(structure).unwrap();
+        let result = GroupAssociations::from_raw(structure).unwrap();
 
         assert_eq!("Dual-Processor CPU Complex", result.group_name, "Group name");
         assert_eq!(sample, result.items.collect::<Vec<_>>(), "Items");
+        assert!(result.is_consistent());
+    }
+
+    #[test]
+    fn inconsistent_length() {
+        use super::*;
+        use crate::{InfoType, RawStructure};
+
+        // A length of 0x0A declares one item and has a trailing byte
+        let structure = RawStructure {
+            version: (3, 4).into(),
+            info: InfoType::GroupAssociations,
+            length: 0x0A,
+            handle: Handle(0x0028),
+            data: &[0x00, 0x04, 0x08, 0x00, 0x04, 0x0A],
+            strings: &[0x00, 0x00],
+            ..Default::default()
+        };
+        let result = GroupAssociations::from_raw(structure.clone()).unwrap();
+        assert_eq!((1, 1), (result.items.declared(), result.items.available()));
+        assert_eq!(1, result.items.count());
+        assert!(!result.is_consistent());
+
+        let short = RawStructure {
+            length: 0x04,
+            ..structure
+        };
+        assert!(matches!(
+            crate::Structure::decode(short),
+            Err(MalformedStructureError::InvalidFormattedSectionLength { .. })
+        ));
     }
 }
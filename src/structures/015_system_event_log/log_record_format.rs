@@ -7,9 +7,11 @@
 //! Most of data in this module does not present in System Event Log (Type 15) structure, but
 //! describes data in Event Log
 
+#[cfg(feature = "strings")]
 use core::fmt;
 
 use crate::bitfield::{BitField, FlagType, Layout};
+use crate::Handle;
 
 /// Log Record format
 ///
@@ -118,14 +120,14 @@ pub enum VariableDataFormatType {
     /// No standard format data is available.
     None,
     /// Contains the handle of the SMBIOS structure associated with the hardware element that failed.
-    Handle { handle: u16 },
+    Handle { handle: Handle },
     /// Multiple-Event\
     /// Contains a multiple-event counter
     MultipleEvent { counter: u32 },
     /// Multiple-Event Handle\
     /// Contains the handle of the SMBIOS structure associated with the hardware element that
     /// failed;  it is followed by a DWORD containing a multiple-event counter
-    MultipleEventHandle { handle: u16, counter: u32 },
+    MultipleEventHandle { handle: Handle, counter: u32 },
     /// POST Results Bitmap
     PostResults(PostResults),
     /// System Management Type
@@ -166,9 +168,11 @@ pub struct MultipleEvent {
 /// identifies that one or more error types have occurred. The bitmap consists of two DWORD
 /// values.\
 /// Defined in [SMBIOS Specification](https://www.dmtf.org/sites/default/files/standards/documents/DSP0134_3.4.0.pdf) 7.16.6.4
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct PostResults(u64);
 
+raw_newtype!(PostResults(u64));
+
 /// System management types
 ///
 /// System management types present in an event log record’s variable data.
@@ -281,6 +285,7 @@ impl From<EventLogType> for u8 {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for EventLogType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match (f.alternate(), self) {
@@ -341,9 +346,12 @@ impl From<u8> for VariableDataFormatType {
     fn from(byte: u8) -> Self {
         match byte {
             0x00 => Self::None,
-            0x01 => Self::Handle { handle: 0 },
+            0x01 => Self::Handle { handle: Handle(0) },
             0x02 => Self::MultipleEvent { counter: 0 },
-            0x03 => Self::MultipleEventHandle { handle: 0, counter: 0 },
+            0x03 => Self::MultipleEventHandle {
+                handle: Handle(0),
+                counter: 0,
+            },
             0x04 => Self::PostResults((0).into()),
             0x05 => Self::SystemManagementType((0xFFFF).into()),
             0x06 => Self::MultipleEventSystemManagementType {
@@ -370,6 +378,7 @@ impl From<VariableDataFormatType> for u8 {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for VariableDataFormatType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match (f.alternate(), self) {
@@ -470,12 +479,6 @@ impl<'a> BitField<'a> for PostResults {
         "Audio Resource Conflict",
     );
 }
-impl From<u64> for PostResults {
-    fn from(qword: u64) -> Self {
-        Self(qword)
-    }
-}
-
 impl From<u32> for SystemManagementType {
     fn from(byte: u32) -> Self {
         match byte {
@@ -501,6 +504,7 @@ impl From<u32> for SystemManagementType {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for SystemManagementType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -570,15 +574,20 @@ mod tests {
         ];
         let result = data.iter().map(|v| v.0.into()).collect::<Vec<SystemManagementType>>();
         let enum_sample = data.iter().map(|v| v.1).collect::<Vec<_>>();
+        #[cfg(feature = "strings")]
         let display_sample = data.iter().map(|v| v.2).collect::<Vec<_>>();
         assert_eq!(enum_sample, result, "Enum variants");
-        assert_eq!(
-            display_sample,
-            result.iter().map(|v| format!("{}", v)).collect::<Vec<_>>(),
-            "Enum variants"
-        );
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                display_sample,
+                result.iter().map(|v| format!("{}", v)).collect::<Vec<_>>(),
+                "Enum variants"
+            );
+        }
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn post_results() {
         use super::PostResults;
@@ -10,14 +10,14 @@
 //! changed, the application can retrieve the entire event log and determine the changes since the
 //! last time it read the event log.
 
-use core::convert::TryInto;
+#[cfg(feature = "strings")]
 use core::fmt;
 use core::hash::{Hash, Hasher};
 use core::slice::Chunks;
 
 use crate::{
     bitfield::{BitField, FlagType, Layout},
-    InfoType,
+    Handle, InfoType, LengthExpectation,
     MalformedStructureError::{self, InvalidFormattedSectionLength},
     RawStructure,
 };
@@ -29,7 +29,7 @@ pub use self::log_record_format::{EventLogType, VariableDataFormatType};
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SystemEventLog<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Length, in bytes, of the overall event log area, from the first byte of header to the last
     /// byte of data
     pub log_area_length: u16,
@@ -42,14 +42,22 @@ pub struct SystemEventLog<'a> {
     /// most-significant byte of the start offset is set to 00h.
     pub log_data_start_offset: u16,
     pub access_method: AccessMethod,
+    /// Access Method Address as stored in the structure, `access_method` only keeps the bytes
+    /// the method defines
+    pub access_method_address: u32,
+    /// Volatile: ignored by [SystemEventLog::eq_stable]
     pub log_status: LogStatus,
     /// Unique token that is reassigned every time the event log changes Can be used to determine
-    /// if additional events have occurred since the last time the log was read.
+    /// if additional events have occurred since the last time the log was read.\
+    /// Volatile: ignored by [SystemEventLog::eq_stable]
     pub log_change_token: u32,
     /// Format of the log header area
     pub log_header_format: Option<LogHeaderFormat>,
     /// List of Supported Event Log Type Descriptors
     pub supported_event_log_type_descriptors: Option<SupportedEventLogTypeDescriptors<'a>>,
+    /// Bytes of the formatted section past the length computed from the descriptors, with which
+    /// some firmware pads the structure; empty for structures of the specified length
+    pub padding: &'a [u8],
 }
 
 /// Defines the Location and Method used by higher-level software to access the log area.
@@ -73,13 +81,48 @@ pub enum AccessMethod {
     OemSpecific { method: u8, address: u32 },
 }
 
+/// How to read the log area, see [SystemEventLog::read_plan]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReadPlan {
+    /// For each offset in `start..start + length`, write the offset to the index port(s) and read
+    /// one byte from the data port
+    IndexedIo {
+        index: IndexPorts,
+        /// I/O address of the 8-bit data port
+        data_port: u16,
+        start: u16,
+        length: u16,
+    },
+    /// Read `length` bytes of physical memory at `address`
+    Memory { address: u32, length: u16 },
+    /// Read `length` bytes from `start` through the General-Purpose NonVolatile Data functions
+    /// of the handle
+    Gpnv { handle: u16, start: u16, length: u16 },
+    /// Access methods that are not defined by the specification, as stored in the structure
+    Unsupported { method: u8, address: u32 },
+}
+
+/// Index ports of indexed I/O access methods, by I/O address
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IndexPorts {
+    /// One 8-bit index port, the offset is below 100h
+    One8Bit(u16),
+    /// Two 8-bit index ports, the first one takes the low byte of the offset, the second one the
+    /// high byte
+    Two8Bit(u16, u16),
+    /// One 16-bit index port
+    One16Bit(u16),
+}
+
 /// Current status of the system event-log
 ///
 /// The Log Status fields might not be up-to-date (dynamic) when the structure is accessed using
 /// the table interface.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub struct LogStatus(u8);
 
+raw_newtype!(LogStatus(u8));
+
 /// Identify the standard formats of the event log headers.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum LogHeaderFormat {
@@ -152,62 +195,93 @@ pub struct EventLogTypeDescriptor {
 }
 
 impl<'a> SystemEventLog<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let handle = structure.handle;
-        let number_of_supported_log_type_descriptors = structure.get::<u8>(0x15).ok();
-        let length_of_each_log_type_descriptor = structure.get::<u8>(0x16).ok();
-        let len_gt_2_1 = number_of_supported_log_type_descriptors
-            .and_then(|x| length_of_each_log_type_descriptor.map(|y| 0x17 + x as usize * y as usize));
-        match (
-            (structure.version.major, structure.version.minor),
-            structure.data.len() + 4,
-        ) {
-            (v, l) if v == (2, 0) && l != 0x14 => Err(InvalidFormattedSectionLength(
-                InfoType::SystemEventLog,
+        let length = structure.data.len() + 4;
+        // Firmware pads the formatted section beyond its computed length, which is only a minimum
+        let (minimum, descriptors) = if version_gate!(structure.version, (2, 0)..(2, 1)) {
+            (0x14, None)
+        } else {
+            match (structure.get::<u8>(0x15), structure.get::<u8>(0x16)) {
+                (Ok(number), Ok(size)) => (0x17 + number as usize * size as usize, Some((number, size))),
+                _ => (0x17, None),
+            }
+        };
+        if length < minimum {
+            return Err(InvalidFormattedSectionLength {
+                info: InfoType::SystemEventLog,
                 handle,
-                "",
-                0x14,
-            )),
-            (v, l) if v >= (2, 1) && Some(l) != len_gt_2_1 => {
-                if let Some(len) = len_gt_2_1 {
-                    Err(InvalidFormattedSectionLength(
-                        InfoType::SystemEventLog,
-                        handle,
-                        "17h+(x*y) = ",
-                        len as u8,
-                    ))
-                } else {
-                    Err(InvalidFormattedSectionLength(
-                        InfoType::SystemEventLog,
-                        handle,
-                        "minimum of ",
-                        0,
-                    ))
-                }
+                expected: LengthExpectation::Minimum(minimum.min(u8::MAX as usize) as u8),
+                actual: length as u8,
+            });
+        }
+
+        let access_method_address = structure.get::<u32>(0x10)?;
+        let access_method = AccessMethod::new(structure.get::<u8>(0x0A)?, access_method_address);
+        let supported_event_log_type_descriptors = descriptors.and_then(|(number, size)| {
+            let data = structure.get_slice(0x17, number as usize * size as usize)?;
+            Some(SupportedEventLogTypeDescriptors::new(data, size as usize))
+        });
+        Ok(Self {
+            handle,
+            log_area_length: structure.get::<u16>(0x04)?,
+            log_header_start_offset: structure.get::<u16>(0x06)?,
+            log_data_start_offset: structure.get::<u16>(0x08)?,
+            access_method,
+            access_method_address,
+            log_status: structure.get::<u8>(0x0B)?.into(),
+            log_change_token: structure.get::<u32>(0x0C)?,
+            log_header_format: structure.get::<u8>(0x14).ok().map(Into::into),
+            supported_event_log_type_descriptors,
+            padding: &structure.data[minimum - 4..],
+        })
+    }
+}
+
+impl<'a> SystemEventLog<'a> {
+    /// Compare with `other` ignoring the log status and change token, which change with the
+    /// content of the log
+    pub fn eq_stable(&self, other: &Self) -> bool {
+        SystemEventLog {
+            log_status: other.log_status,
+            log_change_token: other.log_change_token,
+            ..self.clone()
+        } == *other
+    }
+
+    /// The accesses a log reader performs to fetch the whole log area, header included
+    ///
+    /// Indexed I/O ports are given by the Access Method Address as a whole: its low word is the
+    /// address of the (first) index port and its high word the address of the data port, the
+    /// second index port of [IndexPorts::Two8Bit] follows the first one.
+    pub fn read_plan(&self) -> ReadPlan {
+        let [index_lsb, index_msb, data_lsb, data_msb] = self.access_method_address.to_le_bytes();
+        let index_port = u16::from_le_bytes([index_lsb, index_msb]);
+        let data_port = u16::from_le_bytes([data_lsb, data_msb]);
+        let (start, length) = (self.log_header_start_offset, self.log_area_length);
+        let indexed = |index| ReadPlan::IndexedIo {
+            index,
+            data_port,
+            start,
+            length,
+        };
+        match self.access_method {
+            AccessMethod::IndexedIoOne8bitIndexOne8bitData { .. } => indexed(IndexPorts::One8Bit(index_port)),
+            AccessMethod::IndexedIoTwo8bitIndexOne8bitData { .. } => {
+                indexed(IndexPorts::Two8Bit(index_port, index_port.wrapping_add(1)))
             }
-            _ => {
-                let access_method = {
-                    let method = structure.get::<u8>(0x0A)?;
-                    let address = structure.get::<u32>(0x10)?;
-                    AccessMethod::new(method, address)
-                };
-                let supported_event_log_type_descriptors = (|| {
-                    let number = number_of_supported_log_type_descriptors? as usize;
-                    let length = length_of_each_log_type_descriptor? as usize;
-                    let data = structure.get_slice(0x17, number * length)?;
-                    Some(SupportedEventLogTypeDescriptors::new(data, length))
-                })();
-                Ok(Self {
-                    handle,
-                    log_area_length: structure.get::<u16>(0x04)?,
-                    log_header_start_offset: structure.get::<u16>(0x06)?,
-                    log_data_start_offset: structure.get::<u16>(0x08)?,
-                    access_method,
-                    log_status: structure.get::<u8>(0x0B)?.into(),
-                    log_change_token: structure.get::<u32>(0x0C)?,
-                    log_header_format: structure.get::<u8>(0x14).ok().map(Into::into),
-                    supported_event_log_type_descriptors,
-                })
+            AccessMethod::IndexedIoOne16bitIndexOne8bitData { .. } => indexed(IndexPorts::One16Bit(index_port)),
+            AccessMethod::MemoryMappedPhysicaAddress { physical_address } => ReadPlan::Memory {
+                address: physical_address.wrapping_add(start as u32),
+                length,
+            },
+            AccessMethod::GeneralPurposeNonVolatileData { gpnv_handle } => ReadPlan::Gpnv {
+                handle: gpnv_handle,
+                start,
+                length,
+            },
+            AccessMethod::Available { method, address } | AccessMethod::OemSpecific { method, address } => {
+                ReadPlan::Unsupported { method, address }
             }
         }
     }
@@ -265,6 +339,7 @@ impl AccessMethod {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for AccessMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match (f.alternate(), self) {
@@ -331,12 +406,6 @@ impl<'a> BitField<'a> for LogStatus {
         "Reserved": 6,
     );
 }
-impl From<u8> for LogStatus {
-    fn from(byte: u8) -> Self {
-        Self(byte)
-    }
-}
-
 impl From<u8> for LogHeaderFormat {
     fn from(byte: u8) -> Self {
         match byte {
@@ -347,6 +416,7 @@ impl From<u8> for LogHeaderFormat {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for LogHeaderFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match (f.alternate(), self) {
@@ -362,7 +432,24 @@ impl fmt::Display for LogHeaderFormat {
 
 impl<'a> SupportedEventLogTypeDescriptors<'a> {
     fn new(data: &'a [u8], size: usize) -> Self {
-        Self(data.chunks(size))
+        // A zero length gives no descriptors, `chunks` only takes non-zero sizes
+        let data = if size == 0 { &[] } else { data };
+        Self(data.chunks(size.max(1)))
+    }
+
+    /// Pair every descriptor with the bytes that follow its first two, for OEM-extended
+    /// descriptors longer than the 2 bytes the specification defines
+    pub fn with_extra_bytes(self) -> DescriptorsWithExtraBytes<'a> {
+        DescriptorsWithExtraBytes(self)
+    }
+
+    fn next_descriptor(&mut self) -> Option<(EventLogTypeDescriptor, &'a [u8])> {
+        let next = self.0.next()?;
+        // A descriptor shorter than 2 bytes has no variable data format, 00h means none
+        let log_type = *next.first()?;
+        let variable_data_format_type = next.get(1).copied().unwrap_or(0);
+        let descriptor = EventLogTypeDescriptor::from([log_type, variable_data_format_type]);
+        Some((descriptor, next.get(2..).unwrap_or(&[])))
     }
 }
 impl<'a> PartialEq for SupportedEventLogTypeDescriptors<'a> {
@@ -380,11 +467,20 @@ impl<'a> Iterator for SupportedEventLogTypeDescriptors<'a> {
     type Item = EventLogTypeDescriptor;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.0.next()?;
-        next.try_into().ok().map(|a: [u8; 2]| EventLogTypeDescriptor {
-            log_type: a[0].into(),
-            variable_data_format_type: a[1].into(),
-        })
+        self.next_descriptor().map(|(descriptor, _)| descriptor)
+    }
+}
+
+/// An iterator through Event Log Type Descriptors and their bytes beyond the first two, created
+/// by [SupportedEventLogTypeDescriptors::with_extra_bytes]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DescriptorsWithExtraBytes<'a>(SupportedEventLogTypeDescriptors<'a>);
+
+impl<'a> Iterator for DescriptorsWithExtraBytes<'a> {
+    type Item = (EventLogTypeDescriptor, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_descriptor()
     }
 }
 
@@ -404,9 +500,11 @@ impl From<EventLogTypeDescriptor> for [u8; 2] {
 
 #[cfg(test)]
 mod tests {
+    use crate::Handle;
     use pretty_assertions::assert_eq;
     use std::prelude::v1::*;
 
+    #[cfg(feature = "strings")]
     #[test]
     fn access_method() {
         use super::AccessMethod;
@@ -442,13 +540,20 @@ mod tests {
 
         let byte: u8 = 0b111;
         let ls: LogStatus = byte.into();
-        let sample = vec!["Log area valid", "Log area full"];
-        assert_eq!(
-            sample,
-            ls.significants().map(|v| format!("{:#}", v)).collect::<Vec<_>>()
-        );
+        #[cfg(feature = "strings")]
+        {
+            let sample = vec!["Log area valid", "Log area full"];
+            assert_eq!(
+                sample,
+                ls.significants().map(|v| format!("{:#}", v)).collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(LogStatus::new(0b111), ls);
+        assert_eq!(0b111, ls.raw());
+        assert_eq!(0, LogStatus::default().significants().count());
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn log_header_format() {
         use super::LogHeaderFormat;
@@ -481,7 +586,7 @@ mod tests {
             },
             Desc {
                 log_type: T::BusTimeOut,
-                variable_data_format_type: D::Handle { handle: 0 },
+                variable_data_format_type: D::Handle { handle: Handle(0) },
             },
             Desc {
                 log_type: T::PostError,
@@ -489,7 +594,10 @@ mod tests {
             },
             Desc {
                 log_type: T::LogAreaReset,
-                variable_data_format_type: D::MultipleEventHandle { handle: 0, counter: 0 },
+                variable_data_format_type: D::MultipleEventHandle {
+                    handle: Handle(0),
+                    counter: 0,
+                },
             },
             Desc {
                 log_type: T::Unused(0x66),
@@ -506,6 +614,32 @@ mod tests {
         ];
         let result = SupportedEventLogTypeDescriptors::new(data, 2);
         assert_eq!(sample, result.collect::<Vec<_>>());
+
+        // OEM-extended descriptors keep their extra bytes
+        let data = &[0x02, 0x00, 0xAA, 0x04, 0x01, 0xBB];
+        let result = SupportedEventLogTypeDescriptors::new(data, 3)
+            .with_extra_bytes()
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(sample[0], &[0xAA][..]), (sample[1], &[0xBB][..])], result);
+        assert_eq!(
+            sample[..2].to_vec(),
+            SupportedEventLogTypeDescriptors::new(data, 3).collect::<Vec<_>>()
+        );
+
+        // Short descriptors are kept, without a variable data format
+        let result = SupportedEventLogTypeDescriptors::new(&[0x02, 0x04], 1).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                sample[0],
+                Desc {
+                    variable_data_format_type: D::None,
+                    ..sample[1]
+                }
+            ],
+            result
+        );
+        assert_eq!(0, SupportedEventLogTypeDescriptors::new(&[], 0).count());
+        assert_eq!(0, SupportedEventLogTypeDescriptors::new(&[0x02, 0x04], 0).count());
     }
 
     #[test]
@@ -522,11 +656,12 @@ mod tests {
             version: (2, 7).into(),
             info: InfoType::SystemEventLog,
             length,
-            handle: 0x0036,
+            handle: Handle(0x0036),
             data,
             strings,
+            ..Default::default()
         };
-        let result = SystemEventLog::try_from(structure).unwrap();
+        let result = SystemEventLog::from_raw(structure).unwrap();
 
         let access_method = AccessMethod::MemoryMappedPhysicaAddress {
             physical_address: 0xFFC40000,
@@ -587,16 +722,158 @@ mod tests {
             vec
         });
         let sample = SystemEventLog {
-            handle: 0x0036,
+            handle: Handle(0x0036),
             log_area_length: 16383,
             log_header_start_offset: 0x0000,
             log_data_start_offset: 0x0010,
             access_method,
+            access_method_address: 0xFFC40000,
             log_status,
             log_change_token: 0x00000001,
             log_header_format: Some(LogHeaderFormat::LogHeaderType1),
             supported_event_log_type_descriptors: Some(SupportedEventLogTypeDescriptors::new(&sample_bytes, 2)),
+            padding: &[],
         };
         assert_eq!(sample, result, "SystemEventLog");
+        assert_eq!(
+            ReadPlan::Memory {
+                address: 0xFFC40000,
+                length: 16383
+            },
+            result.read_plan(),
+            "ReadPlan"
+        );
+    }
+
+    #[test]
+    fn padded_length() {
+        use super::*;
+        use crate::{InfoType, RawStructure};
+
+        let bytes = include_bytes!("../../../tests/data/02daadcd/entries/15-0/bin");
+        let mut data = bytes[4..77].to_vec();
+        data.extend_from_slice(&[0x00, 0xFF, 0x00]);
+        fn structure(data: &[u8]) -> RawStructure<'_> {
+            RawStructure {
+                version: (2, 7).into(),
+                info: InfoType::SystemEventLog,
+                length: data.len() as u8 + 4,
+                handle: Handle(0x0036),
+                data,
+                strings: &[0, 0],
+                ..Default::default()
+            }
+        }
+        let padded = SystemEventLog::from_raw(structure(&data)).unwrap();
+        assert_eq!(27, padded.supported_event_log_type_descriptors.unwrap().count());
+        assert_eq!(&[0x00, 0xFF, 0x00], padded.padding);
+
+        match SystemEventLog::from_raw(structure(&data[..72])) {
+            Err(InvalidFormattedSectionLength {
+                expected: LengthExpectation::Minimum(77),
+                actual: 76,
+                ..
+            }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn eq_stable() {
+        use super::*;
+        use crate::{InfoType, RawStructure};
+
+        let bytes = include_bytes!("../../../tests/data/02daadcd/entries/15-0/bin");
+        fn decode(data: &[u8]) -> SystemEventLog<'_> {
+            let structure = RawStructure {
+                version: (2, 7).into(),
+                info: InfoType::SystemEventLog,
+                length: data.len() as u8 + 4,
+                handle: Handle(0x0036),
+                data,
+                strings: &[0, 0],
+                ..Default::default()
+            };
+            SystemEventLog::from_raw(structure).unwrap()
+        }
+        let original = decode(&bytes[4..77]);
+        // Log status and change token, then the log area length
+        let mut changed = bytes[4..77].to_vec();
+        changed[0x0B - 4] ^= 0b11;
+        changed[0x0C - 4] = changed[0x0C - 4].wrapping_add(1);
+        let log_changed = decode(&changed);
+        assert_ne!(original, log_changed);
+        assert!(original.eq_stable(&log_changed));
+        changed[0x04 - 4] = changed[0x04 - 4].wrapping_add(1);
+        assert!(!original.eq_stable(&decode(&changed)));
+    }
+
+    #[test]
+    fn read_plan() {
+        use super::*;
+
+        let log = |method, address| SystemEventLog {
+            handle: Handle(0x0F00),
+            log_area_length: 0x0200,
+            log_header_start_offset: 0x0010,
+            log_data_start_offset: 0x0020,
+            access_method: AccessMethod::new(method, address),
+            access_method_address: address,
+            log_status: 0.into(),
+            log_change_token: 0,
+            log_header_format: None,
+            supported_event_log_type_descriptors: None,
+            padding: &[],
+        };
+        let (start, length) = (0x0010, 0x0200);
+        assert_eq!(
+            ReadPlan::IndexedIo {
+                index: IndexPorts::One8Bit(0x0C70),
+                data_port: 0x0C71,
+                start,
+                length
+            },
+            log(0, 0x0C71_0C70).read_plan()
+        );
+        assert_eq!(
+            ReadPlan::IndexedIo {
+                index: IndexPorts::Two8Bit(0x0070, 0x0071),
+                data_port: 0x0072,
+                start,
+                length
+            },
+            log(1, 0x0072_0070).read_plan()
+        );
+        assert_eq!(
+            ReadPlan::IndexedIo {
+                index: IndexPorts::One16Bit(0x0CA0),
+                data_port: 0x0CA2,
+                start,
+                length
+            },
+            log(2, 0x0CA2_0CA0).read_plan()
+        );
+        assert_eq!(
+            ReadPlan::Memory {
+                address: 0xFFC4_0010,
+                length
+            },
+            log(3, 0xFFC4_0000).read_plan()
+        );
+        assert_eq!(
+            ReadPlan::Gpnv {
+                handle: 0x1234,
+                start,
+                length
+            },
+            log(4, 0x0000_1234).read_plan()
+        );
+        assert_eq!(
+            ReadPlan::Unsupported {
+                method: 0x80,
+                address: 0xDEAD_BEEF
+            },
+            log(0x80, 0xDEAD_BEEF).read_plan()
+        );
     }
 }
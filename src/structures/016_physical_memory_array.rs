@@ -3,14 +3,15 @@
 //! This structure describes a collection of memory devices that operate together to form a memory
 //! address space.
 
-use core::convert::TryInto;
+#[cfg(feature = "strings")]
 use core::fmt;
 
-use crate::{MalformedStructureError, RawStructure};
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub enum MemoryArrayLocation {
     Other,
+    #[default]
     Unknown,
     SystemBoardOrMotherboard,
     IsaAddOnCard,
@@ -28,12 +29,6 @@ pub enum MemoryArrayLocation {
     Undefined(u8),
 }
 
-impl Default for MemoryArrayLocation {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl From<u8> for MemoryArrayLocation {
     fn from(_type: u8) -> Self {
         match _type {
@@ -57,6 +52,7 @@ impl From<u8> for MemoryArrayLocation {
     }
 }
 
+#[cfg(feature = "strings")]
 impl fmt::Display for MemoryArrayLocation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -80,9 +76,10 @@ impl fmt::Display for MemoryArrayLocation {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub enum MemoryArrayUse {
     Other,
+    #[default]
     Unknown,
     SystemMemory,
     VideoMemory,
@@ -92,12 +89,6 @@ pub enum MemoryArrayUse {
     Undefined(u8),
 }
 
-impl Default for MemoryArrayUse {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl From<u8> for MemoryArrayUse {
     fn from(_type: u8) -> Self {
         match _type {
@@ -113,6 +104,7 @@ impl From<u8> for MemoryArrayUse {
     }
 }
 
+#[cfg(feature = "strings")]
 impl fmt::Display for MemoryArrayUse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -128,9 +120,10 @@ impl fmt::Display for MemoryArrayUse {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub enum MemoryArrayErrorCorrectionTypes {
     Other,
+    #[default]
     Unknown,
     None,
     Parity,
@@ -140,12 +133,6 @@ pub enum MemoryArrayErrorCorrectionTypes {
     Undefined(u8),
 }
 
-impl Default for MemoryArrayErrorCorrectionTypes {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl From<u8> for MemoryArrayErrorCorrectionTypes {
     fn from(_type: u8) -> Self {
         match _type {
@@ -161,6 +148,7 @@ impl From<u8> for MemoryArrayErrorCorrectionTypes {
     }
 }
 
+#[cfg(feature = "strings")]
 impl fmt::Display for MemoryArrayErrorCorrectionTypes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -182,7 +170,7 @@ impl fmt::Display for MemoryArrayErrorCorrectionTypes {
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct PhysicalMemoryArray {
-    pub handle: u16,
+    pub handle: Handle,
     /// Physical location of the Memory Array, whether on the system board or an add-in board
     pub location: MemoryArrayLocation,
     /// Function for which the array is used
@@ -197,8 +185,9 @@ pub struct PhysicalMemoryArray {
     /// in the Extended Maximum Capacity field.
     pub maximum_capacity: Option<u32>,
     /// Handle, or instance number, associated with any
-    /// error that was previously detected for the array
-    pub memory_error_information_handle: Option<u16>,
+    /// error that was previously detected for the array, `None` if the error information is not
+    /// provided ([Handle::UNKNOWN]) or no error was detected ([Handle::NONE])
+    pub memory_error_information_handle: Option<Handle>,
     /// Number of slots or sockets available for Memory Devices in this array
     /// This value represents the number of Memory Device structures that compose this Memory
     /// Array. Each Memory Device has a reference to the “owning” Memory Array.
@@ -209,86 +198,123 @@ pub struct PhysicalMemoryArray {
     pub extended_maximum_capacity: Option<u64>,
 }
 
+/// Formatted section of the Physical Memory Array structure, per SMBIOS version
+mod layout {
+    use super::Field;
+
+    pub const LOCATION: Field<u8> = Field::new(0x04, 2, 1);
+    pub const USE: Field<u8> = Field::new(0x05, 2, 1);
+    pub const MEMORY_ERROR_CORRECTION: Field<u8> = Field::new(0x06, 2, 1);
+    pub const MAXIMUM_CAPACITY: Field<u32> = Field::new(0x07, 2, 1);
+    pub const MEMORY_ERROR_INFORMATION_HANDLE: Field<u16> = Field::new(0x0B, 2, 1);
+    pub const NUMBER_OF_MEMORY_DEVICES: Field<u16> = Field::new(0x0D, 2, 1);
+    pub const EXTENDED_MAXIMUM_CAPACITY: Field<u64> = Field::new(0x0F, 2, 7);
+}
+
 impl PhysicalMemoryArray {
-    pub(crate) fn try_from(structure: RawStructure) -> Result<Self, MalformedStructureError> {
-        let mut pma = PhysicalMemoryArray::default();
-        let mut mem_pointer = 0;
-        if structure.version > (2, 1).into() {
-            pma.handle = structure.handle;
-            pma.location = MemoryArrayLocation::from(structure.data[mem_pointer]);
-            mem_pointer += 1;
-            pma.r#use = MemoryArrayUse::from(structure.data[mem_pointer]);
-            mem_pointer += 1;
-            pma.memory_error_correction = MemoryArrayErrorCorrectionTypes::from(structure.data[mem_pointer]);
-            mem_pointer += 1;
-            pma.maximum_capacity = get_optional_dword(&mut mem_pointer, structure.data, 0x80000000)?;
-            pma.memory_error_information_handle = get_optional_word(&mut mem_pointer, structure.data, 0xFFFE)?;
-            pma.number_of_memory_devices = get_word(&mut mem_pointer, structure.data)?;
-        }
-        if structure.version > (2, 7).into() {
-            pma.extended_maximum_capacity = if pma.maximum_capacity.is_none() {
-                get_optional_qword(&mut mem_pointer, structure.data, 0)?
-            } else {
-                None
-            };
-        }
-        Ok(pma)
+    pub(crate) fn from_raw(structure: RawStructure) -> Result<Self, MalformedStructureError> {
+        use self::layout::*;
+
+        // 8000_0000h defers to the Extended Maximum Capacity field
+        let maximum_capacity = MAXIMUM_CAPACITY
+            .get_since(&structure)
+            .filter(|&capacity| capacity != 0x8000_0000);
+        let extended_maximum_capacity = if maximum_capacity.is_none() {
+            EXTENDED_MAXIMUM_CAPACITY
+                .get_since(&structure)
+                .filter(|&capacity| capacity != 0)
+        } else {
+            None
+        };
+        Ok(PhysicalMemoryArray {
+            handle: structure.handle,
+            location: LOCATION.get_since(&structure).map(Into::into).unwrap_or_default(),
+            r#use: USE.get_since(&structure).map(Into::into).unwrap_or_default(),
+            memory_error_correction: MEMORY_ERROR_CORRECTION
+                .get_since(&structure)
+                .map(Into::into)
+                .unwrap_or_default(),
+            maximum_capacity,
+            memory_error_information_handle: MEMORY_ERROR_INFORMATION_HANDLE
+                .get_since(&structure)
+                .and_then(|handle| Handle(handle).as_reference()),
+            number_of_memory_devices: NUMBER_OF_MEMORY_DEVICES.get_since(&structure).unwrap_or(0),
+            extended_maximum_capacity,
+        })
     }
 }
 
-fn get_optional_qword(pointer: &mut usize, data: &[u8], none_val: u64) -> Result<Option<u64>, MalformedStructureError> {
-    let word = get_qword(pointer, data)?;
-    if word == none_val {
-        Ok(None)
-    } else {
-        Ok(Some(word))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::StructureBuilder, InfoType, Structure};
+    use pretty_assertions::assert_eq;
+
+    fn array(version: (usize, usize), maximum_capacity: u32) -> StructureBuilder {
+        StructureBuilder::new(InfoType::PhysicalMemoryArray)
+            .handle(Handle(0x1000))
+            .version(version.into())
+            .bytes(&[0x03, 0x03, 0x06])
+            .dword(maximum_capacity)
+            .handle_ref(Handle::NONE)
+            .word(4)
     }
-}
 
-fn get_optional_dword(pointer: &mut usize, data: &[u8], none_val: u32) -> Result<Option<u32>, MalformedStructureError> {
-    let word = get_dword(pointer, data)?;
-    if word == none_val {
-        Ok(None)
-    } else {
-        Ok(Some(word))
+    fn decode(builder: &StructureBuilder) -> PhysicalMemoryArray {
+        match builder.decode() {
+            Ok(Structure::PhysicalMemoryArray(array)) => array,
+            other => panic!("{:?}", other),
+        }
     }
-}
 
-fn get_optional_word(pointer: &mut usize, data: &[u8], none_val: u16) -> Result<Option<u16>, MalformedStructureError> {
-    let word = get_word(pointer, data)?;
-    if word == none_val {
-        Ok(None)
-    } else {
-        Ok(Some(word))
+    #[test]
+    fn physical_memory_array_2_7() {
+        let decoded = decode(&array((3, 2), 0x8000_0000).qword(4 << 40));
+        assert_eq!(
+            PhysicalMemoryArray {
+                handle: Handle(0x1000),
+                location: MemoryArrayLocation::SystemBoardOrMotherboard,
+                r#use: MemoryArrayUse::SystemMemory,
+                memory_error_correction: MemoryArrayErrorCorrectionTypes::MultiBitEcc,
+                maximum_capacity: None,
+                memory_error_information_handle: None,
+                number_of_memory_devices: 4,
+                extended_maximum_capacity: Some(4 << 40),
+            },
+            decoded
+        );
     }
-}
 
-fn get_word(pointer: &mut usize, data: &[u8]) -> Result<u16, MalformedStructureError> {
-    let word = u16::from_le_bytes(
-        data[*pointer..(*pointer + 2)]
-            .try_into()
-            .map_err(MalformedStructureError::InvalidSlice)?,
-    );
-    *pointer += 2;
-    Ok(word)
-}
+    #[test]
+    fn short_layout() {
+        // The 2.1 layout in tables of later versions, the extended capacity is absent
+        for &version in &[(2, 8), (3, 2)] {
+            let decoded = decode(&array(version, 0x8000_0000));
+            assert_eq!(
+                (None, None),
+                (decoded.maximum_capacity, decoded.extended_maximum_capacity)
+            );
+            assert_eq!(4, decoded.number_of_memory_devices);
 
-fn get_dword(pointer: &mut usize, data: &[u8]) -> Result<u32, MalformedStructureError> {
-    let dword = u32::from_le_bytes(
-        data[*pointer..(*pointer + 4)]
-            .try_into()
-            .map_err(MalformedStructureError::InvalidSlice)?,
-    );
-    *pointer += 4;
-    Ok(dword)
-}
+            let decoded = decode(&array(version, 0x0100_0000));
+            assert_eq!(Some(0x0100_0000), decoded.maximum_capacity);
+        }
+    }
 
-fn get_qword(pointer: &mut usize, data: &[u8]) -> Result<u64, MalformedStructureError> {
-    let qword = u64::from_le_bytes(
-        data[*pointer..(*pointer + 8)]
-            .try_into()
-            .map_err(MalformedStructureError::InvalidSlice)?,
-    );
-    *pointer += 8;
-    Ok(qword)
+    #[test]
+    fn introducing_versions() {
+        let decoded = decode(&array((2, 7), 0x8000_0000).qword(4 << 40));
+        assert_eq!(Some(4 << 40), decoded.extended_maximum_capacity);
+        let decoded = decode(&array((2, 6), 0x8000_0000).qword(4 << 40));
+        assert_eq!(None, decoded.extended_maximum_capacity);
+        let decoded = decode(&array((2, 1), 0x0100_0000));
+        assert_eq!(
+            (MemoryArrayUse::SystemMemory, Some(0x0100_0000), 4),
+            (
+                decoded.r#use,
+                decoded.maximum_capacity,
+                decoded.number_of_memory_devices
+            )
+        );
+    }
 }
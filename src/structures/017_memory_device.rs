@@ -6,11 +6,12 @@
 //! Array](super::physical_memory_array "structures::physical_memory_array") (Type 16)
 //! structure.
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
-};
+#[cfg(feature = "strings")]
+use core::fmt;
+
+#[cfg(feature = "jep106")]
+use crate::jep106::Jep106Id;
+use crate::{Handle, InfoType, MalformedStructureError, PhysicalMemoryArray, RawStructure, Structure, Structures};
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ErrorGranularity {
@@ -115,6 +116,8 @@ pub enum FormFactor {
     SoDimm,
     Srimm,
     FbDimm,
+    Die,
+    Camm,
     Undefined(u8),
 }
 
@@ -137,6 +140,8 @@ impl From<u8> for FormFactor {
             13 => FormFactor::SoDimm,
             14 => FormFactor::Srimm,
             15 => FormFactor::FbDimm,
+            16 => FormFactor::Die,
+            17 => FormFactor::Camm,
             t => FormFactor::Undefined(t),
         }
     }
@@ -206,6 +211,7 @@ pub enum Type {
     LogicalNonVolatileDevice,
     Hbm,
     Hbm2,
+    Hbm3,
     Undefined(u8),
 }
 
@@ -247,6 +253,7 @@ impl From<u8> for Type {
             33 => Type::Hbm2,
             34 => Type::Ddr5,
             35 => Type::LpDdr5,
+            36 => Type::Hbm3,
             t => Type::Undefined(t),
         }
     }
@@ -281,6 +288,7 @@ impl Default for Detail {
 }
 
 bitflags! {
+    #[derive(Default)]
     pub struct OperatingModes: u16 {
         const RESERVED =                    0b0000000000000000;
         const OTHER =                       0b0000000000000010;
@@ -297,9 +305,14 @@ bitflags! {
 /// is high enough to have defined the field.
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct MemoryDevice<'buffer> {
-    pub handle: u16,
-    pub physical_memory_handle: u16,
-    pub memory_error_handle: Option<u16>,
+    pub handle: Handle,
+    /// Handle of the Physical Memory Array the device is part of, see
+    /// [Structures::array_for_device]
+    pub physical_memory_handle: Handle,
+    /// Handle of the structure describing the last error detected on the device, see
+    /// [Structures::error_for_device]. `None` if the error information is not provided
+    /// ([Handle::UNKNOWN]) or no error was detected ([Handle::NONE]).
+    pub memory_error_handle: Option<Handle>,
     /// Total width, in bits, of this memory device, including any check
     /// or error-correction bits. If there are no error-correction bits,
     /// this value should be equal to Data Width
@@ -327,12 +340,14 @@ pub struct MemoryDevice<'buffer> {
     /// Identifies the maximum capable speed of the device, in megatransfers
     /// per second (MT/s)
     pub speed: Option<u16>,
+    /// Manufacturer of the device, empty if not specified or before SMBIOS 2.3
     pub manufacturer: &'buffer str,
     pub serial: &'buffer str,
     pub asset_tag: &'buffer str,
     pub part_number: &'buffer str,
+    /// Attributes, the rank in bits 3:0, see [MemoryDevice::rank]; 0 before SMBIOS 2.6
     pub attributes: u8,
-    /// Extended size of the memory device (complements the Size field)
+    /// Extended size of the memory device (complements the Size field); 0 before SMBIOS 2.7
     pub extended_size: u32,
     /// Identifies the configured speed of the memory device, in
     /// megatransfers per second (MT/s)
@@ -375,70 +390,274 @@ pub struct MemoryDevice<'buffer> {
 }
 
 impl<'a> MemoryDevice<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<MemoryDevice<'a>, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<MemoryDevice<'a>, MalformedStructureError> {
         let handle = structure.handle;
-        // minimum size of memory device for 2.1 BIOS spec. Anything else we'll consider optional
-        if (structure.data.len() + 4) < 0x15 {
-            return Err(InvalidFormattedSectionLength(
-                InfoType::MemoryDevice,
-                handle,
-                "at least",
-                0x15,
-            ));
-        }
-
+        // Strings added by SMBIOS 2.3 read as "not specified" when the structure predates them
+        let string = |offset| {
+            structure
+                .get_optional::<u8>(offset)
+                .map_or(Ok(""), |idx| structure.find_string(idx))
+        };
         Ok(MemoryDevice {
             handle,
-            physical_memory_handle: structure.get::<u16>(0x04)?,
-            memory_error_handle: structure.get::<u16>(0x06).ok().filter(|v| v != &0xFFFE),
-            total_width: structure.get::<u16>(0x08).ok().filter(|v| v != &0xFFFF),
-            data_width: structure.get::<u16>(0x0A).ok().filter(|v| v != &0xFFFF),
-            size: structure.get::<u16>(0x0C).ok().filter(|v| v != &0xFFFF),
+            physical_memory_handle: structure.get::<Handle>(0x04)?,
+            memory_error_handle: structure.get::<Handle>(0x06)?.as_reference(),
+            total_width: structure.get_unless::<u16>(0x08, 0xFFFF),
+            data_width: structure.get_unless::<u16>(0x0A, 0xFFFF),
+            size: structure.get_unless::<u16>(0x0C, 0xFFFF),
             form_factor: structure.get::<u8>(0x0E)?.into(),
             device_set: structure.get::<u8>(0x0F)?.into(),
             device_locator: structure.get_string(0x10)?,
             bank_locator: structure.get_string(0x11)?,
             memory_type: structure.get::<u8>(0x12)?.into(),
             type_detail: Detail::from_bits_truncate(structure.get::<u16>(0x13)?),
-            speed: structure.get::<u16>(0x15).ok().filter(|v| v != &0x0000),
-            manufacturer: structure.get_string(0x17)?,
-            serial: structure.get_string(0x18)?,
-            asset_tag: structure.get_string(0x19)?,
-            part_number: structure.get_string(0x1A)?,
-            attributes: structure.get::<u8>(0x1B)?,
-            extended_size: structure.get::<u32>(0x1C)?,
-            configured_memory_speed: structure.get::<u16>(0x20).ok().filter(|v| v != &0x0000),
-            minimum_voltage: structure.get::<u16>(0x22).ok().filter(|v| v != &0x0000),
-            maximum_voltage: structure.get::<u16>(0x24).ok().filter(|v| v != &0x0000),
-            configured_voltage: structure.get::<u16>(0x26).ok().filter(|v| v != &0x0000),
-            memory_technology: structure.get::<u8>(0x28).ok().map(Into::into),
-            operating_mode_capability: structure.get::<u16>(0x29).ok().map(OperatingModes::from_bits_truncate),
+            speed: structure.get_unless::<u16>(0x15, 0x0000),
+            manufacturer: string(0x17)?,
+            serial: string(0x18)?,
+            asset_tag: string(0x19)?,
+            part_number: string(0x1A)?,
+            attributes: structure.get_optional::<u8>(0x1B).unwrap_or(0),
+            extended_size: structure.get_optional::<u32>(0x1C).unwrap_or(0),
+            configured_memory_speed: structure.get_unless::<u16>(0x20, 0x0000),
+            minimum_voltage: structure.get_unless::<u16>(0x22, 0x0000),
+            maximum_voltage: structure.get_unless::<u16>(0x24, 0x0000),
+            configured_voltage: structure.get_unless::<u16>(0x26, 0x0000),
+            memory_technology: structure.get_optional::<u8>(0x28).map(Into::into),
+            operating_mode_capability: structure
+                .get_optional::<u16>(0x29)
+                .map(OperatingModes::from_bits_truncate),
             firmware_version: structure.get_string(0x2B).ok(),
-            module_manufacturer: structure.get::<u16>(0x2C).ok(),
-            module_product_id: structure.get::<u16>(0x2E).ok(),
-            memory_subsystem_controller_manufacturer_id: structure.get::<u16>(0x30).ok(),
-            memory_subsystem_controller_product_id: structure.get::<u16>(0x32).ok(),
-            non_volatile_size: structure.get::<u64>(0x34).ok(),
-            volatile_size: structure.get::<u64>(0x3C).ok(),
-            cache_size: structure.get::<u64>(0x44).ok(),
-            logical_size: structure.get::<u64>(0x4C).ok(),
-            extended_speed: structure.get::<u32>(0x54).ok(),
-            extended_configured_memory_speed: structure.get::<u32>(0x58).ok(),
+            module_manufacturer: structure.get_optional::<u16>(0x2C),
+            module_product_id: structure.get_optional::<u16>(0x2E),
+            memory_subsystem_controller_manufacturer_id: structure.get_optional::<u16>(0x30),
+            memory_subsystem_controller_product_id: structure.get_optional::<u16>(0x32),
+            non_volatile_size: structure.get_optional::<u64>(0x34),
+            volatile_size: structure.get_optional::<u64>(0x3C),
+            cache_size: structure.get_optional::<u64>(0x44),
+            logical_size: structure.get_optional::<u64>(0x4C),
+            extended_speed: structure.get_optional::<u32>(0x54),
+            extended_configured_memory_speed: structure.get_optional::<u32>(0x58),
         })
     }
+
+    /// Size of the installed memory in bytes, `Some(0)` if the socket is empty and `None` if the
+    /// size is unknown.
+    ///
+    /// Bit 15 of the Size field selects KB instead of MB granularity, and the value 7FFFh defers
+    /// to the Extended Size field (in MB).
+    pub fn size_bytes(&self) -> Option<u64> {
+        match self.size? {
+            0x7FFF => Some(u64::from(self.extended_size & 0x7FFF_FFFF) << 20),
+            size if size & 0x8000 != 0 => Some(u64::from(size & 0x7FFF) << 10),
+            size => Some(u64::from(size) << 20),
+        }
+    }
+
+    /// Rank of the device, from bits 3:0 of the Attributes field, `None` if unknown (0)
+    pub fn rank(&self) -> Option<u8> {
+        Some(self.attributes & 0x0F).filter(|&rank| rank != 0)
+    }
+
+    /// Number of error-correction bits, the Total Width minus the Data Width, `None` if either
+    /// width is unknown or the Total Width is the smaller one
+    ///
+    /// A device with ECC reports e.g. 72 bits of total width for 64 bits of data width, so this is
+    /// `Some(8)`, while a device without ECC gives `Some(0)`.
+    pub fn ecc_bits(&self) -> Option<u16> {
+        self.total_width?.checked_sub(self.data_width?)
+    }
+
+    /// Maximum capable speed of the device, `None` if unknown
+    ///
+    /// Since SMBIOS 3.3 the Speed field is FFFFh for speeds of 65,535 MT/s or more, which are
+    /// given by the Extended Speed field instead.
+    pub fn effective_speed(&self) -> Option<MtPerSec> {
+        MtPerSec::resolve(self.speed, self.extended_speed)
+    }
+
+    /// Configured speed of the device, `None` if unknown, see [MemoryDevice::effective_speed]
+    pub fn effective_configured_speed(&self) -> Option<MtPerSec> {
+        MtPerSec::resolve(self.configured_memory_speed, self.extended_configured_memory_speed)
+    }
+
+    /// JEP-106 name of the module manufacturer, `None` if the ID is missing, unknown (0) or not in
+    /// the [table](crate::jep106)
+    #[cfg(feature = "jep106")]
+    pub fn manufacturer_name(&self) -> Option<&'static str> {
+        Jep106Id::from_smbios(self.module_manufacturer?)?.name()
+    }
+
+    /// JEP-106 name of the memory subsystem controller manufacturer, see
+    /// [MemoryDevice::manufacturer_name]
+    #[cfg(feature = "jep106")]
+    pub fn controller_manufacturer_name(&self) -> Option<&'static str> {
+        Jep106Id::from_smbios(self.memory_subsystem_controller_manufacturer_id?)?.name()
+    }
+}
+
+/// A memory speed in megatransfers per second
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct MtPerSec(pub u32);
+
+impl MtPerSec {
+    /// Combine a Speed field with its Extended Speed field, whose bit 31 is reserved
+    fn resolve(speed: Option<u16>, extended: Option<u32>) -> Option<MtPerSec> {
+        match speed? {
+            0xFFFF => extended
+                .map(|extended| extended & 0x7FFF_FFFF)
+                .filter(|&extended| extended != 0)
+                .map(MtPerSec),
+            speed => Some(MtPerSec(u32::from(speed))),
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+impl fmt::Display for MtPerSec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} MT/s", self.0)
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// The Physical Memory Array (Type 16) the memory device is part of, see
+    /// [Structures::find_handle]
+    pub fn array_for_device(&self, device: &MemoryDevice) -> Option<PhysicalMemoryArray> {
+        match self.find_handle(device.physical_memory_handle)?.ok()? {
+            Structure::PhysicalMemoryArray(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// The error information structure of the memory device: a 32-Bit Memory Error Information
+    /// (Type 18) structure, or the raw 64-Bit Memory Error Information (Type 33) structure, which
+    /// is not decoded by this crate.
+    ///
+    /// Returns `None` if the device reports no error information.
+    pub fn error_for_device(&self, device: &MemoryDevice) -> Option<Structure<'buffer>> {
+        match self.decode_handle(device.memory_error_handle?)? {
+            structure @ Structure::MemoryError32(_) => Some(structure),
+            Structure::Other(raw) if raw.info == InfoType::MemoryError64 => Some(Structure::Other(raw)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn links() {
+        const DMIDECODE_BIN: &[u8] = include_bytes!("../../tests/data/dmi.0.bin");
+        let entry_point = crate::EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        for structure in structures.clone().flatten() {
+            if let Structure::MemoryDevice(device) = structure {
+                assert_eq!(None, structures.error_for_device(&device));
+                let array = structures.array_for_device(&device).unwrap();
+                assert_eq!(device.physical_memory_handle, array.handle);
+            }
+        }
+
+        // 32-bit and 64-bit Memory Error Information, End-of-Table
+        let mut table = std::vec![18, 0x17, 0x50, 0x00];
+        table.extend_from_slice(&[0; 0x13 + 2]);
+        table.extend_from_slice(&[33, 0x1F, 0x51, 0x00]);
+        table.extend_from_slice(&[0; 0x1B + 2]);
+        table.extend_from_slice(&[127, 0x04, 0x52, 0x00, 0, 0]);
+        let structures = Structures::new(&table, (3, 0).into(), table.len() as u32);
+        let error = |handle| {
+            let device = MemoryDevice {
+                memory_error_handle: Some(Handle(handle)),
+                ..Default::default()
+            };
+            structures.error_for_device(&device)
+        };
+        assert!(matches!(error(0x50), Some(Structure::MemoryError32(_))));
+        assert!(matches!(
+            error(0x51),
+            Some(Structure::Other(RawStructure {
+                info: InfoType::MemoryError64,
+                ..
+            }))
+        ));
+        assert_eq!(None, error(0x52));
+        assert_eq!(None, error(0x53));
+        assert_eq!(None, structures.array_for_device(&MemoryDevice::default()));
+    }
+
+    #[test]
+    fn older_layouts() {
+        use crate::testing::StructureBuilder;
+
+        let v2_1 = StructureBuilder::new(InfoType::MemoryDevice)
+            .handle(Handle(0x1100))
+            .version((2, 1).into())
+            .handle_ref(Handle(0x1000))
+            .handle_ref(Handle(0xFFFE))
+            .word(72)
+            .word(64)
+            .word(1024)
+            .byte(0x09)
+            .byte(0x00)
+            .string("DIMM 0")
+            .string("BANK 0")
+            .byte(0x0F)
+            .word(0x0080);
+        let v2_3 = v2_1
+            .clone()
+            .version((2, 3).into())
+            .word(266)
+            .string("Manufacturer")
+            .string("Serial")
+            .string("")
+            .string("Part");
+        let v2_6 = v2_3.clone().version((2, 6).into()).byte(0x02);
+        assert_eq!(0x15, v2_1.raw().length);
+        assert_eq!(0x1B, v2_3.raw().length);
+        assert_eq!(0x1C, v2_6.raw().length);
+
+        let expected = MemoryDevice {
+            handle: Handle(0x1100),
+            physical_memory_handle: Handle(0x1000),
+            total_width: Some(72),
+            data_width: Some(64),
+            size: Some(1024),
+            form_factor: FormFactor::Dimm,
+            device_set: Some(0),
+            device_locator: "DIMM 0",
+            bank_locator: "BANK 0",
+            memory_type: Type::Sdram,
+            type_detail: Detail::SYNCHRONOUS,
+            ..Default::default()
+        };
+        assert_eq!(expected, MemoryDevice::from_raw(v2_1.raw()).unwrap());
+        let expected = MemoryDevice {
+            speed: Some(266),
+            manufacturer: "Manufacturer",
+            serial: "Serial",
+            part_number: "Part",
+            ..expected
+        };
+        assert_eq!(expected, MemoryDevice::from_raw(v2_3.raw()).unwrap());
+        let expected = MemoryDevice {
+            attributes: 2,
+            ..expected
+        };
+        let device = MemoryDevice::from_raw(v2_6.raw()).unwrap();
+        assert_eq!(expected, device);
+        assert_eq!(Some(2), device.rank());
+        assert_eq!(Some(1 << 30), device.size_bytes());
+    }
+
     #[test]
     fn smbios_2_8_memory_device_with_34_bytes_parses() {
         let structure = RawStructure {
             version: (2, 8).into(),
             info: InfoType::MemoryDevice,
             length: 0x22,
-            handle: 0x4e,
+            handle: Handle(0x4e),
             // data amd strins are from memory handler, eg: dmidecode -H 0x4e -u
             data: &[
                 // omit first 4 header bytes
@@ -458,11 +677,12 @@ mod tests {
                 0x00, // FAKE_PART_NUMBER
                 0x46, 0x41, 0x4b, 0x45, 0x5f, 0x50, 0x41, 0x52, 0x54, 0x5f, 0x4e, 0x55, 0x4d, 0x42, 0x45, 0x52, 0x00,
             ],
+            ..Default::default()
         };
         assert_eq!(
             MemoryDevice {
-                handle: 0x4e,
-                physical_memory_handle: 76,
+                handle: Handle(0x4e),
+                physical_memory_handle: Handle(76),
                 total_width: Some(64),
                 data_width: Some(64),
                 size: Some(8192),
@@ -485,7 +705,7 @@ mod tests {
 
                 ..Default::default()
             },
-            MemoryDevice::try_from(structure).unwrap()
+            MemoryDevice::from_raw(structure).unwrap()
         );
     }
 
@@ -495,7 +715,7 @@ mod tests {
             version: (3, 2).into(),
             info: InfoType::MemoryDevice,
             length: 0x28,
-            handle: 0x3b,
+            handle: Handle(0x3b),
             // data amd strins are from memory handler, eg: dmidecode -H 0x3b -u
             data: &[
                 // omit first 4 header bytes
@@ -516,11 +736,12 @@ mod tests {
                 0x00, // FAKE_PART_NUMBER
                 0x46, 0x41, 0x4b, 0x45, 0x5f, 0x50, 0x41, 0x52, 0x54, 0x5f, 0x4e, 0x55, 0x4d, 0x42, 0x45, 0x52, 0x00,
             ],
+            ..Default::default()
         };
         assert_eq!(
             MemoryDevice {
-                handle: 0x3b,
-                physical_memory_handle: 57,
+                handle: Handle(0x3b),
+                physical_memory_handle: Handle(57),
                 total_width: Some(72),
                 data_width: Some(64),
                 size: Some(16384),
@@ -543,29 +764,30 @@ mod tests {
 
                 ..Default::default()
             },
-            MemoryDevice::try_from(structure).unwrap()
+            MemoryDevice::from_raw(structure).unwrap()
         );
     }
 
     #[test]
     fn foo() {
-        let memory_device = MemoryDevice::try_from(RawStructure {
+        let memory_device = MemoryDevice::from_raw(RawStructure {
             version: (3, 14).into(),
             info: InfoType::MemoryDevice,
             length: 34,
-            handle: 112,
+            handle: Handle(112),
             data: &[
                 0, 2, 254, 255, 64, 0, 64, 0, 0, 16, 9, 0, 1, 0, 7, 128, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             ],
             strings: &[68, 73, 77, 77, 32, 48, 0, 0],
+            ..Default::default()
         })
         .expect("failed to create memory device");
 
         assert_eq!(
             memory_device,
             MemoryDevice {
-                handle: 112,
-                physical_memory_handle: 512,
+                handle: Handle(112),
+                physical_memory_handle: Handle(512),
                 memory_error_handle: None,
                 total_width: Some(64),
                 data_width: Some(64),
@@ -588,4 +810,92 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn size_bytes() {
+        let device = |size, extended_size| MemoryDevice {
+            size,
+            extended_size,
+            ..MemoryDevice::default()
+        };
+        assert_eq!(None, device(None, 0).size_bytes());
+        assert_eq!(Some(0), device(Some(0), 0).size_bytes());
+        assert_eq!(Some(8 << 30), device(Some(8192), 0).size_bytes());
+        assert_eq!(Some(512 << 10), device(Some(0x8000 | 512), 0).size_bytes());
+        assert_eq!(Some(64 << 30), device(Some(0x7FFF), 65536).size_bytes());
+    }
+
+    #[test]
+    fn rank_and_ecc_bits() {
+        let device = |attributes, total_width, data_width| MemoryDevice {
+            attributes,
+            total_width,
+            data_width,
+            ..MemoryDevice::default()
+        };
+        assert_eq!(None, device(0x00, None, None).rank());
+        assert_eq!(Some(2), device(0x02, None, None).rank());
+        assert_eq!(
+            Some(4),
+            device(0xF4, None, None).rank(),
+            "Reserved bits 7:4 are ignored"
+        );
+
+        assert_eq!(Some(8), device(0, Some(72), Some(64)).ecc_bits());
+        assert_eq!(Some(0), device(0, Some(64), Some(64)).ecc_bits());
+        assert_eq!(Some(8), device(0, Some(8), Some(0)).ecc_bits());
+        assert_eq!(None, device(0, None, Some(64)).ecc_bits());
+        assert_eq!(None, device(0, Some(64), None).ecc_bits());
+        assert_eq!(None, device(0, Some(32), Some(64)).ecc_bits());
+    }
+
+    #[cfg(feature = "jep106")]
+    #[test]
+    fn manufacturer_name() {
+        let device = MemoryDevice {
+            module_manufacturer: Some(0xCE00),
+            memory_subsystem_controller_manufacturer_id: Some(0),
+            ..MemoryDevice::default()
+        };
+        assert_eq!(Some("Samsung"), device.manufacturer_name());
+        assert_eq!(None, device.controller_manufacturer_name());
+        assert_eq!(None, MemoryDevice::default().manufacturer_name());
+    }
+
+    #[test]
+    fn effective_speed() {
+        let device = |speed, extended_speed| MemoryDevice {
+            speed,
+            extended_speed,
+            configured_memory_speed: speed,
+            extended_configured_memory_speed: extended_speed,
+            ..MemoryDevice::default()
+        };
+        assert_eq!(None, device(None, Some(8800)).effective_speed());
+        assert_eq!(Some(MtPerSec(4800)), device(Some(4800), Some(8800)).effective_speed());
+        assert_eq!(
+            Some(MtPerSec(70_000)),
+            device(Some(0xFFFF), Some(70_000)).effective_speed()
+        );
+        assert_eq!(
+            Some(MtPerSec(70_000)),
+            device(Some(0xFFFF), Some(0x8000_0000 | 70_000)).effective_configured_speed()
+        );
+        assert_eq!(None, device(Some(0xFFFF), Some(0)).effective_configured_speed());
+        assert_eq!(None, device(Some(0xFFFF), None).effective_speed());
+        #[cfg(feature = "strings")]
+        assert_eq!("70000 MT/s", format!("{}", MtPerSec(70_000)));
+    }
+
+    #[test]
+    fn smbios_3_7_enumerations() {
+        assert_eq!(FormFactor::FbDimm, FormFactor::from(15));
+        assert_eq!(FormFactor::Die, FormFactor::from(16));
+        assert_eq!(FormFactor::Camm, FormFactor::from(17));
+        assert_eq!(FormFactor::Undefined(18), FormFactor::from(18));
+        assert_eq!(Type::Ddr5, Type::from(34));
+        assert_eq!(Type::LpDdr5, Type::from(35));
+        assert_eq!(Type::Hbm3, Type::from(36));
+        assert_eq!(Type::Undefined(37), Type::from(37));
+    }
 }
@@ -3,17 +3,15 @@
 //! This structure provides the address mapping for a Physical Memory Array.
 //! One structure is present for each contiguous address range described.
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
-};
+use core::ops::RangeInclusive;
+
+use crate::{Handle, MalformedStructureError, RawStructure};
 
 /// Main struct for *Memory Array Mapped Address (Type 19)*
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct MemoryArrayMappedAddress {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Physical address, in kilobytes, of a range of memory mapped to the specified Physical
     /// Memory Array.\
     /// When the field value is FFFF FFFFh, the actual address is stored in the Extended Starting
@@ -30,7 +28,7 @@ pub struct MemoryArrayMappedAddress {
     /// Handle, or instance number, associated with the Physical Memory Array to which this address
     /// range is mapped.\
     /// Multiple address ranges can be mapped to a single Physical Memory Array.
-    pub memory_array_handle: u16,
+    pub memory_array_handle: Handle,
     /// Number of Memory Devices that form a single row of memory for the address partition defined
     /// by this structure.
     pub partition_width: u8,
@@ -50,31 +48,42 @@ pub struct MemoryArrayMappedAddress {
 }
 
 impl<'a> MemoryArrayMappedAddress {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let handle = structure.handle;
-        match (structure.version.major, structure.version.minor) {
-            v if ((2, 1)..(2, 7)).contains(&v) && structure.length != 0x0F => Err(InvalidFormattedSectionLength(
-                InfoType::MemoryArrayMappedAddress,
-                handle,
-                "",
-                0x0F,
-            )),
-            v if v >= (2, 7) && structure.length != 0x1F => Err(InvalidFormattedSectionLength(
-                InfoType::MemoryArrayMappedAddress,
-                handle,
-                "",
-                0x1F,
-            )),
-            _ => Ok(Self {
-                handle,
-                starting_address: structure.get::<u32>(0x04)?,
-                ending_address: structure.get::<u32>(0x08)?,
-                memory_array_handle: structure.get::<u16>(0x0C)?,
-                partition_width: structure.get::<u8>(0x0E)?,
-                extended_starting_address: structure.get::<u64>(0x0F).ok(),
-                extended_ending_address: structure.get::<u64>(0x17).ok(),
-            }),
-        }
+        Ok(Self {
+            handle,
+            starting_address: structure.get::<u32>(0x04)?,
+            ending_address: structure.get::<u32>(0x08)?,
+            memory_array_handle: structure.get::<Handle>(0x0C)?,
+            partition_width: structure.get::<u8>(0x0E)?,
+            extended_starting_address: structure.get::<u64>(0x0F).ok(),
+            extended_ending_address: structure.get::<u64>(0x17).ok(),
+        })
+    }
+
+    /// The physical address range in bytes, from the kilobyte or the extended address fields.
+    /// `None` if the extended addresses are needed but missing.
+    pub fn address_range(&self) -> Option<RangeInclusive<u64>> {
+        address_range(
+            self.starting_address,
+            self.ending_address,
+            self.extended_starting_address,
+            self.extended_ending_address,
+        )
+    }
+}
+
+/// The range in bytes of kilobyte addresses, FFFF FFFFh telling to use the extended byte addresses
+pub(crate) fn address_range(
+    starting: u32,
+    ending: u32,
+    extended_starting: Option<u64>,
+    extended_ending: Option<u64>,
+) -> Option<RangeInclusive<u64>> {
+    if starting == u32::MAX {
+        Some(extended_starting?..=extended_ending?)
+    } else {
+        Some(u64::from(starting) << 10..=(u64::from(ending) << 10 | 0x3FF))
     }
 }
 
@@ -95,20 +104,36 @@ mod tests {
             version: (2, 7).into(),
             info: InfoType::MemoryArrayMappedAddress,
             length,
-            handle: 0x0027,
+            handle: Handle(0x0027),
             data,
             strings,
+            ..Default::default()
         };
         let sample = MemoryArrayMappedAddress {
-            handle: 0x0027,
+            handle: Handle(0x0027),
             starting_address: 0,
             ending_address: 0x0207C000,
-            memory_array_handle: 0x0026,
+            memory_array_handle: Handle(0x0026),
             partition_width: 255,
             extended_starting_address: Some(0),
             extended_ending_address: Some(0),
         };
-        let result = MemoryArrayMappedAddress::try_from(structure).unwrap();
+        let result = MemoryArrayMappedAddress::from_raw(structure).unwrap();
         assert_eq!(sample, result, "MemoryArrayMappedAddress");
+        assert_eq!(Some(0..=0x8_1F00_03FF), result.address_range());
+
+        let extended = MemoryArrayMappedAddress {
+            starting_address: u32::MAX,
+            ending_address: u32::MAX,
+            extended_starting_address: Some(0x1_0000_0000),
+            extended_ending_address: Some(0x8_7FFF_FFFF),
+            ..sample
+        };
+        assert_eq!(Some(0x1_0000_0000..=0x8_7FFF_FFFF), extended.address_range());
+        let truncated = MemoryArrayMappedAddress {
+            extended_starting_address: None,
+            ..extended
+        };
+        assert_eq!(None, truncated.address_range());
     }
 }
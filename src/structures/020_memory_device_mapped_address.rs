@@ -3,17 +3,15 @@
 //! This structure maps memory address space usually to a device-level granularity.\
 //! One structure is present for each contiguous address range described.
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
-};
+use core::ops::RangeInclusive;
+
+use crate::{structures::memory_array_mapped_address::address_range, Handle, MalformedStructureError, RawStructure};
 
 /// Main struct for *Memory Device Mapped Address (Type 20)*
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct MemoryDeviceMappedAddress {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Physical address, in kilobytes, of a range of memory mapped to the referenced Memory
     /// Device.\
     /// When the field value is FFFF FFFFh the actual address is stored in the Extended Starting
@@ -30,11 +28,11 @@ pub struct MemoryDeviceMappedAddress {
     /// Handle, or instance number, associated with the Memory Device structure to which this
     /// address range is mapped.\
     /// Multiple address ranges can be mapped to a single Memory Device.
-    pub memory_device_handle: u16,
+    pub memory_device_handle: Handle,
     /// Handle, or instance number, associated with the Memory Array Mapped Address structure to
     /// which this device address range is mapped.\
     /// Multiple address ranges can be mapped to a single Memory Array Mapped Address.
-    pub memory_array_mapped_address_handle: u16,
+    pub memory_array_mapped_address_handle: Handle,
     /// Position of the referenced Memory Device in a row of the address partition.\
     /// For example, if two 8-bit devices form a 16-bit row, this field’s value is either 1 or 2.\
     /// The value 0 is reserved. If the position is unknown, the field contains FFh.
@@ -63,34 +61,31 @@ pub struct MemoryDeviceMappedAddress {
 }
 
 impl<'a> MemoryDeviceMappedAddress {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let handle = structure.handle;
-        match (structure.version.major, structure.version.minor) {
-            v if ((2, 1)..(2, 7)).contains(&v) && structure.length != 0x13 => Err(InvalidFormattedSectionLength(
-                InfoType::MemoryDeviceMappedAddress,
-                handle,
-                "",
-                0x13,
-            )),
-            v if v >= (2, 7) && structure.length != 0x23 => Err(InvalidFormattedSectionLength(
-                InfoType::MemoryDeviceMappedAddress,
-                handle,
-                "",
-                0x23,
-            )),
-            _ => Ok(Self {
-                handle,
-                starting_address: structure.get::<u32>(0x04)?,
-                ending_address: structure.get::<u32>(0x08)?,
-                memory_device_handle: structure.get::<u16>(0x0C)?,
-                memory_array_mapped_address_handle: structure.get::<u16>(0x0E)?,
-                partition_row_position: structure.get::<u8>(0x10)?,
-                interleave_position: structure.get::<u8>(0x11)?,
-                interleaved_data_depth: structure.get::<u8>(0x12)?,
-                extended_starting_address: structure.get::<u64>(0x13).ok(),
-                extended_ending_address: structure.get::<u64>(0x1B).ok(),
-            }),
-        }
+        Ok(Self {
+            handle,
+            starting_address: structure.get::<u32>(0x04)?,
+            ending_address: structure.get::<u32>(0x08)?,
+            memory_device_handle: structure.get::<Handle>(0x0C)?,
+            memory_array_mapped_address_handle: structure.get::<Handle>(0x0E)?,
+            partition_row_position: structure.get::<u8>(0x10)?,
+            interleave_position: structure.get::<u8>(0x11)?,
+            interleaved_data_depth: structure.get::<u8>(0x12)?,
+            extended_starting_address: structure.get::<u64>(0x13).ok(),
+            extended_ending_address: structure.get::<u64>(0x1B).ok(),
+        })
+    }
+
+    /// The physical address range in bytes, from the kilobyte or the extended address fields.
+    /// `None` if the extended addresses are needed but missing.
+    pub fn address_range(&self) -> Option<RangeInclusive<u64>> {
+        address_range(
+            self.starting_address,
+            self.ending_address,
+            self.extended_starting_address,
+            self.extended_ending_address,
+        )
     }
 }
 
@@ -111,23 +106,24 @@ mod tests {
             version: (2, 7).into(),
             info: InfoType::MemoryDeviceMappedAddress,
             length,
-            handle: 0x0029,
+            handle: Handle(0x0029),
             data,
             strings,
+            ..Default::default()
         };
         let sample = MemoryDeviceMappedAddress {
-            handle: 0x0029,
+            handle: Handle(0x0029),
             starting_address: 0,
             ending_address: 0xFFFFFF,
-            memory_device_handle: 0x0028,
-            memory_array_mapped_address_handle: 0x0027,
+            memory_device_handle: Handle(0x0028),
+            memory_array_mapped_address_handle: Handle(0x0027),
             partition_row_position: 0,
             interleave_position: 0xFF,
             interleaved_data_depth: 0xFF,
             extended_starting_address: Some(0),
             extended_ending_address: Some(0),
         };
-        let result = MemoryDeviceMappedAddress::try_from(structure).unwrap();
+        let result = MemoryDeviceMappedAddress::from_raw(structure).unwrap();
         assert_eq!(sample, result, "MemoryDeviceMappedAddress");
     }
 }
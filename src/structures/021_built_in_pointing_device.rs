@@ -4,23 +4,21 @@
 //! The presence of this structure does not imply that the built-in pointing device is active for
 //! the system’s use.
 
+#[cfg(feature = "strings")]
 use core::fmt;
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
-};
+use crate::{Handle, MalformedStructureError, RawStructure};
 
 /// Main struct for *Built-in Pointing Device (Type 21)*
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct BuiltInPointingDevice {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     pub type_: Type,
     pub interface: Interface,
     /// Number of buttons on the pointing device.\
-    /// If the device has three buttons, the field value is 03h.
+    /// If the device has three buttons, the field value is 03h, see
+    /// [BuiltInPointingDevice::buttons].
     pub number_of_buttons: u8,
 }
 
@@ -63,21 +61,33 @@ pub enum Interface {
 }
 
 impl<'a> BuiltInPointingDevice {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
         let handle = structure.handle;
-        match (structure.version.major, structure.version.minor) {
-            v if v >= (2, 1) && structure.length != 0x07 => Err(InvalidFormattedSectionLength(
-                InfoType::BuiltInPointingDevice,
-                handle,
-                "",
-                0x07,
-            )),
-            _ => Ok(Self {
-                handle,
-                type_: structure.get::<u8>(0x04)?.into(),
-                interface: structure.get::<u8>(0x05)?.into(),
-                number_of_buttons: structure.get::<u8>(0x06)?,
-            }),
+        Ok(Self {
+            handle,
+            type_: structure.get::<u8>(0x04)?.into(),
+            interface: structure.get::<u8>(0x05)?.into(),
+            number_of_buttons: structure.get::<u8>(0x06)?,
+        })
+    }
+}
+
+impl BuiltInPointingDevice {
+    /// Number of buttons on the pointing device, `None` if unknown (0)
+    pub fn buttons(&self) -> Option<u8> {
+        Some(self.number_of_buttons).filter(|&buttons| buttons != 0)
+    }
+}
+
+/// Formatted for hardware reports, e.g. `Touch Pad, PS/2, 2 buttons`
+#[cfg(feature = "strings")]
+impl fmt::Display for BuiltInPointingDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}, ", self.type_, self.interface)?;
+        match self.buttons() {
+            Some(1) => write!(f, "1 button"),
+            Some(buttons) => write!(f, "{} buttons", buttons),
+            None => write!(f, "unknown number of buttons"),
         }
     }
 }
@@ -98,6 +108,7 @@ impl From<u8> for Type {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -133,6 +144,7 @@ impl From<u8> for Interface {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl fmt::Display for Interface {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -157,6 +169,7 @@ mod tests {
     use pretty_assertions::assert_eq;
     use std::prelude::v1::*;
 
+    #[cfg(feature = "strings")]
     #[test]
     fn type_() {
         use super::Type;
@@ -178,6 +191,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn interface() {
         use super::Interface;
@@ -214,17 +228,31 @@ mod tests {
             version: (2, 7).into(),
             info: InfoType::BuiltInPointingDevice,
             length,
-            handle: 0xAAAA,
+            handle: Handle(0xAAAA),
             data,
             strings,
+            ..Default::default()
         };
         let sample = BuiltInPointingDevice {
-            handle: 0xAAAA,
+            handle: Handle(0xAAAA),
             type_: Type::Mouse,
             interface: Interface::Serial,
             number_of_buttons: 3,
         };
-        let result = BuiltInPointingDevice::try_from(structure).unwrap();
+        let result = BuiltInPointingDevice::from_raw(structure).unwrap();
         assert_eq!(sample, result, "BuiltInPointingDevice");
+        assert_eq!(Some(3), result.buttons());
+        #[cfg(feature = "strings")]
+        assert_eq!("Mouse, Serial, 3 buttons", format!("{}", result));
+
+        let touch_pad = BuiltInPointingDevice {
+            type_: Type::TouchPad,
+            interface: Interface::Usb,
+            number_of_buttons: 0,
+            ..sample
+        };
+        assert_eq!(None, touch_pad.buttons());
+        #[cfg(feature = "strings")]
+        assert_eq!("Touch Pad, USB, unknown number of buttons", format!("{}", touch_pad));
     }
 }
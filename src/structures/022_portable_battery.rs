@@ -4,19 +4,16 @@
 //! The structure contains the static attributes for the group. Each structure describes a single
 //! battery pack’s attributes.
 
+#[cfg(feature = "strings")]
 use core::fmt;
 
-use crate::{
-    InfoType,
-    MalformedStructureError::{self, InvalidFormattedSectionLength},
-    RawStructure,
-};
+use crate::{structures::field::Field, Handle, MalformedStructureError, RawStructure};
 
 /// Main struct for *Portable Battery (Type 22)*
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct PortableBattery<'a> {
     /// Specifies the structure’s handle
-    pub handle: u16,
+    pub handle: Handle,
     /// Location of the battery
     pub location: &'a str,
     /// Company name that manufactured the battery
@@ -39,6 +36,7 @@ pub struct PortableBattery<'a> {
     /// above the energy it reports having.\
     /// If the value is unknown, the field contains FFh.
     pub maximum_error_in_battery_data: u8,
+    /// OEM- or BIOS vendor-specific information, `None` before SMBIOS 2.2
     pub oem_specific: Option<u32>,
 }
 
@@ -88,51 +86,82 @@ pub enum DeviceChemistry<'a> {
     SmartBatteryDataSpecification(&'a str),
 }
 
+/// Formatted section of the Portable Battery structure
+///
+/// The SBDS fields, the capacity multiplier and the OEM-specific dword were added by SMBIOS 2.2;
+/// they are only read from structures of later tables that are long enough to hold them.
+mod layout {
+    use super::Field;
+
+    pub const LOCATION: Field<u8> = Field::new(0x04, 2, 1);
+    pub const MANUFACTURER: Field<u8> = Field::new(0x05, 2, 1);
+    pub const MANUFACTURE_DATE: Field<u8> = Field::new(0x06, 2, 1);
+    pub const SERIAL_NUMBER: Field<u8> = Field::new(0x07, 2, 1);
+    pub const DEVICE_NAME: Field<u8> = Field::new(0x08, 2, 1);
+    pub const DEVICE_CHEMISTRY: Field<u8> = Field::new(0x09, 2, 1);
+    pub const DESIGN_CAPACITY: Field<u16> = Field::new(0x0A, 2, 1);
+    pub const DESIGN_VOLTAGE: Field<u16> = Field::new(0x0C, 2, 1);
+    pub const SBDS_VERSION_NUMBER: Field<u8> = Field::new(0x0E, 2, 1);
+    pub const MAXIMUM_ERROR: Field<u8> = Field::new(0x0F, 2, 1);
+    pub const SBDS_SERIAL_NUMBER: Field<u16> = Field::new(0x10, 2, 2);
+    pub const SBDS_MANUFACTURE_DATE: Field<u16> = Field::new(0x12, 2, 2);
+    pub const SBDS_DEVICE_CHEMISTRY: Field<u8> = Field::new(0x14, 2, 2);
+    pub const DESIGN_CAPACITY_MULTIPLIER: Field<u8> = Field::new(0x15, 2, 2);
+    pub const OEM_SPECIFIC: Field<u32> = Field::new(0x16, 2, 2);
+}
+
 impl<'a> PortableBattery<'a> {
-    pub(crate) fn try_from(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
-        let handle = structure.handle;
-        match (structure.version.major, structure.version.minor) {
-            (2, 1) if structure.length != 0x10 => Err(InvalidFormattedSectionLength(
-                InfoType::PortableBattery,
-                handle,
-                "",
-                0x10,
-            )),
-            v if v >= (2, 2) && structure.length != 0x1A => Err(InvalidFormattedSectionLength(
-                InfoType::PortableBattery,
-                handle,
-                "",
-                0x1A,
-            )),
-            _ => Ok(Self {
-                handle,
-                location: structure.get_string(0x04)?,
-                manufacturer: structure.get_string(0x05)?,
-                manufacture_date: ManufactureDate::new(
-                    structure
-                        .get::<u8>(0x06)
-                        .ok()
-                        .filter(|idx| idx != &0)
-                        .and_then(|idx| structure.find_string(idx).ok()),
-                    structure.get::<u16>(0x12).ok(),
-                ),
-                serial_number: SerialNumber::new(
-                    structure
-                        .get::<u8>(0x07)
-                        .ok()
-                        .filter(|idx| idx != &0)
-                        .and_then(|idx| structure.find_string(idx).ok()),
-                    structure.get::<u16>(0x10).ok(),
-                ),
-                device_name: structure.get_string(0x08)?,
-                device_chemistry: DeviceChemistry::new(structure.get::<u8>(0x09)?, structure.get_string(0x14).ok()),
-                design_capacity: DesignCapacity::new(structure.get::<u16>(0x0A)?, structure.get::<u8>(0x15).ok()),
-                design_voltage: structure.get::<u16>(0x0C)?,
-                sbds_version_number: structure.get_string(0x0E)?,
-                maximum_error_in_battery_data: structure.get::<u8>(0x0F)?,
-                oem_specific: structure.get::<u32>(0x16).ok(),
-            }),
-        }
+    pub(crate) fn from_raw(structure: RawStructure<'a>) -> Result<Self, MalformedStructureError> {
+        use self::layout::*;
+
+        // The basic fields take precedence, the SBDS ones are used when the string is missing
+        let basic_string = |field: Field<u8>| {
+            field
+                .get(&structure)
+                .ok()
+                .filter(|&idx| idx != 0)
+                .and_then(|idx| structure.find_string(idx).ok())
+        };
+        Ok(Self {
+            handle: structure.handle,
+            location: LOCATION.string(&structure)?,
+            manufacturer: MANUFACTURER.string(&structure)?,
+            manufacture_date: ManufactureDate::new(
+                basic_string(MANUFACTURE_DATE),
+                SBDS_MANUFACTURE_DATE.get_since(&structure),
+            ),
+            serial_number: SerialNumber::new(basic_string(SERIAL_NUMBER), SBDS_SERIAL_NUMBER.get_since(&structure)),
+            device_name: DEVICE_NAME.string(&structure)?,
+            device_chemistry: DeviceChemistry::new(
+                DEVICE_CHEMISTRY.get(&structure)?,
+                SBDS_DEVICE_CHEMISTRY.string_since(&structure).ok().flatten(),
+            ),
+            design_capacity: DesignCapacity::new(
+                DESIGN_CAPACITY.get(&structure)?,
+                DESIGN_CAPACITY_MULTIPLIER.get_since(&structure),
+            ),
+            design_voltage: DESIGN_VOLTAGE.get(&structure)?,
+            sbds_version_number: SBDS_VERSION_NUMBER.string(&structure)?,
+            maximum_error_in_battery_data: MAXIMUM_ERROR.get(&structure)?,
+            oem_specific: OEM_SPECIFIC.get_since(&structure),
+        })
+    }
+}
+
+impl<'a> PortableBattery<'a> {
+    /// Design voltage of the battery in mVolts, `None` if the value is unknown
+    pub fn design_voltage_mv(&self) -> Option<u16> {
+        Some(self.design_voltage).filter(|&v| v != 0)
+    }
+    /// Design capacity of the battery in mWatt-hours with the capacity multiplier applied, `None`
+    /// if the value is unknown
+    pub fn design_capacity_mwh(&self) -> Option<u32> {
+        self.design_capacity.mwh()
+    }
+    /// Maximum error in the Watt-hour data reported by the battery, as a percentage, `None` if the
+    /// value is unknown (FFh)
+    pub fn maximum_error(&self) -> Option<u8> {
+        Some(self.maximum_error_in_battery_data).filter(|&v| v != 0xFF)
     }
 }
 
@@ -140,15 +169,33 @@ impl<'a> ManufactureDate<'a> {
     fn new(basic: Option<&'a str>, sbds: Option<u16>) -> Self {
         match (basic, sbds) {
             (Some(s), _) => Self::Basic(s),
-            (None, Some(date)) => Self::SmartBatteryDataSpecification {
-                year: ((date & 0b1111_1110_0000_0000) >> 9) + 1980,
-                month: ((date & 0b0000_0001_1110_0000) >> 5) as u8,
-                date: (date & 0b0000_0000_0001_1111) as u8,
-            },
+            (None, Some(word)) => {
+                let (year, month, date) = Self::decode_sbds(word);
+                Self::SmartBatteryDataSpecification { year, month, date }
+            }
             _ => Self::None,
         }
     }
+    /// Decode the SBDS Manufacture Date word into (year, month, day)
+    ///
+    /// The date is packed in the following format: Bits 15:9 Year (biased by 1980, in the range 0
+    /// to 127), Bits 8:5 Month (in the range 1 to 12), Bits 4:0 Date (in the range 1 to 31).
+    pub fn decode_sbds(word: u16) -> (u16, u8, u8) {
+        (
+            ((word & 0b1111_1110_0000_0000) >> 9) + 1980,
+            ((word & 0b0000_0001_1110_0000) >> 5) as u8,
+            (word & 0b0000_0000_0001_1111) as u8,
+        )
+    }
+    /// Manufacture date as (year, month, day) if it is given in the SBDS format
+    pub fn ymd(&self) -> Option<(u16, u8, u8)> {
+        match *self {
+            Self::SmartBatteryDataSpecification { year, month, date } => Some((year, month, date)),
+            _ => None,
+        }
+    }
 }
+#[cfg(feature = "strings")]
 impl<'a> fmt::Display for ManufactureDate<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -172,6 +219,7 @@ impl<'a> SerialNumber<'a> {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl<'a> fmt::Display for SerialNumber<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -203,6 +251,7 @@ impl<'a> DeviceChemistry<'a> {
         }
     }
 }
+#[cfg(feature = "strings")]
 impl<'a> fmt::Display for DeviceChemistry<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -231,13 +280,17 @@ impl DesignCapacity {
             }
         }
     }
+    /// Design capacity in mWatt-hours with the capacity multiplier applied
+    pub fn mwh(&self) -> Option<u32> {
+        match *self {
+            Self::Unknown => None,
+            Self::Data { value, multiplier } => Some(value as u32 * multiplier as u32),
+        }
+    }
 }
 impl From<DesignCapacity> for u64 {
     fn from(dc: DesignCapacity) -> Self {
-        match dc {
-            DesignCapacity::Unknown => 0,
-            DesignCapacity::Data { value, multiplier } => (value * multiplier as u16).into(),
-        }
+        dc.mwh().unwrap_or(0).into()
     }
 }
 
@@ -246,6 +299,7 @@ mod tests {
     use pretty_assertions::assert_eq;
     use std::prelude::v1::*;
 
+    #[cfg(feature = "strings")]
     #[test]
     fn manufacture_date() {
         use super::ManufactureDate;
@@ -263,6 +317,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sbds_date() {
+        use super::ManufactureDate;
+
+        assert_eq!((2000, 2, 1), ManufactureDate::decode_sbds(0x2841));
+        assert_eq!((1980, 0, 0), ManufactureDate::decode_sbds(0));
+        assert_eq!(Some((2000, 2, 1)), ManufactureDate::new(None, Some(0x2841)).ymd());
+        assert_eq!(None, ManufactureDate::new(Some("07/17/2019"), None).ymd());
+    }
+
+    #[cfg(feature = "strings")]
     #[test]
     fn serial_number() {
         use super::SerialNumber;
@@ -276,6 +341,7 @@ mod tests {
         assert_eq!("0xBEAF", format!("{}", SerialNumber::new(None, Some(0xBEAF))), "SBDS");
     }
 
+    #[cfg(feature = "strings")]
     #[test]
     fn device_chemistry() {
         use super::DeviceChemistry;
@@ -309,6 +375,12 @@ mod tests {
         assert_eq!(0u64, DesignCapacity::new(0, Some(42)).into(), "Unknown");
         assert_eq!(4800u64, DesignCapacity::new(4800, None).into(), "w/o multiplier");
         assert_eq!(9600u64, DesignCapacity::new(4800, Some(2)).into(), "With multiplier");
+        assert_eq!(
+            Some(16711425),
+            DesignCapacity::new(u16::MAX, Some(u8::MAX)).mwh(),
+            "No overflow"
+        );
+        assert_eq!(None, DesignCapacity::new(0, Some(2)).mwh(), "Unknown");
     }
 
     #[test]
@@ -323,12 +395,13 @@ mod tests {
             version: (3, 2).into(),
             info: InfoType::PortableBattery,
             length,
-            handle: 0x002B,
+            handle: Handle(0x002B),
             data,
             strings,
+            ..Default::default()
         };
         let sample = PortableBattery {
-            handle: 0x002B,
+            handle: Handle(0x002B),
             location: "Front",
             manufacturer: "LGC",
             manufacture_date: ManufactureDate::SmartBatteryDataSpecification {
@@ -348,7 +421,73 @@ mod tests {
             maximum_error_in_battery_data: 0xFF,
             oem_specific: Some(0),
         };
-        let result = PortableBattery::try_from(structure).unwrap();
+        let result = PortableBattery::from_raw(structure).unwrap();
         assert_eq!(sample, result, "PortableBattery");
+        assert_eq!(Some(15400), result.design_voltage_mv());
+        assert_eq!(Some(51000), result.design_capacity_mwh());
+        assert_eq!(Some((2020, 7, 1)), result.manufacture_date.ymd());
+        assert_eq!(None, result.maximum_error());
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn version_gated_fields() {
+        use crate::{testing::StructureBuilder, InfoType, Structure};
+
+        let battery = |version: (usize, usize), extended: bool| {
+            let basic = StructureBuilder::new(InfoType::PortableBattery)
+                .version(version.into())
+                .string("Rear")
+                .string("Vendor")
+                .byte(0)
+                .byte(0)
+                .string("Battery")
+                .byte(0x02)
+                .word(4800)
+                .word(11100)
+                .string("")
+                .byte(5);
+            let structure = if extended {
+                basic
+                    .word(0x1234)
+                    .word(0x2841)
+                    .string("LION")
+                    .byte(10)
+                    .dword(0xDEAD_BEEF)
+            } else {
+                basic
+            };
+            match structure.decode() {
+                Ok(Structure::PortableBattery(battery)) => (
+                    format!("{}", battery.serial_number),
+                    format!("{}", battery.manufacture_date),
+                    format!("{}", battery.device_chemistry),
+                    battery.design_capacity_mwh(),
+                    battery.maximum_error(),
+                    battery.oem_specific,
+                ),
+                other => panic!("{:?}", other),
+            }
+        };
+
+        let sbds = (
+            "0x1234".to_string(),
+            "2000-02-01".to_string(),
+            "LION".to_string(),
+            Some(48000),
+            Some(5),
+            Some(0xDEAD_BEEF),
+        );
+        assert_eq!(sbds, battery((2, 2), true));
+        let basic = (
+            String::new(),
+            String::new(),
+            "Unknown".to_string(),
+            Some(4800),
+            Some(5),
+            None,
+        );
+        assert_eq!(basic, battery((2, 1), true), "SMBIOS 2.1 has no SBDS fields");
+        assert_eq!(basic, battery((3, 0), false), "A short 2.1 layout in a later table");
     }
 }
@@ -0,0 +1,119 @@
+//! Field descriptors for versioned structure layouts
+//!
+//! Structures that grew new fields with each SMBIOS revision describe their formatted section as a
+//! table of [Field] descriptors instead of one packed struct per revision. A descriptor records
+//! the offset of the field, as given in the SMBIOS Reference Specification, and the version that
+//! introduced it; a single set of accessors then decides whether a field is present, so that the
+//! parsing code is shared by every layout.
+
+use core::marker::PhantomData;
+
+use crate::{MalformedStructureError, RawStructure, SmbiosVersion, TryFromBytes};
+
+/// Location and minimum SMBIOS version of a field of type `T`
+pub(crate) struct Field<T> {
+    offset: usize,
+    since: SmbiosVersion,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Field<T> {
+    /// Field at `offset` (header included) present since SMBIOS `major`.`minor`
+    pub(crate) const fn new(offset: usize, major: u8, minor: u8) -> Self {
+        Field {
+            offset,
+            since: SmbiosVersion { major, minor },
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'buffer, T: TryFromBytes<'buffer, T>> Field<T> {
+    /// Read a field every structure of this type has
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::InvalidSlice` if the formatted section is too short.
+    pub(crate) fn get(&self, structure: &RawStructure<'buffer>) -> Result<T, MalformedStructureError> {
+        structure.get(self.offset)
+    }
+
+    /// Read a field introduced by a later SMBIOS version
+    ///
+    /// Returns `None` if the table predates the field or if the formatted section does not reach
+    /// it.
+    pub(crate) fn get_since(&self, structure: &RawStructure<'buffer>) -> Option<T> {
+        if structure.version < self.since {
+            None
+        } else {
+            structure.get_optional(self.offset)
+        }
+    }
+}
+
+impl Field<u8> {
+    /// Resolve a *STRING* field every structure of this type has
+    pub(crate) fn string<'buffer>(
+        &self,
+        structure: &RawStructure<'buffer>,
+    ) -> Result<&'buffer str, MalformedStructureError> {
+        structure.get_string(self.offset)
+    }
+
+    /// Resolve a *STRING* field introduced by a later SMBIOS version, see [Field::get_since]
+    ///
+    /// # Errors
+    /// Returns a `MalformedStructureError::InvalidStringIndex` if the field is present but points
+    /// outside of the strings table.
+    pub(crate) fn string_since<'buffer>(
+        &self,
+        structure: &RawStructure<'buffer>,
+    ) -> Result<Option<&'buffer str>, MalformedStructureError> {
+        self.get_since(structure)
+            .map(|idx| structure.find_string(idx))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Handle;
+    use crate::InfoType;
+    use pretty_assertions::assert_eq;
+
+    const WORD: Field<u16> = Field::new(0x06, 2, 0);
+    const LATER: Field<u16> = Field::new(0x08, 2, 6);
+    const BEYOND: Field<u16> = Field::new(0x0A, 2, 6);
+    const STRING: Field<u8> = Field::new(0x04, 2, 6);
+
+    fn structure(version: (usize, usize)) -> RawStructure<'static> {
+        RawStructure {
+            version: version.into(),
+            info: InfoType::Oem(0x80),
+            length: 0x0A,
+            handle: Handle(0x0001),
+            data: &[0x01, 0x00, 0x34, 0x12, 0x78, 0x56],
+            strings: b"first\0\0",
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn version_gate() {
+        let old = structure((2, 5));
+        assert_eq!(0x1234, WORD.get(&old).unwrap());
+        assert_eq!(None, LATER.get_since(&old));
+        assert_eq!(None, STRING.string_since(&old).unwrap());
+
+        let new = structure((3, 0));
+        assert_eq!(Some(0x5678), LATER.get_since(&new));
+        assert_eq!(Some("first"), STRING.string_since(&new).unwrap());
+    }
+
+    #[test]
+    fn short_formatted_section() {
+        let new = structure((3, 0));
+        assert_eq!(None, BEYOND.get_since(&new));
+        assert!(Field::<u32>::new(0x08, 2, 0).get(&new).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Formatted section lengths given by the SMBIOS Reference Specification
+//!
+//! Two tables describe the length of the formatted section of each structure type. The minimum
+//! lengths cover the fields a structure cannot be decoded without, and are checked by
+//! [Structure::decode](crate::Structure) before dispatching to the type: a structure that
+//! declares a later version but keeps the layout of an earlier one still decodes, its newer
+//! fields reading as absent. The expected lengths are the exact lengths of the fixed size types at
+//! each version, which are only reported as warnings.
+//!
+//! Enclosure, System Slots and System Event Log structures have lengths that depend on their
+//! content, and check them while decoding.
+
+use crate::{InfoType, LengthExpectation, MalformedStructureError, RawStructure, SmbiosVersion};
+
+/// (major, minor, length) for every version that changed the length, in increasing order
+type Lengths = &'static [(u8, u8, Option<u8>)];
+
+/// The length of the fields every structure of the type has, from the version that introduced
+/// them. Types absent from the table, and versions that predate the type, have no minimum.
+fn minimum_lengths(info: InfoType) -> Lengths {
+    match info {
+        InfoType::Bios => &[(2, 0, Some(0x12))],
+        InfoType::System => &[(2, 0, Some(0x08))],
+        InfoType::BaseBoard => &[(2, 0, Some(0x08))],
+        InfoType::Processor => &[(2, 0, Some(0x1A))],
+        InfoType::Cache => &[(2, 0, Some(0x0F))],
+        InfoType::PortConnector => &[(2, 0, Some(0x09))],
+        InfoType::OemStrings => &[(2, 0, Some(0x05))],
+        InfoType::SystemConfigurationOptions => &[(2, 0, Some(0x05))],
+        InfoType::BiosLanguage => &[(2, 0, Some(0x16))],
+        InfoType::GroupAssociations => &[(2, 0, Some(0x05))],
+        InfoType::PhysicalMemoryArray => &[(2, 1, Some(0x0F))],
+        InfoType::MemoryDevice => &[(2, 1, Some(0x15))],
+        InfoType::MemoryError32 => &[(2, 1, Some(0x17))],
+        InfoType::MemoryArrayMappedAddress => &[(2, 1, Some(0x0F))],
+        InfoType::MemoryDeviceMappedAddress => &[(2, 1, Some(0x13))],
+        InfoType::BuiltInPointingDevice => &[(2, 1, Some(0x07))],
+        InfoType::PortableBattery => &[(2, 1, Some(0x10))],
+        _ => &[],
+    }
+}
+
+/// The length of the formatted section of fixed size types, `None` for versions with a variable
+/// length
+fn expected_lengths(info: InfoType) -> Lengths {
+    match info {
+        InfoType::Bios => &[(2, 0, None), (2, 4, Some(0x18)), (3, 1, Some(0x1A))],
+        InfoType::System => &[(2, 0, Some(0x08)), (2, 1, Some(0x19)), (2, 4, Some(0x1B))],
+        InfoType::Processor => &[
+            (2, 0, Some(0x1A)),
+            (2, 1, Some(0x20)),
+            (2, 3, Some(0x23)),
+            (2, 5, Some(0x28)),
+            (2, 6, Some(0x2A)),
+            (3, 0, Some(0x30)),
+            (3, 6, Some(0x32)),
+        ],
+        InfoType::Cache => &[(2, 0, Some(0x0F)), (2, 1, Some(0x13)), (3, 1, Some(0x1B))],
+        InfoType::PortConnector => &[(2, 0, Some(0x09))],
+        InfoType::SystemSlots => &[(2, 0, Some(0x0C)), (2, 1, Some(0x0D)), (2, 6, Some(0x11)), (3, 2, None)],
+        InfoType::BiosLanguage => &[(2, 0, Some(0x16))],
+        InfoType::PhysicalMemoryArray => &[(2, 1, Some(0x0F)), (2, 7, Some(0x17))],
+        InfoType::MemoryDevice => &[
+            (2, 1, Some(0x15)),
+            (2, 3, Some(0x1B)),
+            (2, 6, Some(0x1C)),
+            (2, 7, Some(0x22)),
+            (2, 8, Some(0x28)),
+            (3, 2, Some(0x54)),
+            (3, 3, Some(0x5C)),
+        ],
+        InfoType::MemoryError32 => &[(2, 1, Some(0x17))],
+        InfoType::MemoryArrayMappedAddress => &[(2, 1, Some(0x0F)), (2, 7, Some(0x1F))],
+        InfoType::MemoryDeviceMappedAddress => &[(2, 1, Some(0x13)), (2, 7, Some(0x23))],
+        InfoType::BuiltInPointingDevice => &[(2, 1, Some(0x07))],
+        InfoType::PortableBattery => &[(2, 1, Some(0x10)), (2, 2, Some(0x1A))],
+        InfoType::End => &[(2, 0, Some(0x04))],
+        _ => &[],
+    }
+}
+
+fn lookup(lengths: Lengths, version: SmbiosVersion) -> Option<u8> {
+    lengths
+        .iter()
+        .rev()
+        .find(|&&(major, minor, _)| version >= SmbiosVersion { major, minor })
+        .and_then(|&(_, _, length)| length)
+}
+
+/// The length below which a structure of type `info` cannot be decoded
+pub(crate) fn minimum_length(info: InfoType, version: SmbiosVersion) -> Option<u8> {
+    lookup(minimum_lengths(info), version)
+}
+
+/// The length of the formatted section of fixed size structure types, as specified by `version`.
+/// Types with a variable size, and versions that predate the type, have no expected length.
+pub(crate) fn expected_length(info: InfoType, version: SmbiosVersion) -> Option<u8> {
+    lookup(expected_lengths(info), version)
+}
+
+/// Check the formatted section of `structure` against the [minimum_length] of `info`, the type
+/// it is decoded as
+///
+/// # Errors
+/// Returns a `MalformedStructureError::InvalidFormattedSectionLength` if the structure is shorter.
+pub(crate) fn check_minimum(info: InfoType, structure: &RawStructure<'_>) -> Result<(), MalformedStructureError> {
+    match minimum_length(info, structure.version) {
+        Some(minimum) if structure.length < minimum => Err(MalformedStructureError::InvalidFormattedSectionLength {
+            info,
+            handle: structure.handle,
+            expected: LengthExpectation::Minimum(minimum),
+            actual: structure.length,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn lookup_by_version() {
+        assert_eq!(None, minimum_length(InfoType::MemoryDevice, (2, 0).into()));
+        assert_eq!(Some(0x15), minimum_length(InfoType::MemoryDevice, (3, 3).into()));
+        assert_eq!(None, minimum_length(InfoType::Inactive, (3, 4).into()));
+    }
+
+    #[test]
+    fn expected_lengths() {
+        assert_eq!(Some(0x1B), expected_length(InfoType::System, (3, 4).into()));
+        assert_eq!(Some(0x19), expected_length(InfoType::System, (2, 3).into()));
+        assert_eq!(None, expected_length(InfoType::Bios, (2, 3).into()));
+        assert_eq!(Some(0x1A), expected_length(InfoType::Bios, (3, 1).into()));
+        assert_eq!(None, expected_length(InfoType::MemoryDevice, (2, 0).into()));
+        assert_eq!(Some(0x28), expected_length(InfoType::MemoryDevice, (3, 0).into()));
+        assert_eq!(None, expected_length(InfoType::SystemSlots, (3, 4).into()));
+        assert_eq!(None, expected_length(InfoType::Enclosure, (3, 4).into()));
+    }
+
+    #[test]
+    fn checked_before_dispatch() {
+        use crate::{testing::StructureBuilder, Structure};
+
+        // A Memory Array Mapped Address structure of SMBIOS 2.1 in a 3.4 table
+        let mapped = StructureBuilder::new(InfoType::MemoryArrayMappedAddress)
+            .version((3, 4).into())
+            .dword(0)
+            .dword(0x0207_C000)
+            .word(0x0026)
+            .byte(1);
+        match mapped.decode() {
+            Ok(Structure::MemoryArrayMappedAddress(mapped)) => assert_eq!(None, mapped.extended_starting_address),
+            other => panic!("{:?}", other),
+        }
+
+        let short = mapped.length(0x0E);
+        match short.decode() {
+            Err(MalformedStructureError::InvalidFormattedSectionLength {
+                info: InfoType::MemoryArrayMappedAddress,
+                expected: LengthExpectation::Minimum(0x0F),
+                actual: 0x0E,
+                ..
+            }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn minimum_below_expected() {
+        let versions = [(2, 0), (2, 1), (2, 3), (2, 4), (2, 7), (3, 0), (3, 2), (3, 6)];
+        for info in 0..=127u8 {
+            let info = InfoType::from(info);
+            for &version in versions.iter() {
+                let version = version.into();
+                if let (Some(minimum), Some(expected)) = (minimum_length(info, version), expected_length(info, version))
+                {
+                    assert!(minimum <= expected, "{:?} {:?}", info, version);
+                }
+            }
+        }
+    }
+}
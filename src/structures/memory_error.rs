@@ -0,0 +1,267 @@
+//! Fields shared by the Memory Error Information structures
+//!
+//! The 32-Bit (Type 18) and 64-Bit (Type 33) Memory Error Information structures describe an
+//! error with the same enumerations, and use the high bit of their address and resolution fields
+//! to tell that the value is unknown.
+
+#[cfg(feature = "strings")]
+use core::fmt;
+
+/// Value of a 32-bit error address or resolution that is unknown
+pub const UNKNOWN_32: u32 = 0x8000_0000;
+/// Value of a 64-bit error address or resolution that is unknown
+pub const UNKNOWN_64: u64 = 0x8000_0000_0000_0000;
+
+/// Map a 32-bit error address or resolution of 8000 0000h to `None`
+pub fn known_32(value: u32) -> Option<u32> {
+    Some(value).filter(|&value| value != UNKNOWN_32)
+}
+
+/// Map a 64-bit error address or resolution of 8000 0000 0000 0000h to `None`
+pub fn known_64(value: u64) -> Option<u64> {
+    Some(value).filter(|&value| value != UNKNOWN_64)
+}
+
+/// Type of error that is associated with the current status reported for the memory array or
+/// device
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorType {
+    Other,
+    Unknown,
+    Ok,
+    BadRead,
+    ParityError,
+    SingleBitError,
+    DoubleBitError,
+    MultiBitError,
+    NibbleError,
+    ChecksumError,
+    CrcError,
+    CorrectedSingleBitError,
+    CorrectedError,
+    UncorrectableError,
+    Undefined(u8),
+}
+
+/// Granularity (for example, device versus Partition) to which the error can be resolved
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorGranularity {
+    Other,
+    Unknown,
+    /// Device level
+    DeviceLevel,
+    /// Memory partition level
+    MemoryPartitionLevel,
+    Undefined(u8),
+}
+
+/// Memory access operation that caused the error
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorOperation {
+    Other,
+    Unknown,
+    Read,
+    Write,
+    /// Partial write
+    PartialWrite,
+    Undefined(u8),
+}
+
+impl From<u8> for ErrorType {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x01 => Self::Other,
+            0x02 => Self::Unknown,
+            0x03 => Self::Ok,
+            0x04 => Self::BadRead,
+            0x05 => Self::ParityError,
+            0x06 => Self::SingleBitError,
+            0x07 => Self::DoubleBitError,
+            0x08 => Self::MultiBitError,
+            0x09 => Self::NibbleError,
+            0x0a => Self::ChecksumError,
+            0x0b => Self::CrcError,
+            0x0c => Self::CorrectedSingleBitError,
+            0x0d => Self::CorrectedError,
+            0x0e => Self::UncorrectableError,
+            v => Self::Undefined(v),
+        }
+    }
+}
+/// dmidecode wording, e.g. "Single-bit Error", the alternate form is the specification's
+#[cfg(feature = "strings")]
+impl fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_alt = f.alternate();
+        match self {
+            Self::Other => write!(f, "Other"),
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Ok => write!(f, "OK"),
+            Self::BadRead if is_alt => write!(f, "Bad read"),
+            Self::BadRead => write!(f, "Bad Read"),
+            Self::ParityError if is_alt => write!(f, "Parity error"),
+            Self::ParityError => write!(f, "Parity Error"),
+            Self::SingleBitError if is_alt => write!(f, "Single-bit error"),
+            Self::SingleBitError => write!(f, "Single-bit Error"),
+            Self::DoubleBitError if is_alt => write!(f, "Double-bit error"),
+            Self::DoubleBitError => write!(f, "Double-bit Error"),
+            Self::MultiBitError if is_alt => write!(f, "Multi-bit error"),
+            Self::MultiBitError => write!(f, "Multi-bit Error"),
+            Self::NibbleError if is_alt => write!(f, "Nibble error"),
+            Self::NibbleError => write!(f, "Nibble Error"),
+            Self::ChecksumError if is_alt => write!(f, "Checksum error"),
+            Self::ChecksumError => write!(f, "Checksum Error"),
+            Self::CrcError if is_alt => write!(f, "CRC error"),
+            Self::CrcError => write!(f, "CRC Error"),
+            Self::CorrectedSingleBitError if is_alt => write!(f, "Corrected single-bit error"),
+            Self::CorrectedSingleBitError => write!(f, "Corrected Single-bit Error"),
+            Self::CorrectedError if is_alt => write!(f, "Corrected error"),
+            Self::CorrectedError => write!(f, "Corrected Error"),
+            Self::UncorrectableError if is_alt => write!(f, "Uncorrectable error"),
+            Self::UncorrectableError => write!(f, "Uncorrectable Error"),
+            Self::Undefined(v) => write!(f, "Undefined: {}", v),
+        }
+    }
+}
+
+impl From<u8> for ErrorGranularity {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x01 => Self::Other,
+            0x02 => Self::Unknown,
+            0x03 => Self::DeviceLevel,
+            0x04 => Self::MemoryPartitionLevel,
+            v => Self::Undefined(v),
+        }
+    }
+}
+/// dmidecode wording, e.g. "Device Level", the alternate form is the specification's
+#[cfg(feature = "strings")]
+impl fmt::Display for ErrorGranularity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let is_alt = f.alternate();
+        match self {
+            Self::Other => write!(f, "Other"),
+            Self::Unknown => write!(f, "Unknown"),
+            Self::DeviceLevel if is_alt => write!(f, "Device level"),
+            Self::DeviceLevel => write!(f, "Device Level"),
+            Self::MemoryPartitionLevel if is_alt => write!(f, "Memory partition level"),
+            Self::MemoryPartitionLevel => write!(f, "Memory Partition Level"),
+            Self::Undefined(v) => write!(f, "Undefined: {}", v),
+        }
+    }
+}
+
+impl From<u8> for ErrorOperation {
+    fn from(byte: u8) -> Self {
+        match byte {
+            0x01 => Self::Other,
+            0x02 => Self::Unknown,
+            0x03 => Self::Read,
+            0x04 => Self::Write,
+            0x05 => Self::PartialWrite,
+            v => Self::Undefined(v),
+        }
+    }
+}
+/// dmidecode wording, e.g. "Partial Write", the alternate form is the specification's
+#[cfg(feature = "strings")]
+impl fmt::Display for ErrorOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Other => write!(f, "Other"),
+            Self::Unknown => write!(f, "Unknown"),
+            Self::Read => write!(f, "Read"),
+            Self::Write => write!(f, "Write"),
+            Self::PartialWrite if f.alternate() => write!(f, "Partial write"),
+            Self::PartialWrite => write!(f, "Partial Write"),
+            Self::Undefined(v) => write!(f, "Undefined: {}", v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::prelude::v1::*;
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn error_operation() {
+        let sample = &["", "Other", "Unknown", "Read", "Write", "Partial write", "Undefined: 6"];
+        for n in 1u8..7 {
+            assert_eq!(sample[n as usize], format!("{:#}", ErrorOperation::from(n)));
+        }
+        assert_eq!("Partial Write", format!("{}", ErrorOperation::PartialWrite));
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn error_granularity() {
+        let sample = &[
+            "Undefined: 0",
+            "Other",
+            "Unknown",
+            "Device level",
+            "Memory partition level",
+            "Undefined: 5",
+        ];
+        for n in 0u8..6 {
+            assert_eq!(sample[n as usize], format!("{:#}", ErrorGranularity::from(n)));
+        }
+        assert_eq!("Memory Partition Level", format!("{}", ErrorGranularity::from(4)));
+    }
+
+    #[cfg(feature = "strings")]
+    #[test]
+    fn error_type() {
+        let sample = &[
+            "Undefined: 0",
+            "Other",
+            "Unknown",
+            "OK",
+            "Bad read",
+            "Parity error",
+            "Single-bit error",
+            "Double-bit error",
+            "Multi-bit error",
+            "Nibble error",
+            "Checksum error",
+            "CRC error",
+            "Corrected single-bit error",
+            "Corrected error",
+            "Uncorrectable error",
+        ];
+        for n in 0u8..0x0E {
+            assert_eq!(sample[n as usize], format!("{:#}", ErrorType::from(n)));
+        }
+        let dmidecode = &[
+            "Other",
+            "Unknown",
+            "OK",
+            "Bad Read",
+            "Parity Error",
+            "Single-bit Error",
+            "Double-bit Error",
+            "Multi-bit Error",
+            "Nibble Error",
+            "Checksum Error",
+            "CRC Error",
+            "Corrected Single-bit Error",
+            "Corrected Error",
+            "Uncorrectable Error",
+        ];
+        for (n, expected) in (1u8..).zip(dmidecode) {
+            assert_eq!(expected, &format!("{}", ErrorType::from(n)));
+        }
+    }
+
+    #[test]
+    fn unknown_sentinels() {
+        assert_eq!(None, known_32(0x8000_0000));
+        assert_eq!(Some(0x1000), known_32(0x1000));
+        assert_eq!(None, known_64(0x8000_0000_0000_0000));
+        assert_eq!(Some(0x8000_0000), known_64(0x8000_0000));
+    }
+}
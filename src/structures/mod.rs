@@ -2,6 +2,33 @@
 //!
 //! The System Information is presented to an application as a set of structures that are obtained
 //! by traversing the SMBIOS structure table referenced by the SMBIOS Entry Point Structure.
+//!
+//! Every structure type decodes from a [RawStructure] through `TryFrom`, for callers that
+//! dispatch on the structure type themselves, e.g. to decode the structures of an OEM registry:
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use std::convert::TryFrom;
+//! use dmidecode::{EntryPoint, InfoType, System};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let raw = entry_point
+//!     .raw_structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..])
+//!     .flatten()
+//!     .find(|structure| structure.info == InfoType::System)
+//!     .unwrap();
+//! let system = System::try_from(raw).unwrap();
+//! assert_eq!("LENOVO", system.manufacturer);
+//! ```
+
+use core::convert::TryFrom;
+
+use crate::{InfoType, MalformedStructureError, RawStructure};
+
+pub(crate) mod field;
+pub(crate) mod length;
 
 #[path = "000_bios.rs"]
 pub mod bios;
@@ -63,6 +90,8 @@ pub use self::physical_memory_array::PhysicalMemoryArray;
 pub mod memory_device;
 pub use self::memory_device::MemoryDevice;
 
+pub mod memory_error;
+
 #[path = "018_memory_error_32.rs"]
 pub mod memory_error_32;
 pub use self::memory_error_32::MemoryError32;
@@ -82,3 +111,223 @@ pub use self::built_in_pointing_device::BuiltInPointingDevice;
 #[path = "022_portable_battery.rs"]
 pub mod portable_battery;
 pub use self::portable_battery::PortableBattery;
+
+/// Decode the structure as the type, whatever its [InfoType], after checking that it is as long as
+/// the minimum length of the type, as [Structure::decode](crate::Structure) does
+macro_rules! try_from_raw {
+    ($($structure:ident$(<$lifetime:lifetime>)?),+ $(,)?) => {
+        $(
+            impl<'buffer> TryFrom<RawStructure<'buffer>> for $structure$(<$lifetime>)? {
+                type Error = MalformedStructureError;
+
+                fn try_from(structure: RawStructure<'buffer>) -> Result<Self, Self::Error> {
+                    length::check_minimum(InfoType::$structure, &structure)?;
+                    $structure::from_raw(structure)
+                }
+            }
+        )+
+    };
+}
+
+try_from_raw!(
+    Bios<'buffer>,
+    System<'buffer>,
+    BaseBoard<'buffer>,
+    Enclosure<'buffer>,
+    Processor<'buffer>,
+    Cache<'buffer>,
+    PortConnector<'buffer>,
+    SystemSlots<'buffer>,
+    OemStrings<'buffer>,
+    SystemConfigurationOptions<'buffer>,
+    BiosLanguage<'buffer>,
+    GroupAssociations<'buffer>,
+    SystemEventLog<'buffer>,
+    PhysicalMemoryArray,
+    MemoryDevice<'buffer>,
+    MemoryError32,
+    MemoryArrayMappedAddress,
+    MemoryDeviceMappedAddress,
+    BuiltInPointingDevice,
+    PortableBattery<'buffer>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::prelude::v1::*;
+
+    /// Every byte value must convert (and format) without panicking, values not defined by the
+    /// specification end up in the `Undefined` variants
+    macro_rules! assert_total {
+        ($repr:ty: $($t:ty),+ $(,)?) => {
+            for n in <$repr>::MIN..=<$repr>::MAX {
+                $(
+                    let value = <$t>::from(n);
+                    let _ = format!("{:?}", value);
+                )+
+            }
+        };
+        ($repr:ty => Display: $($t:ty),+ $(,)?) => {
+            for n in <$repr>::MIN..=<$repr>::MAX {
+                $(
+                    let value = <$t>::from(n);
+                    let _ = format!("{:?}", value);
+                    #[cfg(feature = "strings")]
+                    let _ = format!("{}", value);
+                )+
+            }
+        };
+    }
+
+    #[test]
+    fn u8_enumerations_are_total() {
+        assert_total!(u8 => Display:
+            crate::InfoType,
+            system::WakeupType,
+            baseboard::BoardType,
+            enclosure::EnclosureType,
+            enclosure::State,
+            enclosure::SecurityStatus,
+            enclosure::ContainedElementType,
+            processor::Voltage,
+            processor::ProcessorUpgrade,
+            processor::CpuStatus,
+            cache::CacheErrorCorrectionType,
+            cache::SystemCacheType,
+            cache::CacheAssociativity,
+            port_connector::ConnectorType,
+            port_connector::PortType,
+            system_slots::SlotType,
+            system_slots::SlotWidth,
+            system_slots::CurrentUsage,
+            system_slots::SlotLength,
+            system_slots::SlotHeight,
+            physical_memory_array::MemoryArrayLocation,
+            physical_memory_array::MemoryArrayUse,
+            physical_memory_array::MemoryArrayErrorCorrectionTypes,
+            memory_error::ErrorType,
+            memory_error::ErrorGranularity,
+            memory_error::ErrorOperation,
+            built_in_pointing_device::Type,
+            built_in_pointing_device::Interface,
+            system_event_log::LogHeaderFormat,
+            system_event_log::EventLogType,
+            system_event_log::VariableDataFormatType,
+        );
+        assert_total!(u8:
+            processor::ProcessorType,
+            memory_device::ErrorGranularity,
+            memory_device::ErrorOperation,
+            memory_device::ErrorType,
+            memory_device::FormFactor,
+            memory_device::MemoryTechnology,
+            memory_device::Type,
+            system_event_log::LogStatus,
+        );
+        for n in 0..=u8::MAX {
+            if let Ok(family) = processor::ProcessorFamily::try_from(n) {
+                let _ = format!("{:?}", family);
+                #[cfg(feature = "strings")]
+                let _ = format!("{}", family);
+            }
+        }
+    }
+
+    #[test]
+    fn u16_enumerations_are_total() {
+        assert_total!(u16 => Display:
+            cache::CacheLevel,
+            cache::CacheLocation,
+            cache::CacheOperationalMode,
+        );
+        assert_total!(u16: cache::CacheConfiguration, cache::CacheSize);
+        for n in 0..=u16::MAX {
+            if let Ok(family) = processor::ProcessorFamily::try_from(n) {
+                let _ = format!("{:?}", family);
+                #[cfg(feature = "strings")]
+                let _ = format!("{}", family);
+            }
+        }
+    }
+
+    #[test]
+    fn try_from_raw() {
+        use crate::{testing::StructureBuilder, InfoType, LengthExpectation, Structure};
+
+        let array = StructureBuilder::new(InfoType::PhysicalMemoryArray)
+            .byte(0x03)
+            .byte(0x03)
+            .byte(0x03)
+            .dword(0x0100_0000)
+            .word(0xFFFE)
+            .word(2);
+        match (PhysicalMemoryArray::try_from(array.raw()), array.decode()) {
+            (Ok(array), Ok(Structure::PhysicalMemoryArray(decoded))) => assert_eq!(decoded, array),
+            other => panic!("{:?}", other),
+        }
+        // Too short for the formatted section indexed by the decoder
+        let short = StructureBuilder::new(InfoType::PhysicalMemoryArray).byte(0x03);
+        match PhysicalMemoryArray::try_from(short.raw()) {
+            Err(MalformedStructureError::InvalidFormattedSectionLength {
+                expected: LengthExpectation::Minimum(0x0F),
+                actual: 0x05,
+                ..
+            }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn try_from_other_types() {
+        use crate::{testing::StructureBuilder, EntryPoint, InfoType};
+
+        macro_rules! try_from_all {
+            ($structure:expr, $($t:ident),+ $(,)?) => {
+                [$($t::try_from($structure).is_ok()),+]
+            };
+        }
+        macro_rules! try_from_every_type {
+            ($structure:expr) => {
+                try_from_all!(
+                    $structure,
+                    Bios,
+                    System,
+                    BaseBoard,
+                    Enclosure,
+                    Processor,
+                    Cache,
+                    PortConnector,
+                    SystemSlots,
+                    OemStrings,
+                    SystemConfigurationOptions,
+                    BiosLanguage,
+                    GroupAssociations,
+                    SystemEventLog,
+                    PhysicalMemoryArray,
+                    MemoryDevice,
+                    MemoryError32,
+                    MemoryArrayMappedAddress,
+                    MemoryDeviceMappedAddress,
+                    BuiltInPointingDevice,
+                    PortableBattery,
+                )
+            };
+        }
+
+        // Structures without a formatted section are too short for every type
+        for info in [InfoType::End, InfoType::Inactive, InfoType::Oem(0x80)] {
+            let structure = StructureBuilder::new(info).extra_string("OEM");
+            assert_eq!([false; 20], try_from_every_type!(structure.raw()), "{:?}", info);
+        }
+        // Decoding a structure as another type must not panic, whatever the outcome
+        const DMIDECODE_BIN: &[u8] = include_bytes!("../../tests/data/dmidecode.bin");
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        for structure in entry_point
+            .raw_structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..])
+            .flatten()
+        {
+            try_from_every_type!(structure.clone());
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Aggregate answers computed from a whole SMBIOS table
+//!
+//! Questions such as "how much RAM is installed" or "how many cores does this machine have" need
+//! several structures to be joined: memory devices only count when their owning Physical Memory
+//! Array holds system memory, and processor sockets may be empty. [Summary] does this in one call.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{summary::Summary, EntryPoint};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! let summary = Summary::new(structures);
+//! assert_eq!(1, summary.processor_sockets);
+//! ```
+
+use crate::{enclosure::EnclosureType, physical_memory_array::MemoryArrayUse, Structure, Structures};
+
+/// Aggregate hardware information computed by [Summary::new]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Summary {
+    /// Sum of the sizes of all populated system memory devices, in bytes
+    pub total_memory: u64,
+    /// Number of system memory device sockets
+    pub memory_slots: usize,
+    /// Number of system memory device sockets with a device installed
+    pub populated_memory_slots: usize,
+    /// Number of processor sockets
+    pub processor_sockets: usize,
+    /// Number of processor sockets with a processor installed
+    pub populated_processor_sockets: usize,
+    /// Sum of the core counts of all populated sockets
+    pub cores: u32,
+    /// Sum of the enabled core counts of all populated sockets
+    pub cores_enabled: u32,
+    /// Sum of the thread counts of all populated sockets
+    pub threads: u32,
+    /// Type of the first System Enclosure structure in the table
+    pub chassis_type: Option<EnclosureType>,
+}
+
+impl Summary {
+    /// Compute the summary of all structures in the table. Structures that fail to decode are
+    /// skipped.
+    pub fn new(structures: Structures<'_>) -> Summary {
+        let mut summary = Summary::default();
+        for structure in structures.clone().flatten() {
+            match structure {
+                Structure::MemoryDevice(device) => {
+                    let system_memory = structures
+                        .array_for_device(&device)
+                        .map_or(true, |array| array.r#use == MemoryArrayUse::SystemMemory);
+                    // Devices that refer to an array missing from the table are assumed to be
+                    // system memory
+                    if !system_memory {
+                        lib_debug!(
+                            "memory device {:#06X} not counted, array {:#06X} is not system memory",
+                            device.handle,
+                            device.physical_memory_handle
+                        );
+                        continue;
+                    }
+                    summary.memory_slots += 1;
+                    if let Some(size) = device.size_bytes().filter(|&size| size > 0) {
+                        summary.populated_memory_slots += 1;
+                        summary.total_memory += size;
+                    }
+                }
+                Structure::Processor(processor) => {
+                    summary.processor_sockets += 1;
+                    if processor.is_populated() {
+                        summary.populated_processor_sockets += 1;
+                        summary.cores += u32::from(processor.core_count.unwrap_or(0));
+                        summary.cores_enabled += u32::from(processor.core_enabled.unwrap_or(0));
+                        summary.threads += u32::from(processor.thread_count.unwrap_or(0));
+                    }
+                }
+                Structure::Enclosure(enclosure) if summary.chassis_type.is_none() => {
+                    summary.chassis_type = Some(enclosure.enclosure_type);
+                }
+                _ => {}
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EntryPoint;
+    use pretty_assertions::assert_eq;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmi.0.bin");
+
+    #[test]
+    fn summary() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let summary = Summary::new(structures);
+        assert_eq!(
+            Summary {
+                total_memory: 128 << 30,
+                memory_slots: 24,
+                populated_memory_slots: 8,
+                processor_sockets: 2,
+                populated_processor_sockets: 2,
+                cores: 28,
+                cores_enabled: 28,
+                threads: 56,
+                chassis_type: Some(EnclosureType::RackMountChassis),
+            },
+            summary
+        );
+    }
+}
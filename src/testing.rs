@@ -0,0 +1,247 @@
+//! Builders for synthetic structures and tables, for tests
+//!
+//! [StructureBuilder] appends the fields of a formatted section in order and numbers *STRING*
+//! fields itself, so that tests do not need to hand-write header bytes, lengths and string
+//! indexes. [TableBuilder] concatenates structures into a structure table. Requires the `testing`
+//! feature.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{testing::StructureBuilder, Handle, InfoType, Structure};
+//!
+//! let system = StructureBuilder::new(InfoType::System)
+//!     .handle(Handle(0x0100))
+//!     .version((2, 0).into())
+//!     .string("Manufacturer")
+//!     .string("Product")
+//!     .string("")
+//!     .string("Serial");
+//! match system.decode() {
+//!     Ok(Structure::System(system)) => assert_eq!("Serial", system.serial),
+//!     _ => panic!("System Information expected"),
+//! }
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Handle, InfoType, MalformedStructureError, RawStructure, SmbiosVersion, Structure, Structures};
+
+/// Builder of a [RawStructure]
+///
+/// The structure has handle 0 and SMBIOS version 3.0 unless set otherwise.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StructureBuilder {
+    info: InfoType,
+    handle: Handle,
+    version: SmbiosVersion,
+    length: Option<u8>,
+    data: Vec<u8>,
+    strings: Vec<u8>,
+    string_count: u8,
+}
+
+impl StructureBuilder {
+    /// An empty structure of type `info`
+    pub fn new(info: InfoType) -> Self {
+        StructureBuilder {
+            info,
+            handle: Handle(0),
+            version: SmbiosVersion { major: 3, minor: 0 },
+            length: None,
+            data: Vec::new(),
+            strings: vec![0, 0],
+            string_count: 0,
+        }
+    }
+
+    /// Set the handle of the structure
+    pub fn handle(mut self, handle: Handle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Set the SMBIOS version of the table the structure is decoded from
+    pub fn version(mut self, version: SmbiosVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Override the length in the header, by default the header and the appended fields
+    pub fn length(mut self, length: u8) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Append a BYTE field
+    pub fn byte(self, value: u8) -> Self {
+        self.bytes(&[value])
+    }
+
+    /// Append a WORD field
+    pub fn word(self, value: u16) -> Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    /// Append a DWORD field
+    pub fn dword(self, value: u32) -> Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    /// Append a QWORD field
+    pub fn qword(self, value: u64) -> Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    /// Append a handle field
+    pub fn handle_ref(self, handle: Handle) -> Self {
+        self.word(handle.0)
+    }
+
+    /// Append raw bytes
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    /// Append a *STRING* field and add `value` to the strings table; an empty `value` is stored as
+    /// string number 0, i.e. no string.
+    ///
+    /// # Panics
+    /// Panics when adding more than 255 strings.
+    pub fn string(self, value: &str) -> Self {
+        if value.is_empty() {
+            return self.byte(0);
+        }
+        let mut builder = self.extra_string(value);
+        let index = builder.string_count;
+        builder.data.push(index);
+        builder
+    }
+
+    /// Add `value` to the strings table without referring to it from a field, like OEM additions
+    pub fn extra_string(mut self, value: &str) -> Self {
+        // Replace the terminator, which is a lone empty string in an empty strings table
+        if self.string_count == 0 {
+            self.strings.clear();
+        } else {
+            self.strings.pop();
+        }
+        self.string_count = self.string_count.checked_add(1).expect("too many strings");
+        self.strings.extend_from_slice(value.as_bytes());
+        self.strings.extend_from_slice(&[0, 0]);
+        self
+    }
+
+    /// The structure as read from a table
+    pub fn raw(&self) -> RawStructure<'_> {
+        RawStructure {
+            version: self.version,
+            info: self.info,
+            length: self.header_length(),
+            handle: self.handle,
+            data: &self.data,
+            strings: &self.strings,
+            trim_strings: false,
+            tolerate_bad_string_indexes: false,
+        }
+    }
+
+    /// Decode the structure
+    pub fn decode(&self) -> Result<Structure<'_>, MalformedStructureError> {
+        Structure::decode(self.raw())
+    }
+
+    /// The structure as stored in a table: the header, the formatted section and the strings
+    /// table with its double NUL terminator
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.data.len() + self.strings.len());
+        bytes.push(self.info.code());
+        bytes.push(self.header_length());
+        bytes.extend_from_slice(&self.handle.0.to_le_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes.extend_from_slice(&self.strings);
+        bytes
+    }
+
+    fn header_length(&self) -> u8 {
+        self.length.unwrap_or((4 + self.data.len()) as u8)
+    }
+}
+
+/// Builder of a structure table
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TableBuilder {
+    version: SmbiosVersion,
+    bytes: Vec<u8>,
+}
+
+impl TableBuilder {
+    /// An empty table of the SMBIOS `version`
+    pub fn new(version: SmbiosVersion) -> Self {
+        TableBuilder {
+            version,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Append a structure
+    pub fn push(mut self, structure: &StructureBuilder) -> Self {
+        self.bytes.extend_from_slice(&structure.to_bytes());
+        self
+    }
+
+    /// Append an End-of-Table structure with handle `handle`
+    pub fn end(self, handle: Handle) -> Self {
+        self.push(&StructureBuilder::new(InfoType::End).handle(handle))
+    }
+
+    /// The table bytes
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Iterate over the structures of the table
+    pub fn structures(&self) -> Structures<'_> {
+        Structures::new(&self.bytes, self.version, self.bytes.len() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn structure_bytes() {
+        let builder = StructureBuilder::new(InfoType::Oem(0x80))
+            .handle(Handle(0x1234))
+            .string("first")
+            .word(0xBEEF)
+            .string("")
+            .string("second")
+            .extra_string("oem");
+        assert_eq!(
+            b"\x80\x09\x34\x12\x01\xEF\xBE\x00\x02first\0second\0oem\0\0".to_vec(),
+            builder.to_bytes()
+        );
+
+        let raw = builder.raw();
+        assert_eq!(9, raw.length);
+        assert_eq!("second", raw.get_string(0x08).unwrap());
+        assert_eq!(vec!["first", "second", "oem"], raw.strings().collect::<Vec<_>>());
+
+        let empty = StructureBuilder::new(InfoType::End).handle(Handle(0xFEFF));
+        assert_eq!(vec![0x7F, 0x04, 0xFF, 0xFE, 0x00, 0x00], empty.to_bytes());
+        assert_eq!(&[0, 0], empty.raw().strings);
+    }
+
+    #[test]
+    fn table() {
+        let table = TableBuilder::new((3, 0).into())
+            .push(&StructureBuilder::new(InfoType::Oem(0x80)).byte(1).string("a"))
+            .end(Handle(0xFEFF));
+        let structures = table.structures().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(2, structures.len());
+        assert!(matches!(structures[1], Structure::Other(ref raw) if raw.info == InfoType::End));
+    }
+}
@@ -0,0 +1,204 @@
+//! Memory devices grouped by the physical memory array they belong to
+//!
+//! The memory layout of a system is spread over four structure types joined by handles: each
+//! Memory Device (Type 17) refers to its Physical Memory Array (Type 16), each Memory Array Mapped
+//! Address (Type 19) to the array whose addresses it maps, and each Memory Device Mapped Address
+//! (Type 20) to the device it maps. [Structures::memory_topology] walks these references and
+//! yields the arrays as the root of a tree of their devices and address ranges.
+//!
+//! The tree is built lazily, every level walks the table again, so that no allocation is needed.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::EntryPoint;
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! for array in structures.memory_topology() {
+//!     println!("array {:?}, {} slots", array.array.r#use, array.array.number_of_memory_devices);
+//!     for range in array.mapped_addresses() {
+//!         println!("  mapped at {:#X?}", range.address_range());
+//!     }
+//!     for device in array.devices() {
+//!         println!("  {}: {:?} bytes", device.device.device_locator, device.device.size_bytes());
+//!     }
+//! }
+//! ```
+
+use crate::{
+    Handle, MemoryArrayMappedAddress, MemoryDevice, MemoryDeviceMappedAddress, PhysicalMemoryArray, Structure,
+    Structures,
+};
+
+/// A Physical Memory Array and the structures that refer to it
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ArrayTopology<'buffer> {
+    pub array: PhysicalMemoryArray,
+    table: Structures<'buffer>,
+}
+
+impl<'buffer> ArrayTopology<'buffer> {
+    /// The memory devices of the array, in table order
+    pub fn devices(&self) -> impl Iterator<Item = DeviceTopology<'buffer>> + 'buffer {
+        let (handle, table) = (self.array.handle, self.table.clone());
+        self.table
+            .clone()
+            .flatten()
+            .filter_map(move |structure| match structure {
+                Structure::MemoryDevice(device) if device.physical_memory_handle == handle => Some(DeviceTopology {
+                    device,
+                    table: table.clone(),
+                }),
+                _ => None,
+            })
+    }
+
+    /// The address ranges mapped to the array, in table order
+    pub fn mapped_addresses(&self) -> impl Iterator<Item = MemoryArrayMappedAddress> + 'buffer {
+        let handle = self.array.handle;
+        self.table
+            .clone()
+            .flatten()
+            .filter_map(move |structure| match structure {
+                Structure::MemoryArrayMappedAddress(mapped) if mapped.memory_array_handle == handle => Some(mapped),
+                _ => None,
+            })
+    }
+}
+
+/// A Memory Device and the address ranges mapped to it
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DeviceTopology<'buffer> {
+    pub device: MemoryDevice<'buffer>,
+    table: Structures<'buffer>,
+}
+
+impl<'buffer> DeviceTopology<'buffer> {
+    /// The address ranges mapped to the device, in table order
+    pub fn mapped_addresses(&self) -> impl Iterator<Item = MemoryDeviceMappedAddress> + 'buffer {
+        let handle = self.device.handle;
+        self.table
+            .clone()
+            .flatten()
+            .filter_map(move |structure| match structure {
+                Structure::MemoryDeviceMappedAddress(mapped) if mapped.memory_device_handle == handle => Some(mapped),
+                _ => None,
+            })
+    }
+}
+
+/// An iterator over the Physical Memory Arrays of a table, created by
+/// [Structures::memory_topology]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MemoryTopology<'buffer> {
+    table: Structures<'buffer>,
+    structures: Structures<'buffer>,
+}
+
+impl<'buffer> Iterator for MemoryTopology<'buffer> {
+    type Item = ArrayTopology<'buffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = &self.table;
+        self.structures
+            .by_ref()
+            .flatten()
+            .find_map(|structure| match structure {
+                Structure::PhysicalMemoryArray(array) => Some(ArrayTopology {
+                    array,
+                    table: table.clone(),
+                }),
+                _ => None,
+            })
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Iterate over the Physical Memory Arrays of the table, with their memory devices and mapped
+    /// address ranges. Structures that fail to decode are skipped.
+    ///
+    /// Devices that refer to an array missing from the table are listed by
+    /// [Structures::orphan_memory_devices].
+    pub fn memory_topology(&self) -> MemoryTopology<'buffer> {
+        MemoryTopology {
+            table: self.clone(),
+            structures: self.clone(),
+        }
+    }
+
+    /// The memory devices whose Physical Memory Array is missing from the table
+    pub fn orphan_memory_devices(&self) -> impl Iterator<Item = MemoryDevice<'buffer>> + 'buffer {
+        let table = self.clone();
+        self.clone().flatten().filter_map(move |structure| match structure {
+            Structure::MemoryDevice(device) if !is_array(&table, device.physical_memory_handle) => Some(device),
+            _ => None,
+        })
+    }
+}
+
+fn is_array(table: &Structures<'_>, handle: Handle) -> bool {
+    matches!(table.decode_handle(handle), Some(Structure::PhysicalMemoryArray(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::StructureBuilder, testing::TableBuilder, EntryPoint, InfoType};
+    use pretty_assertions::assert_eq;
+    use std::{vec, vec::Vec};
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn test_data() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+        let arrays = structures.memory_topology().collect::<Vec<_>>();
+        assert_eq!(1, arrays.len());
+        let array = &arrays[0];
+        assert_eq!(Handle(0x0007), array.array.handle);
+        let ranges = array
+            .mapped_addresses()
+            .map(|mapped| (mapped.handle, mapped.address_range()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(Handle(0x000C), Some(0..=0x1_FFFF_FFFF))], ranges);
+
+        let devices = array
+            .devices()
+            .map(|device| {
+                let mapped = device
+                    .mapped_addresses()
+                    .map(|mapped| mapped.handle)
+                    .collect::<Vec<_>>();
+                (device.device.handle, mapped)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Handle(0x0008), vec![Handle(0x000A), Handle(0x000B)]),
+                (Handle(0x0009), vec![])
+            ],
+            devices
+        );
+        assert_eq!(0, structures.orphan_memory_devices().count());
+    }
+
+    #[test]
+    fn orphan_devices() {
+        let device = StructureBuilder::new(InfoType::MemoryDevice)
+            .handle(Handle(0x1100))
+            .handle_ref(Handle(0x1000))
+            .handle_ref(Handle::NONE)
+            .bytes(&[0; 0x20]);
+        let table = TableBuilder::new((2, 8).into()).push(&device).end(Handle(0xFEFF));
+        let structures = table.structures();
+        assert_eq!(0, structures.memory_topology().count());
+        let orphans = structures
+            .orphan_memory_devices()
+            .map(|device| device.handle)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Handle(0x1100)], orphans);
+    }
+}
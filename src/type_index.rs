@@ -0,0 +1,124 @@
+//! Number of structures of each type in a table
+//!
+//! Probing for a capability, e.g. whether the platform reports a TPM Device (Type 43), only needs
+//! to know whether a structure type is present. [Structures::type_index] reads the headers of the
+//! table once, without decoding any structure, and answers such questions in constant time.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{EntryPoint, InfoType};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! let index = structures.type_index();
+//! assert!(!index.contains_type(InfoType::TpmDevice));
+//! assert_eq!(2, index.count_type(InfoType::MemoryDevice));
+//! ```
+
+use core::fmt;
+
+use crate::{InfoType, Structures};
+
+/// The number of structures of each type in a table, created by [Structures::type_index]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct TypeIndex {
+    counts: [u16; 256],
+}
+
+impl TypeIndex {
+    /// Whether the table holds at least one structure of type `info`
+    pub fn contains_type(&self, info: InfoType) -> bool {
+        self.count_type(info) != 0
+    }
+
+    /// Number of structures of type `info` in the table
+    pub fn count_type(&self, info: InfoType) -> usize {
+        self.counts[info.code() as usize] as usize
+    }
+
+    /// Number of structures in the table, the End-of-Table structure included
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    /// The structure types present in the table with their number of structures, by increasing
+    /// type
+    pub fn iter(&self) -> impl Iterator<Item = (InfoType, usize)> + '_ {
+        (0..=u8::MAX)
+            .map(move |code| (InfoType::from(code), self.counts[code as usize] as usize))
+            .filter(|&(_, count)| count != 0)
+    }
+}
+
+impl fmt::Debug for TypeIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Count the structures of each type in one pass over the structure headers, without decoding
+    /// the structures. Counting stops at the first structure that cannot be read.
+    pub fn type_index(&self) -> TypeIndex {
+        let mut counts = [0u16; 256];
+        for structure in self.clone().raw().map_while(Result::ok) {
+            let count = &mut counts[structure.info.code() as usize];
+            *count = count.saturating_add(1);
+        }
+        TypeIndex { counts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::StructureBuilder, testing::TableBuilder, EntryPoint, Handle};
+    use pretty_assertions::assert_eq;
+    use std::{format, vec, vec::Vec};
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmidecode.bin");
+
+    #[test]
+    fn test_data() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+        let index = structures.type_index();
+        assert_eq!(69, index.len());
+        assert_eq!(structures.clone().count(), index.len());
+        assert_eq!(1, index.count_type(InfoType::Bios));
+        assert_eq!(1, index.count_type(InfoType::PhysicalMemoryArray));
+        assert!(index.contains_type(InfoType::End));
+        assert!(!index.contains_type(InfoType::TpmDevice));
+        for (info, count) in index.iter() {
+            let decoded = structures
+                .clone()
+                .raw()
+                .map(Result::unwrap)
+                .filter(|structure| structure.info == info)
+                .count();
+            assert_eq!(decoded, count, "{}", info);
+        }
+    }
+
+    #[test]
+    fn oem_types() {
+        let table = TableBuilder::new((3, 0).into())
+            .push(&StructureBuilder::new(InfoType::Oem(0x80)).handle(Handle(0x8000)))
+            .push(&StructureBuilder::new(InfoType::Oem(0x80)).handle(Handle(0x8001)))
+            .push(&StructureBuilder::new(InfoType::TpmDevice).handle(Handle(0x2B00)))
+            .end(Handle(0xFEFF));
+        let index = table.structures().type_index();
+        assert_eq!(
+            vec![(InfoType::TpmDevice, 1), (InfoType::End, 1), (InfoType::Oem(0x80), 2)],
+            index.iter().collect::<Vec<_>>()
+        );
+        assert_eq!("{TpmDevice: 1, End: 1, Oem(128): 2}", format!("{:?}", index));
+        assert!(TableBuilder::new((3, 0).into()).structures().type_index().is_empty());
+    }
+}
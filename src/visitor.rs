@@ -0,0 +1,140 @@
+//! Streaming callback-based access to SMBIOS structures
+//!
+//! The [StructureVisitor] trait has a callback method for every decoded structure type. All the
+//! methods have empty default implementations, so a consumer implements only the ones it is
+//! interested in and new structure types can be added without breaking existing visitors.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{visitor::StructureVisitor, EntryPoint, Processor};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! #[derive(Default)]
+//! struct ProcessorCount(usize);
+//! impl<'buffer> StructureVisitor<'buffer> for ProcessorCount {
+//!     fn on_processor(&mut self, _: Processor<'buffer>) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! let mut count = ProcessorCount::default();
+//! dmidecode::visit(structures, &mut count);
+//! assert_eq!(1, count.0);
+//! ```
+
+use crate::{
+    BaseBoard, Bios, BiosLanguage, BuiltInPointingDevice, Cache, Enclosure, GroupAssociations, MalformedStructureError,
+    MemoryArrayMappedAddress, MemoryDevice, MemoryDeviceMappedAddress, MemoryError32, OemStrings, PhysicalMemoryArray,
+    PortConnector, PortableBattery, Processor, RawStructure, Structure, Structures, System, SystemConfigurationOptions,
+    SystemEventLog, SystemSlots,
+};
+
+/// Per-type callbacks invoked by [visit]
+#[allow(unused_variables)]
+pub trait StructureVisitor<'buffer> {
+    fn on_bios(&mut self, bios: Bios<'buffer>) {}
+    fn on_system(&mut self, system: System<'buffer>) {}
+    fn on_baseboard(&mut self, baseboard: BaseBoard<'buffer>) {}
+    fn on_enclosure(&mut self, enclosure: Enclosure<'buffer>) {}
+    fn on_processor(&mut self, processor: Processor<'buffer>) {}
+    fn on_cache(&mut self, cache: Cache<'buffer>) {}
+    fn on_port_connector(&mut self, port_connector: PortConnector<'buffer>) {}
+    fn on_system_slots(&mut self, system_slots: SystemSlots<'buffer>) {}
+    fn on_oem_strings(&mut self, oem_strings: OemStrings<'buffer>) {}
+    fn on_system_configuration_options(&mut self, options: SystemConfigurationOptions<'buffer>) {}
+    fn on_bios_language(&mut self, bios_language: BiosLanguage<'buffer>) {}
+    fn on_group_associations(&mut self, group_associations: GroupAssociations<'buffer>) {}
+    fn on_system_event_log(&mut self, system_event_log: SystemEventLog<'buffer>) {}
+    fn on_physical_memory_array(&mut self, physical_memory_array: PhysicalMemoryArray) {}
+    fn on_memory_device(&mut self, memory_device: MemoryDevice<'buffer>) {}
+    fn on_memory_error_32(&mut self, memory_error: MemoryError32) {}
+    fn on_memory_array_mapped_address(&mut self, mapped_address: MemoryArrayMappedAddress) {}
+    fn on_memory_device_mapped_address(&mut self, mapped_address: MemoryDeviceMappedAddress) {}
+    fn on_built_in_pointing_device(&mut self, pointing_device: BuiltInPointingDevice) {}
+    fn on_portable_battery(&mut self, portable_battery: PortableBattery<'buffer>) {}
+    /// Structures that are not decoded by this crate, such as OEM structures
+    fn on_unknown(&mut self, structure: RawStructure<'buffer>) {}
+    /// Structures that failed to decode
+    fn on_error(&mut self, error: MalformedStructureError) {}
+}
+
+/// Decode every structure and pass it to the matching `visitor` callback
+pub fn visit<'buffer, V: StructureVisitor<'buffer> + ?Sized>(structures: Structures<'buffer>, visitor: &mut V) {
+    for structure in structures {
+        match structure {
+            Ok(Structure::Bios(s)) => visitor.on_bios(s),
+            Ok(Structure::System(s)) => visitor.on_system(s),
+            Ok(Structure::BaseBoard(s)) => visitor.on_baseboard(s),
+            Ok(Structure::Enclosure(s)) => visitor.on_enclosure(s),
+            Ok(Structure::Processor(s)) => visitor.on_processor(s),
+            Ok(Structure::Cache(s)) => visitor.on_cache(s),
+            Ok(Structure::PortConnector(s)) => visitor.on_port_connector(s),
+            Ok(Structure::SystemSlots(s)) => visitor.on_system_slots(s),
+            Ok(Structure::OemStrings(s)) => visitor.on_oem_strings(s),
+            Ok(Structure::SystemConfigurationOptions(s)) => visitor.on_system_configuration_options(s),
+            Ok(Structure::BiosLanguage(s)) => visitor.on_bios_language(s),
+            Ok(Structure::GroupAssociations(s)) => visitor.on_group_associations(s),
+            Ok(Structure::SystemEventLog(s)) => visitor.on_system_event_log(s),
+            Ok(Structure::PhysicalMemoryArray(s)) => visitor.on_physical_memory_array(s),
+            Ok(Structure::MemoryDevice(s)) => visitor.on_memory_device(s),
+            Ok(Structure::MemoryError32(s)) => visitor.on_memory_error_32(s),
+            Ok(Structure::MemoryArrayMappedAddress(s)) => visitor.on_memory_array_mapped_address(s),
+            Ok(Structure::MemoryDeviceMappedAddress(s)) => visitor.on_memory_device_mapped_address(s),
+            Ok(Structure::BuiltInPointingDevice(s)) => visitor.on_built_in_pointing_device(s),
+            Ok(Structure::PortableBattery(s)) => visitor.on_portable_battery(s),
+            Ok(Structure::Other(s)) => visitor.on_unknown(s),
+            Err(e) => visitor.on_error(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EntryPoint;
+
+    const DMIDECODE_BIN: &[u8] = include_bytes!("../tests/data/dmi.0.bin");
+
+    #[derive(Default)]
+    struct Counter {
+        slots: usize,
+        memory_devices: usize,
+        unknown: usize,
+    }
+    impl<'buffer> StructureVisitor<'buffer> for Counter {
+        fn on_system_slots(&mut self, _: SystemSlots<'buffer>) {
+            self.slots += 1;
+        }
+        fn on_memory_device(&mut self, _: MemoryDevice<'buffer>) {
+            self.memory_devices += 1;
+        }
+        fn on_unknown(&mut self, _: RawStructure<'buffer>) {
+            self.unknown += 1;
+        }
+    }
+
+    #[test]
+    fn visit_counts() {
+        let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+        let structures = entry_point.structures(&DMIDECODE_BIN[(entry_point.smbios_address() as usize)..]);
+        let mut counter = Counter::default();
+        visit(structures.clone(), &mut counter);
+
+        let slots = structures
+            .clone()
+            .filter(|s| matches!(s, Ok(Structure::SystemSlots(_))))
+            .count();
+        let memory_devices = structures
+            .clone()
+            .filter(|s| matches!(s, Ok(Structure::MemoryDevice(_))))
+            .count();
+        let unknown = structures.filter(|s| matches!(s, Ok(Structure::Other(_)))).count();
+        assert_eq!(4, counter.slots);
+        assert_eq!(slots, counter.slots);
+        assert_eq!(memory_devices, counter.memory_devices);
+        assert_eq!(unknown, counter.unknown);
+    }
+}
@@ -0,0 +1,1038 @@
+//! Firmware bugs that do not prevent parsing
+//!
+//! Decoding is lenient: a structure that is shorter than its specification requires still decodes
+//! as long as the mandatory fields are present. [Structures::warnings] walks the table once more
+//! and reports such deviations as machine-readable [Warning]s, e.g. to aggregate firmware bugs per
+//! vendor.
+//!
+//! ```
+//! # extern crate dmidecode;
+//! use dmidecode::{warnings::Warning, EntryPoint};
+//!
+//! const DMIDECODE_BIN: &'static [u8] = include_bytes!("../tests/data/dmidecode.bin");
+//!
+//! let entry_point = EntryPoint::search(DMIDECODE_BIN).unwrap();
+//! let structures = entry_point.structures(&DMIDECODE_BIN[entry_point.smbios_address() as usize..]);
+//! for warning in structures.warnings() {
+//!     if let Warning::LengthMismatch(mismatch) = warning {
+//!         println!("{}: expected {} bytes", mismatch.handle, mismatch.expected);
+//!     }
+//! }
+//! ```
+//!
+//! # Strict mode
+//!
+//! With the `strict` feature, `Structures::conformance` runs the checks above and a stricter set
+//! meant for firmware validation: structure types reserved by the specification, reserved bits
+//! that are set, enumeration values the specification does not define, strings no field refers to
+//! and handle references to missing structures. Strict checks only know the fields of the
+//! structure types this crate decodes, and of a few more with simple layouts.
+
+use core::fmt;
+
+use crate::structures::{bios::Quirk, length};
+use crate::{
+    Bios, BiosLanguage, EntryPoint, GroupAssociations, Handle, InfoType, RawStructure, SmbiosVersion, Structures,
+};
+
+/// A deviation from the SMBIOS specification
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Warning {
+    LengthMismatch(LengthMismatch),
+    /// The number of BIOS language strings differs from the declared installable languages count
+    LanguageCountMismatch {
+        handle: Handle,
+        declared: u8,
+        actual: usize,
+    },
+    /// The SMBIOS 2.x entry point has a revision other than [crate::EntryPointV2::REVISION_2_1], its
+    /// formatted area is not interpreted
+    UnknownEntryPointRevision {
+        revision: u8,
+        formatted: [u8; 5],
+    },
+    /// The length of a Group Associations structure is not a whole number of items, or some of
+    /// the declared items are missing
+    GroupItemsMismatch {
+        handle: Handle,
+        length: u8,
+        declared: usize,
+        available: usize,
+    },
+    /// The SMBIOS 3.x maximum table length exceeds the buffer holding the table, and the table was
+    /// read up to the end of the buffer without reaching an End-of-Table structure
+    TableLengthClamped {
+        declared: u32,
+        available: u32,
+    },
+    /// A structure, header and strings included, is larger than the maximum structure size of the
+    /// SMBIOS 2.x entry point
+    StructureTooLarge {
+        info: InfoType,
+        handle: Handle,
+        size: usize,
+        maximum: u16,
+    },
+    /// A BIOS Information structure was decoded with a workaround from the quirks registry, see
+    /// [Structures::with_bios_quirks]
+    QuirkApplied {
+        handle: Handle,
+        quirk: Quirk,
+    },
+    /// The structure type is reserved by the specification for future standard structures
+    ReservedType {
+        handle: Handle,
+        code: u8,
+    },
+    /// Bits of a bit field that the specification reserves, and requires to be zero, are set
+    ReservedBits {
+        info: InfoType,
+        handle: Handle,
+        offset: u8,
+        bits: u64,
+    },
+    /// An enumerated field holds a value the specification does not define
+    UndefinedValue {
+        info: InfoType,
+        handle: Handle,
+        offset: u8,
+        value: u8,
+    },
+    /// A string of the strings table is not referred to by any field
+    UnreferencedString {
+        info: InfoType,
+        handle: Handle,
+        index: u8,
+    },
+    /// A handle field refers to a structure missing from the table
+    DanglingHandle {
+        info: InfoType,
+        handle: Handle,
+        offset: u8,
+        target: Handle,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::LengthMismatch(mismatch) => write!(f, "{}", mismatch),
+            Warning::LanguageCountMismatch {
+                handle,
+                declared,
+                actual,
+            } => write!(
+                f,
+                "BIOS Language Information structure {} declares {} languages, has {}",
+                handle, declared, actual
+            ),
+            Warning::UnknownEntryPointRevision { revision, formatted } => write!(
+                f,
+                "SMBIOS 2.x entry point revision {:#04X} is unknown, formatted area {:02X?} ignored",
+                revision, formatted
+            ),
+            Warning::GroupItemsMismatch {
+                handle,
+                length,
+                declared,
+                available,
+            } => write!(
+                f,
+                "Group Associations structure {} is {:#04X} bytes long for {} items, {} are present",
+                handle, length, declared, available
+            ),
+            Warning::TableLengthClamped { declared, available } => write!(
+                f,
+                "SMBIOS 3.x table maximum length {:#X} exceeds the {:#X} bytes available",
+                declared, available
+            ),
+            Warning::StructureTooLarge {
+                info,
+                handle,
+                size,
+                maximum,
+            } => write!(
+                f,
+                "{} structure {} is {} bytes long, the entry point maximum structure size is {}",
+                info, handle, size, maximum
+            ),
+            Warning::QuirkApplied { handle, quirk } => {
+                write!(f, "BIOS structure {} decoded with a workaround: {}", handle, quirk)
+            }
+            Warning::ReservedType { handle, code } => write!(
+                f,
+                "Structure {} is of type {}, reserved by the specification",
+                handle, code
+            ),
+            Warning::ReservedBits {
+                info,
+                handle,
+                offset,
+                bits,
+            } => write!(
+                f,
+                "{} structure {} sets reserved bits {:#X} of the field at offset {:#04X}",
+                info, handle, bits, offset
+            ),
+            Warning::UndefinedValue {
+                info,
+                handle,
+                offset,
+                value,
+            } => write!(
+                f,
+                "{} structure {} has undefined value {:#04X} at offset {:#04X}",
+                info, handle, value, offset
+            ),
+            Warning::UnreferencedString { info, handle, index } => write!(
+                f,
+                "{} structure {} has string {} that no field refers to",
+                info, handle, index
+            ),
+            Warning::DanglingHandle {
+                info,
+                handle,
+                offset,
+                target,
+            } => write!(
+                f,
+                "{} structure {} refers to missing structure {} at offset {:#04X}",
+                info, handle, target, offset
+            ),
+        }
+    }
+}
+
+/// The formatted section of a structure differs in length from the one the SMBIOS version of the
+/// table specifies
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LengthMismatch {
+    pub info: InfoType,
+    pub handle: Handle,
+    /// Length specified for the structure type by `version`
+    pub expected: u8,
+    /// Length found in the structure header
+    pub actual: u8,
+    /// SMBIOS version of the table
+    pub version: SmbiosVersion,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} structure {} is {:#04X} bytes long, SMBIOS {} specifies {:#04X}",
+            self.info, self.handle, self.actual, self.version, self.expected
+        )
+    }
+}
+
+/// An iterator over the warnings of a table, created by [Structures::warnings]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Warnings<'buffer> {
+    structures: Structures<'buffer>,
+    /// The table from the first structure walked
+    table: Table<'buffer>,
+    structure: Option<RawStructure<'buffer>>,
+    /// Warning about the table as a whole, reported before the ones of the structures
+    table_warning: Option<Warning>,
+    /// Index of the next check to run on `structure`
+    check: usize,
+    /// Index of the next warning of the check
+    nth: usize,
+    #[cfg(feature = "strict")]
+    strict: bool,
+}
+
+/// The table the checked structures belong to
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Table<'buffer> {
+    structures: Structures<'buffer>,
+    /// The handles of the structures of the table, to resolve handle references in strict mode
+    #[cfg(feature = "strict")]
+    handles: Option<strict::HandleSet>,
+}
+
+/// A check run on every structure, returns the `nth` warning it finds in the structure
+type Check = fn(&RawStructure<'_>, &Table<'_>, usize) -> Option<Warning>;
+
+/// Checks run on every structure, in order
+const CHECKS: &[Check] = &[
+    |structure, _, nth| first(nth, || length_mismatch(structure).map(Warning::LengthMismatch)),
+    |structure, _, nth| first(nth, || language_count_mismatch(structure)),
+    |structure, _, nth| first(nth, || group_items_mismatch(structure)),
+    |structure, table, nth| first(nth, || structure_too_large(structure, &table.structures)),
+    quirk_applied,
+];
+
+/// Run a check that finds at most one warning in a structure
+fn first(nth: usize, check: impl FnOnce() -> Option<Warning>) -> Option<Warning> {
+    if nth == 0 {
+        check()
+    } else {
+        None
+    }
+}
+
+impl<'buffer> Warnings<'buffer> {
+    fn new(structures: &Structures<'buffer>) -> Self {
+        Warnings {
+            structures: structures.clone(),
+            table: Table {
+                structures: structures.clone(),
+                #[cfg(feature = "strict")]
+                handles: None,
+            },
+            structure: None,
+            table_warning: table_length_clamped(structures),
+            check: 0,
+            nth: 0,
+            #[cfg(feature = "strict")]
+            strict: false,
+        }
+    }
+
+    fn check(&self) -> Option<Check> {
+        #[cfg(feature = "strict")]
+        let strict = if self.strict { strict::CHECKS } else { &[] };
+        #[cfg(not(feature = "strict"))]
+        let strict: &[Check] = &[];
+        CHECKS.iter().chain(strict).nth(self.check).copied()
+    }
+}
+
+impl<'buffer> Iterator for Warnings<'buffer> {
+    type Item = Warning;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(warning) = self.table_warning.take() {
+            lib_debug!("{}", warning);
+            return Some(warning);
+        }
+        loop {
+            if let Some(ref structure) = self.structure {
+                while let Some(check) = self.check() {
+                    if let Some(warning) = check(structure, &self.table, self.nth) {
+                        self.nth += 1;
+                        lib_debug!("{}", warning);
+                        return Some(warning);
+                    }
+                    self.check += 1;
+                    self.nth = 0;
+                }
+            }
+            // Malformed structures are errors reported by the structure iterator, and stop the walk
+            self.structure = Some(self.structures.next_structure()?.ok()?);
+            self.check = 0;
+            self.nth = 0;
+        }
+    }
+}
+
+impl<'buffer> Structures<'buffer> {
+    /// Walk the table and report its deviations from the specification
+    pub fn warnings(&self) -> Warnings<'buffer> {
+        Warnings::new(self)
+    }
+
+    /// Walk the table and report its deviations from the specification, including the ones of the
+    /// strict mode, see the [module documentation](crate::warnings)
+    #[cfg(feature = "strict")]
+    pub fn conformance(&self) -> Warnings<'buffer> {
+        let warnings = Warnings::new(self);
+        Warnings {
+            table: Table {
+                handles: Some(strict::HandleSet::new(self)),
+                ..warnings.table
+            },
+            strict: true,
+            ..warnings
+        }
+    }
+}
+
+impl EntryPoint {
+    /// Report the deviations of the entry point from the specification
+    pub fn warnings(&self) -> impl Iterator<Item = Warning> {
+        let warning = match self {
+            EntryPoint::V2(point) if !point.known_revision() => Some(Warning::UnknownEntryPointRevision {
+                revision: point.revision,
+                formatted: point.formatted,
+            }),
+            _ => None,
+        };
+        warning.into_iter()
+    }
+}
+
+fn table_length_clamped(structures: &Structures<'_>) -> Option<Warning> {
+    let available = structures.buffer.len().min(u32::MAX as usize) as u32;
+    // A maximum past a table that ends within the buffer is usual and harmless
+    if structures.smbios_version.major >= 3
+        && structures.declared_len > available
+        && !structures.clone().finish().end_of_table
+    {
+        Some(Warning::TableLengthClamped {
+            declared: structures.declared_len,
+            available,
+        })
+    } else {
+        None
+    }
+}
+
+fn length_mismatch(structure: &RawStructure<'_>) -> Option<LengthMismatch> {
+    let expected = length::expected_length(structure.info, structure.version)?;
+    if expected == structure.length {
+        return None;
+    }
+    Some(LengthMismatch {
+        info: structure.info,
+        handle: structure.handle,
+        expected,
+        actual: structure.length,
+        version: structure.version,
+    })
+}
+
+fn structure_too_large(structure: &RawStructure<'_>, table: &Structures<'_>) -> Option<Warning> {
+    let maximum = table.max_structure_size?;
+    if structure.size() <= maximum as usize {
+        return None;
+    }
+    Some(Warning::StructureTooLarge {
+        info: structure.info,
+        handle: structure.handle,
+        size: structure.size(),
+        maximum,
+    })
+}
+
+/// The `nth` quirk applied to a BIOS Information structure; the ROM size quirks only apply to
+/// structures with an extended ROM size
+fn quirk_applied(structure: &RawStructure<'_>, table: &Table<'_>, nth: usize) -> Option<Warning> {
+    let table = &table.structures;
+    if structure.info != InfoType::Bios || table.bios_quirks.is_empty() {
+        return None;
+    }
+    let bios = Bios::from_raw(structure.clone())
+        .ok()?
+        .with_quirks(structure, table.bios_quirks);
+    Quirk::ALL
+        .iter()
+        .copied()
+        .filter(|&quirk| bios.has_quirk(table.bios_quirks, quirk))
+        .filter(|&quirk| quirk == Quirk::IgnoreVersion || bios.rom_size.extended.is_some())
+        .nth(nth)
+        .map(|quirk| Warning::QuirkApplied {
+            handle: structure.handle,
+            quirk,
+        })
+}
+
+fn language_count_mismatch(structure: &RawStructure<'_>) -> Option<Warning> {
+    if structure.info != InfoType::BiosLanguage {
+        return None;
+    }
+    let language = BiosLanguage::from_raw(structure.clone()).ok()?;
+    if !language.count_mismatch() {
+        return None;
+    }
+    Some(Warning::LanguageCountMismatch {
+        handle: language.handle,
+        declared: language.installable_count,
+        actual: language.installable_languages.count(),
+    })
+}
+
+fn group_items_mismatch(structure: &RawStructure<'_>) -> Option<Warning> {
+    if structure.info != InfoType::GroupAssociations {
+        return None;
+    }
+    let group = GroupAssociations::from_raw(structure.clone()).ok()?;
+    if group.is_consistent() {
+        return None;
+    }
+    Some(Warning::GroupItemsMismatch {
+        handle: group.handle,
+        length: structure.length,
+        declared: group.items.declared(),
+        available: group.items.available(),
+    })
+}
+
+/// Checks of the strict mode
+#[cfg(feature = "strict")]
+mod strict {
+    use core::fmt;
+
+    use super::{first, Check, Table, Warning};
+    use crate::structures::{
+        baseboard::BoardType,
+        built_in_pointing_device,
+        cache::{CacheAssociativity, CacheErrorCorrectionType, SystemCacheType},
+        enclosure::{EnclosureType, SecurityStatus, State},
+        memory_device, memory_error,
+        physical_memory_array::{MemoryArrayErrorCorrectionTypes, MemoryArrayLocation, MemoryArrayUse},
+        port_connector::{ConnectorType, PortType},
+        processor::{ProcessorType, ProcessorUpgrade},
+        system::WakeupType,
+        system_slots::{CurrentUsage, SlotLength, SlotType, SlotWidth},
+    };
+    use crate::{Handle, InfoType, RawStructure, Structures};
+
+    /// Checks run on every structure after [super::CHECKS], in order
+    pub(super) const CHECKS: &[Check] = &[
+        |structure, _, nth| first(nth, || reserved_type(structure)),
+        |structure, _, nth| reserved_bits(structure, nth),
+        |structure, _, nth| undefined_value(structure, nth),
+        |structure, _, nth| unreferenced_string(structure, nth),
+        dangling_handle,
+    ];
+
+    /// (type, offset, size, mask) of the reserved bits of bit fields
+    const RESERVED_BITS: &[(InfoType, usize, usize, u64)] = &[
+        (InfoType::Bios, 0x0A, 8, 0x0003),
+        (InfoType::Bios, 0x13, 1, 0xE0),
+        (InfoType::BaseBoard, 0x09, 1, 0xE0),
+        (InfoType::Processor, 0x18, 1, 0xB8),
+        (InfoType::Processor, 0x26, 2, 0xFC01),
+        (InfoType::Cache, 0x05, 2, 0xFC10),
+        (InfoType::Cache, 0x0B, 2, 0xFF80),
+        (InfoType::Cache, 0x0D, 2, 0xFF80),
+        (InfoType::BiosLanguage, 0x05, 1, 0xFE),
+        (InfoType::SystemEventLog, 0x0B, 1, 0xFC),
+        (InfoType::MemoryDevice, 0x13, 2, 0x0001),
+        (InfoType::MemoryDevice, 0x1B, 1, 0xF0),
+        (InfoType::MemoryDevice, 0x29, 2, 0xFFC1),
+    ];
+
+    /// Whether `value` decodes to a variant other than `Undefined` of an enumeration
+    macro_rules! defined {
+        ($($path:ident)::+) => {
+            |value| !matches!($($path)::+::from(value), $($path)::+::Undefined(_))
+        };
+    }
+
+    /// Whether a value is defined by the enumeration of a field
+    type Defined = fn(u8) -> bool;
+
+    /// (type, offset, definedness) of enumerated fields
+    const ENUMERATIONS: &[(InfoType, usize, Defined)] = &[
+        (InfoType::System, 0x18, defined!(WakeupType)),
+        (InfoType::BaseBoard, 0x0D, defined!(BoardType)),
+        // Bit 7 is the chassis lock
+        (InfoType::Enclosure, 0x05, |value| {
+            !matches!(EnclosureType::from(value & 0x7F), EnclosureType::Undefined(_))
+        }),
+        (InfoType::Enclosure, 0x09, defined!(State)),
+        (InfoType::Enclosure, 0x0A, defined!(State)),
+        (InfoType::Enclosure, 0x0B, defined!(State)),
+        (InfoType::Enclosure, 0x0C, defined!(SecurityStatus)),
+        (InfoType::Processor, 0x05, defined!(ProcessorType)),
+        (InfoType::Processor, 0x19, defined!(ProcessorUpgrade)),
+        (InfoType::Cache, 0x10, defined!(CacheErrorCorrectionType)),
+        (InfoType::Cache, 0x11, defined!(SystemCacheType)),
+        (InfoType::Cache, 0x12, defined!(CacheAssociativity)),
+        (InfoType::PortConnector, 0x05, defined!(ConnectorType)),
+        (InfoType::PortConnector, 0x07, defined!(ConnectorType)),
+        (InfoType::PortConnector, 0x08, defined!(PortType)),
+        (InfoType::SystemSlots, 0x05, defined!(SlotType)),
+        (InfoType::SystemSlots, 0x06, defined!(SlotWidth)),
+        (InfoType::SystemSlots, 0x07, defined!(CurrentUsage)),
+        (InfoType::SystemSlots, 0x08, defined!(SlotLength)),
+        (InfoType::PhysicalMemoryArray, 0x04, defined!(MemoryArrayLocation)),
+        (InfoType::PhysicalMemoryArray, 0x05, defined!(MemoryArrayUse)),
+        (
+            InfoType::PhysicalMemoryArray,
+            0x06,
+            defined!(MemoryArrayErrorCorrectionTypes),
+        ),
+        (InfoType::MemoryDevice, 0x0E, defined!(memory_device::FormFactor)),
+        (InfoType::MemoryDevice, 0x12, defined!(memory_device::Type)),
+        (InfoType::MemoryDevice, 0x28, defined!(memory_device::MemoryTechnology)),
+        (InfoType::MemoryError32, 0x04, defined!(memory_error::ErrorType)),
+        (InfoType::MemoryError32, 0x05, defined!(memory_error::ErrorGranularity)),
+        (InfoType::MemoryError32, 0x06, defined!(memory_error::ErrorOperation)),
+        (
+            InfoType::BuiltInPointingDevice,
+            0x04,
+            defined!(built_in_pointing_device::Type),
+        ),
+        (
+            InfoType::BuiltInPointingDevice,
+            0x05,
+            defined!(built_in_pointing_device::Interface),
+        ),
+    ];
+
+    /// (type, offsets) of handle fields, group items aside
+    const HANDLES: &[(InfoType, &[usize])] = &[
+        (InfoType::Processor, &[0x1A, 0x1C, 0x1E]),
+        (InfoType::PhysicalMemoryArray, &[0x0B]),
+        (InfoType::MemoryDevice, &[0x04, 0x06]),
+        (InfoType::MemoryArrayMappedAddress, &[0x0C]),
+        (InfoType::MemoryDeviceMappedAddress, &[0x0C, 0x0E]),
+        (InfoType::CoolingDevice, &[0x04]),
+        (InfoType::ManagementDeviceComponent, &[0x05, 0x07, 0x09]),
+        (InfoType::ProcessorAdditionalInformation, &[0x04]),
+        (InfoType::StringProperty, &[0x07]),
+    ];
+
+    fn reserved_type(structure: &RawStructure<'_>) -> Option<Warning> {
+        match structure.info {
+            InfoType::Reserved(code) => Some(Warning::ReservedType {
+                handle: structure.handle,
+                code,
+            }),
+            _ => None,
+        }
+    }
+
+    fn reserved_bits(structure: &RawStructure<'_>, nth: usize) -> Option<Warning> {
+        RESERVED_BITS
+            .iter()
+            .filter(|&&(info, ..)| info == structure.info)
+            .filter_map(|&(_, offset, size, mask)| {
+                let value = structure
+                    .get_slice(offset, size)?
+                    .iter()
+                    .rev()
+                    .fold(0u64, |value, &byte| value << 8 | byte as u64);
+                Some((offset, value & mask)).filter(|&(_, bits)| bits != 0)
+            })
+            .nth(nth)
+            .map(|(offset, bits)| Warning::ReservedBits {
+                info: structure.info,
+                handle: structure.handle,
+                offset: offset as u8,
+                bits,
+            })
+    }
+
+    fn undefined_value(structure: &RawStructure<'_>, nth: usize) -> Option<Warning> {
+        ENUMERATIONS
+            .iter()
+            .filter(|&&(info, ..)| info == structure.info)
+            .filter_map(|&(_, offset, defined)| {
+                let value = structure.get_optional::<u8>(offset)?;
+                Some((offset, value)).filter(|_| !defined(value))
+            })
+            .nth(nth)
+            .map(|(offset, value)| Warning::UndefinedValue {
+                info: structure.info,
+                handle: structure.handle,
+                offset: offset as u8,
+                value,
+            })
+    }
+
+    fn unreferenced_string(structure: &RawStructure<'_>, nth: usize) -> Option<Warning> {
+        structure
+            .string_audit()?
+            .orphaned()
+            .iter()
+            .nth(nth)
+            .map(|index| Warning::UnreferencedString {
+                info: structure.info,
+                handle: structure.handle,
+                index,
+            })
+    }
+
+    fn dangling_handle(structure: &RawStructure<'_>, table: &Table<'_>, nth: usize) -> Option<Warning> {
+        let fields = HANDLES
+            .iter()
+            .filter(|&&(info, _)| info == structure.info)
+            .flat_map(|&(_, offsets)| offsets.iter().cloned());
+        // Group items are a type byte followed by a handle
+        let items = match structure.info {
+            InfoType::GroupAssociations => 0..(structure.length as usize).saturating_sub(5) / 3,
+            _ => 0..0,
+        };
+        fields
+            .chain(items.map(|item| 0x06 + 3 * item))
+            .filter_map(|offset| {
+                let target = structure.get_optional::<Handle>(offset)?;
+                // FFFEh and FFFFh tell that there is no structure to refer to
+                let exists = table.handles.as_ref().map_or(true, |handles| handles.contains(target));
+                Some((offset, target)).filter(|_| target.0 < 0xFFFE && !exists)
+            })
+            .nth(nth)
+            .map(|(offset, target)| Warning::DanglingHandle {
+                info: structure.info,
+                handle: structure.handle,
+                offset: offset as u8,
+                target,
+            })
+    }
+
+    /// The handles of the structures of a table, collected in one walk of the table
+    #[derive(Clone, Eq, Hash, PartialEq)]
+    pub(super) struct HandleSet([u64; 1024]);
+
+    impl HandleSet {
+        pub(super) fn new(table: &Structures<'_>) -> Self {
+            let mut bits = [0; 1024];
+            let mut table = table.clone();
+            while let Some(Ok(structure)) = table.next_structure() {
+                let handle = structure.handle.0 as usize;
+                bits[handle / 64] |= 1 << (handle % 64);
+            }
+            HandleSet(bits)
+        }
+
+        fn contains(&self, handle: Handle) -> bool {
+            let handle = handle.0 as usize;
+            self.0[handle / 64] & 1 << (handle % 64) != 0
+        }
+    }
+
+    impl fmt::Debug for HandleSet {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_set()
+                .entries((0..=u16::MAX).map(Handle).filter(|&handle| self.contains(handle)))
+                .finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{StructureBuilder, TableBuilder};
+    use pretty_assertions::assert_eq;
+    use std::{format, vec::Vec};
+
+    #[test]
+    fn short_structure() {
+        let table = TableBuilder::new((2, 4).into())
+            // System Information with only the SMBIOS 2.0 fields
+            .push(&StructureBuilder::new(InfoType::System).handle(Handle(0x0100)).dword(0))
+            // Port Connector Information of the specified length
+            .push(&StructureBuilder::new(InfoType::PortConnector).bytes(&[0; 5]))
+            .end(Handle(0xFEFF));
+        let structures = table.structures();
+
+        let warnings = structures.warnings().collect::<Vec<_>>();
+        let expected = LengthMismatch {
+            info: InfoType::System,
+            handle: Handle(0x0100),
+            expected: 0x1B,
+            actual: 0x08,
+            version: (2, 4).into(),
+        };
+        assert_eq!(vec![Warning::LengthMismatch(expected)], warnings);
+        #[cfg(feature = "strings")]
+        assert_eq!(
+            "System Information structure 0x0100 is 0x08 bytes long, SMBIOS 2.4 specifies 0x1B",
+            format!("{}", warnings[0])
+        );
+    }
+
+    #[test]
+    fn language_count() {
+        let language = StructureBuilder::new(InfoType::BiosLanguage)
+            .handle(Handle(0x0D00))
+            .byte(2)
+            .byte(0)
+            .bytes(&[0; 15])
+            .byte(1)
+            .extra_string("en|US|iso8859-1");
+        let table = TableBuilder::new((3, 0).into()).push(&language).end(Handle(0xFEFF));
+        let warnings = table.structures().warnings().collect::<Vec<_>>();
+        assert_eq!(
+            vec![Warning::LanguageCountMismatch {
+                handle: Handle(0x0D00),
+                declared: 2,
+                actual: 1
+            }],
+            warnings
+        );
+
+        // Both checks report on the same structure
+        let table = TableBuilder::new((3, 0).into())
+            .push(&language.length(0x17))
+            .end(Handle(0xFEFF));
+        assert_eq!(2, table.structures().warnings().count());
+    }
+
+    #[test]
+    fn group_items() {
+        let group = StructureBuilder::new(InfoType::GroupAssociations)
+            .handle(Handle(0x0E00))
+            .string("Cores")
+            .byte(4)
+            .word(0x0400);
+        let table = TableBuilder::new((3, 0).into()).push(&group).end(Handle(0xFEFF));
+        assert_eq!(0, table.structures().warnings().count());
+
+        let table = TableBuilder::new((3, 0).into())
+            .push(&group.byte(4))
+            .end(Handle(0xFEFF));
+        let warnings = table.structures().warnings().collect::<Vec<_>>();
+        assert_eq!(
+            vec![Warning::GroupItemsMismatch {
+                handle: Handle(0x0E00),
+                length: 0x09,
+                declared: 1,
+                available: 1,
+            }],
+            warnings
+        );
+        assert_eq!(
+            "Group Associations structure 0x0E00 is 0x09 bytes long for 1 items, 1 are present",
+            format!("{}", warnings[0])
+        );
+    }
+
+    #[test]
+    fn table_length_clamped() {
+        let table = TableBuilder::new((3, 0).into()).push(&StructureBuilder::new(InfoType::Oem(0x80)));
+        let available = table.bytes().len() as u32;
+        let structures = Structures::new(table.bytes(), (3, 0).into(), 0x1000);
+        assert_eq!(
+            vec![Warning::TableLengthClamped {
+                declared: 0x1000,
+                available
+            }],
+            structures.warnings().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "SMBIOS 3.x table maximum length 0x1000 exceeds the 0x6 bytes available",
+            format!("{}", structures.warnings().next().unwrap())
+        );
+        assert_eq!(0, table.structures().warnings().count());
+
+        let ended = table.end(Handle(0xFEFF));
+        assert_eq!(
+            0,
+            Structures::new(ended.bytes(), (3, 0).into(), 0x1000).warnings().count()
+        );
+    }
+
+    #[test]
+    fn structure_too_large() {
+        let oem = StructureBuilder::new(InfoType::OemStrings)
+            .handle(Handle(0x0B00))
+            .byte(1)
+            .extra_string("0123456789");
+        let table = TableBuilder::new((2, 8).into()).push(&oem).end(Handle(0xFEFF));
+        let structures = || Structures::new(table.bytes(), (2, 8).into(), table.bytes().len() as u32);
+        assert_eq!(0, structures().with_max_structure_size(0x11).warnings().count());
+
+        let expected = Warning::StructureTooLarge {
+            info: InfoType::OemStrings,
+            handle: Handle(0x0B00),
+            size: 0x11,
+            maximum: 0x10,
+        };
+        let too_small = structures().with_max_structure_size(0x10);
+        assert_eq!(vec![expected], too_small.warnings().collect::<Vec<_>>());
+        #[cfg(feature = "strings")]
+        assert_eq!(
+            "OEM Strings structure 0x0B00 is 17 bytes long, the entry point maximum structure size is 16",
+            format!("{}", expected)
+        );
+        assert!(matches!(
+            too_small.enforce_max_structure_size().next(),
+            Some(Err(crate::MalformedStructureError::StructureTooLarge {
+                size: 0x11,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn quirk_applied() {
+        use crate::bios::{Quirk, QuirkEntry};
+
+        const QUIRKS: &[QuirkEntry] = &[
+            QuirkEntry {
+                vendor: "Vendor",
+                version: None,
+                quirk: Quirk::IgnoreVersion,
+            },
+            QuirkEntry {
+                vendor: "Vendor",
+                version: None,
+                quirk: Quirk::PreferExtendedRomSize,
+            },
+        ];
+        let bios = StructureBuilder::new(InfoType::Bios)
+            .handle(Handle(0x0000))
+            .version((2, 0).into())
+            .string("Vendor")
+            .string("1.0")
+            .word(0xE800)
+            .string("01/01/2020")
+            .byte(0x3F)
+            .qword(0x0001_0000)
+            .byte(0x03)
+            .byte(0x0D)
+            .word(0xFFFF)
+            .word(0xFFFF)
+            .word(0x0040);
+        let table = TableBuilder::new((2, 0).into()).push(&bios).end(Handle(0xFEFF));
+        let structures = || Structures::new(table.bytes(), (2, 0).into(), table.bytes().len() as u32);
+        assert_eq!(0, structures().warnings().count());
+
+        let expected = vec![
+            Warning::QuirkApplied {
+                handle: Handle(0x0000),
+                quirk: Quirk::IgnoreVersion,
+            },
+            Warning::QuirkApplied {
+                handle: Handle(0x0000),
+                quirk: Quirk::PreferExtendedRomSize,
+            },
+        ];
+        let quirks = structures().with_bios_quirks(QUIRKS);
+        assert_eq!(expected, quirks.warnings().collect::<Vec<_>>());
+        assert_eq!(
+            "BIOS structure 0x0000 decoded with a workaround: fields read regardless of the SMBIOS version",
+            format!("{}", expected[0])
+        );
+    }
+
+    #[test]
+    fn entry_point_revision() {
+        const ENTRY_V2_BIN: &[u8] = include_bytes!("../tests/data/entry.bin");
+
+        let entry_point = EntryPoint::search(ENTRY_V2_BIN).unwrap();
+        assert_eq!(0, entry_point.warnings().count());
+
+        let mut point = match entry_point {
+            EntryPoint::V2(point) => point,
+            _ => unreachable!(),
+        };
+        point.revision = 0x01;
+        point.formatted = [1, 2, 3, 4, 5];
+        point.recompute_checksums();
+        let mut buffer = [0; 0x1F];
+        point.write_to(&mut buffer).unwrap();
+        let entry_point = EntryPoint::search(&buffer).unwrap();
+        assert_eq!(
+            vec![Warning::UnknownEntryPointRevision {
+                revision: 0x01,
+                formatted: [1, 2, 3, 4, 5]
+            }],
+            entry_point.warnings().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "SMBIOS 2.x entry point revision 0x01 is unknown, formatted area [01, 02, 03, 04, 05] ignored",
+            format!("{}", entry_point.warnings().next().unwrap())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn cxl_slot_characteristic() {
+        // Bit 7 of Slot Characteristics 2 tells CXL 3.0 support since SMBIOS 3.6
+        let slot = StructureBuilder::new(InfoType::SystemSlots)
+            .handle(Handle(0x0900))
+            .version((2, 1).into())
+            .string("Slot 1")
+            .bytes(&[0xA5, 0x0D, 0x03, 0x04])
+            .word(1)
+            .byte(0x04)
+            .byte(0x80);
+        let table = TableBuilder::new((2, 1).into()).push(&slot).end(Handle(0xFEFF));
+        let structures = table.structures();
+        assert!(!structures
+            .conformance()
+            .any(|warning| matches!(warning, Warning::ReservedBits { .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    fn strict_checks() {
+        let board = StructureBuilder::new(InfoType::BaseBoard)
+            .handle(Handle(0x0200))
+            .string("Manufacturer")
+            .string("Product")
+            .string("")
+            .string("")
+            .string("")
+            .byte(0b1000_0001)
+            .byte(0)
+            .word(0x0300)
+            .byte(0x0E)
+            .byte(0)
+            .extra_string("Left over");
+        let array = StructureBuilder::new(InfoType::PhysicalMemoryArray)
+            .handle(Handle(0x1000))
+            .bytes(&[0x03, 0x03, 0x06])
+            .dword(0x0800_0000)
+            .word(0xFFFE)
+            .word(2)
+            .qword(0);
+        let device = StructureBuilder::new(InfoType::MemoryDevice)
+            .handle(Handle(0x1100))
+            .word(0x1000)
+            .word(0x1800)
+            .bytes(&[0; 0x0E]);
+        let reserved = StructureBuilder::new(InfoType::Reserved(100)).handle(Handle(0x6400));
+        let table = TableBuilder::new((3, 0).into())
+            .push(&board)
+            .push(&array)
+            .push(&device)
+            .push(&reserved)
+            .end(Handle(0xFEFF));
+        let structures = table.structures();
+
+        let expected = vec![
+            Warning::ReservedBits {
+                info: InfoType::BaseBoard,
+                handle: Handle(0x0200),
+                offset: 0x09,
+                bits: 0x80,
+            },
+            Warning::UndefinedValue {
+                info: InfoType::BaseBoard,
+                handle: Handle(0x0200),
+                offset: 0x0D,
+                value: 0x0E,
+            },
+            Warning::UnreferencedString {
+                info: InfoType::BaseBoard,
+                handle: Handle(0x0200),
+                index: 3,
+            },
+            Warning::LengthMismatch(LengthMismatch {
+                info: InfoType::MemoryDevice,
+                handle: Handle(0x1100),
+                expected: 0x28,
+                actual: 0x16,
+                version: (3, 0).into(),
+            }),
+            Warning::UndefinedValue {
+                info: InfoType::MemoryDevice,
+                handle: Handle(0x1100),
+                offset: 0x12,
+                value: 0x00,
+            },
+            Warning::DanglingHandle {
+                info: InfoType::MemoryDevice,
+                handle: Handle(0x1100),
+                offset: 0x06,
+                target: Handle(0x1800),
+            },
+            Warning::ReservedType {
+                handle: Handle(0x6400),
+                code: 100,
+            },
+        ];
+        let warnings = structures.conformance().collect::<Vec<_>>();
+        assert_eq!(expected, warnings);
+        #[cfg(feature = "strings")]
+        {
+            assert_eq!(
+                "Memory Device structure 0x1100 refers to missing structure 0x1800 at offset 0x06",
+                format!("{}", warnings[5])
+            );
+        }
+        assert_eq!(vec![expected[3]], structures.warnings().collect::<Vec<_>>());
+    }
+}
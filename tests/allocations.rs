@@ -0,0 +1,60 @@
+//! Checks the "no allocation" guarantee documented at the crate level. The counting allocator is
+//! global to this test binary only, allocations are counted per thread so that concurrently
+//! running tests do not interfere.
+
+extern crate dmidecode;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use dmidecode::{EntryPoint, StringEncoding};
+
+struct CountingAllocator;
+
+std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn iteration_does_not_allocate() {
+    const DMIDECODE_BIN: &[u8] = include_bytes!("data/dmidecode.bin");
+    const DMI_0_BIN: &[u8] = include_bytes!("data/dmi.0.bin");
+
+    for &buffer in &[DMIDECODE_BIN, DMI_0_BIN] {
+        let entry_point = EntryPoint::search(buffer).unwrap();
+        let structures = entry_point.structures(&buffer[(entry_point.smbios_address() as usize)..]);
+        let count = allocations(|| {
+            // Use every decoded value so that none of the decoding is optimized out
+            let (mut handles, mut ascii) = (0u32, 0);
+            for (structure, strings) in structures.with_strings().flatten() {
+                handles += u32::from(structure.handle().0);
+                ascii += strings
+                    .raw()
+                    .filter(|s| StringEncoding::detect(s) == StringEncoding::Ascii)
+                    .count();
+            }
+            assert!(handles > 0 && ascii > 0);
+        });
+        assert_eq!(0, count);
+    }
+}